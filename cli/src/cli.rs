@@ -20,7 +20,7 @@ pub use polkadot_node_primitives::NODE_VERSION;
 
 use clap::Parser;
 use std::path::PathBuf;
-use vitreus_service::eth::EthConfiguration;
+use vitreus_service::{energy_fee::EnergyFeeConfiguration, eth::EthConfiguration};
 
 #[allow(missing_docs)]
 #[derive(Debug, Parser)]
@@ -159,4 +159,7 @@ pub struct Cli {
 
     #[command(flatten)]
     pub eth: EthConfiguration,
+
+    #[command(flatten)]
+    pub energy_fee: EnergyFeeConfiguration,
 }
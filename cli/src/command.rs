@@ -143,6 +143,7 @@ where
         let vitreus_service::NewFull { task_manager, .. } = vitreus_service::build_full(
             config,
             cli.eth,
+            cli.energy_fee,
             vitreus_service::NewFullParams {
                 is_parachain_node: vitreus_service::IsParachainNode::No,
                 enable_beefy,
@@ -10,20 +10,21 @@ use sp_consensus_beefy::ecdsa_crypto::AuthorityId as BeefyId;
 use sp_consensus_grandpa::AuthorityId as GrandpaId;
 use sp_core::ecdsa;
 use sp_core::{Pair, Public};
-use sp_runtime::traits::{IdentifyAccount, Verify};
-use sp_runtime::{FixedU128, Perbill};
+use sp_runtime::traits::{IdentifyAccount, Verify, Zero};
+use sp_runtime::{FixedPointNumber, FixedU128, Perbill};
 
 // Frontier
 
 use crate::tech_addresses::treasury;
 use vitreus_power_plant_runtime::{
-    opaque, vtrs, AccountId, AssetsConfig, AuthorityDiscoveryConfig, BabeConfig, Balance,
+    opaque, vnrg, vtrs, AccountId, AssetsConfig, AuthorityDiscoveryConfig, BabeConfig, Balance,
     BalancesConfig, Claiming, ClaimingConfig, ConfigurationConfig, CouncilConfig, EVMChainIdConfig,
-    EnergyFeeConfig, EnergyGenerationConfig, ImOnlineConfig, ImOnlineId, MaxCooperations,
-    NacManagingConfig, PrivilegesConfig, ReputationConfig, ReputationPoint, RuntimeGenesisConfig,
-    SS58Prefix, SessionConfig, Signature, SimpleVestingConfig, StakerStatus, SudoConfig,
-    SystemConfig, TechnicalCommitteeConfig, BABE_GENESIS_EPOCH_CONFIG,
-    COLLABORATIVE_VALIDATOR_REPUTATION_THRESHOLD, VNRG, WASM_BINARY,
+    EnergyBrokerConfig, EnergyFeeConfig, EnergyGenerationConfig, ImOnlineConfig, ImOnlineId,
+    MaxCooperations, NacManagingConfig, PrivilegesConfig, ReputationConfig, ReputationPoint,
+    RuntimeGenesisConfig, SS58Prefix, SessionConfig, Signature, SimpleVestingConfig, StakerStatus,
+    SudoConfig, SystemConfig, TechnicalCommitteeConfig, BABE_GENESIS_EPOCH_CONFIG,
+    COLLABORATIVE_VALIDATOR_REPUTATION_THRESHOLD, ENERGY_PER_STAKE_CURRENCY, INITIAL_ENERGY_RATE,
+    VNRG, WASM_BINARY,
 };
 
 /// Node `ChainSpec` extensions.
@@ -47,8 +48,14 @@ pub struct Extensions {
 pub type ChainSpec = sc_service::GenericChainSpec<Extensions>;
 
 const INITIAL_ENERGY_BALANCE: Balance = 100_000_000_000_000_000_000u128;
-/// 1 VTRS = 0.9 gVolt => 1.11111... VTRS = 1 gVolt
-const INITIAL_ENERGY_RATE: FixedU128 = FixedU128::from_inner(1_111_111_111_111_111_111_111_111_111);
+/// VNRG reserve of the VTRS/VNRG broker pool seeded at genesis.
+const INITIAL_POOL_VNRG: Balance = 900_000 * vnrg::GVOLT;
+
+/// VTRS reserve of the VTRS/VNRG broker pool seeded at genesis, pricing VNRG at
+/// `INITIAL_ENERGY_RATE` like the oracle.
+fn initial_pool_vtrs() -> Balance {
+    INITIAL_ENERGY_RATE.saturating_mul_int(INITIAL_POOL_VNRG)
+}
 
 /// Min validator stake for user who has NAC level = 1.
 const MIN_COMMON_VALIDATOR_BOND: Balance = 1_000_000 * vtrs::UNITS;
@@ -57,7 +64,6 @@ const MIN_COMMON_VALIDATOR_BOND: Balance = 1_000_000 * vtrs::UNITS;
 const MIN_TRUST_VALIDATOR_BOND: Balance = 1 * vtrs::UNITS;
 
 const MIN_COOPERATOR_BOND: Balance = 1_000_000_000_000_000_000;
-const ENERGY_PER_STAKE_CURRENCY: Balance = 19_909_091_036_891;
 
 #[cfg(feature = "testnet-native")]
 pub fn development_config() -> ChainSpec {
@@ -66,35 +72,34 @@ pub fn development_config() -> ChainSpec {
 
     let wasm_binary = WASM_BINARY.expect("WASM not available");
 
+    let chain_type = ChainType::Development;
+
     ChainSpec::builder(wasm_binary, Default::default())
         .with_name("Development")
         .with_id("dev")
-        .with_chain_type(ChainType::Development)
+        .with_chain_type(chain_type)
         .with_properties(properties())
-        .with_genesis_config(
-            serde_json::to_value(testnet_genesis(
-                wasm_binary,
-                // Sudo account
+        .with_genesis_config(checked_genesis(testnet_genesis(
+            wasm_binary,
+            // Sudo account
+            alith(),
+            // Pre-funded accounts
+            vec![
                 alith(),
-                // Pre-funded accounts
-                vec![
-                    alith(),
-                    baltathar(),
-                    charleth(),
-                    dorothy(),
-                    ethan(),
-                    faith(),
-                    goliath(),
-                    treasury(),
-                ],
-                // Initial Validators
-                vec![authority_keys_from_seed("Alice")],
-                vec![],
-                // Ethereum chain ID
-                SS58Prefix::get() as u64,
-            ))
-            .expect("Invalid genesis config"),
-        )
+                baltathar(),
+                charleth(),
+                dorothy(),
+                ethan(),
+                faith(),
+                goliath(),
+                treasury(),
+            ],
+            // Initial Validators
+            vec![authority_keys_from_seed("Alice")],
+            vec![],
+            // Ethereum chain ID
+            SS58Prefix::get() as u64,
+        )))
         .build()
 }
 
@@ -105,34 +110,33 @@ pub fn devnet_config() -> ChainSpec {
 
     let wasm_binary = WASM_BINARY.expect("WASM not available");
 
+    let chain_type = ChainType::Custom("Devnet".to_string());
+
     ChainSpec::builder(wasm_binary, Default::default())
         .with_name("Devnet")
         .with_id("devnet")
-        .with_chain_type(ChainType::Custom("Devnet".to_string()))
+        .with_chain_type(chain_type)
         .with_properties(properties())
-        .with_genesis_config(
-            serde_json::to_value(testnet_genesis(
-                wasm_binary,
-                // Sudo account
+        .with_genesis_config(checked_genesis(testnet_genesis(
+            wasm_binary,
+            // Sudo account
+            alith(),
+            // Pre-funded accounts
+            vec![
                 alith(),
-                // Pre-funded accounts
-                vec![
-                    alith(),
-                    baltathar(),
-                    charleth(),
-                    dorothy(),
-                    ethan(),
-                    faith(),
-                    goliath(),
-                    treasury(),
-                ],
-                // Initial Validators
-                vec![authority_keys_from_seed("Alice"), authority_keys_from_seed("Bob")],
-                vec![],
-                SS58Prefix::get() as u64,
-            ))
-            .unwrap(),
-        )
+                baltathar(),
+                charleth(),
+                dorothy(),
+                ethan(),
+                faith(),
+                goliath(),
+                treasury(),
+            ],
+            // Initial Validators
+            vec![authority_keys_from_seed("Alice"), authority_keys_from_seed("Bob")],
+            vec![],
+            SS58Prefix::get() as u64,
+        )))
         .build()
 }
 
@@ -143,34 +147,33 @@ pub fn localnet_config() -> ChainSpec {
 
     let wasm_binary = WASM_BINARY.expect("WASM not available");
 
+    let chain_type = ChainType::Local;
+
     ChainSpec::builder(wasm_binary, Default::default())
         .with_name("Localnet")
         .with_id("localnet")
-        .with_chain_type(ChainType::Local)
+        .with_chain_type(chain_type)
         .with_properties(properties())
-        .with_genesis_config(
-            serde_json::to_value(testnet_genesis(
-                wasm_binary,
-                // Sudo account
+        .with_genesis_config(checked_genesis(testnet_genesis(
+            wasm_binary,
+            // Sudo account
+            alith(),
+            // Pre-funded accounts
+            vec![
                 alith(),
-                // Pre-funded accounts
-                vec![
-                    alith(),
-                    baltathar(),
-                    charleth(),
-                    dorothy(),
-                    ethan(),
-                    faith(),
-                    goliath(),
-                    treasury(),
-                ],
-                // Initial Validators
-                vec![authority_keys_from_seed("Alice"), authority_keys_from_seed("Bob")],
-                vec![],
-                SS58Prefix::get() as u64,
-            ))
-            .unwrap(),
-        )
+                baltathar(),
+                charleth(),
+                dorothy(),
+                ethan(),
+                faith(),
+                goliath(),
+                treasury(),
+            ],
+            // Initial Validators
+            vec![authority_keys_from_seed("Alice"), authority_keys_from_seed("Bob")],
+            vec![],
+            SS58Prefix::get() as u64,
+        )))
         .build()
 }
 
@@ -180,25 +183,24 @@ pub fn testnet_config() -> ChainSpec {
 
     let wasm_binary = WASM_BINARY.expect("WASM not available");
 
+    let chain_type = ChainType::Custom("Testnet".to_string());
+
     ChainSpec::builder(wasm_binary, Default::default())
         .with_name("Testnet")
         .with_id("testnet")
-        .with_chain_type(ChainType::Custom("Testnet".to_string()))
+        .with_chain_type(chain_type)
         .with_properties(properties())
-        .with_genesis_config(
-            serde_json::to_value(testnet_genesis(
-                wasm_binary,
-                // Sudo account
-                root(),
-                // Pre-funded accounts
-                vec![root(), account_1(), account_2(), account_3()],
-                // Initial Validators
-                vec![validator_1_keys(), validator_2_keys(), validator_3_keys()],
-                vec![],
-                SS58Prefix::get() as u64,
-            ))
-            .unwrap(),
-        )
+        .with_genesis_config(checked_genesis(testnet_genesis(
+            wasm_binary,
+            // Sudo account
+            root(),
+            // Pre-funded accounts
+            vec![root(), account_1(), account_2(), account_3()],
+            // Initial Validators
+            vec![validator_1_keys(), validator_2_keys(), validator_3_keys()],
+            vec![],
+            SS58Prefix::get() as u64,
+        )))
         .build()
 }
 
@@ -211,23 +213,22 @@ pub fn stagenet_config() -> ChainSpec {
     let validators = vec![validator_1_keys(), validator_2_keys(), validator_3_keys()];
     let invulnerables = validators.iter().map(|x| x.0).collect();
 
+    let chain_type = ChainType::Custom("Stagenet".to_string());
+
     ChainSpec::builder(wasm_binary, Default::default())
         .with_name("Stagenet")
         .with_id("stagenet")
-        .with_chain_type(ChainType::Custom("Stagenet".to_string()))
+        .with_chain_type(chain_type)
         .with_properties(properties())
-        .with_genesis_config(
-            serde_json::to_value(mainnet_genesis(
-                wasm_binary,
-                // Sudo account
-                root(),
-                // Initial Validators
-                validators,
-                // Invulnerables
-                invulnerables,
-            ))
-            .expect("Invalid genesis config"),
-        )
+        .with_genesis_config(checked_genesis(mainnet_genesis(
+            wasm_binary,
+            // Sudo account
+            root(),
+            // Initial Validators
+            validators,
+            // Invulnerables
+            invulnerables,
+        )))
         .build()
 }
 
@@ -237,29 +238,28 @@ pub fn mainnet_config() -> ChainSpec {
 
     let wasm_binary = WASM_BINARY.expect("WASM not available");
 
+    let chain_type = ChainType::Live;
+
     ChainSpec::builder(wasm_binary, Default::default())
         .with_name("Mainnet")
         .with_id("mainnet")
-        .with_chain_type(ChainType::Live)
+        .with_chain_type(chain_type)
         .with_properties(properties())
-        .with_genesis_config(
-            serde_json::to_value(mainnet_genesis(
-                wasm_binary,
-                // Sudo account
-                root(),
-                // Initial Validators
-                vec![
-                    validator_1_keys(),
-                    validator_2_keys(),
-                    validator_3_keys(),
-                    validator_4_keys(),
-                    validator_5_keys(),
-                ],
-                // Invulnerables
-                vec![],
-            ))
-            .unwrap(),
-        )
+        .with_genesis_config(checked_genesis(mainnet_genesis(
+            wasm_binary,
+            // Sudo account
+            root(),
+            // Initial Validators
+            vec![
+                validator_1_keys(),
+                validator_2_keys(),
+                validator_3_keys(),
+                validator_4_keys(),
+                validator_5_keys(),
+            ],
+            // Invulnerables
+            vec![],
+        )))
         .build()
 }
 
@@ -331,7 +331,12 @@ pub fn testnet_genesis(
 
         // Monetary
         balances: BalancesConfig {
-            balances: endowed_accounts.iter().cloned().map(|k| (k, ENDOWMENT)).collect(),
+            balances: endowed_accounts
+                .iter()
+                .cloned()
+                .map(|k| (k, ENDOWMENT))
+                .chain([(tech_addresses::liquidity(), initial_pool_vtrs() + ENDOWMENT)])
+                .collect(),
         },
         claiming: genesis::claiming_config(),
         vesting: Default::default(),
@@ -365,10 +370,19 @@ pub fn testnet_genesis(
                 .iter()
                 .cloned()
                 .map(|account| (VNRG::get(), account, INITIAL_ENERGY_BALANCE))
+                .chain([(VNRG::get(), tech_addresses::liquidity(), INITIAL_POOL_VNRG)])
                 .collect(),
             next_asset_id: Default::default(),
         },
         pool_assets: Default::default(),
+        energy_broker: EnergyBrokerConfig {
+            pools: vec![(
+                tech_addresses::liquidity(),
+                VNRG::get(),
+                initial_pool_vtrs(),
+                INITIAL_POOL_VNRG,
+            )],
+        },
         reputation: ReputationConfig {
             accounts: stakers
                 .iter()
@@ -521,6 +535,7 @@ fn mainnet_genesis(
             next_asset_id: Default::default(),
         },
         pool_assets: Default::default(),
+        energy_broker: Default::default(),
         reputation: ReputationConfig {
             accounts: stakers
                 .iter()
@@ -1224,6 +1239,45 @@ mod genesis {
     }
 }
 
+/// Serialize a genesis config after checking it is able to run the energy economy.
+fn checked_genesis(genesis: RuntimeGenesisConfig) -> serde_json::Value {
+    validate_genesis(&genesis);
+    serde_json::to_value(genesis).expect("Invalid genesis config")
+}
+
+/// Check the parts of the genesis config without which no fee can ever be paid.
+///
+/// Panics if the VNRG asset is not created or the initial energy rate is zero. A VTRS/VNRG broker
+/// pool is optional, fees are exchanged at the oracle rate without one, but if seeded it must have
+/// nonzero reserves pricing VNRG at the initial energy rate, so that the two never diverge at
+/// launch.
+pub fn validate_genesis(genesis: &RuntimeGenesisConfig) {
+    assert!(
+        genesis.assets.assets.iter().any(|(id, ..)| *id == VNRG::get()),
+        "Invalid genesis config: VNRG asset {} is not created",
+        VNRG::get(),
+    );
+    assert!(
+        !genesis.energy_fee.initial_energy_rate.is_zero(),
+        "Invalid genesis config: initial energy rate must be nonzero",
+    );
+    let energy_rate = genesis.energy_fee.initial_energy_rate;
+    for (_, asset, native_reserve, asset_reserve) in &genesis.energy_broker.pools {
+        if *asset != VNRG::get() {
+            continue;
+        }
+        assert!(
+            *native_reserve > 0 && *asset_reserve > 0,
+            "Invalid genesis config: VTRS/VNRG broker pool is not seeded",
+        );
+        assert_eq!(
+            energy_rate.saturating_mul_int(*asset_reserve),
+            *native_reserve,
+            "Invalid genesis config: VTRS/VNRG broker pool is not priced at the initial energy rate",
+        );
+    }
+}
+
 fn session_keys(
     babe: BabeId,
     grandpa: GrandpaId,
@@ -1287,3 +1341,70 @@ fn default_parachains_host_configuration(
         ..Default::default()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn genesis() -> RuntimeGenesisConfig {
+        let owner = devnet_keys::alith();
+        RuntimeGenesisConfig {
+            assets: AssetsConfig {
+                assets: vec![(VNRG::get(), owner, false, 1)],
+                ..Default::default()
+            },
+            energy_fee: EnergyFeeConfig {
+                initial_energy_rate: INITIAL_ENERGY_RATE,
+                ..Default::default()
+            },
+            energy_broker: EnergyBrokerConfig {
+                pools: vec![(owner, VNRG::get(), initial_pool_vtrs(), INITIAL_POOL_VNRG)],
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn valid_genesis_passes() {
+        validate_genesis(&genesis());
+    }
+
+    #[test]
+    #[should_panic(expected = "VNRG asset")]
+    fn genesis_without_vnrg_asset_is_rejected() {
+        let mut genesis = genesis();
+        genesis.assets.assets.clear();
+        validate_genesis(&genesis);
+    }
+
+    #[test]
+    #[should_panic(expected = "initial energy rate")]
+    fn genesis_with_zero_energy_rate_is_rejected() {
+        let mut genesis = genesis();
+        genesis.energy_fee.initial_energy_rate = FixedU128::zero();
+        validate_genesis(&genesis);
+    }
+
+    #[test]
+    #[should_panic(expected = "broker pool")]
+    fn genesis_with_empty_broker_pool_is_rejected() {
+        let mut genesis = genesis();
+        genesis.energy_broker.pools[0].3 = 0;
+        validate_genesis(&genesis);
+    }
+
+    #[test]
+    #[should_panic(expected = "initial energy rate")]
+    fn genesis_with_mispriced_broker_pool_is_rejected() {
+        let mut genesis = genesis();
+        genesis.energy_broker.pools[0].2 = 2_000_000 * vtrs::UNITS;
+        validate_genesis(&genesis);
+    }
+
+    #[test]
+    fn genesis_may_skip_broker_pool() {
+        let mut genesis = genesis();
+        genesis.energy_broker.pools.clear();
+        validate_genesis(&genesis);
+    }
+}
@@ -137,8 +137,8 @@ pub fn create_benchmark_extrinsic(
         frame_system::CheckEra::<runtime::Runtime>::from(era),
         frame_system::CheckNonce::<runtime::Runtime>::from(nonce),
         frame_system::CheckWeight::<runtime::Runtime>::new(),
-        pallet_transaction_payment::ChargeTransactionPayment::<runtime::Runtime>::from(0),
         pallet_energy_fee::CheckEnergyFee::<runtime::Runtime>::new(),
+        pallet_transaction_payment::ChargeTransactionPayment::<runtime::Runtime>::from(0),
     );
 
     let raw_payload = runtime::SignedPayload::from_raw(
@@ -0,0 +1,59 @@
+use sp_runtime::Perbill;
+use vitreus_rpc::SwapQuoteConfig;
+
+/// The energy fee configuration used to serve the fee RPCs of a node.
+#[derive(Clone, Debug, clap::Parser)]
+pub struct EnergyFeeConfiguration {
+    /// Slippage in basis points recommended with the VTRS/VNRG swap rate quotes.
+    #[arg(long, default_value = "50")]
+    pub swap_quote_slippage_bps: u32,
+
+    /// Number of blocks a VTRS/VNRG swap rate quote stays valid for.
+    #[arg(long, default_value = "10")]
+    pub swap_quote_max_age: u32,
+}
+
+impl EnergyFeeConfiguration {
+    /// Slippage and validity of the swap rate quotes served over RPC.
+    pub fn swap_quote_config(&self) -> SwapQuoteConfig {
+        SwapQuoteConfig {
+            default_slippage: Perbill::from_rational(
+                self.swap_quote_slippage_bps.min(10_000),
+                10_000,
+            ),
+            max_age: self.swap_quote_max_age,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[test]
+    fn default_swap_quote_config_matches_the_rpc_default() {
+        let config = EnergyFeeConfiguration::parse_from(["vitreus"]);
+        assert_eq!(config.swap_quote_config(), SwapQuoteConfig::default());
+    }
+
+    #[test]
+    fn swap_quote_flags_set_slippage_and_max_age() {
+        let config = EnergyFeeConfiguration::parse_from([
+            "vitreus",
+            "--swap-quote-slippage-bps",
+            "125",
+            "--swap-quote-max-age",
+            "3",
+        ]);
+        assert_eq!(
+            config.swap_quote_config(),
+            SwapQuoteConfig { default_slippage: Perbill::from_parts(12_500_000), max_age: 3 }
+        );
+
+        // slippage can't exceed the whole amount
+        let config =
+            EnergyFeeConfiguration::parse_from(["vitreus", "--swap-quote-slippage-bps", "20000"]);
+        assert_eq!(config.swap_quote_config().default_slippage, Perbill::one());
+    }
+}
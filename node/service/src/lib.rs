@@ -27,6 +27,7 @@
 #![deny(unused_results)]
 
 pub mod benchmarking;
+pub mod energy_fee;
 pub mod eth;
 mod parachains_db;
 
@@ -108,6 +109,7 @@ pub use sp_runtime::{
     traits::{self as runtime_traits, BlakeTwo256, Block as BlockT, Header as HeaderT, NumberFor},
 };
 
+use energy_fee::EnergyFeeConfiguration;
 use eth::{
     db_config_dir, new_frontier_partial, spawn_frontier_tasks, BackendType, EthConfiguration,
     FrontierBackend, FrontierPartialComponents, StorageOverride, StorageOverrideHandler,
@@ -518,6 +520,7 @@ pub fn new_full<
 >(
     mut config: Configuration,
     eth_config: EthConfiguration,
+    energy_fee_config: EnergyFeeConfiguration,
     NewFullParams {
         is_parachain_node,
         enable_beefy,
@@ -845,6 +848,7 @@ pub fn new_full<
         let enable_dev_signer = eth_config.enable_dev_signer;
         let max_past_logs = eth_config.max_past_logs;
         let execute_gas_limit_multiplier = eth_config.execute_gas_limit_multiplier;
+        let swap_quote = energy_fee_config.swap_quote_config();
         let filter_pool = filter_pool.clone();
         let frontier_backend = frontier_backend.clone();
         let pubsub_notification_sinks = pubsub_notification_sinks.clone();
@@ -898,7 +902,11 @@ pub fn new_full<
                 pending_create_inherent_data_providers,
             };
 
-            let node_deps = vitreus_rpc::NodeDeps { name: node_name.clone() };
+            let node_deps = vitreus_rpc::NodeDeps {
+                name: node_name.clone(),
+                swap_quote,
+                deny_unsafe,
+            };
 
             let deps =
                 vitreus_rpc::ExtraDeps { client: client.clone(), eth: eth_deps, node: node_deps };
@@ -1328,14 +1336,25 @@ pub fn new_chain_ops(
 pub fn build_full<OverseerGenerator: OverseerGen>(
     config: Configuration,
     eth_config: EthConfiguration,
+    energy_fee_config: EnergyFeeConfiguration,
     params: NewFullParams<OverseerGenerator>,
 ) -> Result<NewFull, Error> {
     match config.network.network_backend {
         sc_network::config::NetworkBackendType::Libp2p => {
-            new_full::<_, sc_network::NetworkWorker<Block, Hash>>(config, eth_config, params)
+            new_full::<_, sc_network::NetworkWorker<Block, Hash>>(
+                config,
+                eth_config,
+                energy_fee_config,
+                params,
+            )
         },
         sc_network::config::NetworkBackendType::Litep2p => {
-            new_full::<_, sc_network::Litep2pNetworkBackend>(config, eth_config, params)
+            new_full::<_, sc_network::Litep2pNetworkBackend>(
+                config,
+                eth_config,
+                energy_fee_config,
+                params,
+            )
         },
     }
 }
@@ -28,7 +28,8 @@
 //!
 //! ## Security Model
 //! - Uses secp256k1 ECDSA for signature verification
-//! - Single-use claims that are removed after successful processing
+//! - Single-use claims that are removed after successful processing, resubmitting a processed
+//!   claim fails with `AlreadyClaimed` or, with `IdempotentClaims`, succeeds without effect
 //! - Protected token supply managed only by root
 //! - Vesting protection preventing claiming to accounts that already have schedules
 //!
@@ -179,6 +180,11 @@ pub mod pallet {
         #[pallet::constant]
         type Prefix: Get<&'static [u8]>;
 
+        /// Whether resubmitting a processed claim to the same account succeeds without effect,
+        /// e.g. for a retried UI submission, rather than failing with `AlreadyClaimed`.
+        #[pallet::constant]
+        type IdempotentClaims: Get<bool>;
+
         /// Weight information for extrinsic.
         type WeightInfo: WeightInfo;
     }
@@ -196,6 +202,13 @@ pub mod pallet {
     pub(super) type Vesting<T: Config> =
         StorageMap<_, Identity, EthereumAddress, (BalanceOf<T>, BalanceOf<T>, BlockNumberFor<T>)>;
 
+    /// Account the claim of an Ethereum address was last paid to.
+    ///
+    /// Only claims processed since it was introduced are recorded.
+    #[pallet::storage]
+    #[pallet::getter(fn claimed)]
+    pub(super) type Claimed<T: Config> = StorageMap<_, Identity, EthereumAddress, T::AccountId>;
+
     #[pallet::storage]
     #[pallet::getter(fn total)]
     pub(super) type Total<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
@@ -225,6 +238,8 @@ pub mod pallet {
         SignerHasNoClaim,
         /// The account already has a vested balance.
         VestedBalanceExists,
+        /// The claim of the Ethereum address was already processed.
+        AlreadyClaimed,
     }
 
     #[pallet::genesis_config]
@@ -312,8 +327,17 @@ impl<T: Config> Pallet<T> {
     }
 
     /// Claims tokens to account wallet.
+    ///
+    /// Once processed the claim is removed, so the signature can't be replayed. A claim minted
+    /// again to the address afterwards can be claimed like a new one.
     fn process_claim(signer: EthereumAddress, dest: T::AccountId) -> DispatchResult {
-        let amount = <Claims<T>>::get(signer).ok_or(Error::<T>::SignerHasNoClaim)?;
+        let Some(amount) = <Claims<T>>::get(signer) else {
+            return match <Claimed<T>>::get(signer) {
+                Some(claimed_to) if claimed_to == dest && T::IdempotentClaims::get() => Ok(()),
+                Some(_) => Err(Error::<T>::AlreadyClaimed.into()),
+                None => Err(Error::<T>::SignerHasNoClaim.into()),
+            };
+        };
 
         let new_total =
             Self::total().checked_sub(&amount).ok_or(Error::<T>::NotEnoughTokensForClaim)?;
@@ -329,15 +353,16 @@ impl<T: Config> Pallet<T> {
 
         // Check if this claim should have a vesting schedule.
         if let Some(vs) = vesting {
-            // This can only fail if the account already has a vesting schedule,
-            // but this is checked above.
+            // No other vesting schedule exists as checked above, unless `OnClaim` added one.
+            // The whole claim is then reverted rather than vesting the account twice.
             T::VestingSchedule::add_vesting_schedule(&dest, vs.0, vs.1, vs.2)
-                .expect("No other vesting schedule exists, as checked above; qed");
+                .map_err(|_| Error::<T>::VestedBalanceExists)?;
         }
 
         <Total<T>>::put(new_total);
         <Claims<T>>::remove(signer);
         <Vesting<T>>::remove(signer);
+        <Claimed<T>>::insert(signer, &dest);
 
         Self::deposit_event(Event::<T>::Claimed { account_id: dest, amount });
 
@@ -106,6 +106,7 @@ impl pallet_vesting::Config for Test {
 
 parameter_types! {
     pub Prefix: &'static [u8] = b"Pay RUSTs to the TEST account:";
+    pub static IdempotentClaims: bool = false;
 }
 
 impl pallet_claiming::Config for Test {
@@ -114,6 +115,7 @@ impl pallet_claiming::Config for Test {
     type VestingSchedule = Vesting;
     type OnClaim = ();
     type Prefix = Prefix;
+    type IdempotentClaims = IdempotentClaims;
     type WeightInfo = ();
 }
 
@@ -148,8 +148,51 @@ fn double_claiming_doesnt_work() {
                 RuntimeOrigin::signed(42),
                 sig::<Test>(&alice(), &42u64.encode(), &[][..])
             ),
-            Error::<Test>::SignerHasNoClaim
+            Error::<Test>::AlreadyClaimed
+        );
+        // nor to another account
+        assert_noop!(
+            Claiming::claim(
+                RuntimeOrigin::signed(69),
+                sig::<Test>(&alice(), &69u64.encode(), &[][..])
+            ),
+            Error::<Test>::AlreadyClaimed
         );
+        assert_eq!(Balances::free_balance(&42), 100);
+        assert_eq!(Vesting::vesting_balance(&42), Some(50));
+        assert_eq!(Claiming::total(), 50);
+    });
+}
+
+#[test]
+fn idempotent_double_claiming_has_no_effect() {
+    new_test_ext().execute_with(|| {
+        IdempotentClaims::set(true);
+        assert_ok!(Claiming::mint_tokens_to_claim(RuntimeOrigin::root(), 150));
+
+        let claim = |who: u64| {
+            Claiming::claim(
+                RuntimeOrigin::signed(who),
+                sig::<Test>(&alice(), &who.encode(), &[][..]),
+            )
+        };
+        assert_ok!(claim(42));
+        assert_ok!(claim(42));
+        assert_eq!(Balances::free_balance(&42), 100);
+        assert_eq!(Vesting::vesting_balance(&42), Some(50));
+        assert_eq!(pallet_vesting::Vesting::<Test>::get(42).unwrap().len(), 1);
+        assert_eq!(Claiming::total(), 50);
+        assert_eq!(Claiming::claimed(&eth(&alice())), Some(42));
+
+        // the claim went to 42, another account can't claim it again
+        assert_noop!(claim(69), Error::<Test>::AlreadyClaimed);
+
+        // a claim minted again can be claimed, without vesting it twice
+        assert_ok!(Claiming::mint_claim(RuntimeOrigin::root(), eth(&alice()), 30));
+        assert_ok!(claim(42));
+        assert_eq!(Balances::free_balance(&42), 130);
+        assert_eq!(pallet_vesting::Vesting::<Test>::get(42).unwrap().len(), 1);
+        assert_eq!(Claiming::total(), 20);
     });
 }
 
@@ -38,10 +38,30 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().writes(2_u64))
     }
 
+    /// Storage: Claiming Claims (r:1 w:1)
+    /// Proof Skipped: Claiming Claims (max_values: None, max_size: None, mode: Measured)
+    /// Storage: Claiming Total (r:1 w:1)
+    /// Proof Skipped: Claiming Total (max_values: Some(1), max_size: None, mode: Measured)
+    /// Storage: Claiming Vesting (r:1 w:1)
+    /// Proof Skipped: Claiming Vesting (max_values: None, max_size: None, mode: Measured)
+    /// Storage: Vesting Vesting (r:1 w:1)
+    /// Proof: Vesting Vesting (max_values: None, max_size: Some(1045), added: 3520, mode: MaxEncodedLen)
+    /// Storage: System Account (r:2 w:2)
+    /// Proof: System Account (max_values: None, max_size: Some(116), added: 2591, mode: MaxEncodedLen)
+    /// Storage: Balances Locks (r:1 w:1)
+    /// Proof: Balances Locks (max_values: None, max_size: Some(1287), added: 3762, mode: MaxEncodedLen)
+    /// Storage: Balances Freezes (r:1 w:0)
+    /// Proof: Balances Freezes (max_values: None, max_size: Some(193), added: 2668, mode: MaxEncodedLen)
+    /// Storage: Claiming Claimed (r:0 w:1)
+    /// Proof Skipped: Claiming Claimed (max_values: None, max_size: None, mode: Measured)
     fn claim() -> Weight {
-        Weight::from_parts(38_924_000, 3643)
-            .saturating_add(T::DbWeight::get().reads(3_u64))
-            .saturating_add(T::DbWeight::get().writes(3_u64))
+        // Proof Size summary in bytes:
+        //  Measured:  `1248`
+        //  Estimated: `6172`
+        // Minimum execution time: 112_736_000 picoseconds.
+        Weight::from_parts(115_129_000, 6172)
+            .saturating_add(T::DbWeight::get().reads(8_u64))
+            .saturating_add(T::DbWeight::get().writes(8_u64))
     }
 
     fn mint_claim() -> Weight {
@@ -58,10 +78,30 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().writes(2_u64))
     }
 
+    /// Storage: Claiming Claims (r:1 w:1)
+    /// Proof Skipped: Claiming Claims (max_values: None, max_size: None, mode: Measured)
+    /// Storage: Claiming Total (r:1 w:1)
+    /// Proof Skipped: Claiming Total (max_values: Some(1), max_size: None, mode: Measured)
+    /// Storage: Claiming Vesting (r:1 w:1)
+    /// Proof Skipped: Claiming Vesting (max_values: None, max_size: None, mode: Measured)
+    /// Storage: Vesting Vesting (r:1 w:1)
+    /// Proof: Vesting Vesting (max_values: None, max_size: Some(1045), added: 3520, mode: MaxEncodedLen)
+    /// Storage: System Account (r:2 w:2)
+    /// Proof: System Account (max_values: None, max_size: Some(116), added: 2591, mode: MaxEncodedLen)
+    /// Storage: Balances Locks (r:1 w:1)
+    /// Proof: Balances Locks (max_values: None, max_size: Some(1287), added: 3762, mode: MaxEncodedLen)
+    /// Storage: Balances Freezes (r:1 w:0)
+    /// Proof: Balances Freezes (max_values: None, max_size: Some(193), added: 2668, mode: MaxEncodedLen)
+    /// Storage: Claiming Claimed (r:0 w:1)
+    /// Proof Skipped: Claiming Claimed (max_values: None, max_size: None, mode: Measured)
     fn claim() -> Weight {
-        Weight::from_parts(38_924_000, 3643)
-            .saturating_add(RocksDbWeight::get().reads(3_u64))
-            .saturating_add(RocksDbWeight::get().writes(3_u64))
+        // Proof Size summary in bytes:
+        //  Measured:  `1248`
+        //  Estimated: `6172`
+        // Minimum execution time: 112_736_000 picoseconds.
+        Weight::from_parts(115_129_000, 6172)
+            .saturating_add(RocksDbWeight::get().reads(8_u64))
+            .saturating_add(RocksDbWeight::get().writes(8_u64))
     }
 
     fn mint_claim() -> Weight {
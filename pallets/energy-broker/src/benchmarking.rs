@@ -18,7 +18,7 @@
 //! Asset Conversion pallet benchmarking.
 
 use super::*;
-use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_benchmarking::{benchmarks, whitelisted_caller, BenchmarkError};
 use frame_support::{
     assert_ok,
     traits::{
@@ -28,7 +28,10 @@ use frame_support::{
     },
 };
 use frame_system::RawOrigin as SystemOrigin;
-use sp_runtime::traits::{Bounded, StaticLookup};
+use sp_runtime::{
+    traits::{Bounded, StaticLookup},
+    FixedPointNumber, FixedU128,
+};
 use sp_std::{ops::Div, prelude::*};
 
 use crate::Pallet as AssetConversion;
@@ -170,6 +173,32 @@ benchmarks! {
         );
     }
 
+    stabilize_pool {
+        let asset = T::BenchmarkHelper::asset_id(0);
+        let asset1 = T::MultiAssetIdConverter::get_native();
+        let asset2 = T::MultiAssetIdConverter::into_multiasset_id(&asset);
+        let (_, caller, _) = create_asset_and_pool::<T>(&asset1, &asset2);
+        let ed: u128 = T::Currency::minimum_balance().into();
+        let add_amount = 100 * ed;
+        AssetConversion::<T>::add_liquidity(
+            SystemOrigin::Signed(caller.clone()).into(),
+            asset1,
+            asset2,
+            add_amount.into(),
+            1000.into(),
+            0.into(),
+            0.into(),
+            caller,
+        )?;
+        let origin = T::ManageOrigin::try_successful_origin()
+            .map_err(|_| BenchmarkError::Weightless)?;
+        // halving the rate mints into the asset reserve, which also checks the asset's status
+        let target_rate = FixedU128::from_rational(add_amount, 2000);
+    }: _<T::RuntimeOrigin>(origin, asset.clone(), target_rate)
+    verify {
+        assert!(!StabilizationUsage::<T>::get(&asset).1.is_zero());
+    }
+
     // TODO: fix it
     /*
     swap_exact_tokens_for_tokens {
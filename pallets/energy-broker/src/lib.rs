@@ -37,6 +37,7 @@
 //!
 //! ### Security Features
 //! - Slippage protection
+//! - Bounded, governance-only pool stabilization
 //! - Minimum liquidity requirements
 //! - Pool setup fees
 //! - Withdrawal fees
@@ -99,8 +100,8 @@ use parity_scale_codec::Codec;
 use sp_arithmetic::traits::Unsigned;
 use sp_runtime::{
     traits::{
-        CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Ensure, MaybeDisplay, StaticLookup,
-        TrailingZeroInput, Zero,
+        CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Ensure, MaybeDisplay,
+        MaybeSerializeDeserialize, StaticLookup, TrailingZeroInput, Zero,
     },
     DispatchError,
 };
@@ -127,9 +128,12 @@ pub mod pallet {
         },
         BoundedBTreeSet, PalletId,
     };
-    use sp_arithmetic::Permill;
+    use sp_arithmetic::{
+        helpers_128bit::multiply_by_rational_with_rounding, FixedPointNumber, FixedU128, Perbill,
+        Permill, Rounding,
+    };
     use sp_runtime::{
-        traits::{IntegerSquareRoot, One, Zero},
+        traits::{IntegerSquareRoot, One, UniqueSaturatedInto, Zero},
         Saturating,
     };
     use sp_std::prelude::*;
@@ -150,10 +154,10 @@ pub mod pallet {
             + MutateFungible<Self::AccountId>;
 
         /// The `Currency::Balance` type of the native currency.
-        type Balance: Balance;
+        type Balance: Balance + MaybeSerializeDeserialize;
 
         /// The type used to describe the amount of fractions converted into assets.
-        type AssetBalance: Balance;
+        type AssetBalance: Balance + MaybeSerializeDeserialize;
 
         /// A type used for conversions between `Balance` and `AssetBalance`.
         type HigherPrecisionBalance: IntegerSquareRoot
@@ -169,7 +173,7 @@ pub mod pallet {
         /// Identifier for the class of non-native asset.
         /// Note: A `From<u32>` bound here would prevent `MultiLocation` from being used as an
         /// `AssetId`.
-        type AssetId: AssetId + PartialOrd;
+        type AssetId: AssetId + PartialOrd + MaybeSerializeDeserialize;
 
         /// Type that identifies either the native currency or a token class from `Assets`.
         type MultiAssetId: AssetId + Ord;
@@ -224,6 +228,34 @@ pub mod pallet {
         #[pallet::constant]
         type AllowMultiAssetPools: Get<bool>;
 
+        /// The maximum share of the output reserve a single fee swap may take. Only applies to
+        /// the native swaps their callers make for [`SwapKind::Fee`], not to swaps initiated by
+        /// users.
+        #[pallet::constant]
+        type MaxFeeSwapImpact: Get<Perbill>;
+
+        /// Whether every swap also deposits an [`Event::Swapped`] with its price impact and
+        /// whether it paid a fee.
+        #[pallet::constant]
+        type EmitSwapDetails: Get<bool>;
+
+        /// Origin allowed to mint or burn the asset reserve of a pool to steer its rate.
+        type ManageOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// The maximum amount of an asset a single [`Pallet::stabilize_pool`] call may mint into
+        /// or burn from a pool.
+        #[pallet::constant]
+        type MaxStabilizationPerCall: Get<Self::AssetBalance>;
+
+        /// The maximum amount of an asset that may be minted into or burned from its pool within
+        /// one `StabilizationPeriod`, summed over all calls.
+        #[pallet::constant]
+        type MaxStabilizationPerPeriod: Get<Self::AssetBalance>;
+
+        /// The length in blocks of the window `MaxStabilizationPerPeriod` applies to.
+        #[pallet::constant]
+        type StabilizationPeriod: Get<BlockNumberFor<Self>>;
+
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
 
@@ -243,6 +275,23 @@ pub mod pallet {
     #[pallet::storage]
     pub type NextPoolAssetId<T: Config> = StorageValue<_, T::PoolAssetId, OptionQuery>;
 
+    /// The reserves the pool of each asset was seeded with at genesis as
+    /// `(native reserve, asset reserve)`. Written once at genesis and never changed.
+    #[pallet::storage]
+    pub type GenesisPoolSeeds<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AssetId, (T::Balance, T::AssetBalance), OptionQuery>;
+
+    /// The amount of each asset minted or burned by [`Pallet::stabilize_pool`] as
+    /// `(stabilization period, amount)`. Only the amount of the current period counts.
+    #[pallet::storage]
+    pub type StabilizationUsage<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AssetId,
+        (BlockNumberFor<T>, T::AssetBalance),
+        ValueQuery,
+    >;
+
     // Pallet's events.
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -311,6 +360,25 @@ pub mod pallet {
             /// The amount of the second asset that was received.
             amount_out: T::AssetBalance,
         },
+        /// Assets have been converted from one to another, with the details explorers need.
+        /// Only deposited if `EmitSwapDetails` is set, alongside `SwapExecuted`.
+        Swapped {
+            /// Which account was the instigator of the swap.
+            who: T::AccountId,
+            /// The asset that was swapped.
+            asset_in: T::MultiAssetId,
+            /// The asset that was received.
+            asset_out: T::MultiAssetId,
+            /// The amount of `asset_in` that was swapped.
+            amount_in: T::AssetBalance,
+            /// The amount of `asset_out` that was received.
+            amount_out: T::AssetBalance,
+            /// The largest share of a pool's output reserve the swap took, measured against the
+            /// reserve before the swap.
+            price_impact: Perbill,
+            /// Whether the swap was made by a user or to pay a fee.
+            kind: SwapKind,
+        },
         /// An amount has been transferred from one account to another.
         Transfer {
             /// The account that the assets were transferred from.
@@ -322,6 +390,22 @@ pub mod pallet {
             /// The amount of the asset that was transferred.
             amount: T::AssetBalance,
         },
+        /// The asset reserve of a pool was changed by governance to move the pool rate towards a
+        /// target.
+        PoolStabilized {
+            /// The asset paired with the native currency in the pool.
+            asset: T::AssetId,
+            /// The rate requested by governance, in native currency per asset unit.
+            target_rate: FixedU128,
+            /// The pool rate before the adjustment.
+            rate_before: FixedU128,
+            /// The pool rate after the adjustment.
+            rate_after: FixedU128,
+            /// The amount of the asset that was minted into the pool.
+            minted: T::AssetBalance,
+            /// The amount of the asset that was burned from the pool.
+            burned: T::AssetBalance,
+        },
     }
 
     #[pallet::error]
@@ -383,6 +467,59 @@ pub mod pallet {
         CorrespondenceError,
         /// It was not possible to get or increment the Id of the pool.
         IncorrectPoolAssetId,
+        /// The fee swap would take more than `MaxFeeSwapImpact` of the pool reserve.
+        FeeSwapImpactTooHigh,
+        /// The target rate of a pool can't be zero.
+        ZeroTargetRate,
+        /// The pool rate already matches the target rate.
+        PoolAlreadyAtTarget,
+        /// The stabilization allowance of the current period is used up.
+        StabilizationLimitReached,
+    }
+
+    #[pallet::genesis_config]
+    #[derive(frame_support::DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        /// Pools of the native currency and an asset seeded at genesis as
+        /// `(depositor, asset, native reserve, asset reserve)`.
+        ///
+        /// The depositor must hold both reserves and receives the LP tokens. The initial price
+        /// of one asset unit is `native reserve / asset reserve` of the native currency.
+        pub pools: Vec<(T::AccountId, T::AssetId, T::Balance, T::AssetBalance)>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        fn build(&self) {
+            for (depositor, asset, native_reserve, asset_reserve) in &self.pools {
+                assert!(
+                    !native_reserve.is_zero() && !asset_reserve.is_zero(),
+                    "genesis pool reserves must be nonzero"
+                );
+                GenesisPoolSeeds::<T>::insert(asset, (*native_reserve, *asset_reserve));
+
+                let native = T::MultiAssetIdConverter::get_native();
+                let asset = T::MultiAssetIdConverter::into_multiasset_id(asset);
+                let native_reserve =
+                    Pallet::<T>::convert_native_balance_to_asset_balance(*native_reserve)
+                        .expect("native reserve must fit into the asset balance");
+
+                Pallet::<T>::do_create_pool(depositor, asset.clone(), native.clone(), false)
+                    .expect("genesis pool must be unique");
+                Pallet::<T>::do_add_liquidity(
+                    depositor.clone(),
+                    native,
+                    asset,
+                    native_reserve,
+                    *asset_reserve,
+                    native_reserve,
+                    *asset_reserve,
+                    depositor.clone(),
+                    true,
+                )
+                .expect("depositor must hold the genesis pool reserves");
+            }
+        }
     }
 
     #[pallet::hooks]
@@ -413,54 +550,7 @@ pub mod pallet {
             ensure_root(origin)?;
             let depositor = T::Lookup::lookup(depositor)?;
 
-            ensure!(asset1 != asset2, Error::<T>::EqualAssets);
-
-            let pool_id = Self::get_pool_id(asset1.clone(), asset2.clone());
-            let (asset1, asset2) = pool_id.clone();
-
-            if !T::AllowMultiAssetPools::get() && !T::MultiAssetIdConverter::is_native(&asset1) {
-                Err(Error::<T>::PoolMustContainNativeCurrency)?;
-            }
-
-            ensure!(!Pools::<T>::contains_key(&pool_id), Error::<T>::PoolExists);
-
-            let pool_account = Self::get_pool_account(&pool_id);
-            frame_system::Pallet::<T>::inc_providers(&pool_account);
-
-            // pay the setup fee
-            T::Currency::transfer(
-                &depositor,
-                &T::PoolSetupFeeReceiver::get(),
-                T::PoolSetupFee::get(),
-                Preserve,
-            )?;
-
-            if let Ok(asset) = T::MultiAssetIdConverter::try_convert(&asset1) {
-                if !T::Assets::contains(&asset, &pool_account) {
-                    T::Assets::touch(asset, &pool_account, &depositor)?;
-                }
-            }
-            if let Ok(asset) = T::MultiAssetIdConverter::try_convert(&asset2) {
-                if !T::Assets::contains(&asset, &pool_account) {
-                    T::Assets::touch(asset, &pool_account, &depositor)?;
-                }
-            }
-
-            let lp_token = NextPoolAssetId::<T>::get()
-                .or(T::PoolAssetId::initial_value())
-                .ok_or(Error::<T>::IncorrectPoolAssetId)?;
-            let next_lp_token_id = lp_token.increment().ok_or(Error::<T>::IncorrectPoolAssetId)?;
-            NextPoolAssetId::<T>::set(Some(next_lp_token_id));
-
-            T::PoolAssets::create(lp_token.clone(), pool_account.clone(), false, 1u32.into())?;
-            T::PoolAssets::touch(lp_token.clone(), &pool_account, &depositor)?;
-
-            let pool_info = PoolInfo { lp_token: lp_token.clone() };
-            Pools::<T>::insert(pool_id.clone(), pool_info);
-
-            Self::deposit_event(Event::PoolCreated { creator: depositor, pool_id, lp_token });
-
-            Ok(())
+            Self::do_create_pool(&depositor, asset1, asset2, true)
         }
 
         /// Provide liquidity into the pool of `asset1` and `asset2`.
@@ -622,7 +712,7 @@ pub mod pallet {
                 );
             }
 
-            Self::do_swap(sender, &amounts, path, send_to, keep_alive)?;
+            Self::do_swap(sender, &amounts, path, send_to, keep_alive, SwapKind::User)?;
             Ok(())
         }
 
@@ -664,7 +754,7 @@ pub mod pallet {
                 );
             }
 
-            Self::do_swap(sender, &amounts, path, send_to, keep_alive)?;
+            Self::do_swap(sender, &amounts, path, send_to, keep_alive, SwapKind::User)?;
             Ok(())
         }
 
@@ -701,9 +791,102 @@ pub mod pallet {
 
             Ok(())
         }
+
+        /// Move the rate of the pool of `asset` and the native currency towards `target_rate`,
+        /// given as the amount of native currency per asset unit, by minting or burning the
+        /// asset reserve of the pool.
+        ///
+        /// The adjustment is bounded by `MaxStabilizationPerCall` and by what is left of
+        /// `MaxStabilizationPerPeriod`, and never overshoots the target. Only the pool account is
+        /// touched: no LP tokens are minted and nothing is paid out to anyone.
+        ///
+        /// The dispatch origin must be `ManageOrigin`.
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::stabilize_pool())]
+        pub fn stabilize_pool(
+            origin: OriginFor<T>,
+            asset: T::AssetId,
+            target_rate: FixedU128,
+        ) -> DispatchResult {
+            T::ManageOrigin::ensure_origin(origin)?;
+            ensure!(!target_rate.is_zero(), Error::<T>::ZeroTargetRate);
+
+            let native = T::MultiAssetIdConverter::get_native();
+            let multi_asset = T::MultiAssetIdConverter::into_multiasset_id(&asset);
+            let pool_id = Self::get_pool_id(native.clone(), multi_asset.clone());
+            ensure!(Pools::<T>::contains_key(&pool_id), Error::<T>::PoolNotFound);
+
+            let (native_reserve, asset_reserve) = Self::get_reserves(&native, &multi_asset)?;
+            let rate_before = Self::reserves_rate(native_reserve, asset_reserve)?;
+            let target_reserve: T::AssetBalance = multiply_by_rational_with_rounding(
+                native_reserve.unique_saturated_into(),
+                FixedU128::DIV,
+                target_rate.into_inner(),
+                Rounding::Down,
+            )
+            .and_then(|reserve| reserve.try_into().ok())
+            .ok_or(Error::<T>::Overflow)?;
+            ensure!(target_reserve != asset_reserve, Error::<T>::PoolAlreadyAtTarget);
+
+            let period = frame_system::Pallet::<T>::block_number()
+                / T::StabilizationPeriod::get().max(One::one());
+            let (used_period, used) = StabilizationUsage::<T>::get(&asset);
+            let used = if used_period == period { used } else { Zero::zero() };
+            let allowance = T::MaxStabilizationPerCall::get()
+                .min(T::MaxStabilizationPerPeriod::get().saturating_sub(used));
+            ensure!(!allowance.is_zero(), Error::<T>::StabilizationLimitReached);
+
+            let pool_account = Self::get_pool_account(&pool_id);
+            let (minted, burned) = if target_reserve > asset_reserve {
+                let amount = target_reserve.saturating_sub(asset_reserve).min(allowance);
+                T::Assets::mint_into(asset.clone(), &pool_account, amount)?;
+                (amount, Zero::zero())
+            } else {
+                let amount = asset_reserve.saturating_sub(target_reserve).min(allowance);
+                Self::validate_minimal_amount(asset_reserve.saturating_sub(amount), &multi_asset)
+                    .map_err(|_| Error::<T>::ReserveLeftLessThanMinimal)?;
+                T::Assets::burn_from(
+                    asset.clone(),
+                    &pool_account,
+                    amount,
+                    Expendable,
+                    Exact,
+                    Polite,
+                )?;
+                (Zero::zero(), amount)
+            };
+            StabilizationUsage::<T>::insert(
+                &asset,
+                (period, used.saturating_add(minted).saturating_add(burned)),
+            );
+
+            let (native_reserve, asset_reserve) = Self::get_reserves(&native, &multi_asset)?;
+            Self::deposit_event(Event::PoolStabilized {
+                asset,
+                target_rate,
+                rate_before,
+                rate_after: Self::reserves_rate(native_reserve, asset_reserve)?,
+                minted,
+                burned,
+            });
+
+            Ok(())
+        }
     }
 
     impl<T: Config> Pallet<T> {
+        /// The amount of native currency per asset unit implied by the reserves of a pool.
+        fn reserves_rate(
+            native_reserve: T::AssetBalance,
+            asset_reserve: T::AssetBalance,
+        ) -> Result<FixedU128, Error<T>> {
+            FixedU128::checked_from_rational(
+                UniqueSaturatedInto::<u128>::unique_saturated_into(native_reserve),
+                UniqueSaturatedInto::<u128>::unique_saturated_into(asset_reserve),
+            )
+            .ok_or(Error::<T>::Overflow)
+        }
+
         /// Transfer an `amount` of `asset_id`, respecting the `keep_alive` requirements.
         fn transfer(
             asset_id: &T::MultiAssetId,
@@ -760,6 +943,69 @@ pub mod pallet {
                 .map_err(|_| Error::<T>::Overflow)
         }
 
+        /// Create the pool of `asset1` and `asset2` with `depositor` paying the deposits of the
+        /// pool accounts and, if `charge_setup_fee` is set, the pool setup fee.
+        fn do_create_pool(
+            depositor: &T::AccountId,
+            asset1: T::MultiAssetId,
+            asset2: T::MultiAssetId,
+            charge_setup_fee: bool,
+        ) -> DispatchResult {
+            ensure!(asset1 != asset2, Error::<T>::EqualAssets);
+
+            let pool_id = Self::get_pool_id(asset1.clone(), asset2.clone());
+            let (asset1, asset2) = pool_id.clone();
+
+            if !T::AllowMultiAssetPools::get() && !T::MultiAssetIdConverter::is_native(&asset1) {
+                Err(Error::<T>::PoolMustContainNativeCurrency)?;
+            }
+
+            ensure!(!Pools::<T>::contains_key(&pool_id), Error::<T>::PoolExists);
+
+            let pool_account = Self::get_pool_account(&pool_id);
+            frame_system::Pallet::<T>::inc_providers(&pool_account);
+
+            if charge_setup_fee {
+                T::Currency::transfer(
+                    depositor,
+                    &T::PoolSetupFeeReceiver::get(),
+                    T::PoolSetupFee::get(),
+                    Preserve,
+                )?;
+            }
+
+            if let Ok(asset) = T::MultiAssetIdConverter::try_convert(&asset1) {
+                if !T::Assets::contains(&asset, &pool_account) {
+                    T::Assets::touch(asset, &pool_account, depositor)?;
+                }
+            }
+            if let Ok(asset) = T::MultiAssetIdConverter::try_convert(&asset2) {
+                if !T::Assets::contains(&asset, &pool_account) {
+                    T::Assets::touch(asset, &pool_account, depositor)?;
+                }
+            }
+
+            let lp_token = NextPoolAssetId::<T>::get()
+                .or(T::PoolAssetId::initial_value())
+                .ok_or(Error::<T>::IncorrectPoolAssetId)?;
+            let next_lp_token_id = lp_token.increment().ok_or(Error::<T>::IncorrectPoolAssetId)?;
+            NextPoolAssetId::<T>::set(Some(next_lp_token_id));
+
+            T::PoolAssets::create(lp_token.clone(), pool_account.clone(), false, 1u32.into())?;
+            T::PoolAssets::touch(lp_token.clone(), &pool_account, depositor)?;
+
+            let pool_info = PoolInfo { lp_token: lp_token.clone() };
+            Pools::<T>::insert(pool_id.clone(), pool_info);
+
+            Self::deposit_event(Event::PoolCreated {
+                creator: depositor.clone(),
+                pool_id,
+                lp_token,
+            });
+
+            Ok(())
+        }
+
         fn do_add_liquidity(
             sender: T::AccountId,
             asset1: T::MultiAssetId,
@@ -844,13 +1090,15 @@ pub mod pallet {
             Ok(())
         }
 
-        /// Swap assets along a `path`, depositing in `send_to`.
+        /// Swap assets along a `path`, depositing in `send_to`. `kind` only tags the
+        /// [`Event::Swapped`] of the swap.
         pub(crate) fn do_swap(
             sender: T::AccountId,
             amounts: &Vec<T::AssetBalance>,
             path: BoundedVec<T::MultiAssetId, T::MaxSwapPathLength>,
             send_to: T::AccountId,
             keep_alive: bool,
+            kind: SwapKind,
         ) -> Result<(), DispatchError> {
             ensure!(amounts.len() > 1, Error::<T>::CorrespondenceError);
             if let Some([asset1, asset2]) = &path.get(0..2) {
@@ -862,6 +1110,7 @@ pub mod pallet {
                 Self::transfer(asset1, &sender, &pool_account, *first_amount, keep_alive)?;
 
                 let mut i = 0;
+                let mut price_impact = Perbill::zero();
                 let path_len = path.len() as u32;
                 for assets_pair in path.windows(2) {
                     if let [asset1, asset2] = assets_pair {
@@ -885,17 +1134,31 @@ pub mod pallet {
                         let reserve_left = reserve.saturating_sub(*amount_out);
                         Self::validate_minimal_amount(reserve_left, asset2)
                             .map_err(|_| Error::<T>::ReserveLeftLessThanMinimal)?;
+                        price_impact =
+                            price_impact.max(Perbill::from_rational(*amount_out, reserve));
 
                         Self::transfer(asset2, &pool_account, &to, *amount_out, true)?;
                     }
                     i.saturating_inc();
                 }
+                let amount_out = *amounts.last().expect("Always has more than 1 element");
+                if T::EmitSwapDetails::get() {
+                    Self::deposit_event(Event::Swapped {
+                        who: sender.clone(),
+                        asset_in: asset1.clone(),
+                        asset_out: path.last().cloned().ok_or(Error::<T>::PathError)?,
+                        amount_in: *first_amount,
+                        amount_out,
+                        price_impact,
+                        kind,
+                    });
+                }
                 Self::deposit_event(Event::SwapExecuted {
                     who: sender,
                     send_to,
                     path,
                     amount_in: *first_amount,
-                    amount_out: *amounts.last().expect("Always has more than 1 element"),
+                    amount_out,
                 });
             } else {
                 return Err(Error::<T>::InvalidPath.into());
@@ -1112,6 +1375,20 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Ensure that a fee swap receiving `amount_out` of the last asset in `path` takes at most
+        /// `MaxFeeSwapImpact` of the pool's reserve of that asset at execution time.
+        pub(crate) fn ensure_fee_swap_impact(
+            path: &BoundedVec<T::MultiAssetId, T::MaxSwapPathLength>,
+            amount_out: T::AssetBalance,
+        ) -> Result<(), DispatchError> {
+            if let [.., asset_in, asset_out] = path.as_slice() {
+                let (_, reserve_out) = Self::get_reserves(asset_in, asset_out)?;
+                let impact = Perbill::from_rational(amount_out, reserve_out);
+                ensure!(impact <= T::MaxFeeSwapImpact::get(), Error::<T>::FeeSwapImpactTooHigh);
+            }
+            Ok(())
+        }
+
         /// Ensure that a path is valid.
         fn validate_swap_path(
             path: &BoundedVec<T::MultiAssetId, T::MaxSwapPathLength>,
@@ -1156,6 +1433,9 @@ impl<T: Config> Pallet<T> {
     /// desired.
     ///
     /// If successful, returns the amount of `asset_id` acquired for the `amount_in`.
+    ///
+    /// The swap is reported as made for `kind`, swaps paying fees are bounded by
+    /// `MaxFeeSwapImpact`.
     pub fn swap_exact_native_for_tokens(
         sender: T::AccountId,
         asset_id: T::AssetId,
@@ -1163,6 +1443,7 @@ impl<T: Config> Pallet<T> {
         amount_out_min: Option<T::AssetBalance>,
         send_to: T::AccountId,
         keep_alive: bool,
+        kind: SwapKind,
     ) -> Result<T::AssetBalance, DispatchError> {
         ensure!(amount_in > Zero::zero(), Error::<T>::ZeroAmount);
         if let Some(amount_out_min) = amount_out_min {
@@ -1186,8 +1467,11 @@ impl<T: Config> Pallet<T> {
         if let Some(amount_out_min) = amount_out_min {
             ensure!(amount_out >= amount_out_min, Error::<T>::ProvidedMaximumNotSufficientForSwap);
         }
+        if kind == SwapKind::Fee {
+            Self::ensure_fee_swap_impact(&path, amount_out)?;
+        }
 
-        Self::do_swap(sender, &amounts, path, send_to, keep_alive)?;
+        Self::do_swap(sender, &amounts, path, send_to, keep_alive, kind)?;
         Ok(amount_out)
     }
     /// Take an `asset_id` and swap some amount of the chain's native asset for `amount_out` of it.
@@ -1195,6 +1479,9 @@ impl<T: Config> Pallet<T> {
     /// too costly.
     ///
     /// If successful returns the amount of the native asset taken to provide `amount_out`.
+    ///
+    /// The swap is reported as made for `kind`, swaps paying fees are bounded by
+    /// `MaxFeeSwapImpact`.
     pub fn swap_native_for_exact_tokens(
         sender: T::AccountId,
         asset_id: T::AssetId,
@@ -1202,6 +1489,7 @@ impl<T: Config> Pallet<T> {
         amount_in_max: Option<T::AssetBalance>,
         send_to: T::AccountId,
         keep_alive: bool,
+        kind: SwapKind,
     ) -> Result<T::AssetBalance, DispatchError> {
         ensure!(amount_out > Zero::zero(), Error::<T>::ZeroAmount);
         if let Some(amount_in_max) = amount_in_max {
@@ -1225,10 +1513,56 @@ impl<T: Config> Pallet<T> {
         if let Some(amount_in_max) = amount_in_max {
             ensure!(amount_in <= amount_in_max, Error::<T>::ProvidedMaximumNotSufficientForSwap);
         }
+        if kind == SwapKind::Fee {
+            Self::ensure_fee_swap_impact(&path, amount_out)?;
+        }
 
-        Self::do_swap(sender, &amounts, path, send_to, keep_alive)?;
+        Self::do_swap(sender, &amounts, path, send_to, keep_alive, kind)?;
         Ok(amount_in)
     }
+
+    /// Take `amount_in` of `asset_id` and swap it for the chain's native asset. If an
+    /// `amount_out_min` is specified, it will return an error if it is unable to acquire the
+    /// amount desired.
+    ///
+    /// If successful, returns the amount of the native asset acquired for the `amount_in`.
+    ///
+    /// Initiated by users redeeming the asset, so the swap is only bounded by their slippage.
+    pub fn swap_exact_tokens_for_native(
+        sender: T::AccountId,
+        asset_id: T::AssetId,
+        amount_in: T::AssetBalance,
+        amount_out_min: Option<T::Balance>,
+        send_to: T::AccountId,
+        keep_alive: bool,
+    ) -> Result<T::Balance, DispatchError> {
+        ensure!(amount_in > Zero::zero(), Error::<T>::ZeroAmount);
+        if let Some(amount_out_min) = amount_out_min {
+            ensure!(amount_out_min > Zero::zero(), Error::<T>::ZeroAmount);
+        }
+
+        let path = vec![
+            T::MultiAssetIdConverter::into_multiasset_id(&asset_id),
+            T::MultiAssetIdConverter::get_native(),
+        ]
+        .try_into()
+        .expect("`MaxSwapPathLength` is ensured by to be greater than 2; pushed only twice; qed");
+
+        // calculate the amount we should receive
+        let amounts = Self::get_amounts_out(&amount_in, &path)?;
+        let amount_out =
+            *amounts.last().defensive_ok_or("get_amounts_out() returned an empty result")?;
+        ensure!(amount_out > Zero::zero(), Error::<T>::ZeroAmount);
+
+        // convert `amount_out` from asset balance type, to native balance type
+        let amount_out = Self::convert_asset_balance_to_native_balance(amount_out)?;
+        if let Some(amount_out_min) = amount_out_min {
+            ensure!(amount_out >= amount_out_min, Error::<T>::ProvidedMinimumNotSufficientForSwap);
+        }
+
+        Self::do_swap(sender, &amounts, path, send_to, keep_alive, SwapKind::User)?;
+        Ok(amount_out)
+    }
 }
 
 sp_api::decl_runtime_apis! {
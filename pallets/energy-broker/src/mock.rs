@@ -26,12 +26,12 @@ use frame_support::{
     ord_parameter_types, parameter_types,
     traits::{
         tokens::{ConversionFromAssetBalance, ConversionToAssetBalance},
-        AsEnsureOriginWithArg, ConstU128, ConstU32,
+        AsEnsureOriginWithArg, ConstBool, ConstU128, ConstU32,
     },
     PalletId,
 };
 use frame_system::{EnsureSigned, EnsureSignedBy};
-use sp_arithmetic::{FixedPointNumber, FixedU128, Permill};
+use sp_arithmetic::{FixedPointNumber, FixedU128, Perbill, Permill};
 use sp_runtime::{
     traits::{AccountIdConversion, IdentityLookup},
     BuildStorage,
@@ -117,6 +117,10 @@ parameter_types! {
     pub const AssetConversionPalletId: PalletId = PalletId(*b"py/ascon");
     pub storage AllowMultiAssetPools: bool = true;
     pub storage LiquidityWithdrawalFee: Permill = Permill::from_percent(0); // should be non-zero if AllowMultiAssetPools is true, otherwise can be zero
+    pub const MaxFeeSwapImpact: Perbill = Perbill::from_percent(10);
+    pub const MaxStabilizationPerCall: u128 = 100;
+    pub const MaxStabilizationPerPeriod: u128 = 150;
+    pub const StabilizationPeriod: u64 = 10;
 }
 
 ord_parameter_types! {
@@ -167,6 +171,12 @@ impl Config for Test {
     type AllowMultiAssetPools = AllowMultiAssetPools;
     type MaxSwapPathLength = ConstU32<4>;
     type MintMinLiquidity = ConstU128<100>; // 100 is good enough when the main currency has 12 decimals.
+    type MaxFeeSwapImpact = MaxFeeSwapImpact;
+    type EmitSwapDetails = ConstBool<true>;
+    type ManageOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type MaxStabilizationPerCall = MaxStabilizationPerCall;
+    type MaxStabilizationPerPeriod = MaxStabilizationPerPeriod;
+    type StabilizationPeriod = StabilizationPeriod;
 
     type Balance = u128;
     type HigherPrecisionBalance = sp_core::U256;
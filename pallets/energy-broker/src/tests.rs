@@ -21,9 +21,12 @@ use frame_support::{
     instances::Instance1,
     traits::{fungible::Inspect, fungibles::InspectEnumerable, Get},
 };
-use sp_arithmetic::Permill;
+use sp_arithmetic::{FixedPointNumber, FixedU128, Perbill, Permill};
 use sp_runtime::DispatchError::BadOrigin;
-use sp_runtime::{DispatchError, TokenError};
+use sp_runtime::{
+    traits::{One, Zero},
+    DispatchError, TokenError,
+};
 
 fn events() -> Vec<Event<Test>> {
     let result = System::events()
@@ -1450,3 +1453,356 @@ fn cannot_block_pool_creation() {
         ));
     });
 }
+
+#[test]
+fn fee_swap_should_not_work_if_price_impact_too_high() {
+    new_test_ext().execute_with(|| {
+        let user = 1;
+        let token_1 = NativeOrAssetId::Native;
+        let token_2 = NativeOrAssetId::Asset(2);
+
+        create_tokens(user, vec![token_2]);
+        assert_ok!(AssetConversion::create_pool(RuntimeOrigin::root(), user, token_1, token_2));
+
+        assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 2000));
+        assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 2000));
+
+        assert_ok!(AssetConversion::add_liquidity(
+            RuntimeOrigin::signed(user),
+            token_1,
+            token_2,
+            1000,
+            1000,
+            1,
+            1,
+            user,
+        ));
+
+        // `MaxFeeSwapImpact` is 10% of the 1000 reserve
+        assert_noop!(
+            AssetConversion::swap_native_for_exact_tokens(
+                user,
+                2,
+                101,
+                None,
+                user,
+                true,
+                SwapKind::Fee
+            ),
+            Error::<Test>::FeeSwapImpactTooHigh
+        );
+        assert_noop!(
+            AssetConversion::swap_exact_native_for_tokens(
+                user,
+                2,
+                60,
+                None,
+                user,
+                true,
+                SwapKind::Fee
+            ),
+            Error::<Test>::FeeSwapImpactTooHigh
+        );
+        assert_ok!(AssetConversion::swap_native_for_exact_tokens(
+            user,
+            2,
+            100,
+            None,
+            user,
+            true,
+            SwapKind::Fee
+        ));
+
+        // swaps initiated by users are not bounded
+        assert_ok!(AssetConversion::swap_tokens_for_exact_tokens(
+            RuntimeOrigin::signed(user),
+            bvec![token_1, token_2],
+            150,
+            100,
+            user,
+            true,
+        ));
+    });
+}
+
+#[test]
+fn swaps_emit_details_tagged_by_kind() {
+    new_test_ext().execute_with(|| {
+        let user = 1;
+        let token_1 = NativeOrAssetId::Native;
+        let token_2 = NativeOrAssetId::Asset(2);
+
+        create_tokens(user, vec![token_2]);
+        assert_ok!(AssetConversion::create_pool(RuntimeOrigin::root(), user, token_1, token_2));
+
+        assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 2000));
+        assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 2000));
+
+        assert_ok!(AssetConversion::add_liquidity(
+            RuntimeOrigin::signed(user),
+            token_1,
+            token_2,
+            1000,
+            1000,
+            1,
+            1,
+            user,
+        ));
+
+        let amount_out = AssetConversion::get_amount_out(&100, (&token_1, &token_2)).unwrap();
+        assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+            RuntimeOrigin::signed(user),
+            bvec![token_1, token_2],
+            100,
+            None,
+            user,
+            true,
+        ));
+        assert!(events().contains(&Event::<Test>::Swapped {
+            who: user,
+            asset_in: token_1,
+            asset_out: token_2,
+            amount_in: 100,
+            amount_out,
+            price_impact: Perbill::from_rational(amount_out, 1000),
+            kind: SwapKind::User,
+        }));
+
+        // the impact of the fee swap is measured against the reserve left by the user swap
+        let asset_reserve = 1000 - amount_out;
+        let amount_in = AssetConversion::get_amount_in(&50, (&token_1, &token_2)).unwrap();
+        assert_ok!(AssetConversion::swap_native_for_exact_tokens(
+            user,
+            2,
+            50,
+            None,
+            user,
+            true,
+            SwapKind::Fee
+        ));
+        assert!(events().contains(&Event::<Test>::Swapped {
+            who: user,
+            asset_in: token_1,
+            asset_out: token_2,
+            amount_in,
+            amount_out: 50,
+            price_impact: Perbill::from_rational(50, asset_reserve),
+            kind: SwapKind::Fee,
+        }));
+
+        // a native swap made for a user, e.g. buying fee credit, is tagged by its caller
+        let asset_reserve = asset_reserve - 50;
+        let amount_in = AssetConversion::get_amount_in(&20, (&token_1, &token_2)).unwrap();
+        assert_ok!(AssetConversion::swap_native_for_exact_tokens(
+            user,
+            2,
+            20,
+            None,
+            user,
+            true,
+            SwapKind::User
+        ));
+        assert!(events().contains(&Event::<Test>::Swapped {
+            who: user,
+            asset_in: token_1,
+            asset_out: token_2,
+            amount_in,
+            amount_out: 20,
+            price_impact: Perbill::from_rational(20, asset_reserve),
+            kind: SwapKind::User,
+        }));
+    });
+}
+
+#[test]
+fn genesis_pool_is_seeded_and_swappable() {
+    let token_1 = NativeOrAssetId::Native;
+    let token_2 = NativeOrAssetId::Asset(2);
+    let (depositor, user) = (1, 2);
+    let (native_reserve, asset_reserve) = (10000, 200);
+
+    let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(depositor, native_reserve + 10000), (user, 20000)],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+    pallet_assets::GenesisConfig::<Test, Instance1> {
+        assets: vec![(2, depositor, false, 1)],
+        metadata: vec![],
+        accounts: vec![(2, depositor, asset_reserve)],
+        next_asset_id: None,
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+    crate::GenesisConfig::<Test> { pools: vec![(depositor, 2, native_reserve, asset_reserve)] }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
+    sp_io::TestExternalities::new(t).execute_with(|| {
+        System::set_block_number(1);
+        assert_eq!(pools(), vec![(token_1, token_2)]);
+        assert_eq!(
+            AssetConversion::get_reserves(&token_1, &token_2),
+            Ok((native_reserve, asset_reserve))
+        );
+        assert_eq!(balance(depositor, token_2), 0);
+
+        let expect_receive =
+            AssetConversion::get_amount_out(&100, (&token_1, &token_2)).ok().unwrap();
+        assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+            RuntimeOrigin::signed(user),
+            bvec![token_1, token_2],
+            100,
+            Some(1),
+            user,
+            false,
+        ));
+        assert_eq!(balance(user, token_2), expect_receive);
+    });
+}
+
+#[test]
+fn stabilize_pool_moves_rate_towards_target_within_bounds() {
+    new_test_ext().execute_with(|| {
+        let user = 1;
+        let token_1 = NativeOrAssetId::Native;
+        let token_2 = NativeOrAssetId::Asset(2);
+        let lp_token = AssetConversion::get_next_pool_asset_id();
+
+        create_tokens(user, vec![token_2]);
+        assert_ok!(AssetConversion::create_pool(RuntimeOrigin::root(), user, token_1, token_2));
+
+        assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 2000));
+        assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 2000));
+        assert_ok!(AssetConversion::add_liquidity(
+            RuntimeOrigin::signed(user),
+            token_1,
+            token_2,
+            1000,
+            400,
+            1,
+            1,
+            user,
+        ));
+        let lp_supply = PoolAssets::total_supply(lp_token);
+        let user_balance = balance(user, token_2);
+        events();
+
+        assert_noop!(
+            AssetConversion::stabilize_pool(RuntimeOrigin::signed(user), 2, FixedU128::one()),
+            BadOrigin
+        );
+        assert_noop!(
+            AssetConversion::stabilize_pool(RuntimeOrigin::root(), 2, FixedU128::zero()),
+            Error::<Test>::ZeroTargetRate
+        );
+        assert_noop!(
+            AssetConversion::stabilize_pool(RuntimeOrigin::root(), 3, FixedU128::one()),
+            Error::<Test>::PoolNotFound
+        );
+
+        // the target needs 600 more units of the asset, but a call mints at most 100
+        assert_ok!(AssetConversion::stabilize_pool(RuntimeOrigin::root(), 2, FixedU128::one()));
+        assert_eq!(AssetConversion::get_reserves(&token_1, &token_2), Ok((1000, 500)));
+        assert_eq!(
+            events(),
+            [Event::<Test>::PoolStabilized {
+                asset: 2,
+                target_rate: FixedU128::one(),
+                rate_before: FixedU128::from_rational(5, 2),
+                rate_after: FixedU128::from(2),
+                minted: 100,
+                burned: 0,
+            }]
+        );
+
+        // only 50 are left of the 150 allowed per period
+        assert_ok!(AssetConversion::stabilize_pool(RuntimeOrigin::root(), 2, FixedU128::one()));
+        assert_eq!(AssetConversion::get_reserves(&token_1, &token_2), Ok((1000, 550)));
+        assert_noop!(
+            AssetConversion::stabilize_pool(RuntimeOrigin::root(), 2, FixedU128::one()),
+            Error::<Test>::StabilizationLimitReached
+        );
+
+        // the allowance resets in the next period and burning counts against it too
+        System::set_block_number(10);
+        let target_rate = FixedU128::from_rational(5, 2);
+        assert_ok!(AssetConversion::stabilize_pool(RuntimeOrigin::root(), 2, target_rate));
+        assert_eq!(AssetConversion::get_reserves(&token_1, &token_2), Ok((1000, 450)));
+
+        // the target is never overshot
+        assert_ok!(AssetConversion::stabilize_pool(RuntimeOrigin::root(), 2, target_rate));
+        assert_eq!(AssetConversion::get_reserves(&token_1, &token_2), Ok((1000, 400)));
+        assert_noop!(
+            AssetConversion::stabilize_pool(RuntimeOrigin::root(), 2, target_rate),
+            Error::<Test>::PoolAlreadyAtTarget
+        );
+        assert_eq!(StabilizationUsage::<Test>::get(2), (1, 150));
+
+        // nobody but the pool is paid and no LP tokens are minted
+        assert_eq!(balance(user, token_2), user_balance);
+        assert_eq!(PoolAssets::total_supply(lp_token), lp_supply);
+    });
+}
+
+#[test]
+fn swap_exact_tokens_for_native_should_respect_minimum_out() {
+    new_test_ext().execute_with(|| {
+        let user = 1;
+        let token_1 = NativeOrAssetId::Native;
+        let token_2 = NativeOrAssetId::Asset(2);
+
+        create_tokens(user, vec![token_2]);
+        assert_ok!(AssetConversion::create_pool(RuntimeOrigin::root(), user, token_1, token_2));
+
+        assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 2000));
+        assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 2000));
+
+        assert_ok!(AssetConversion::add_liquidity(
+            RuntimeOrigin::signed(user),
+            token_1,
+            token_2,
+            1000,
+            1000,
+            1,
+            1,
+            user,
+        ));
+
+        let expected_out = AssetConversion::get_amount_out(&200, &(&token_2, &token_1)).unwrap();
+        assert_noop!(
+            AssetConversion::swap_exact_tokens_for_native(
+                user,
+                2,
+                200,
+                Some(expected_out + 1),
+                user,
+                true
+            ),
+            Error::<Test>::ProvidedMinimumNotSufficientForSwap
+        );
+
+        let native_balance = balance(user, token_1);
+        let asset_balance = balance(user, token_2);
+        // redemptions are not bounded by `MaxFeeSwapImpact`
+        assert_eq!(
+            AssetConversion::swap_exact_tokens_for_native(
+                user,
+                2,
+                200,
+                Some(expected_out),
+                user,
+                true
+            ),
+            Ok(expected_out)
+        );
+        assert_eq!(balance(user, token_1), native_balance + expected_out);
+        assert_eq!(balance(user, token_2), asset_balance - 200);
+
+        assert_noop!(
+            AssetConversion::swap_exact_tokens_for_native(user, 2, 0, None, user, true),
+            Error::<Test>::ZeroAmount
+        );
+    });
+}
@@ -33,6 +33,15 @@ pub struct PoolInfo<PoolAssetId> {
     pub lp_token: PoolAssetId,
 }
 
+/// Who a swap was made for.
+#[derive(Decode, Encode, MaxEncodedLen, TypeInfo, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SwapKind {
+    /// Initiated by a user, through an extrinsic or a redemption.
+    User,
+    /// Made on behalf of an account to pay a transaction fee.
+    Fee,
+}
+
 /// A trait that converts between a MultiAssetId and either the native currency or an AssetId.
 pub trait MultiAssetIdConverter<MultiAssetId, AssetId> {
     /// Returns the MultiAssetId reperesenting the native currency of the chain.
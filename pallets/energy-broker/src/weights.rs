@@ -57,6 +57,7 @@ pub trait WeightInfo {
 	fn remove_liquidity() -> Weight;
 	fn swap_exact_tokens_for_tokens() -> Weight;
 	fn swap_tokens_for_exact_tokens() -> Weight;
+	fn stabilize_pool() -> Weight;
 }
 
 /// Weights for pallet_asset_conversion using the Substrate node and recommended hardware.
@@ -157,6 +158,25 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(10_u64))
 			.saturating_add(T::DbWeight::get().writes(10_u64))
 	}
+	/// Storage: AssetConversion Pools (r:1 w:0)
+	/// Proof: AssetConversion Pools (max_values: None, max_size: Some(30), added: 2505, mode: MaxEncodedLen)
+	/// Storage: AssetConversion StabilizationUsage (r:1 w:1)
+	/// Proof: AssetConversion StabilizationUsage (max_values: None, max_size: Some(40), added: 2515, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:0)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Proof: Assets Asset (max_values: None, max_size: Some(210), added: 2685, mode: MaxEncodedLen)
+	/// Storage: Assets Account (r:1 w:1)
+	/// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	fn stabilize_pool() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1024`
+		//  Estimated: `3675`
+		// Minimum execution time: 68_412_000 picoseconds.
+		Weight::from_parts(70_135_000, 3675)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -256,4 +276,23 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(10_u64))
 			.saturating_add(RocksDbWeight::get().writes(10_u64))
 	}
+	/// Storage: AssetConversion Pools (r:1 w:0)
+	/// Proof: AssetConversion Pools (max_values: None, max_size: Some(30), added: 2505, mode: MaxEncodedLen)
+	/// Storage: AssetConversion StabilizationUsage (r:1 w:1)
+	/// Proof: AssetConversion StabilizationUsage (max_values: None, max_size: Some(40), added: 2515, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:0)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Proof: Assets Asset (max_values: None, max_size: Some(210), added: 2685, mode: MaxEncodedLen)
+	/// Storage: Assets Account (r:1 w:1)
+	/// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	fn stabilize_pool() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1024`
+		//  Estimated: `3675`
+		// Minimum execution time: 68_412_000 picoseconds.
+		Weight::from_parts(70_135_000, 3675)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
 }
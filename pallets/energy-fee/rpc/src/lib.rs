@@ -23,7 +23,36 @@
 //! - `energyFee_vtrsToVnrgSwapRate`: Gets current VTRS/VNRG exchange rate
 //! - Parameters:
 //!   - Optional block hash
-//! - Returns: Exchange rate as u128
+//! - Returns: `SwapRateQuote` with the rate, the block it was computed at, the last block it
+//!   should be used in and the recommended slippage, or `null` without a rate
+//! - Quotes are point-in-time: the rate may have moved by the time a swap is included, so
+//!   wallets should apply the recommended slippage and requote after `validUntil`
+//!
+//! ### Fee Payment Assets
+//! - `energyFee_feePayableAssets`: Lists the assets with a conversion rate
+//! - Parameters:
+//!   - Optional block hash
+//! - Returns: `(asset, rate, has broker pool)` for each asset
+//!
+//! ### Pending Fee Obligations
+//! - `energyFee_pendingFeeObligations`: Sums the VNRG fees of the account's ready transactions
+//!   in the pool
+//! - Parameters:
+//!   - Account ID
+//!   - Optional block hash
+//! - Returns: Total fee in VNRG
+//! - The pool is local to the queried node, so the result isn't part of consensus and may
+//!   differ between nodes
+//! - Unsafe: every ready transaction of the pool is decoded by the runtime, whoever sent it
+//!
+//! ### EVM Fee Receipts
+//! - `energyFee_evmFeeReceipt`: Gets the VNRG fee paid by a recent Ethereum transaction
+//! - Parameters:
+//!   - Transaction hash
+//! - Returns: `(block number, fee, finalized)`, or `null` for unknown or pruned receipts
+//! - Always answered from the best block, which descends from the finalized one: receipts of
+//!   blocks reverted by a reorg are never returned, and receipts of blocks which may still be
+//!   reverted are reported as not finalized
 //!
 //! ## Implementation Details
 //! - Uses runtime API to perform calculations
@@ -42,17 +71,72 @@ use jsonrpsee::{
     types::{ErrorCode, ErrorObject},
 };
 use parity_scale_codec::{Codec, Decode};
+use sc_rpc_api::DenyUnsafe;
+use sc_transaction_pool_api::{InPoolTransaction, TransactionPool};
+use serde::{Deserialize, Serialize};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
-use sp_core::Bytes;
-use sp_runtime::traits::Block as BlockT;
+use sp_core::{Bytes, H256};
+use sp_runtime::{
+    traits::{Block as BlockT, NumberFor, Saturating},
+    FixedU128, Perbill,
+};
 use std::sync::Arc;
 // Runtime API imports.
 pub use energy_fee_runtime_api::EnergyFeeApi as EnergyFeeRuntimeApi;
 use energy_fee_runtime_api::{CallRequest, FeeDetails};
 
+/// Slippage and validity the node recommends for swaps built from its rate quotes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SwapQuoteConfig {
+    /// Slippage recommended for swaps built from a quote.
+    pub default_slippage: Perbill,
+    /// Number of blocks after the quoted one a quote should still be used in.
+    pub max_age: u32,
+}
+
+impl Default for SwapQuoteConfig {
+    fn default() -> Self {
+        Self { default_slippage: Perbill::from_parts(5_000_000), max_age: 10 }
+    }
+}
+
+impl SwapQuoteConfig {
+    /// Quote `rate` as computed at the block `at_hash` numbered `at_block`.
+    pub fn quote<BlockHash, BlockNumber: Saturating + From<u32> + Copy>(
+        &self,
+        rate: u128,
+        at_hash: BlockHash,
+        at_block: BlockNumber,
+    ) -> SwapRateQuote<BlockHash, BlockNumber> {
+        SwapRateQuote {
+            rate,
+            at_hash,
+            valid_until: at_block.saturating_add(self.max_age.into()),
+            at_block,
+            recommended_slippage: self.default_slippage,
+        }
+    }
+}
+
+/// A VTRS/VNRG swap rate as of a block.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapRateQuote<BlockHash, BlockNumber> {
+    /// The swap rate.
+    pub rate: u128,
+    /// Hash of the block the rate was computed at.
+    pub at_hash: BlockHash,
+    /// Number of the block the rate was computed at.
+    pub at_block: BlockNumber,
+    /// Last block a swap built from the quote should be submitted for.
+    pub valid_until: BlockNumber,
+    /// Slippage to allow for when building a swap from the quote.
+    pub recommended_slippage: Perbill,
+}
+
 #[rpc(server, client)]
-pub trait EnergyFeeApi<BlockHash, AccountId, Balance, Call> {
+pub trait EnergyFeeApi<BlockHash, BlockNumber, AccountId, Balance, Call, AssetId> {
     #[method(name = "energyFee_estimateGas")]
     fn estimate_gas(&self, request: CallRequest, at: Option<BlockHash>) -> RpcResult<U256>;
 
@@ -65,30 +149,67 @@ pub trait EnergyFeeApi<BlockHash, AccountId, Balance, Call> {
     ) -> RpcResult<Option<FeeDetails<Balance>>>;
 
     #[method(name = "energyFee_vtrsToVnrgSwapRate")]
-    fn vtrs_to_vnrg_swap_rate(&self, at: Option<BlockHash>) -> RpcResult<Option<u128>>;
+    fn vtrs_to_vnrg_swap_rate(
+        &self,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<SwapRateQuote<BlockHash, BlockNumber>>>;
+
+    #[method(name = "energyFee_feePayableAssets")]
+    fn fee_payable_assets(
+        &self,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(AssetId, FixedU128, bool)>>;
+
+    #[method(name = "energyFee_pendingFeeObligations")]
+    fn pending_fee_obligations(
+        &self,
+        account: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Balance>;
+
+    #[method(name = "energyFee_evmFeeReceipt")]
+    fn evm_fee_receipt(&self, tx_hash: H256) -> RpcResult<Option<(BlockNumber, Balance, bool)>>;
 }
 
-pub struct EnergyFee<C, B> {
+pub struct EnergyFee<C, P, B> {
     client: Arc<C>,
+    pool: Arc<P>,
+    deny_unsafe: DenyUnsafe,
+    quote_config: SwapQuoteConfig,
     _marker: std::marker::PhantomData<B>,
 }
 
-impl<C, B> EnergyFee<C, B> {
-    pub fn new(client: Arc<C>) -> Self {
-        Self { client, _marker: Default::default() }
+impl<C, P, B> EnergyFee<C, P, B> {
+    pub fn new(client: Arc<C>, pool: Arc<P>, deny_unsafe: DenyUnsafe) -> Self {
+        Self {
+            client,
+            pool,
+            deny_unsafe,
+            quote_config: Default::default(),
+            _marker: Default::default(),
+        }
+    }
+
+    /// Use `config` for the slippage and validity of swap rate quotes.
+    pub fn with_quote_config(mut self, config: SwapQuoteConfig) -> Self {
+        self.quote_config = config;
+        self
     }
 }
 
-impl<C, Block, AccountId, Balance, Call>
-    EnergyFeeApiServer<<Block as BlockT>::Hash, AccountId, Balance, Call> for EnergyFee<C, Block>
+impl<C, P, Block, AccountId, Balance, Call, AssetId>
+    EnergyFeeApiServer<<Block as BlockT>::Hash, NumberFor<Block>, AccountId, Balance, Call, AssetId>
+    for EnergyFee<C, P, Block>
 where
     Block: BlockT,
-    AccountId: Codec,
-    Balance: Codec,
+    AccountId: Codec + PartialEq,
+    Balance: Codec + Default + Saturating,
     Call: Codec,
+    AssetId: Codec,
     C: Send + Sync + 'static,
     C: ProvideRuntimeApi<Block> + HeaderBackend<Block>,
-    C::Api: EnergyFeeRuntimeApi<Block, AccountId, Balance, Call>,
+    C::Api: EnergyFeeRuntimeApi<Block, AccountId, Balance, Call, AssetId>,
+    P: TransactionPool<Block = Block> + 'static,
 {
     fn estimate_gas(
         &self,
@@ -141,18 +262,130 @@ where
     fn vtrs_to_vnrg_swap_rate(
         &self,
         at: Option<<Block as BlockT>::Hash>,
-    ) -> RpcResult<Option<u128>> {
+    ) -> RpcResult<Option<SwapRateQuote<<Block as BlockT>::Hash, NumberFor<Block>>>> {
         let api = self.client.runtime_api();
         let at = at.unwrap_or(
             // If the block hash is not supplied assume the best block.
             self.client.info().best_hash,
         );
-        api.vtrs_to_vnrg_swap_rate(at).map_err(|e| {
+        let rate = api.vtrs_to_vnrg_swap_rate(at).map_err(|e| {
             ErrorObject::owned(
                 ErrorCode::InternalError.code(),
                 "Unable to query vtrs_to_vnrg_swap_rate.",
                 Some(e.to_string()),
             )
+        })?;
+        let Some(rate) = rate else {
+            return Ok(None);
+        };
+
+        let at_block = self
+            .client
+            .number(at)
+            .map_err(|e| {
+                ErrorObject::owned(
+                    ErrorCode::InternalError.code(),
+                    "Unable to query the block number.",
+                    Some(e.to_string()),
+                )
+            })?
+            .ok_or_else(|| {
+                ErrorObject::owned(
+                    ErrorCode::InvalidParams.code(),
+                    "Unknown block.",
+                    Some(at.to_string()),
+                )
+            })?;
+
+        Ok(Some(self.quote_config.quote(rate, at, at_block)))
+    }
+
+    fn fee_payable_assets(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<(AssetId, FixedU128, bool)>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        );
+        api.fee_payable_assets(at).map_err(|e| {
+            ErrorObject::owned(
+                ErrorCode::InternalError.code(),
+                "Unable to query fee_payable_assets.",
+                Some(e.to_string()),
+            )
         })
     }
+
+    fn pending_fee_obligations(
+        &self,
+        account: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Balance> {
+        // costs a runtime call per transaction in the pool
+        self.deny_unsafe.check_if_safe()?;
+
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        );
+
+        let mut total = Balance::default();
+        for tx in self.pool.ready() {
+            let fee = api.extrinsic_fee(at, tx.data().clone()).map_err(|e| {
+                ErrorObject::owned(
+                    ErrorCode::InternalError.code(),
+                    "Unable to query extrinsic_fee.",
+                    Some(e.to_string()),
+                )
+            })?;
+            if let Some((_, fee)) = fee.filter(|(signer, _)| *signer == account) {
+                total = total.saturating_add(fee);
+            }
+        }
+        Ok(total)
+    }
+
+    fn evm_fee_receipt(
+        &self,
+        tx_hash: H256,
+    ) -> RpcResult<Option<(NumberFor<Block>, Balance, bool)>> {
+        let api = self.client.runtime_api();
+        let info = self.client.info();
+
+        let receipt = api.evm_fee_receipt(info.best_hash, tx_hash).map_err(|e| {
+            ErrorObject::owned(
+                ErrorCode::InternalError.code(),
+                "Unable to query evm_fee_receipt.",
+                Some(e.to_string()),
+            )
+        })?;
+        Ok(receipt.map(|(block, fee)| (block, fee, block <= info.finalized_number)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_carry_their_block_and_validity() {
+        let config = SwapQuoteConfig { default_slippage: Perbill::from_percent(1), max_age: 5 };
+        let quote = config.quote(42, H256::repeat_byte(1), 100u32);
+        assert_eq!(
+            quote,
+            SwapRateQuote {
+                rate: 42,
+                at_hash: H256::repeat_byte(1),
+                at_block: 100,
+                valid_until: 105,
+                recommended_slippage: Perbill::from_percent(1),
+            }
+        );
+
+        // the validity saturates instead of wrapping around
+        assert_eq!(config.quote(42, H256::zero(), u32::MAX).valid_until, u32::MAX);
+    }
 }
@@ -19,12 +19,63 @@
 //! - `vtrs`: Native token fee amount
 //! - `vnrg`: Energy token fee amount
 //!
+//! ### FeeClass
+//! How the fee of a call is priced:
+//! - `Constant`: Base fee scaled by the fee multiplier
+//! - `Weight`: Fee computed from the call weight and length
+//! - `EVM`: Fee charged by the EVM for the executed gas
+//! - `Zero`: No fee is charged
+//! - `Nested`: Fee derived from the wrapped calls
+//!
+//! ### RateDivergence
+//! Relative difference between the broker spot rate and the oracle rate of VNRG:
+//! - `magnitude`: `|spot - oracle| / oracle`, saturating at 100%
+//! - `spot_above_oracle`: Whether the broker prices VNRG higher than the oracle
+//!
+//! ### FeePaymentPath
+//! The ordered steps taken to pay the fee of a transaction, each a `FeePaymentStep`:
+//! - `FeeCredit`: Prepaid fee credit spent
+//! - `AssetExchanged`: VNRG bought with an asset at its conversion rate
+//! - `Swapped`: Missing VNRG swapped for VTRS, the rate is `amount / price`
+//! - `FeeToken`: VNRG withdrawn
+//!
 //! ## Runtime API Methods
 //!
 //! ### Fee Estimation
-//! - `estimate_gas`: Calculate gas cost for EVM calls
+//! - `estimate_gas`: Calculate gas cost for EVM calls, dry running contract calls and adding
+//!   the governance-set buffer to the gas they used
 //! - `estimate_call_fee`: Calculate total fee for runtime calls
+//! - `fee_range`: Get the lowest and highest VNRG fee of a call over the fee multiplier range
+//! - `can_afford_batch`: Check whether an account can pay the fee of a `Utility::batch` of
+//!   SCALE encoded calls, exchanging missing VNRG for VTRS like fee withdrawal does. Nested
+//!   batches and EVM calls are priced like in the batch itself; undecodable calls are never
+//!   affordable
+//! - `validator_operating_cost`: Estimate the VNRG fees a validator spends per era on payouts,
+//!   client version reports and session key rotation
 //! - `vtrs_to_vnrg_swap_rate`: Get current token exchange rate
+//! - `energy_price_history`: Get up to `samples` snapshots of the VTRS/VNRG broker rate at
+//!   least `interval_blocks` apart, for charting
+//! - `rate_divergence`: Compare the broker spot rate with the oracle rate, if both exist
+//! - `fee_classification`: Get the fee class of every call as
+//!   `(pallet index, call name, class)`
+//! - `evm_fee_paid`: Get the fee token amount paid by a recently included Ethereum
+//!   transaction, by its hash
+//! - `evm_fee_receipt`: Like `evm_fee_paid`, with the number of the block including the
+//!   transaction, to tell whether the receipt is finalized
+//! - `evm_fee_floor`: Get the lowest VNRG fee an Ethereum transaction is charged
+//! - `fee_payable_assets`: List every asset with a conversion rate as
+//!   `(asset, rate, has broker pool)`; assets without a pool are oracle-only
+//! - `extrinsic_fee`: Get the signer and the VNRG fee charged for a signed or Ethereum
+//!   extrinsic, used to sum the fees of the transactions waiting in the pool
+//! - `fee_payment_path`: Get the steps taken to pay the fee of the extrinsic at an index of a
+//!   recent block, for audits and dispute resolution
+//! - `fee_spend_history`: Get the VNRG an account spent on fees in each of its latest eras
+//!   with fees, as `(era, amount)` oldest first
+//!
+//! ### Governance Support
+//! - `simulate_fee_change`: Calculate fees for sample calls under proposed base fee and
+//!   multiplier without changing the live parameters
+//! - `multiplier_at`: Get the fee multiplier applied in a recent block
 //!
 //! ## Implementation Notes
 //! - No-std compatible
@@ -37,11 +88,15 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use ethereum::AccessListItem;
-use ethereum_types::{H160, U256};
-use parity_scale_codec::{Codec, Decode, Encode};
+use ethereum_types::{H160, H256, U256};
+use parity_scale_codec::{Codec, Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
+use sp_runtime::{
+    traits::{Block as BlockT, NumberFor},
+    FixedU128, Perbill,
+};
 use sp_std::prelude::*;
 
 /// Introduced for compatibility with eth_estimateGas RPC schema.
@@ -57,6 +112,12 @@ impl Bytes {
     }
 }
 
+impl From<Vec<u8>> for Bytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
 /// Introduced for compatibility with eth_estimateGas RPC schema.
 /// Similar to fc_rpc_core::types::CallRequest, which does not
 /// implement necessary traits
@@ -83,17 +144,104 @@ pub struct FeeDetails<Balance> {
     pub vnrg: Balance,
 }
 
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+pub enum FeeClass {
+    Constant,
+    Weight,
+    EVM,
+    Zero,
+    Nested,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+pub struct RateDivergence {
+    pub magnitude: Perbill,
+    pub spot_above_oracle: bool,
+}
+
+/// A step taken to pay the fee of a transaction, also kept in the energy fee pallet's storage.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+pub enum FeePaymentStep<AssetId, Balance> {
+    /// Fee credit bought in advance was spent
+    FeeCredit { amount: Balance },
+    /// `amount` of fee token was bought with `price` of `asset` at the asset's conversion rate
+    AssetExchanged { asset: AssetId, amount: Balance, price: Balance },
+    /// `amount` of missing fee token was swapped for `price` of main token
+    Swapped { amount: Balance, price: Balance },
+    /// `amount` of fee token was withdrawn
+    FeeToken { amount: Balance },
+}
+
+pub type FeePaymentPath<AssetId, Balance> = Vec<FeePaymentStep<AssetId, Balance>>;
+
 sp_api::decl_runtime_apis! {
-    pub trait EnergyFeeApi<AccountId, Balance, Call>
+    #[api_version(16)]
+    pub trait EnergyFeeApi<AccountId, Balance, Call, AssetId>
     where
         AccountId: Codec,
         Balance: Codec,
         Call: Codec,
+        AssetId: Codec,
     {
         fn estimate_gas(request: CallRequest) -> U256;
 
         fn estimate_call_fee(account: AccountId, call: Call) -> Option<FeeDetails<Balance>>;
 
+        #[api_version(5)]
+        fn fee_range(call: Call) -> (Balance, Balance);
+
+        #[api_version(14)]
+        fn can_afford_batch(who: AccountId, calls: Vec<Vec<u8>>) -> bool;
+
+        #[api_version(13)]
+        fn validator_operating_cost() -> Balance;
+
+        #[api_version(2)]
+        fn simulate_fee_change(
+            new_base: Balance,
+            new_multiplier: FixedU128,
+            sample_calls: Vec<Call>,
+        ) -> Vec<Balance>;
+
+        #[api_version(3)]
+        fn multiplier_at(block: NumberFor<Block>) -> Option<FixedU128>;
+
         fn vtrs_to_vnrg_swap_rate() -> Option<u128>;
+
+        #[api_version(10)]
+        fn energy_price_history(samples: u32, interval_blocks: u32) -> Vec<(NumberFor<Block>, Balance)>;
+
+        #[api_version(4)]
+        fn fee_classification() -> Vec<(u8, Vec<u8>, FeeClass)>;
+
+        #[api_version(6)]
+        fn rate_divergence() -> Option<RateDivergence>;
+
+        #[api_version(7)]
+        fn fee_payable_assets() -> Vec<(AssetId, FixedU128, bool)>;
+
+        #[api_version(8)]
+        fn evm_fee_paid(tx_hash: H256) -> Option<Balance>;
+
+        #[api_version(11)]
+        fn evm_fee_receipt(tx_hash: H256) -> Option<(NumberFor<Block>, Balance)>;
+
+        #[api_version(9)]
+        fn extrinsic_fee(uxt: <Block as BlockT>::Extrinsic) -> Option<(AccountId, Balance)>;
+
+        #[api_version(12)]
+        fn evm_fee_floor() -> Balance;
+
+        #[api_version(15)]
+        fn fee_payment_path(
+            block: NumberFor<Block>,
+            index: u32,
+        ) -> Option<FeePaymentPath<AssetId, Balance>>;
+
+        #[api_version(16)]
+        fn fee_spend_history(who: AccountId) -> Vec<(u32, Balance)>;
     }
 }
@@ -3,6 +3,7 @@
 use super::*;
 
 use frame_benchmarking::v2::*;
+use frame_support::assert_ok;
 use frame_system::RawOrigin;
 use sp_runtime::traits::One;
 
@@ -10,6 +11,19 @@ fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
     frame_system::Pallet::<T>::assert_last_event(generic_event.into());
 }
 
+fn fund_delegation_deposit<T: Config>(who: &T::AccountId) {
+    let amount = T::FeeDiscountDelegationDeposit::get()
+        .max(T::MainTokenBalanced::minimum_balance())
+        .saturating_mul(10u32.into());
+    T::MainTokenBalanced::make_free_balance_be(who, amount);
+}
+
+fn fee_token_amount<T: Config>() -> BalanceOf<T> {
+    T::FeeTokenBalanced::minimum_balance()
+        .max(One::one())
+        .saturating_mul(10u32.into())
+}
+
 #[benchmarks]
 mod benchmarks {
     use super::*;
@@ -38,5 +52,181 @@ mod benchmarks {
         assert_last_event::<T>(Event::<T>::UpperFeeMultiplierUpdated { new_multiplier }.into());
     }
 
-    impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(0), crate::mock::Test);
+    #[benchmark]
+    fn update_block_gas_limit() {
+        let new_limit = T::MaxBlockGasLimit::get();
+        #[extrinsic_call]
+        _(RawOrigin::Root, new_limit);
+        assert_last_event::<T>(Event::<T>::BlockGasLimitScheduled { new_limit }.into());
+    }
+
+    #[benchmark]
+    fn delegate_fee_discount() {
+        let caller: T::AccountId = whitelisted_caller();
+        let previous: T::AccountId = account("previous", 0, 0);
+        let delegate: T::AccountId = account("delegate", 0, 0);
+        fund_delegation_deposit::<T>(&caller);
+        // replacing an accepted delegation is the worst case
+        assert_ok!(Pallet::<T>::delegate_fee_discount(
+            RawOrigin::Signed(caller.clone()).into(),
+            previous.clone()
+        ));
+        assert_ok!(Pallet::<T>::accept_fee_discount_delegation(
+            RawOrigin::Signed(previous).into(),
+            caller.clone()
+        ));
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), delegate.clone());
+
+        assert_last_event::<T>(
+            Event::<T>::FeeDiscountDelegated { delegator: caller, delegate }.into(),
+        );
+    }
+
+    #[benchmark]
+    fn accept_fee_discount_delegation() {
+        let delegator: T::AccountId = account("delegator", 0, 0);
+        let caller: T::AccountId = whitelisted_caller();
+        fund_delegation_deposit::<T>(&delegator);
+        assert_ok!(Pallet::<T>::delegate_fee_discount(
+            RawOrigin::Signed(delegator.clone()).into(),
+            caller.clone()
+        ));
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), delegator.clone());
+
+        assert_last_event::<T>(
+            Event::<T>::FeeDiscountDelegationAccepted { delegator, delegate: caller }.into(),
+        );
+    }
+
+    #[benchmark]
+    fn revoke_fee_discount_delegation() {
+        let caller: T::AccountId = whitelisted_caller();
+        let delegate: T::AccountId = account("delegate", 0, 0);
+        fund_delegation_deposit::<T>(&caller);
+        assert_ok!(Pallet::<T>::delegate_fee_discount(
+            RawOrigin::Signed(caller.clone()).into(),
+            delegate.clone()
+        ));
+        assert_ok!(Pallet::<T>::accept_fee_discount_delegation(
+            RawOrigin::Signed(delegate.clone()).into(),
+            caller.clone()
+        ));
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()));
+
+        assert_last_event::<T>(
+            Event::<T>::FeeDiscountDelegationRevoked { delegator: caller, delegate }.into(),
+        );
+    }
+
+    #[benchmark]
+    fn update_fee_swap_limit() {
+        let new_limit = Some(1);
+        #[extrinsic_call]
+        _(RawOrigin::Root, new_limit);
+        assert_last_event::<T>(Event::<T>::FeeSwapLimitUpdated { new_limit }.into());
+    }
+
+    #[benchmark]
+    fn approve_energy() {
+        let caller: T::AccountId = whitelisted_caller();
+        let spender: T::AccountId = account("spender", 0, 0);
+        let amount = fee_token_amount::<T>();
+        // a new allowance, counted against the limit, is the worst case
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), spender.clone(), amount);
+
+        assert_eq!(EnergyAllowanceCount::<T>::get(&caller), 1);
+        assert_last_event::<T>(
+            Event::<T>::EnergyApproval { owner: caller, spender, amount }.into(),
+        );
+    }
+
+    #[benchmark]
+    fn transfer_energy_from() -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        let from: T::AccountId = account("from", 0, 0);
+        let to: T::AccountId = account("to", 0, 0);
+        let amount = fee_token_amount::<T>();
+        T::FeeTokenBalanced::set_balance(&from, amount.saturating_mul(2u32.into()));
+        // spending the whole allowance removes it, which is the worst case
+        assert_ok!(Pallet::<T>::approve_energy(
+            RawOrigin::Signed(from.clone()).into(),
+            caller.clone(),
+            amount
+        ));
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), from.clone(), to.clone(), amount);
+
+        assert_eq!(T::FeeTokenBalanced::balance(&to), amount);
+        assert_last_event::<T>(
+            Event::<T>::EnergyTransferredFrom { spender: caller, from, to, amount }.into(),
+        );
+        Ok(())
+    }
+
+    #[benchmark]
+    fn buy_fee_credit() -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        let amount = fee_token_amount::<T>();
+        let price = T::EnergyExchange::convert_from_output(amount)?;
+        T::MainTokenBalanced::make_free_balance_be(
+            &caller,
+            price
+                .saturating_add(T::MainTokenBalanced::minimum_balance())
+                .saturating_mul(10u32.into()),
+        );
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), amount, price);
+
+        assert_eq!(FeeCredit::<T>::get(&caller), amount);
+        assert_last_event::<T>(Event::<T>::FeeCreditBought { who: caller, amount, price }.into());
+        Ok(())
+    }
+
+    #[benchmark]
+    fn claim_rebate() -> Result<(), BenchmarkError> {
+        // only the rebates of an era that ended can be claimed
+        let era = T::RebateEra::get().checked_sub(1).ok_or(BenchmarkError::Skip)?;
+        let caller: T::AccountId = whitelisted_caller();
+        let amount = fee_token_amount::<T>();
+        T::FeeTokenBalanced::set_balance(&T::RebatePot::get(), amount.saturating_mul(2u32.into()));
+        RebatePools::<T>::insert(
+            era,
+            RebatePool { amount, unclaimed: amount, contributions: amount },
+        );
+        FeeContributions::<T>::insert(era, &caller, amount);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), era);
+
+        assert!(FeeContributions::<T>::get(era, &caller).is_zero());
+        assert_last_event::<T>(Event::<T>::RebateClaimed { who: caller, era, amount }.into());
+        Ok(())
+    }
+
+    #[benchmark]
+    fn redeem_energy() -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        let amount = fee_token_amount::<T>();
+        T::FeeTokenBalanced::set_balance(&caller, amount.saturating_mul(2u32.into()));
+        let price = T::EnergyRedemption::quote_redemption(amount)?;
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), amount, price);
+
+        assert_eq!(T::FeeTokenBalanced::balance(&caller), amount);
+        assert_last_event::<T>(Event::<T>::EnergyRedeemed { who: caller, amount, price }.into());
+        Ok(())
+    }
+
+    impl_benchmark_test_suite!(Pallet, crate::mock::new_benchmark_ext(), crate::mock::Test);
 }
@@ -3,33 +3,85 @@
 use crate::{BalanceOf, CallFee, Config, CustomFee, Pallet};
 use core::fmt::Debug;
 use frame_support::dispatch::{Callable, DispatchInfo};
-use frame_support::traits::IsSubType;
+use frame_support::traits::{ConstU32, Get, IsSubType};
+use frame_support::BoundedVec;
 use pallet_sudo::{Config as SudoConfig, Pallet as SudoPallet};
 use pallet_transaction_payment::{
     Config as TransactionPaymentConfig, OnChargeTransaction, Pallet as TransactionPaymentPallet,
 };
-use parity_scale_codec::{Decode, Encode};
+use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
+use sp_core::RuntimeDebug;
 use sp_runtime::{
     traits::{DispatchInfoOf, Dispatchable, SignedExtension},
-    transaction_validity::{InvalidTransaction, TransactionValidityError},
+    transaction_validity::{
+        InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+    },
 };
 use sp_std::marker::PhantomData;
 
+/// Maximum number of fee sources a transaction can list
+pub const MAX_FEE_SOURCES: u32 = 4;
+
+/// A source the fee of a transaction can be paid from
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub enum FeeSource<AssetId> {
+    /// The fee token balance of the account
+    FeeToken,
+    /// An asset exchanged for the fee token at its conversion rate
+    Asset(AssetId),
+    /// The main token exchanged for the fee token, always tried last
+    MainToken,
+}
+
+/// Fee sources of a transaction in order of preference
+pub type FeePreferences<T> =
+    BoundedVec<FeeSource<<T as pallet_asset_rate::Config>::AssetKind>, ConstU32<MAX_FEE_SOURCES>>;
+
 /// A structure to validate transactions based on user call's fee during the pre-dispatch phase.
+///
+/// The signer can list the sources the fee should be paid from in order of preference, the
+/// first one covering the fee is used. Without preferences the fee is paid with the fee token,
+/// exchanging the main token for what is missing.
 #[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
 #[scale_info(skip_type_params(T))]
-pub struct CheckEnergyFee<T: Config>(PhantomData<T>);
+pub struct CheckEnergyFee<T: Config>(FeePreferences<T>, PhantomData<T>);
 
 impl<T: Config> Debug for CheckEnergyFee<T> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.debug_tuple("CheckEnergyFee").finish()
+        f.debug_tuple("CheckEnergyFee").field(&self.0).finish()
     }
 }
 
 impl<T: Config> CheckEnergyFee<T> {
     pub fn new() -> Self {
-        Self(PhantomData)
+        Self(Default::default(), PhantomData)
+    }
+
+    pub fn with_preferences(preferences: FeePreferences<T>) -> Self {
+        Self(preferences, PhantomData)
+    }
+}
+
+impl<T: Config + SudoConfig> CheckEnergyFee<T>
+where
+    <T as frame_system::Config>::RuntimeCall:
+        Dispatchable<Info = DispatchInfo> + IsSubType<<SudoPallet<T> as Callable<T>>::RuntimeCall>,
+    <T as TransactionPaymentConfig>::OnChargeTransaction:
+        OnChargeTransaction<T, Balance = BalanceOf<T>>,
+{
+    /// Fee `who` pays for `call`, after the discount of their NAC level and the fee floor.
+    fn fee(
+        who: &T::AccountId,
+        call: &<T as frame_system::Config>::RuntimeCall,
+        info: &DispatchInfoOf<<T as frame_system::Config>::RuntimeCall>,
+        len: usize,
+    ) -> BalanceOf<T> {
+        let fee = TransactionPaymentPallet::<T>::compute_fee(len as u32, info, 0u32.into());
+        let fee = match T::CustomFee::dispatch_info_to_fee(call, Some(info), Some(fee)) {
+            CallFee::Regular(custom_fee) | CallFee::EVM(custom_fee) => custom_fee,
+        };
+        Pallet::<T>::charged_fee(who, call, fee)
     }
 }
 
@@ -50,25 +102,51 @@ where
         Ok(())
     }
 
+    // The fee is withdrawn by `ChargeTransactionPayment`, which comes after this extension and
+    // knows the tip. The payment is prepared from the preferred fee sources when the fee is
+    // withdrawn, on validation as well.
+    fn validate(
+        &self,
+        _who: &Self::AccountId,
+        call: &Self::Call,
+        _info: &DispatchInfoOf<Self::Call>,
+        _len: usize,
+    ) -> TransactionValidity {
+        if !Pallet::<T>::is_fee_paused(call) {
+            Pallet::<T>::note_fee_preferences(self.0.clone());
+        }
+        Ok(ValidTransaction::default())
+    }
+
     fn pre_dispatch(
         self,
-        _who: &Self::AccountId,
+        who: &Self::AccountId,
         call: &Self::Call,
         info: &DispatchInfoOf<Self::Call>,
         len: usize,
     ) -> Result<Self::Pre, TransactionValidityError> {
+        let fee = Self::fee(who, call, info, len);
+
         // Check if call is sudo
-        if call.is_sub_type().is_some() {
-            return Ok(());
+        if call.is_sub_type().is_none() {
+            Pallet::<T>::validate_call_fee(fee).map_err(|_| {
+                TransactionValidityError::Invalid(InvalidTransaction::ExhaustsResources)
+            })?;
         }
 
-        let fee = TransactionPaymentPallet::<T>::compute_fee(len as u32, info, 0u32.into());
-        let fee = match T::CustomFee::dispatch_info_to_fee(call, Some(info), Some(fee)) {
-            CallFee::Regular(custom_fee) | CallFee::EVM(custom_fee) => custom_fee,
-        };
-        Pallet::<T>::validate_call_fee(fee).map_err(|_| {
-            TransactionValidityError::Invalid(InvalidTransaction::ExhaustsResources)
-        })?;
+        if !Pallet::<T>::is_fee_paused(call) {
+            Pallet::<T>::note_fee_preferences(self.0);
+            // The preferences are kept until the fee is withdrawn, the steps taken to pay the fee
+            // are recorded in the fee payment path and a fee swap is counted against the swap
+            // limit.
+            frame_system::Pallet::<T>::register_extra_weight_unchecked(
+                T::DbWeight::get()
+                    .reads_writes(1, 1)
+                    .saturating_add(Pallet::<T>::fee_payment_path_weight())
+                    .saturating_add(Pallet::<T>::fee_swap_weight()),
+                info.class,
+            );
+        }
         Ok(())
     }
 }
@@ -7,7 +7,8 @@
 //!
 //! This pallet implements a sophisticated fee mechanism that:
 //! - Supports payment in both VTRS and VNRG tokens
-//! - Dynamically adjusts fees based on block fullness
+//! - Dynamically adjusts fees based on block fullness, announcing each change of the fee
+//!   multiplier with `MultiplierUpdated`
 //! - Provides automatic token exchange for fee payments
 //! - Manages energy burning thresholds
 //! - Integrates with EVM transaction fee handling
@@ -27,6 +28,15 @@
 //! - Dynamic multiplier based on block fullness
 //! - Custom fee logic for specific extrinsics
 //! - EVM-specific fee calculations
+//! - A discount depending on the NAC level of the payer, or of the account which delegated its
+//!   discount to the payer with `delegate_fee_discount`, whichever is higher. The delegation
+//!   applies once the payer accepts it, and the delegator reserves
+//!   `FeeDiscountDelegationDeposit` until it's revoked
+//! - A `MinimumFee` floor, which only calls of `FeeExemptCalls` bypass
+//! - A governance-set `MinEvmFee` floor for Ethereum transactions, never below `MinimumFee`
+//!
+//! Gas estimates of contract calls add the governance-set `GasEstimationBuffer` to the gas used
+//! by a dry run, see [`Pallet::buffered_gas`].
 //!
 //! # Interface
 //!
@@ -35,6 +45,7 @@
 //! - `OnChargeEVMTransaction`: Handles EVM transaction fee withdrawal
 //! - `MultiplierUpdate`: Controls fee multiplier adjustments
 //! - `TokenExchange`: Manages VTRS/VNRG exchange for fees
+//! - `TokenRedemption`: Exchanges VNRG back to VTRS for `redeem_energy`
 //!
 //! # Configuration
 //!
@@ -45,6 +56,75 @@
 //! - `FeeTokenBalanced`: Fee token (VNRG) operations
 //! - `MainTokenBalanced`: Main token (VTRS) operations
 //! - `EnergyExchange`: Token exchange mechanism
+//! - `EnergyRedemption`: Exchange of the fee token back to the main token
+//! - `NacLevel`, `NacFeeDiscount`: Fee discount per NAC level
+//! - `FeeAssets`, `FeeAssetRate`, `FeeAssetCollector`: Assets fees can be paid with, see
+//!   [`CheckEnergyFee`]
+//! - `EvmFeeReceiptRetention`, `MaxEvmFeeReceiptsPerBlock`: How long and how many fees paid by
+//!   Ethereum transactions are kept, see [`Pallet::evm_fee_paid`]
+//! - `FeePaymentPathRetention`: How long the steps taken to pay the fee of each transaction are
+//!   kept, see [`Pallet::fee_payment_path`]
+//! - `MaxFeePaymentPathRemovalsPerBlock`: How many fee payment paths leaving the retention window
+//!   are removed per block, the rest is removed in the following blocks
+//! - `FeeSpendHistoryEras`: Number of eras for which the fees spent by each account are kept,
+//!   see [`Pallet::fee_spend_history`]
+//!
+//! # Automatic Swap
+//!
+//! The fee token an account lacks is exchanged for its main token when the fee is withdrawn.
+//! Governance can switch this off network-wide with `set_auto_swap_enabled`, fees must then be
+//! paid with the fee token, fee credit or a fee asset, and both substrate and Ethereum
+//! transactions of accounts without enough fee token are rejected.
+//!
+//! So that no account churns the exchange pool, governance can limit the fee swaps an account
+//! triggers per era with `update_fee_swap_limit`. The runtime may grant accounts more swaps with
+//! `FeeSwapLimitBonus`, e.g. by reputation. Once its swaps are used up, an account pays its fees
+//! with fee token until the era ends.
+//!
+//! # Fee Credit
+//!
+//! Accounts can prepay fees with `buy_fee_credit`, locking in the current exchange rate. The
+//! credit is spent before the account's fee token balance and can't be transferred.
+//!
+//! # Energy Redemption
+//!
+//! Accounts holding more fee token than they need can exchange it back to the main token with
+//! `redeem_energy`, stating the least main token they accept to receive.
+//!
+//! # Starter Energy
+//!
+//! Set as the runtime's `OnNewAccount` handler, the pallet grants new accounts
+//! `StarterEnergyAmount` of fee token from the `StarterEnergyPot` at the start of the next block,
+//! so that they can transact right away. Each account is granted once, until the
+//! `StarterEnergyBudget` set by governance with `set_starter_energy` is spent. A zero amount
+//! disables the grants.
+//!
+//! # Fee Sources
+//!
+//! A transaction can list the sources its fee is paid from in order of preference through
+//! [`CheckEnergyFee`]: the fee token, an asset with a conversion rate, or the main token. The
+//! first source covering the whole fee is used, the main token is only exchanged as the last
+//! resort. A transaction none of its sources can pay for is rejected.
+//! Inherents and unsigned transactions aren't charged at all.
+//!
+//! # Fee Rebates
+//!
+//! Governance can route a `RebateFraction` of the fees paid with the fee token into a rebate
+//! pool. Once its era ended, the pool of an era is shared by the accounts which paid fees in it
+//! in proportion to their fees, an account never gets back more than it paid. Rebates are claimed
+//! with `claim_rebate` within `RebateClaimEras` eras, unclaimed ones then go to
+//! `UnclaimedRebateDestination`.
+//!
+//! # Dispute Surcharge
+//!
+//! While parachain disputes are active, a block may still be reverted. Governance can set a
+//! `DisputeSurcharge` then charged on top of the fee of Ethereum transactions and of
+//! `CrossChainCalls`. Surcharges are escrowed in `SurchargePot` and refunded once no dispute is
+//! active anymore, a reverted block takes its surcharges with it.
+//!
+//! The runtime notes disputes with `note_open_dispute` when their statements are imported, and
+//! drops the concluded ones with `prune_open_disputes`, so checking for active disputes only
+//! reads `OpenDisputeCount`.
 //!
 //! # Warning
 //!
@@ -53,14 +133,18 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-pub use crate::extension::CheckEnergyFee;
-pub use crate::traits::{CustomFee, TokenExchange};
+pub use crate::extension::{CheckEnergyFee, FeePreferences, FeeSource};
+pub use crate::traits::{CustomFee, GetByKey, TokenExchange, TokenRedemption};
 use frame_support::dispatch::{DispatchClass, RawOrigin};
 use frame_support::traits::{
-    fungible::{Balanced, Credit, Inspect},
-    tokens::{Fortitude, Imbalance, Precision, Preservation},
-    Currency,
+    fungible::{Balanced, Credit, Inspect, Mutate},
+    fungibles,
+    tokens::{ConversionToAssetBalance, Fortitude, Imbalance, Precision, Preservation},
+    Contains, Currency, OnNewAccount, ReservableCurrency,
 };
+use frame_support::weights::Weight;
+use frame_support::{ensure, traits::ConstU32, BoundedVec};
+use frame_system::pallet_prelude::BlockNumberFor;
 pub use pallet::*;
 use pallet_asset_rate::Pallet as AssetRatePallet;
 pub(crate) use pallet_evm::{AddressMapping, OnChargeEVMTransaction};
@@ -68,14 +152,19 @@ pub use pallet_transaction_payment::{
     Config as TransactionPaymentConfig, Multiplier, MultiplierUpdate, OnChargeTransaction,
 };
 
-use sp_arithmetic::{traits::CheckedAdd, ArithmeticError::Overflow};
-use sp_core::{RuntimeDebug, H160, U256};
+use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_arithmetic::{
+    traits::{CheckedAdd, CheckedSub},
+    ArithmeticError::Overflow,
+};
+use sp_core::{RuntimeDebug, H160, H256, U256};
 use sp_runtime::{
     traits::{Convert, DispatchInfoOf, Get, PostDispatchInfoOf, Saturating, Zero},
     transaction_validity::{InvalidTransaction, TransactionValidityError},
-    DispatchError, Perbill, Perquintill,
+    DispatchError, Perbill, Perquintill, TokenError, TransactionOutcome,
 };
-use sp_std::boxed::Box;
+use sp_std::{boxed::Box, vec::Vec};
 
 #[cfg(test)]
 pub(crate) mod mock;
@@ -88,6 +177,9 @@ pub mod benchmarking;
 
 pub mod extension;
 pub mod traits;
+pub mod weights;
+
+pub use weights::WeightInfo;
 
 pub(crate) type BalanceOf<T> =
     <<T as pallet_asset_rate::Config>::Currency as Inspect<AccountIdOf<T>>>::Balance;
@@ -109,6 +201,42 @@ pub enum CallFee<Balance> {
     EVM(Balance),
 }
 
+/// Fee rebates of an era
+#[derive(
+    Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub struct RebatePool<Balance> {
+    /// Fee token put into the pool
+    pub amount: Balance,
+    /// Fee token of the pool not claimed yet
+    pub unclaimed: Balance,
+    /// Fees paid by all accounts during the era
+    pub contributions: Balance,
+}
+
+/// A delegation of the fee discount of an account
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct FeeDiscountDelegation<AccountId, Balance> {
+    /// Account the fee discount is delegated to
+    pub delegate: AccountId,
+    /// Main token reserved from the delegator
+    pub deposit: Balance,
+    /// Whether the delegate accepted the delegation, the discount only applies then
+    pub accepted: bool,
+}
+
+/// Maximum number of steps kept in the fee payment path of a transaction
+pub const MAX_FEE_PAYMENT_STEPS: u32 = 8;
+
+pub use energy_fee_runtime_api::FeePaymentStep;
+
+/// The steps taken to pay the fee of a transaction, in order
+pub type FeePaymentPath<AssetKind, Balance> =
+    BoundedVec<FeePaymentStep<AssetKind, Balance>, ConstU32<MAX_FEE_PAYMENT_STEPS>>;
+
+pub type FeePaymentPathOf<T> =
+    FeePaymentPath<<T as pallet_asset_rate::Config>::AssetKind, BalanceOf<T>>;
+
 impl<Balance> CallFee<Balance> {
     pub fn into_inner(self) -> Balance {
         match self {
@@ -156,9 +284,10 @@ pub mod pallet {
         >;
         /// Fee token manipulation traits
         type FeeTokenBalanced: Balanced<Self::AccountId>
-            + Inspect<Self::AccountId, Balance = BalanceOf<Self>>;
+            + Inspect<Self::AccountId, Balance = BalanceOf<Self>>
+            + Mutate<Self::AccountId>;
         /// Chain currency (main token) manipulation traits
-        type MainTokenBalanced: Currency<Self::AccountId, Balance = BalanceOf<Self>>;
+        type MainTokenBalanced: ReservableCurrency<Self::AccountId, Balance = BalanceOf<Self>>;
         /// Exchange main token -> fee token
         /// Could not be used for fee token -> main token exchange
         type EnergyExchange: TokenExchange<
@@ -168,6 +297,8 @@ pub mod pallet {
             Self::MainRecycleDestination,
             BalanceOf<Self>,
         >;
+        /// Exchange fee token -> main token
+        type EnergyRedemption: TokenRedemption<Self::AccountId, BalanceOf<Self>>;
         /// Used for initializing the pallet
         type EnergyAssetId: Get<Self::AssetKind>;
         /// Handler for when a fee has been withdrawn
@@ -175,6 +306,98 @@ pub mod pallet {
 
         type MainRecycleDestination: OnUnbalanced<NegativeImbalanceOf<Self>>;
         type FeeRecycleDestination: OnUnbalanced<FeeCreditOf<Self>>;
+
+        /// EVM block gas limit used until governance sets another one
+        type DefaultBlockGasLimit: Get<u64>;
+        /// Lowest EVM block gas limit governance may set
+        type MinBlockGasLimit: Get<u64>;
+        /// Highest EVM block gas limit governance may set
+        type MaxBlockGasLimit: Get<u64>;
+        /// Number of recent blocks for which the applied fee multiplier is kept
+        type FeeMultiplierHistoryDepth: Get<BlockNumberFor<Self>>;
+        /// NAC level of an account, `None` if the account holds no NAC
+        type NacLevel: for<'a> Convert<&'a Self::AccountId, Option<u8>>;
+        /// Fee discount granted to accounts of a NAC level. Level 0 never gets a discount
+        type NacFeeDiscount: GetByKey<u8, Perbill>;
+        /// Lowest fee charged for a transaction, whatever its weight or discount, so that no
+        /// call is free to spam
+        #[pallet::constant]
+        type MinimumFee: Get<BalanceOf<Self>>;
+        /// Calls which aren't subject to `MinimumFee`, e.g. governance calls priced at zero
+        type FeeExemptCalls: Contains<<Self as frame_system::Config>::RuntimeCall>;
+        /// Number of recent blocks for which the fee paid by each Ethereum transaction is kept
+        type EvmFeeReceiptRetention: Get<BlockNumberFor<Self>>;
+        /// Maximum number of Ethereum transaction fees kept per block
+        #[pallet::constant]
+        type MaxEvmFeeReceiptsPerBlock: Get<u32>;
+        /// Number of recent blocks for which the fee payment path of each transaction is kept
+        type FeePaymentPathRetention: Get<BlockNumberFor<Self>>;
+        /// Maximum number of fee payment paths which left the retention window removed per block
+        #[pallet::constant]
+        type MaxFeePaymentPathRemovalsPerBlock: Get<u32>;
+        /// Assets other than the fee token which fees can be paid with
+        type FeeAssets: fungibles::Mutate<
+            Self::AccountId,
+            AssetId = Self::AssetKind,
+            Balance = BalanceOf<Self>,
+        >;
+        /// Converts main token amounts into amounts of `FeeAssets`
+        type FeeAssetRate: ConversionToAssetBalance<
+            BalanceOf<Self>,
+            Self::AssetKind,
+            BalanceOf<Self>,
+            Error = DispatchError,
+        >;
+        /// Receives the assets fees are paid with
+        type FeeAssetCollector: Get<Self::AccountId>;
+        /// Era fees are accounted to for rebates and the fee spend history
+        type RebateEra: Get<u32>;
+        /// Number of the latest eras with fees for which the fee token spent by each account is
+        /// kept
+        #[pallet::constant]
+        type FeeSpendHistoryEras: Get<u32>;
+        /// Account holding the fee token of the rebate pools
+        type RebatePot: Get<Self::AccountId>;
+        /// Number of eras after its end during which the rebates of an era can be claimed
+        #[pallet::constant]
+        type RebateClaimEras: Get<u32>;
+        /// Receives the rebates which weren't claimed in time
+        type UnclaimedRebateDestination: Get<Self::AccountId>;
+        /// Native calls interacting with cross-chain state. Ethereum transactions are always
+        /// treated as such
+        type CrossChainCalls: Contains<<Self as frame_system::Config>::RuntimeCall>;
+        /// Account escrowing the dispute surcharges until they're refunded
+        type SurchargePot: Get<Self::AccountId>;
+        /// Maximum number of dispute surcharges refunded per block
+        #[pallet::constant]
+        type MaxSurchargeRefundsPerBlock: Get<u32>;
+        /// Current price of one main token in fee token, `None` while it can't be quoted
+        type EnergyPrice: Get<Option<BalanceOf<Self>>>;
+        /// Number of blocks between two snapshots of the `EnergyPrice`, zero disables them
+        #[pallet::constant]
+        type PriceSnapshotInterval: Get<BlockNumberFor<Self>>;
+        /// Maximum number of price snapshots kept, the oldest one is dropped for a new one
+        #[pallet::constant]
+        type MaxPriceSnapshots: Get<u32>;
+        /// Account holding the fee token granted to new accounts
+        type StarterEnergyPot: Get<Self::AccountId>;
+        /// Maximum number of new accounts granted starter energy per block
+        #[pallet::constant]
+        type MaxStarterEnergyGrantsPerBlock: Get<u32>;
+        /// Fee swaps an account may trigger per era on top of `FeeSwapLimit`, e.g. for its
+        /// reputation
+        type FeeSwapLimitBonus: for<'a> Convert<&'a Self::AccountId, u32>;
+        /// Maximum number of spenders an owner may give a fee token allowance to
+        #[pallet::constant]
+        type MaxEnergyAllowances: Get<u32>;
+        /// Maximum number of fee contributions of expired rebate eras removed per block
+        #[pallet::constant]
+        type MaxFeeContributionRemovalsPerBlock: Get<u32>;
+        /// Main token reserved from an account delegating its fee discount
+        #[pallet::constant]
+        type FeeDiscountDelegationDeposit: Get<BalanceOf<Self>>;
+        /// Weight information for extrinsics in this pallet
+        type WeightInfo: WeightInfo;
     }
 
     #[pallet::storage]
@@ -209,6 +432,263 @@ pub mod pallet {
     #[pallet::getter(fn base_fee)]
     pub type BaseFee<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery, T::GetConstantFee>;
 
+    #[pallet::storage]
+    #[pallet::getter(fn block_gas_limit)]
+    pub type BlockGasLimit<T: Config> = StorageValue<_, u64, ValueQuery, T::DefaultBlockGasLimit>;
+
+    /// Block gas limit which takes effect at the beginning of the next block
+    #[pallet::storage]
+    #[pallet::getter(fn pending_block_gas_limit)]
+    pub type PendingBlockGasLimit<T: Config> = StorageValue<_, u64, OptionQuery>;
+
+    /// Whether the energy asset had no conversion rate at the start of the last block
+    #[pallet::storage]
+    pub type EnergyRateMissingNoted<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+    /// Fee multiplier applied to the transactions of recent blocks
+    #[pallet::storage]
+    pub type FeeMultiplierHistory<T: Config> =
+        StorageMap<_, Twox64Concat, BlockNumberFor<T>, Multiplier, OptionQuery>;
+
+    /// Amount of fee token an owner allows a spender to transfer on their behalf
+    #[pallet::storage]
+    #[pallet::getter(fn energy_allowance)]
+    pub type EnergyAllowances<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::AccountId,
+        BalanceOf<T>,
+        ValueQuery,
+    >;
+
+    /// Number of `EnergyAllowances` each owner gave, at most `MaxEnergyAllowances`
+    #[pallet::storage]
+    pub type EnergyAllowanceCount<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+    /// Accounts which allow swap-funded fees to take their main token balance below the
+    /// existential deposit
+    #[pallet::storage]
+    pub type KeepAliveOptOut<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+    /// Pallets, by index, whose calls are not charged a fee, e.g. while their calls are
+    /// disabled during a storage migration
+    #[pallet::storage]
+    pub type FeePausedPallets<T: Config> = StorageMap<_, Twox64Concat, u8, (), OptionQuery>;
+
+    /// Fee withdrawn by the EVM execution in progress, until it's attributed to its Ethereum
+    /// transaction
+    #[pallet::storage]
+    pub type PendingEvmFee<T: Config> = StorageValue<_, BalanceOf<T>, OptionQuery>;
+
+    /// Set while a signed extrinsic whose fee covers the EVM calls it wraps is dispatched, the
+    /// EVM runner doesn't charge them again
+    #[pallet::storage]
+    pub type EvmFeePrepaid<T: Config> = StorageValue<_, (), OptionQuery>;
+
+    /// Fee sources listed by the signed extrinsic being applied, until its fee is withdrawn
+    #[pallet::storage]
+    pub type PendingFeePreferences<T: Config> = StorageValue<_, FeePreferences<T>, OptionQuery>;
+
+    /// Block and fee paid by recent Ethereum transactions, by transaction hash
+    #[pallet::storage]
+    pub type EvmFeeReceipts<T: Config> =
+        StorageMap<_, Identity, H256, (BlockNumberFor<T>, BalanceOf<T>), OptionQuery>;
+
+    /// Steps taken to pay the fees of recent transactions, by block and extrinsic index
+    #[pallet::storage]
+    pub type FeePaymentPaths<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        Twox64Concat,
+        u32,
+        FeePaymentPathOf<T>,
+        OptionQuery,
+    >;
+
+    /// Block whose fee payment paths are being removed, with the cursor to resume the removal
+    /// from, empty to start it
+    #[pallet::storage]
+    pub type FeePaymentPathPruning<T: Config> =
+        StorageValue<_, (BlockNumberFor<T>, BoundedVec<u8, ConstU32<128>>), OptionQuery>;
+
+    /// Hashes of the Ethereum transactions with a fee receipt, by block, used for pruning
+    #[pallet::storage]
+    pub type EvmFeeReceiptsByBlock<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        BoundedVec<H256, T::MaxEvmFeeReceiptsPerBlock>,
+        ValueQuery,
+    >;
+
+    /// Fee token prepaid by accounts with `buy_fee_credit`. It can't be transferred and is
+    /// spent on fees before the account's fee token balance
+    #[pallet::storage]
+    #[pallet::getter(fn fee_credit)]
+    pub type FeeCredit<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+    /// Fraction of the fees paid with the fee token routed into the rebate pool
+    #[pallet::storage]
+    #[pallet::getter(fn rebate_fraction)]
+    pub type RebateFraction<T: Config> = StorageValue<_, Perbill, ValueQuery>;
+
+    /// Rebate pools of the eras whose rebates can still be claimed, by era
+    #[pallet::storage]
+    #[pallet::getter(fn rebate_pool)]
+    pub type RebatePools<T: Config> =
+        StorageMap<_, Twox64Concat, u32, RebatePool<BalanceOf<T>>, OptionQuery>;
+
+    /// Fees paid by accounts during an era whose rebate wasn't claimed yet, by era and account
+    #[pallet::storage]
+    pub type FeeContributions<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        u32,
+        Blake2_128Concat,
+        T::AccountId,
+        BalanceOf<T>,
+        ValueQuery,
+    >;
+
+    /// Expired rebate eras whose fee contributions are still being removed, with the cursor to
+    /// resume the removal from, empty to start it
+    #[pallet::storage]
+    pub type ExpiredFeeContributions<T: Config> =
+        StorageMap<_, Twox64Concat, u32, BoundedVec<u8, ConstU32<128>>, ValueQuery>;
+
+    /// Fee token spent on fees by an account in each of its latest eras with fees, oldest first
+    #[pallet::storage]
+    pub type AccountFeeSpend<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedVec<(u32, BalanceOf<T>), T::FeeSpendHistoryEras>,
+        ValueQuery,
+    >;
+
+    /// Rebate era of the last block, used to detect the start of a new era
+    #[pallet::storage]
+    pub type LastRebateEra<T: Config> = StorageValue<_, u32, OptionQuery>;
+
+    /// Fraction of the fee charged on top of it for cross-chain transactions while disputes are
+    /// active, zero disables the surcharge
+    #[pallet::storage]
+    #[pallet::getter(fn dispute_surcharge)]
+    pub type DisputeSurcharge<T: Config> = StorageValue<_, Perbill, ValueQuery>;
+
+    /// Minimum fee token amount charged for an Ethereum transaction, on top of `MinimumFee`
+    #[pallet::storage]
+    #[pallet::getter(fn min_evm_fee)]
+    pub type MinEvmFee<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+    /// Dispute surcharges escrowed until no dispute is active, by account
+    #[pallet::storage]
+    #[pallet::getter(fn escrowed_surcharge)]
+    pub type EscrowedSurcharges<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+    /// Parachain disputes that haven't concluded yet, by session and candidate hash
+    #[pallet::storage]
+    pub type OpenDisputes<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, u32, Identity, H256, (), OptionQuery>;
+
+    /// Number of `OpenDisputes`, cross-chain transactions pay the `DisputeSurcharge` while it
+    /// isn't zero
+    #[pallet::storage]
+    #[pallet::getter(fn open_dispute_count)]
+    pub type OpenDisputeCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    /// Snapshots of the `EnergyPrice` taken every `PriceSnapshotInterval` blocks, oldest first
+    #[pallet::storage]
+    pub type PriceSnapshots<T: Config> = StorageValue<
+        _,
+        BoundedVec<(BlockNumberFor<T>, BalanceOf<T>), T::MaxPriceSnapshots>,
+        ValueQuery,
+    >;
+
+    /// Account whose fee discount applies to the fees of an account, by delegate which accepted
+    /// the delegation
+    #[pallet::storage]
+    #[pallet::getter(fn fee_discount_delegator)]
+    pub type FeeDiscountDelegators<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId, OptionQuery>;
+
+    /// Delegation of the fee discount of an account, by delegator
+    #[pallet::storage]
+    #[pallet::getter(fn fee_discount_delegation)]
+    pub type FeeDiscountDelegates<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        FeeDiscountDelegation<T::AccountId, BalanceOf<T>>,
+        OptionQuery,
+    >;
+
+    /// Whether the fee pool was liquid once, fees are never exchanged at the bootstrap rate again
+    /// afterwards, see [`crate::traits::BootstrapExchange`]
+    #[pallet::storage]
+    #[pallet::getter(fn fee_pool_liquid)]
+    pub type FeePoolLiquid<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+    #[pallet::type_value]
+    pub fn DefaultAutoSwapEnabled<T: Config>() -> bool {
+        true
+    }
+
+    /// Whether the fee token missing to pay a fee is exchanged for the main token
+    #[pallet::storage]
+    #[pallet::getter(fn auto_swap_enabled)]
+    pub type AutoSwapEnabled<T: Config> =
+        StorageValue<_, bool, ValueQuery, DefaultAutoSwapEnabled<T>>;
+
+    #[pallet::type_value]
+    pub fn DefaultGasEstimationBuffer<T: Config>() -> Perbill {
+        Perbill::from_percent(10)
+    }
+
+    /// Fraction of the gas used by a dry run added to the gas estimate of a contract call
+    #[pallet::storage]
+    #[pallet::getter(fn gas_estimation_buffer)]
+    pub type GasEstimationBuffer<T: Config> =
+        StorageValue<_, Perbill, ValueQuery, DefaultGasEstimationBuffer<T>>;
+
+    /// Fee token granted to each new account, zero disables the grants
+    #[pallet::storage]
+    #[pallet::getter(fn starter_energy_amount)]
+    pub type StarterEnergyAmount<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+    /// Fee token left to grant to new accounts
+    #[pallet::storage]
+    #[pallet::getter(fn starter_energy_budget)]
+    pub type StarterEnergyBudget<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+    /// New accounts waiting for their starter energy, granted at the start of the next block
+    #[pallet::storage]
+    pub type PendingStarterEnergy<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+    /// Accounts which were granted starter energy, they aren't granted it again when recreated
+    #[pallet::storage]
+    pub type StarterEnergyGranted<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+    /// Number of fee swaps an account can trigger per era before `FeeSwapLimitBonus`, `None`
+    /// for no limit
+    #[pallet::storage]
+    #[pallet::getter(fn fee_swap_limit)]
+    pub type FeeSwapLimit<T: Config> = StorageValue<_, u32, OptionQuery>;
+
+    /// Era of the last fee swap triggered by an account and the number of its swaps in that era
+    #[pallet::storage]
+    pub type FeeSwapCount<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, (u32, u32), OptionQuery>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -220,6 +700,111 @@ pub mod pallet {
         BlockFullnessThresholdUpdated { new_threshold: Perquintill },
         ///
         UpperFeeMultiplierUpdated { new_multiplier: Multiplier },
+        /// The block gas limit will be updated in the next block [new_limit]
+        BlockGasLimitScheduled { new_limit: u64 },
+        /// The block gas limit was updated [new_limit]
+        BlockGasLimitUpdated { new_limit: u64 },
+        /// The account changed whether swap-funded fees keep it alive [who, keep_alive]
+        FeeKeepAliveUpdated { who: T::AccountId, keep_alive: bool },
+        /// The energy asset has no conversion rate, fees are converted with the fallback rate
+        /// of the configured converter, if any
+        EnergyRateMissing,
+        /// The energy asset has a conversion rate again, fees no longer use the fallback rate
+        EnergyRateRestored,
+        /// The fee pool became liquid, fees are exchanged through it from now on
+        FeePoolBecameLiquid,
+        /// The owner allowed the spender to transfer fee token [owner, spender, amount]
+        EnergyApproval { owner: T::AccountId, spender: T::AccountId, amount: BalanceOf<T> },
+        /// The spender transferred fee token on behalf of its owner [spender, from, to, amount]
+        EnergyTransferredFrom {
+            spender: T::AccountId,
+            from: T::AccountId,
+            to: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        /// Fee collection for calls of the pallet was paused or resumed [pallet_index, paused]
+        FeePauseUpdated { pallet_index: u8, paused: bool },
+        /// The account bought fee credit for main token [who, amount, price]
+        FeeCreditBought { who: T::AccountId, amount: BalanceOf<T>, price: BalanceOf<T> },
+        /// Fee credit of the account was spent on a fee [who, amount]
+        FeeCreditSpent { who: T::AccountId, amount: BalanceOf<T> },
+        /// The account exchanged an asset for fee token to pay a fee [who, asset, amount, price]
+        FeeAssetExchanged {
+            who: T::AccountId,
+            asset: T::AssetKind,
+            amount: BalanceOf<T>,
+            price: BalanceOf<T>,
+        },
+        /// The fraction of fees routed into the rebate pool was updated [new_fraction]
+        RebateFractionUpdated { new_fraction: Perbill },
+        /// The account claimed its fee rebate of the era [who, era, amount]
+        RebateClaimed { who: T::AccountId, era: u32, amount: BalanceOf<T> },
+        /// The rebates of the era which weren't claimed in time expired [era, amount]
+        RebatesExpired { era: u32, amount: BalanceOf<T> },
+        /// The dispute surcharge was updated [new_surcharge]
+        DisputeSurchargeUpdated { new_surcharge: Perbill },
+        /// The account paid a dispute surcharge, escrowed until disputes end [who, amount]
+        DisputeSurchargeCharged { who: T::AccountId, amount: BalanceOf<T> },
+        /// The escrowed dispute surcharges of the account were refunded [who, amount]
+        DisputeSurchargeRefunded { who: T::AccountId, amount: BalanceOf<T> },
+        /// The automatic exchange of the main token for missing fee token was switched on or
+        /// off [enabled]
+        AutoSwapUpdated { enabled: bool },
+        /// The minimum fee of Ethereum transactions was updated [new_fee]
+        MinEvmFeeUpdated { new_fee: BalanceOf<T> },
+        /// The account delegated its fee discount [delegator, delegate]
+        FeeDiscountDelegated { delegator: T::AccountId, delegate: T::AccountId },
+        /// The delegate accepted the delegation of the fee discount [delegator, delegate]
+        FeeDiscountDelegationAccepted { delegator: T::AccountId, delegate: T::AccountId },
+        /// The account revoked the delegation of its fee discount [delegator, delegate]
+        FeeDiscountDelegationRevoked { delegator: T::AccountId, delegate: T::AccountId },
+        /// The account redeemed fee token for main token [who, amount, price]
+        EnergyRedeemed { who: T::AccountId, amount: BalanceOf<T>, price: BalanceOf<T> },
+        /// The fee multiplier changed at the end of a block, driven by the fullness of its
+        /// normal dispatch class [old, new, block_fullness]
+        MultiplierUpdated { old: Multiplier, new: Multiplier, block_fullness: Perquintill },
+        /// The buffer added to gas estimates of contract calls was updated [new_buffer]
+        GasEstimationBufferUpdated { new_buffer: Perbill },
+        /// The starter energy of new accounts and its budget were set [amount, budget]
+        StarterEnergySet { amount: BalanceOf<T>, budget: BalanceOf<T> },
+        /// The new account was granted starter energy [who, amount]
+        StarterEnergyGranted { who: T::AccountId, amount: BalanceOf<T> },
+        /// The number of fee swaps an account can trigger per era was updated [new_limit]
+        FeeSwapLimitUpdated { new_limit: Option<u32> },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// The block gas limit is outside of `MinBlockGasLimit..=MaxBlockGasLimit`
+        BlockGasLimitOutOfBounds,
+        /// The spender's allowance is lower than the transferred amount
+        InsufficientEnergyAllowance,
+        /// The owner already gave `MaxEnergyAllowances` allowances
+        TooManyEnergyAllowances,
+        /// Buying the fee credit costs more main token than the caller accepts to pay
+        FeeCreditPriceTooHigh,
+        /// None of the fee sources of the transaction can pay its fee
+        NoFeeSourceSufficient,
+        /// The asset can't pay fees: it is the fee token, or the fee is worth less than one unit
+        /// of it
+        InvalidFeeAsset,
+        /// Rebates of the era can only be claimed once it ended
+        RebateEraNotEnded,
+        /// The account has no rebate to claim for the era
+        NoRebate,
+        /// The account lacks fee token and the automatic swap of the main token is disabled
+        AutoSwapDisabled,
+        /// An account can't delegate its fee discount to itself
+        SelfDelegation,
+        /// The delegate already receives the fee discount of another account
+        DelegateTaken,
+        /// The account hasn't delegated its fee discount, or not to the caller
+        NoFeeDiscountDelegation,
+        /// Redeeming the fee token yields less main token than the caller accepts to receive
+        RedemptionPriceTooLow,
+        /// The account triggered all the fee swaps it can in this era, its fees must be paid
+        /// with fee token until the next one
+        FeeSwapLimitReached,
     }
 
     #[pallet::genesis_config]
@@ -242,9 +827,53 @@ pub mod pallet {
 
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-        fn on_initialize(_now: BlockNumberFor<T>) -> Weight {
+        fn on_initialize(now: BlockNumberFor<T>) -> Weight {
             BurnedEnergy::<T>::put(BalanceOf::<T>::zero());
-            T::DbWeight::get().writes(1)
+            let mut weight = T::DbWeight::get().reads_writes(1, 1);
+
+            let rate_missing = !pallet_asset_rate::ConversionRateToNative::<T>::contains_key(
+                T::EnergyAssetId::get(),
+            );
+            weight.saturating_accrue(T::DbWeight::get().reads(2));
+            if rate_missing != EnergyRateMissingNoted::<T>::get() {
+                EnergyRateMissingNoted::<T>::put(rate_missing);
+                Self::deposit_event(if rate_missing {
+                    Event::<T>::EnergyRateMissing
+                } else {
+                    Event::<T>::EnergyRateRestored
+                });
+                weight.saturating_accrue(T::DbWeight::get().writes(1));
+            }
+
+            if let Some(new_limit) = PendingBlockGasLimit::<T>::take() {
+                BlockGasLimit::<T>::put(new_limit);
+                Self::deposit_event(Event::<T>::BlockGasLimitUpdated { new_limit });
+                weight.saturating_accrue(T::DbWeight::get().writes(2));
+            }
+
+            weight.saturating_accrue(Self::prune_evm_fee_receipts(now));
+            weight.saturating_accrue(Self::prune_fee_payment_paths(now));
+            weight.saturating_accrue(Self::expire_rebates());
+            weight.saturating_accrue(Self::remove_expired_fee_contributions());
+            weight.saturating_accrue(Self::refund_dispute_surcharges());
+            weight.saturating_accrue(Self::record_price_snapshot(now));
+            weight.saturating_accrue(Self::grant_starter_energy());
+            weight.saturating_add(Self::record_fee_multiplier(now))
+        }
+
+        fn on_finalize(_now: BlockNumberFor<T>) {
+            // Fees of EVM executions outside of an Ethereum transaction have no receipt
+            PendingEvmFee::<T>::kill();
+            EvmFeePrepaid::<T>::kill();
+            PendingFeePreferences::<T>::kill();
+        }
+
+        fn integrity_test() {
+            assert!(
+                T::MinBlockGasLimit::get() <= T::DefaultBlockGasLimit::get()
+                    && T::DefaultBlockGasLimit::get() <= T::MaxBlockGasLimit::get(),
+                "DefaultBlockGasLimit must be within MinBlockGasLimit..=MaxBlockGasLimit"
+            );
         }
     }
 
@@ -296,6 +925,362 @@ pub mod pallet {
             BaseFee::<T>::put(new_base_fee);
             Ok(().into())
         }
+
+        /// Schedule a new EVM block gas limit. It is applied at the beginning of the next block
+        /// so transactions of the current block keep a consistent gas budget.
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::DbWeight::get().writes(1))]
+        pub fn update_block_gas_limit(
+            origin: OriginFor<T>,
+            new_limit: u64,
+        ) -> DispatchResultWithPostInfo {
+            T::ManageOrigin::ensure_origin(origin)?;
+            ensure!(
+                (T::MinBlockGasLimit::get()..=T::MaxBlockGasLimit::get()).contains(&new_limit),
+                Error::<T>::BlockGasLimitOutOfBounds
+            );
+            PendingBlockGasLimit::<T>::put(new_limit);
+            Self::deposit_event(Event::<T>::BlockGasLimitScheduled { new_limit });
+            Ok(().into())
+        }
+
+        /// Choose whether swap-funded fees must leave at least the existential deposit of the
+        /// main token on the caller's account. Keeping the account alive is the default.
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::DbWeight::get().writes(1))]
+        pub fn set_fee_keep_alive(
+            origin: OriginFor<T>,
+            keep_alive: bool,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            if keep_alive {
+                KeepAliveOptOut::<T>::remove(&who);
+            } else {
+                KeepAliveOptOut::<T>::insert(&who, ());
+            }
+            Self::deposit_event(Event::<T>::FeeKeepAliveUpdated { who, keep_alive });
+            Ok(().into())
+        }
+
+        /// Allow `spender` to transfer up to `amount` of the caller's fee token, replacing any
+        /// previous allowance. An `amount` of zero revokes the allowance.
+        ///
+        /// An owner may give at most `MaxEnergyAllowances` allowances.
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::approve_energy())]
+        pub fn approve_energy(
+            origin: OriginFor<T>,
+            spender: T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let owner = ensure_signed(origin)?;
+            let existing = EnergyAllowances::<T>::contains_key(&owner, &spender);
+            if amount.is_zero() {
+                if existing {
+                    EnergyAllowances::<T>::remove(&owner, &spender);
+                    Self::note_allowance_removed(&owner);
+                }
+            } else {
+                if !existing {
+                    EnergyAllowanceCount::<T>::try_mutate(&owner, |count| {
+                        ensure!(
+                            *count < T::MaxEnergyAllowances::get(),
+                            Error::<T>::TooManyEnergyAllowances
+                        );
+                        *count += 1;
+                        Ok::<_, DispatchError>(())
+                    })?;
+                }
+                EnergyAllowances::<T>::insert(&owner, &spender, amount);
+            }
+            Self::deposit_event(Event::<T>::EnergyApproval { owner, spender, amount });
+            Ok(().into())
+        }
+
+        /// Transfer `amount` of fee token from `from` to `to` within the allowance `from` gave
+        /// to the caller.
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::WeightInfo::transfer_energy_from())]
+        pub fn transfer_energy_from(
+            origin: OriginFor<T>,
+            from: T::AccountId,
+            to: T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let spender = ensure_signed(origin)?;
+            Self::do_transfer_energy_from(&spender, &from, &to, amount)?;
+            Ok(().into())
+        }
+
+        /// Pause or resume fee collection for the calls of the pallet at `pallet_index`.
+        ///
+        /// Meant for migration windows when the pallet's calls are rejected anyway. Calls should
+        /// still be filtered before dispatch, this only avoids charging for them.
+        #[pallet::call_index(8)]
+        #[pallet::weight(T::DbWeight::get().writes(1))]
+        pub fn set_fee_paused(
+            origin: OriginFor<T>,
+            pallet_index: u8,
+            paused: bool,
+        ) -> DispatchResultWithPostInfo {
+            T::ManageOrigin::ensure_origin(origin)?;
+            if paused {
+                FeePausedPallets::<T>::insert(pallet_index, ());
+            } else {
+                FeePausedPallets::<T>::remove(pallet_index);
+            }
+            Self::deposit_event(Event::<T>::FeePauseUpdated { pallet_index, paused });
+            Ok(().into())
+        }
+
+        /// Exchange main token for `amount` of fee credit at the current rate, paying at most
+        /// `max_price` of main token.
+        ///
+        /// The credit is spent on the caller's future fees before their fee token balance,
+        /// whatever the exchange rate is by then. It can't be transferred.
+        #[pallet::call_index(9)]
+        #[pallet::weight(T::WeightInfo::buy_fee_credit())]
+        pub fn buy_fee_credit(
+            origin: OriginFor<T>,
+            amount: BalanceOf<T>,
+            max_price: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let price = T::EnergyExchange::convert_from_output(amount)?;
+            ensure!(price <= max_price, Error::<T>::FeeCreditPriceTooHigh);
+
+            // exchanges report either side of the swap, `amount` is what was received
+            T::EnergyExchange::exchange_from_output(&who, amount)?;
+            // the credit is prepaid fee, so the fee token is recycled like fees are
+            let credit = T::FeeTokenBalanced::withdraw(
+                &who,
+                amount,
+                Precision::Exact,
+                Preservation::Expendable,
+                Fortitude::Force,
+            )?;
+            T::FeeRecycleDestination::on_unbalanced(credit);
+            FeeCredit::<T>::mutate(&who, |total| total.saturating_accrue(amount));
+
+            Self::deposit_event(Event::<T>::FeeCreditBought { who, amount, price });
+            Ok(().into())
+        }
+
+        /// Update the fraction of fees routed into the rebate pool of the era
+        #[pallet::call_index(10)]
+        #[pallet::weight(T::DbWeight::get().writes(1))]
+        pub fn update_rebate_fraction(
+            origin: OriginFor<T>,
+            new_fraction: Perbill,
+        ) -> DispatchResultWithPostInfo {
+            T::ManageOrigin::ensure_origin(origin)?;
+            RebateFraction::<T>::put(new_fraction);
+            Self::deposit_event(Event::<T>::RebateFractionUpdated { new_fraction });
+            Ok(().into())
+        }
+
+        /// Claim the caller's share of the rebate pool of an ended `era`, in proportion to the
+        /// fees they paid during it.
+        ///
+        /// The rebate never exceeds the fees paid, and can only be claimed for
+        /// `RebateClaimEras` eras after the end of `era`.
+        #[pallet::call_index(11)]
+        #[pallet::weight(T::WeightInfo::claim_rebate())]
+        pub fn claim_rebate(origin: OriginFor<T>, era: u32) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(era < T::RebateEra::get(), Error::<T>::RebateEraNotEnded);
+
+            let amount = Self::pending_rebate(&who, era);
+            ensure!(!amount.is_zero(), Error::<T>::NoRebate);
+            T::FeeTokenBalanced::transfer(
+                &T::RebatePot::get(),
+                &who,
+                amount,
+                Preservation::Expendable,
+            )?;
+            FeeContributions::<T>::remove(era, &who);
+            RebatePools::<T>::mutate(era, |pool| {
+                if let Some(pool) = pool {
+                    pool.unclaimed.saturating_reduce(amount);
+                }
+            });
+
+            Self::deposit_event(Event::<T>::RebateClaimed { who, era, amount });
+            Ok(().into())
+        }
+
+        /// Update the fraction of the fee charged on top of it for cross-chain transactions
+        /// while disputes are active
+        #[pallet::call_index(12)]
+        #[pallet::weight(T::DbWeight::get().writes(1))]
+        pub fn update_dispute_surcharge(
+            origin: OriginFor<T>,
+            new_surcharge: Perbill,
+        ) -> DispatchResultWithPostInfo {
+            T::ManageOrigin::ensure_origin(origin)?;
+            DisputeSurcharge::<T>::put(new_surcharge);
+            Self::deposit_event(Event::<T>::DisputeSurchargeUpdated { new_surcharge });
+            Ok(().into())
+        }
+
+        /// Switch the automatic exchange of the main token for the fee token an account lacks
+        /// to pay a fee on or off, network-wide
+        #[pallet::call_index(13)]
+        #[pallet::weight(T::DbWeight::get().writes(1))]
+        pub fn set_auto_swap_enabled(
+            origin: OriginFor<T>,
+            enabled: bool,
+        ) -> DispatchResultWithPostInfo {
+            T::ManageOrigin::ensure_origin(origin)?;
+            AutoSwapEnabled::<T>::put(enabled);
+            Self::deposit_event(Event::<T>::AutoSwapUpdated { enabled });
+            Ok(().into())
+        }
+
+        /// Update the minimum fee token amount charged for an Ethereum transaction
+        #[pallet::call_index(14)]
+        #[pallet::weight(T::DbWeight::get().writes(1))]
+        pub fn update_min_evm_fee(
+            origin: OriginFor<T>,
+            new_fee: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            T::ManageOrigin::ensure_origin(origin)?;
+            MinEvmFee::<T>::put(new_fee);
+            Self::deposit_event(Event::<T>::MinEvmFeeUpdated { new_fee });
+            Ok(().into())
+        }
+
+        /// Offer the fee discount of the caller to `to`, e.g. a custodial account transacting on
+        /// behalf of the caller, reserving `FeeDiscountDelegationDeposit` from the caller.
+        ///
+        /// The discount applies once `to` accepts it with `accept_fee_discount_delegation`. An
+        /// account delegates to one account at a time, delegating again replaces the previous
+        /// delegation. The delegate pays the lower of its own and the delegated discounted fee,
+        /// still never below `MinimumFee`.
+        #[pallet::call_index(15)]
+        #[pallet::weight(T::WeightInfo::delegate_fee_discount())]
+        pub fn delegate_fee_discount(
+            origin: OriginFor<T>,
+            to: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(who != to, Error::<T>::SelfDelegation);
+
+            Self::remove_fee_discount_delegation(&who);
+            let deposit = T::FeeDiscountDelegationDeposit::get();
+            T::MainTokenBalanced::reserve(&who, deposit)?;
+            FeeDiscountDelegates::<T>::insert(
+                &who,
+                FeeDiscountDelegation { delegate: to.clone(), deposit, accepted: false },
+            );
+            Self::deposit_event(Event::<T>::FeeDiscountDelegated { delegator: who, delegate: to });
+            Ok(().into())
+        }
+
+        /// Revoke the delegation of the caller's fee discount, unreserving its deposit
+        #[pallet::call_index(16)]
+        #[pallet::weight(T::WeightInfo::revoke_fee_discount_delegation())]
+        pub fn revoke_fee_discount_delegation(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let delegation = Self::remove_fee_discount_delegation(&who)
+                .ok_or(Error::<T>::NoFeeDiscountDelegation)?;
+            Self::deposit_event(Event::<T>::FeeDiscountDelegationRevoked {
+                delegator: who,
+                delegate: delegation.delegate,
+            });
+            Ok(().into())
+        }
+
+        /// Exchange `amount` of the caller's fee token back to main token at the current rate,
+        /// receiving at least `min_price` of main token.
+        ///
+        /// Unlike the exchanges paying fees, the redemption is only bounded by `min_price` and the
+        /// liquidity of the exchange.
+        #[pallet::call_index(17)]
+        #[pallet::weight(T::WeightInfo::redeem_energy())]
+        pub fn redeem_energy(
+            origin: OriginFor<T>,
+            amount: BalanceOf<T>,
+            min_price: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let quote = T::EnergyRedemption::quote_redemption(amount)?;
+            ensure!(quote >= min_price, Error::<T>::RedemptionPriceTooLow);
+
+            let price = T::EnergyRedemption::redeem(&who, amount, min_price)?;
+            Self::deposit_event(Event::<T>::EnergyRedeemed { who, amount, price });
+            Ok(().into())
+        }
+
+        /// Update the fraction of the dry run gas added to gas estimates of contract calls
+        #[pallet::call_index(18)]
+        #[pallet::weight(T::DbWeight::get().writes(1))]
+        pub fn update_gas_estimation_buffer(
+            origin: OriginFor<T>,
+            new_buffer: Perbill,
+        ) -> DispatchResultWithPostInfo {
+            T::ManageOrigin::ensure_origin(origin)?;
+            GasEstimationBuffer::<T>::put(new_buffer);
+            Self::deposit_event(Event::<T>::GasEstimationBufferUpdated { new_buffer });
+            Ok(().into())
+        }
+
+        /// Set the fee token granted to each new account and the total left to grant. A zero
+        /// amount disables the grants
+        #[pallet::call_index(19)]
+        #[pallet::weight(T::DbWeight::get().writes(2))]
+        pub fn set_starter_energy(
+            origin: OriginFor<T>,
+            amount: BalanceOf<T>,
+            budget: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            T::ManageOrigin::ensure_origin(origin)?;
+            StarterEnergyAmount::<T>::put(amount);
+            StarterEnergyBudget::<T>::put(budget);
+            Self::deposit_event(Event::<T>::StarterEnergySet { amount, budget });
+            Ok(().into())
+        }
+
+        /// Update the number of fee swaps an account can trigger per era, `None` lifts the
+        /// limit. Swaps already triggered in the current era count towards the new limit
+        #[pallet::call_index(20)]
+        #[pallet::weight(T::WeightInfo::update_fee_swap_limit())]
+        pub fn update_fee_swap_limit(
+            origin: OriginFor<T>,
+            new_limit: Option<u32>,
+        ) -> DispatchResultWithPostInfo {
+            T::ManageOrigin::ensure_origin(origin)?;
+            FeeSwapLimit::<T>::set(new_limit);
+            Self::deposit_event(Event::<T>::FeeSwapLimitUpdated { new_limit });
+            Ok(().into())
+        }
+
+        /// Accept the fee discount `delegator` offered to the caller with
+        /// `delegate_fee_discount`, applying it to the caller's fees.
+        ///
+        /// An account receives a single delegated discount at a time.
+        #[pallet::call_index(21)]
+        #[pallet::weight(T::WeightInfo::accept_fee_discount_delegation())]
+        pub fn accept_fee_discount_delegation(
+            origin: OriginFor<T>,
+            delegator: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(!FeeDiscountDelegators::<T>::contains_key(&who), Error::<T>::DelegateTaken);
+            FeeDiscountDelegates::<T>::try_mutate(&delegator, |delegation| match delegation {
+                Some(delegation) if delegation.delegate == who => {
+                    delegation.accepted = true;
+                    Ok(())
+                },
+                _ => Err(Error::<T>::NoFeeDiscountDelegation),
+            })?;
+            FeeDiscountDelegators::<T>::insert(&who, &delegator);
+            Self::deposit_event(Event::<T>::FeeDiscountDelegationAccepted {
+                delegator,
+                delegate: who,
+            });
+            Ok(().into())
+        }
     }
 
     impl<T: Config> OnChargeTransaction<T> for Pallet<T> {
@@ -309,40 +1294,56 @@ pub mod pallet {
             fee: Self::Balance,
             _tip: Self::Balance,
         ) -> Result<Self::LiquidityInfo, TransactionValidityError> {
-            if fee.is_zero() {
+            let preferences = PendingFeePreferences::<T>::take().unwrap_or_default();
+            if fee.is_zero() || Self::is_fee_paused(call) {
                 return Ok(None);
             }
 
+            // `fee` includes the tip, unless the custom fee of the call replaces it
             let fee = match T::CustomFee::dispatch_info_to_fee(call, Some(dispatch_info), Some(fee))
             {
-                CallFee::Regular(fee) => fee,
+                CallFee::Regular(fee) => Self::charged_fee(who, call, fee),
                 CallFee::EVM(fee) => {
-                    Self::on_low_balance_exchange(who, fee).map_err(|_| {
-                        TransactionValidityError::Invalid(InvalidTransaction::Payment)
-                    })?;
+                    let fee = Self::charged_fee(who, call, fee);
+                    // the EVM runner charges the dispute surcharge of EVM calls
+                    let surcharge = Self::pending_dispute_surcharge(fee);
+                    Self::prepare_fee_payment(who, fee.saturating_add(surcharge), &preferences)
+                        .and_then(|_| {
+                            Self::on_low_balance_exchange(
+                                who,
+                                fee.saturating_sub(Self::fee_credit(who)),
+                            )
+                        })
+                        .map_err(|_| {
+                            TransactionValidityError::Invalid(InvalidTransaction::Payment)
+                        })?;
                     return Ok(None);
                 },
             };
 
-            Self::on_low_balance_exchange(who, fee)
+            let surcharge = if T::CrossChainCalls::contains(call) {
+                Self::pending_dispute_surcharge(fee)
+            } else {
+                Zero::zero()
+            };
+            Self::prepare_fee_payment(who, fee.saturating_add(surcharge), &preferences)
                 .map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
 
-            let imbalance = T::FeeTokenBalanced::withdraw(
-                who,
-                fee,
-                Precision::Exact,
-                Preservation::Expendable,
-                Fortitude::Force,
-            )
-            .map(|imbalance| {
-                Self::deposit_event(Event::<T>::EnergyFeePaid { who: who.clone(), amount: fee });
-                imbalance
-            })
-            .map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
-
-            Self::update_burned_energy(imbalance.peek())
+            let imbalance = Self::withdraw_fee_with_credit(who, fee)
+                .map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
+            if T::CrossChainCalls::contains(call) {
+                Self::charge_dispute_surcharge(who, fee, dispatch_info.class)
+                    .map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
+            }
+            Self::deposit_event(Event::<T>::EnergyFeePaid { who: who.clone(), amount: fee });
+            Self::note_fee_spend(who, fee);
+
+            Self::update_burned_energy(fee)
                 .map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
             T::OnWithdrawFee::on_withdraw_fee(who);
+            if T::CustomFee::wraps_evm_calls(call) {
+                EvmFeePrepaid::<T>::put(());
+            }
 
             Ok(Some(imbalance))
         }
@@ -351,7 +1352,7 @@ pub mod pallet {
         // TODO: decide what to do with fee debt generated during exchange (if it would remain
         // relevant after EnergyBroker implementation)
         fn correct_and_deposit_fee(
-            _who: &T::AccountId,
+            who: &T::AccountId,
             _dispatch_info: &DispatchInfoOf<T::RuntimeCall>,
             _post_info: &PostDispatchInfoOf<T::RuntimeCall>,
             _corrected_fee: Self::Balance,
@@ -359,7 +1360,10 @@ pub mod pallet {
             already_withdrawn: Self::LiquidityInfo,
         ) -> Result<(), TransactionValidityError> {
             if let Some(credit) = already_withdrawn {
-                T::FeeRecycleDestination::on_unbalanced(credit);
+                Self::recycle_fee(who, credit);
+            }
+            if EvmFeePrepaid::<T>::exists() {
+                EvmFeePrepaid::<T>::kill();
             }
             Ok(())
         }
@@ -373,46 +1377,41 @@ pub mod pallet {
             who: &H160,
             fee: U256,
         ) -> Result<Self::LiquidityInfo, pallet_evm::Error<T>> {
-            if fee.is_zero() {
+            if fee.is_zero() || EvmFeePrepaid::<T>::exists() {
+                PendingEvmFee::<T>::put(BalanceOf::<T>::zero());
                 return Ok(None);
             }
 
-            let const_energy_fee = T::CustomFee::ethereum_fee();
             let account_id = <T as pallet_evm::Config>::AddressMapping::into_account_id(*who);
+            let const_energy_fee = Self::discounted_fee(&account_id, T::CustomFee::ethereum_fee())
+                .max(Self::evm_fee_floor());
 
-            Self::on_low_balance_exchange(&account_id, const_energy_fee)
+            let imbalance = Self::withdraw_fee_with_credit(&account_id, const_energy_fee)
                 .map_err(|_| pallet_evm::Error::<T>::BalanceLow)?;
-
-            let imbalance = T::FeeTokenBalanced::withdraw(
-                &account_id,
-                const_energy_fee,
-                Precision::Exact,
-                Preservation::Expendable,
-                Fortitude::Force,
-            )
-            .map(|imbalance| {
-                Self::deposit_event(Event::<T>::EnergyFeePaid {
-                    who: account_id.clone(),
-                    amount: const_energy_fee,
-                });
-                imbalance
-            })
-            .map_err(|_| pallet_evm::Error::<T>::BalanceLow)?;
-            Self::update_burned_energy(imbalance.peek())
+            Self::charge_dispute_surcharge(&account_id, const_energy_fee, DispatchClass::Normal)
+                .map_err(|_| pallet_evm::Error::<T>::BalanceLow)?;
+            Self::deposit_event(Event::<T>::EnergyFeePaid {
+                who: account_id.clone(),
+                amount: const_energy_fee,
+            });
+            Self::note_fee_spend(&account_id, const_energy_fee);
+            Self::update_burned_energy(const_energy_fee)
                 .map_err(|_| pallet_evm::Error::<T>::FeeOverflow)?;
             T::OnWithdrawFee::on_withdraw_fee(&account_id);
+            PendingEvmFee::<T>::put(const_energy_fee);
 
             Ok(Some(imbalance))
         }
 
         fn correct_and_deposit_fee(
-            _who: &H160,
+            who: &H160,
             _corrected_fee: U256,
             _base_fee: U256,
             already_withdrawn: Self::LiquidityInfo,
         ) -> Self::LiquidityInfo {
             if let Some(credit) = already_withdrawn {
-                T::FeeRecycleDestination::on_unbalanced(credit);
+                let account_id = <T as pallet_evm::Config>::AddressMapping::into_account_id(*who);
+                Self::recycle_fee(&account_id, credit);
             };
             None
         }
@@ -425,9 +1424,333 @@ pub mod pallet {
 }
 
 impl<T: Config> Pallet<T> {
+    /// Whether fee collection is paused for the pallet of `call`.
+    ///
+    /// The pallet index is the first byte of an encoded runtime call.
+    pub fn is_fee_paused(call: &T::RuntimeCall) -> bool {
+        call.using_encoded(|encoded| {
+            encoded
+                .first()
+                .map_or(false, |index| FeePausedPallets::<T>::contains_key(index))
+        })
+    }
+
+    /// Fee `who` pays instead of `fee` after the discount of their NAC level, or of the NAC
+    /// level of the account which delegated its discount to `who` if it's higher.
+    ///
+    /// Accounts without a NAC or with NAC level 0 pay the full fee.
+    pub fn discounted_fee(who: &T::AccountId, fee: BalanceOf<T>) -> BalanceOf<T> {
+        let discount = |account: &T::AccountId| match T::NacLevel::convert(account) {
+            Some(level) if level > 0 => T::NacFeeDiscount::get(&level),
+            _ => Perbill::zero(),
+        };
+        let delegated = Self::fee_discount_delegator(who).map_or(Perbill::zero(), |d| discount(&d));
+
+        fee.saturating_sub(discount(who).max(delegated).mul_floor(fee))
+    }
+
+    /// Fee `who` is charged for `call` instead of `fee`: the discount of their NAC level applies,
+    /// but the fee is never lower than `MinimumFee` unless `call` is in `FeeExemptCalls`.
+    pub fn charged_fee(
+        who: &T::AccountId,
+        call: &T::RuntimeCall,
+        fee: BalanceOf<T>,
+    ) -> BalanceOf<T> {
+        if T::FeeExemptCalls::contains(call) {
+            return fee;
+        }
+        Self::discounted_fee(who, fee).max(T::MinimumFee::get())
+    }
+
+    /// The lowest fee an Ethereum transaction is charged, applied after the NAC discount: the
+    /// higher of `MinEvmFee` and `MinimumFee`.
+    pub fn evm_fee_floor() -> BalanceOf<T> {
+        Self::min_evm_fee().max(T::MinimumFee::get())
+    }
+
+    /// The gas to estimate for a contract call which used `used_gas` in a dry run: the used gas
+    /// plus the `GasEstimationBuffer`, rounded up.
+    pub fn buffered_gas(used_gas: u64) -> u64 {
+        used_gas.saturating_add(Self::gas_estimation_buffer().mul_ceil(used_gas))
+    }
+
+    /// Whether a parachain dispute hasn't concluded yet.
+    pub fn disputes_active() -> bool {
+        !Self::open_dispute_count().is_zero()
+    }
+
+    /// Note that the dispute of `candidate` from `session` hasn't concluded yet.
+    pub fn note_open_dispute(session: u32, candidate: H256) {
+        if !OpenDisputes::<T>::contains_key(session, candidate) {
+            OpenDisputes::<T>::insert(session, candidate, ());
+            OpenDisputeCount::<T>::mutate(|count| count.saturating_inc());
+        }
+    }
+
+    /// Forget the open disputes `is_open` doesn't report as open anymore.
+    pub fn prune_open_disputes(is_open: impl Fn(u32, &H256) -> bool) {
+        if Self::open_dispute_count().is_zero() {
+            return;
+        }
+
+        let concluded: Vec<_> = OpenDisputes::<T>::iter_keys()
+            .filter(|(session, candidate)| !is_open(*session, candidate))
+            .collect();
+        for (session, candidate) in concluded {
+            OpenDisputes::<T>::remove(session, candidate);
+            OpenDisputeCount::<T>::mutate(|count| count.saturating_dec());
+        }
+    }
+
+    /// Weight of `prune_open_disputes` with the current open disputes, each checked with
+    /// `reads_per_dispute` reads.
+    pub fn prune_open_disputes_weight(reads_per_dispute: u64) -> Weight {
+        let count = u64::from(Self::open_dispute_count());
+        T::DbWeight::get().reads_writes(
+            count.saturating_mul(reads_per_dispute.saturating_add(1)).saturating_add(1),
+            count.saturating_mul(2),
+        )
+    }
+
+    /// Dispute surcharge charged on `fee` while disputes are active.
+    fn pending_dispute_surcharge(fee: BalanceOf<T>) -> BalanceOf<T> {
+        if !Self::disputes_active() {
+            return Zero::zero();
+        }
+        Self::dispute_surcharge().mul_floor(fee)
+    }
+
+    /// Escrow the `DisputeSurcharge` on `fee` from `who` if disputes are active, the check is
+    /// charged to the block as a transaction of `class`.
+    fn charge_dispute_surcharge(
+        who: &T::AccountId,
+        fee: BalanceOf<T>,
+        class: DispatchClass,
+    ) -> Result<(), DispatchError> {
+        let amount = Self::dispute_surcharge().mul_floor(fee);
+        if amount.is_zero() {
+            return Ok(());
+        }
+        frame_system::Pallet::<T>::register_extra_weight_unchecked(
+            T::DbWeight::get().reads(1),
+            class,
+        );
+        if !Self::disputes_active() {
+            return Ok(());
+        }
+
+        Self::on_low_balance_exchange(who, amount)?;
+        T::FeeTokenBalanced::transfer(
+            who,
+            &T::SurchargePot::get(),
+            amount,
+            Preservation::Expendable,
+        )?;
+        EscrowedSurcharges::<T>::mutate(who, |escrowed| escrowed.saturating_accrue(amount));
+        Self::deposit_event(Event::<T>::DisputeSurchargeCharged { who: who.clone(), amount });
+        Ok(())
+    }
+
+    /// Once no dispute is active, refund up to `MaxSurchargeRefundsPerBlock` escrowed dispute
+    /// surcharges.
+    fn refund_dispute_surcharges() -> Weight {
+        let mut weight = T::DbWeight::get().reads(1);
+        if Self::disputes_active() {
+            return weight;
+        }
+
+        let refunds: Vec<_> = EscrowedSurcharges::<T>::drain()
+            .take(T::MaxSurchargeRefundsPerBlock::get() as usize)
+            .collect();
+        weight.saturating_accrue(T::DbWeight::get().reads(1));
+        for (who, amount) in refunds {
+            // the pot holds at least all the escrowed surcharges
+            let _ = T::FeeTokenBalanced::transfer(
+                &T::SurchargePot::get(),
+                &who,
+                amount,
+                Preservation::Expendable,
+            );
+            Self::deposit_event(Event::<T>::DisputeSurchargeRefunded { who, amount });
+            weight.saturating_accrue(T::DbWeight::get().reads_writes(2, 3));
+        }
+        weight
+    }
+
+    /// Grant the starter energy to up to `MaxStarterEnergyGrantsPerBlock` pending new accounts,
+    /// while the budget and the pot allow it.
+    fn grant_starter_energy() -> Weight {
+        let mut weight = T::DbWeight::get().reads(1);
+        let pending: Vec<_> = PendingStarterEnergy::<T>::drain()
+            .take(T::MaxStarterEnergyGrantsPerBlock::get() as usize)
+            .map(|(who, ())| who)
+            .collect();
+        if pending.is_empty() {
+            return weight;
+        }
+
+        let amount = Self::starter_energy_amount();
+        let mut budget = Self::starter_energy_budget();
+        weight.saturating_accrue(T::DbWeight::get().reads(2));
+        for who in pending {
+            weight.saturating_accrue(T::DbWeight::get().reads_writes(3, 4));
+            if amount.is_zero() || budget < amount {
+                continue;
+            }
+            let granted = T::FeeTokenBalanced::transfer(
+                &T::StarterEnergyPot::get(),
+                &who,
+                amount,
+                Preservation::Expendable,
+            );
+            if granted.is_ok() {
+                budget.saturating_reduce(amount);
+                StarterEnergyGranted::<T>::insert(&who, ());
+                Self::deposit_event(Event::<T>::StarterEnergyGranted { who, amount });
+            }
+        }
+        StarterEnergyBudget::<T>::put(budget);
+        weight
+    }
+
+    /// Recycle the fee paid by `who` with the fee token, after routing the `RebateFraction` of it
+    /// into the rebate pool of the era.
+    ///
+    /// The rebate part is recycled as well if the rebate pot can't receive it.
+    fn recycle_fee(who: &T::AccountId, credit: FeeCreditOf<T>) {
+        let fraction = Self::rebate_fraction();
+        let fee = credit.peek();
+        if fraction.is_zero() || fee.is_zero() {
+            T::FeeRecycleDestination::on_unbalanced(credit);
+            return;
+        }
+
+        let era = T::RebateEra::get();
+        let (rebate, credit) = credit.split(fraction.mul_floor(fee));
+        let amount = rebate.peek();
+        let credit = if amount.is_zero() {
+            credit.merge(rebate)
+        } else {
+            match T::FeeTokenBalanced::resolve(&T::RebatePot::get(), rebate) {
+                Ok(()) => credit,
+                Err(rebate) => credit.merge(rebate),
+            }
+        };
+        let amount = fee.saturating_sub(credit.peek());
+        T::FeeRecycleDestination::on_unbalanced(credit);
+
+        RebatePools::<T>::mutate(era, |pool| {
+            let pool = pool.get_or_insert_with(Default::default);
+            pool.amount.saturating_accrue(amount);
+            pool.unclaimed.saturating_accrue(amount);
+            pool.contributions.saturating_accrue(fee);
+        });
+        FeeContributions::<T>::mutate(era, who, |contribution| contribution.saturating_accrue(fee));
+    }
+
+    /// Account the fee paid by `who` to its spend in the current era, dropping the oldest era
+    /// of the history when it's full.
+    fn note_fee_spend(who: &T::AccountId, fee: BalanceOf<T>) {
+        let era = T::RebateEra::get();
+        AccountFeeSpend::<T>::mutate(who, |history| match history.last_mut() {
+            Some((last_era, spent)) if *last_era == era => spent.saturating_accrue(fee),
+            _ => {
+                if history.is_full() && !history.is_empty() {
+                    history.remove(0);
+                }
+                let _ = history.try_push((era, fee));
+            },
+        });
+    }
+
+    /// Fee token `who` spent on fees, substrate and Ethereum transactions alike, in each of its
+    /// latest `FeeSpendHistoryEras` eras with fees, oldest first.
+    ///
+    /// Eras without fees are skipped, so the history may span more eras than it has entries.
+    pub fn fee_spend_history(who: &T::AccountId) -> Vec<(u32, BalanceOf<T>)> {
+        AccountFeeSpend::<T>::get(who).into_inner()
+    }
+
+    /// Rebate `who` can claim for `era`, zero if `era` has no rebate pool anymore.
+    ///
+    /// The pool is shared in proportion to the fees paid during the era, capped at the fees
+    /// `who` paid.
+    pub fn pending_rebate(who: &T::AccountId, era: u32) -> BalanceOf<T> {
+        let Some(pool) = Self::rebate_pool(era) else {
+            return Zero::zero();
+        };
+        let contribution = FeeContributions::<T>::get(era, who);
+        Perquintill::from_rational(contribution, pool.contributions)
+            .mul_floor(pool.amount)
+            .min(contribution)
+            .min(pool.unclaimed)
+    }
+
+    /// On the start of a new rebate era, move the rebates that can't be claimed anymore to
+    /// `UnclaimedRebateDestination`.
+    fn expire_rebates() -> Weight {
+        let era = T::RebateEra::get();
+        let mut weight = T::DbWeight::get().reads(1);
+        if LastRebateEra::<T>::get() == Some(era) {
+            return weight;
+        }
+        LastRebateEra::<T>::put(era);
+        weight.saturating_accrue(T::DbWeight::get().writes(1));
+
+        let expired: Vec<_> = RebatePools::<T>::iter()
+            .filter(|(pool_era, _)| pool_era.saturating_add(T::RebateClaimEras::get()) < era)
+            .collect();
+        weight.saturating_accrue(T::DbWeight::get().reads(expired.len() as u64 + 1));
+        for (pool_era, pool) in expired {
+            RebatePools::<T>::remove(pool_era);
+            ExpiredFeeContributions::<T>::insert(pool_era, BoundedVec::default());
+            if !pool.unclaimed.is_zero() {
+                // the pot holds at least the unclaimed rebates of all pools
+                let _ = T::FeeTokenBalanced::transfer(
+                    &T::RebatePot::get(),
+                    &T::UnclaimedRebateDestination::get(),
+                    pool.unclaimed,
+                    Preservation::Expendable,
+                );
+            }
+            Self::deposit_event(Event::<T>::RebatesExpired {
+                era: pool_era,
+                amount: pool.unclaimed,
+            });
+            weight.saturating_accrue(T::DbWeight::get().writes(4));
+        }
+        weight
+    }
+
+    /// Remove up to `MaxFeeContributionRemovalsPerBlock` fee contributions of an expired rebate
+    /// era, the next blocks continue with the rest.
+    fn remove_expired_fee_contributions() -> Weight {
+        let mut weight = T::DbWeight::get().reads(1);
+        let Some((era, cursor)) = ExpiredFeeContributions::<T>::iter().next() else {
+            return weight;
+        };
+
+        let removal = FeeContributions::<T>::clear_prefix(
+            era,
+            T::MaxFeeContributionRemovalsPerBlock::get(),
+            (!cursor.is_empty()).then_some(cursor.as_slice()),
+        );
+        match removal.maybe_cursor {
+            Some(cursor) => ExpiredFeeContributions::<T>::insert(
+                era,
+                BoundedVec::try_from(cursor).unwrap_or_default(),
+            ),
+            None => ExpiredFeeContributions::<T>::remove(era),
+        }
+        weight.saturating_accrue(T::DbWeight::get().writes(1));
+        weight.saturating_add(
+            T::DbWeight::get().reads_writes(removal.loops.into(), removal.unique.into()),
+        )
+    }
+
     /// Check if user `who` owns reducible balance of token used for charging fees
     /// of at least `amount`, and if no, then exchange missing funds for user `who` using
-    /// `T::EnergyExchange`
+    /// `T::EnergyExchange`, unless the automatic swap is disabled
     fn on_low_balance_exchange(
         who: &T::AccountId,
         amount: BalanceOf<T>,
@@ -435,15 +1758,243 @@ impl<T: Config> Pallet<T> {
         let current_balance =
             T::FeeTokenBalanced::reducible_balance(who, Preservation::Expendable, Fortitude::Force);
 
-        (current_balance < amount)
-            .then(|| {
-                T::EnergyExchange::exchange_from_output(who, amount.saturating_sub(current_balance))
-                    .map(|_| ())
-            })
-            .map_or(Ok(()), |v| v)
+        if current_balance >= amount {
+            return Ok(());
+        }
+
+        ensure!(Self::auto_swap_enabled(), Error::<T>::AutoSwapDisabled);
+        ensure!(Self::remaining_fee_swaps(who) != Some(0), Error::<T>::FeeSwapLimitReached);
+        let missing_amount = amount.saturating_sub(current_balance);
+        if !KeepAliveOptOut::<T>::contains_key(who) {
+            Self::ensure_keep_alive_after_exchange(who, missing_amount)?;
+        }
+        let main_balance = T::MainTokenBalanced::free_balance(who);
+        T::EnergyExchange::exchange_fee_from_output(who, missing_amount)?;
+        Self::note_fee_swap(who);
+        Self::note_fee_payment_step(FeePaymentStep::Swapped {
+            amount: missing_amount,
+            price: main_balance.saturating_sub(T::MainTokenBalanced::free_balance(who)),
+        });
+        Ok(())
+    }
+
+    /// Fee swaps `who` can still trigger in the current era, `None` if they aren't limited.
+    ///
+    /// The limit is `FeeSwapLimit` plus the `FeeSwapLimitBonus` of `who`, swaps of earlier eras
+    /// don't count.
+    pub fn remaining_fee_swaps(who: &T::AccountId) -> Option<u32> {
+        let limit = Self::fee_swap_limit()?.saturating_add(T::FeeSwapLimitBonus::convert(who));
+        let era = T::RebateEra::get();
+        let swaps = FeeSwapCount::<T>::get(who)
+            .filter(|(swap_era, _)| *swap_era == era)
+            .map_or(0, |(_, swaps)| swaps);
+        Some(limit.saturating_sub(swaps))
+    }
+
+    /// Count a fee swap triggered by `who` in the current era, while swaps are limited.
+    fn note_fee_swap(who: &T::AccountId) {
+        if Self::fee_swap_limit().is_none() {
+            return;
+        }
+        let era = T::RebateEra::get();
+        FeeSwapCount::<T>::mutate(who, |count| match count {
+            Some((swap_era, swaps)) if *swap_era == era => swaps.saturating_inc(),
+            _ => *count = Some((era, 1)),
+        });
+    }
+
+    /// Withdraw `fee` from `who`, spending their fee credit first and exchanging the fee token
+    /// still missing after it.
+    ///
+    /// The returned credit only holds the part of the fee paid with the fee token, the fee
+    /// credit was recycled when it was bought.
+    fn withdraw_fee_with_credit(
+        who: &T::AccountId,
+        fee: BalanceOf<T>,
+    ) -> Result<FeeCreditOf<T>, DispatchError> {
+        let spent_credit = Self::fee_credit(who).min(fee);
+        let fee = fee.saturating_sub(spent_credit);
+
+        if !spent_credit.is_zero() {
+            Self::note_fee_payment_step(FeePaymentStep::FeeCredit { amount: spent_credit });
+        }
+        let imbalance = if fee.is_zero() {
+            FeeCreditOf::<T>::zero()
+        } else {
+            Self::on_low_balance_exchange(who, fee)?;
+            let imbalance = T::FeeTokenBalanced::withdraw(
+                who,
+                fee,
+                Precision::Exact,
+                Preservation::Expendable,
+                Fortitude::Force,
+            )?;
+            Self::note_fee_payment_step(FeePaymentStep::FeeToken { amount: fee });
+            imbalance
+        };
+
+        if !spent_credit.is_zero() {
+            FeeCredit::<T>::mutate_exists(who, |credit| {
+                *credit = credit
+                    .map(|credit| credit.saturating_sub(spent_credit))
+                    .filter(|credit| !credit.is_zero());
+            });
+            Self::deposit_event(Event::<T>::FeeCreditSpent {
+                who: who.clone(),
+                amount: spent_credit,
+            });
+        }
+        Ok(imbalance)
+    }
+
+    /// Make sure `who` can pay `fee`, tip and dispute surcharge included, trying the sources of
+    /// `preferences` in order until one of them covers the fee not paid with fee credit.
+    ///
+    /// An asset source only pays what the fee token of `who` is missing. The main token is tried
+    /// last, what is missing is then exchanged when the fee is withdrawn. Without preferences
+    /// nothing is done.
+    pub fn prepare_fee_payment(
+        who: &T::AccountId,
+        fee: BalanceOf<T>,
+        preferences: &[FeeSource<T::AssetKind>],
+    ) -> Result<(), DispatchError> {
+        let fee = fee.saturating_sub(Self::fee_credit(who));
+        if preferences.is_empty() || fee.is_zero() {
+            return Ok(());
+        }
+
+        let shortfall = fee.saturating_sub(T::FeeTokenBalanced::reducible_balance(
+            who,
+            Preservation::Expendable,
+            Fortitude::Force,
+        ));
+        for source in preferences {
+            let covered = match source {
+                FeeSource::FeeToken => shortfall.is_zero(),
+                FeeSource::Asset(asset) => {
+                    shortfall.is_zero()
+                        || frame_support::storage::with_storage_layer(|| {
+                            Self::exchange_fee_asset(who, asset.clone(), shortfall)
+                        })
+                        .is_ok()
+                },
+                FeeSource::MainToken => false,
+            };
+            if covered {
+                return Ok(());
+            }
+        }
+
+        ensure!(preferences.contains(&FeeSource::MainToken), Error::<T>::NoFeeSourceSufficient);
+        frame_support::storage::with_transaction_unchecked(|| {
+            TransactionOutcome::Rollback(Self::on_low_balance_exchange(who, fee))
+        })
+    }
+
+    /// Exchange `asset` of `who` for `amount` of fee token at the conversion rate of the asset.
+    /// The asset is transferred to `T::FeeAssetCollector`.
+    fn exchange_fee_asset(
+        who: &T::AccountId,
+        asset: T::AssetKind,
+        amount: BalanceOf<T>,
+    ) -> Result<(), DispatchError> {
+        ensure!(asset != T::EnergyAssetId::get(), Error::<T>::InvalidFeeAsset);
+        let price = T::FeeAssetRate::to_asset_balance(
+            T::EnergyExchange::convert_from_output(amount)?,
+            asset.clone(),
+        )?;
+        ensure!(!price.is_zero(), Error::<T>::InvalidFeeAsset);
+        <T::FeeAssets as fungibles::Mutate<_>>::transfer(
+            asset.clone(),
+            who,
+            &T::FeeAssetCollector::get(),
+            price,
+            Preservation::Preserve,
+        )?;
+        let _ = T::FeeTokenBalanced::deposit(who, amount, Precision::Exact)?;
+        Self::note_fee_payment_step(FeePaymentStep::AssetExchanged {
+            asset: asset.clone(),
+            amount,
+            price,
+        });
+        Self::deposit_event(Event::<T>::FeeAssetExchanged {
+            who: who.clone(),
+            asset,
+            amount,
+            price,
+        });
+        Ok(())
+    }
+
+    /// Remove the fee discount delegation of `delegator`, unreserving its deposit.
+    fn remove_fee_discount_delegation(
+        delegator: &T::AccountId,
+    ) -> Option<FeeDiscountDelegation<T::AccountId, BalanceOf<T>>> {
+        let delegation = FeeDiscountDelegates::<T>::take(delegator)?;
+        if delegation.accepted {
+            FeeDiscountDelegators::<T>::remove(&delegation.delegate);
+        }
+        T::MainTokenBalanced::unreserve(delegator, delegation.deposit);
+        Some(delegation)
+    }
+
+    /// Free up one of the `MaxEnergyAllowances` allowances of `owner`.
+    fn note_allowance_removed(owner: &T::AccountId) {
+        EnergyAllowanceCount::<T>::mutate_exists(owner, |count| {
+            *count = count.map(|count| count.saturating_sub(1)).filter(|count| *count > 0);
+        });
+    }
+
+    /// Transfer `amount` of fee token from `from` to `to` on behalf of `spender`, consuming
+    /// the allowance `from` gave to `spender`.
+    pub fn do_transfer_energy_from(
+        spender: &T::AccountId,
+        from: &T::AccountId,
+        to: &T::AccountId,
+        amount: BalanceOf<T>,
+    ) -> Result<(), DispatchError> {
+        EnergyAllowances::<T>::try_mutate_exists(from, spender, |maybe_allowance| {
+            let remaining = maybe_allowance
+                .unwrap_or_default()
+                .checked_sub(&amount)
+                .ok_or(Error::<T>::InsufficientEnergyAllowance)?;
+            T::FeeTokenBalanced::transfer(from, to, amount, Preservation::Preserve)?;
+            *maybe_allowance = Some(remaining).filter(|remaining| !remaining.is_zero());
+            if maybe_allowance.is_none() {
+                Self::note_allowance_removed(from);
+            }
+            Ok::<_, DispatchError>(())
+        })?;
+        Self::deposit_event(Event::<T>::EnergyTransferredFrom {
+            spender: spender.clone(),
+            from: from.clone(),
+            to: to.clone(),
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Ensure that exchanging for `amount` of fee token leaves at least the existential deposit
+    /// of the main token on `who`, so that a fee payment never reaps the account.
+    fn ensure_keep_alive_after_exchange(
+        who: &T::AccountId,
+        amount: BalanceOf<T>,
+    ) -> Result<(), DispatchError> {
+        let required = T::EnergyExchange::convert_from_output(amount)?;
+        let remaining = T::MainTokenBalanced::free_balance(who)
+            .checked_sub(&required)
+            .ok_or(DispatchError::Token(TokenError::FundsUnavailable))?;
+        ensure!(
+            remaining >= T::MainTokenBalanced::minimum_balance(),
+            DispatchError::Token(TokenError::NotExpendable)
+        );
+        Ok(())
     }
 
     /// Calculate fee as VTRS and VNRG parts based on the presence of VNRG tokens
+    ///
+    /// Fails if VNRG is missing while the automatic swap is disabled, or `who` can't trigger
+    /// another fee swap in this era.
     pub fn calculate_fee_parts(
         who: &T::AccountId,
         amount: BalanceOf<T>,
@@ -452,6 +2003,8 @@ impl<T: Config> Pallet<T> {
             T::FeeTokenBalanced::reducible_balance(who, Preservation::Expendable, Fortitude::Force);
 
         if current_balance < amount {
+            ensure!(Self::auto_swap_enabled(), Error::<T>::AutoSwapDisabled);
+            ensure!(Self::remaining_fee_swaps(who) != Some(0), Error::<T>::FeeSwapLimitReached);
             let missing_amount =
                 T::EnergyExchange::convert_from_output(amount.saturating_sub(current_balance))?;
             Ok((current_balance, missing_amount))
@@ -460,6 +2013,275 @@ impl<T: Config> Pallet<T> {
         }
     }
 
+    /// Whether `who` can currently pay the fee of `call`, exchanging missing fee tokens the way
+    /// fee withdrawal does. No state is changed.
+    pub fn can_afford_fee(who: &T::AccountId, call: &T::RuntimeCall) -> bool {
+        if Self::is_fee_paused(call) {
+            return true;
+        }
+        let fee = T::CustomFee::dispatch_info_to_fee(call, None, None).into_inner();
+        let fee = Self::charged_fee(who, call, fee).saturating_sub(Self::fee_credit(who));
+
+        frame_support::storage::with_transaction_unchecked(|| {
+            TransactionOutcome::Rollback(Self::on_low_balance_exchange(who, fee).is_ok())
+        })
+    }
+
+    /// Fee multiplier which was applied to the transactions of `block`.
+    ///
+    /// Returns `None` for blocks outside of the `FeeMultiplierHistoryDepth` window.
+    pub fn multiplier_at(block: BlockNumberFor<T>) -> Option<Multiplier> {
+        let now = frame_system::Pallet::<T>::block_number();
+        if block > now || now.saturating_sub(block) >= T::FeeMultiplierHistoryDepth::get() {
+            return None;
+        }
+        FeeMultiplierHistory::<T>::get(block)
+    }
+
+    /// Fee paid in the fee token by the Ethereum transaction `tx_hash`.
+    ///
+    /// Returns `None` for unknown transactions and transactions included before the
+    /// `EvmFeeReceiptRetention` window.
+    pub fn evm_fee_paid(tx_hash: H256) -> Option<BalanceOf<T>> {
+        Self::evm_fee_receipt(tx_hash).map(|(_, fee)| fee)
+    }
+
+    /// Block including the Ethereum transaction `tx_hash` and the fee it paid in the fee token.
+    ///
+    /// Receipts are part of the state of the chain they were recorded on: after a reorg, the
+    /// receipts of the reverted blocks are gone with them and a transaction included again gets
+    /// the receipt of its new block. Receipts are pruned by block number relative to the block
+    /// of the state, so a receipt is only reliable once its block is finalized, which callers
+    /// have to check against the finalized block of their node.
+    pub fn evm_fee_receipt(tx_hash: H256) -> Option<(BlockNumberFor<T>, BalanceOf<T>)> {
+        let (block, fee) = EvmFeeReceipts::<T>::get(tx_hash)?;
+        let now = frame_system::Pallet::<T>::block_number();
+        if now.saturating_sub(block) >= T::EvmFeeReceiptRetention::get() {
+            return None;
+        }
+        Some((block, fee))
+    }
+
+    /// Attribute the fee withdrawn by the last EVM execution to the Ethereum transaction
+    /// `tx_hash`. Must be called right after the transaction is applied.
+    ///
+    /// Nothing is recorded if the transaction didn't reach fee withdrawal or the receipts of the
+    /// block are full.
+    pub fn note_evm_fee_receipt(tx_hash: H256) {
+        let Some(fee) = PendingEvmFee::<T>::take() else { return };
+        if T::EvmFeeReceiptRetention::get().is_zero() {
+            return;
+        }
+
+        let now = frame_system::Pallet::<T>::block_number();
+        if EvmFeeReceiptsByBlock::<T>::mutate(now, |hashes| hashes.try_push(tx_hash)).is_ok() {
+            EvmFeeReceipts::<T>::insert(tx_hash, (now, fee));
+        }
+    }
+
+    /// The steps taken to pay the fee of the transaction at `index` of `block`, in order.
+    ///
+    /// Returns `None` for transactions which paid no fee and blocks before the
+    /// `FeePaymentPathRetention` window. Ethereum transactions and substrate extrinsics are
+    /// indexed alike, by their position in the block.
+    pub fn fee_payment_path(block: BlockNumberFor<T>, index: u32) -> Option<FeePaymentPathOf<T>> {
+        let now = frame_system::Pallet::<T>::block_number();
+        if block > now || now.saturating_sub(block) >= T::FeePaymentPathRetention::get() {
+            return None;
+        }
+        FeePaymentPaths::<T>::get(block, index)
+    }
+
+    /// Latch the switch of [`crate::traits::BootstrapExchange`] to the fee pool, it became liquid.
+    pub(crate) fn note_fee_pool_liquid() {
+        FeePoolLiquid::<T>::put(true);
+        Self::deposit_event(Event::<T>::FeePoolBecameLiquid);
+    }
+
+    /// Keep the fee sources listed by the extrinsic being applied until its fee is withdrawn.
+    pub(crate) fn note_fee_preferences(preferences: FeePreferences<T>) {
+        if preferences.is_empty() {
+            PendingFeePreferences::<T>::kill();
+        } else {
+            PendingFeePreferences::<T>::put(preferences);
+        }
+    }
+
+    /// Weight of recording the fee payment path of an extrinsic, charged by [`CheckEnergyFee`].
+    pub(crate) fn fee_payment_path_weight() -> Weight {
+        if T::FeePaymentPathRetention::get().is_zero() {
+            return Weight::zero();
+        }
+        T::DbWeight::get().reads_writes(1, 1)
+    }
+
+    /// Weight of counting the fee swap an extrinsic may trigger, charged by [`CheckEnergyFee`].
+    pub(crate) fn fee_swap_weight() -> Weight {
+        if Self::fee_swap_limit().is_none() {
+            return T::DbWeight::get().reads(1);
+        }
+        T::DbWeight::get().reads_writes(2, 1)
+    }
+
+    /// Append `step` to the fee payment path of the extrinsic being applied.
+    ///
+    /// Steps beyond `MAX_FEE_PAYMENT_STEPS` are dropped. Nothing is recorded outside of an
+    /// extrinsic, e.g. when validating a transaction.
+    fn note_fee_payment_step(step: FeePaymentStep<T::AssetKind, BalanceOf<T>>) {
+        if T::FeePaymentPathRetention::get().is_zero() {
+            return;
+        }
+        let Some(index) = frame_system::Pallet::<T>::extrinsic_index() else { return };
+
+        let now = frame_system::Pallet::<T>::block_number();
+        FeePaymentPaths::<T>::mutate(now, index, |path| {
+            let _ = path.get_or_insert_with(Default::default).try_push(step);
+        });
+    }
+
+    /// Remove up to `MaxFeePaymentPathRemovalsPerBlock` fee payment paths of the blocks which
+    /// left the retention window, resuming where the previous block stopped.
+    fn prune_fee_payment_paths(now: BlockNumberFor<T>) -> Weight {
+        let retention = T::FeePaymentPathRetention::get();
+        if retention.is_zero() || now < retention {
+            return Weight::zero();
+        }
+
+        let mut weight = T::DbWeight::get().reads_writes(1, 1);
+        let expired = now.saturating_sub(retention);
+        let (mut block, mut cursor) =
+            FeePaymentPathPruning::<T>::get().unwrap_or((expired, Default::default()));
+        let mut limit = T::MaxFeePaymentPathRemovalsPerBlock::get();
+        while block <= expired && !limit.is_zero() {
+            let removal = FeePaymentPaths::<T>::clear_prefix(
+                block,
+                limit,
+                (!cursor.is_empty()).then_some(cursor.as_slice()),
+            );
+            weight.saturating_accrue(
+                T::DbWeight::get().reads_writes(removal.loops.into(), removal.unique.into()),
+            );
+            // Every block costs at least a read, even without paths.
+            limit = limit.saturating_sub(removal.loops.max(1));
+            match removal.maybe_cursor {
+                Some(next) => {
+                    cursor = BoundedVec::try_from(next).unwrap_or_default();
+                    break;
+                },
+                None => {
+                    block.saturating_inc();
+                    cursor = Default::default();
+                },
+            }
+        }
+        FeePaymentPathPruning::<T>::put((block, cursor));
+        weight
+    }
+
+    /// Remove the fee receipts of the block which left the retention window.
+    fn prune_evm_fee_receipts(now: BlockNumberFor<T>) -> Weight {
+        let retention = T::EvmFeeReceiptRetention::get();
+        if retention.is_zero() || now < retention {
+            return Weight::zero();
+        }
+
+        let expired = EvmFeeReceiptsByBlock::<T>::take(now.saturating_sub(retention));
+        for tx_hash in &expired {
+            EvmFeeReceipts::<T>::remove(tx_hash);
+        }
+
+        T::DbWeight::get().reads_writes(1, 1 + expired.len() as u64)
+    }
+
+    /// Store the multiplier used in the current block and prune the entry which left the
+    /// history window.
+    fn record_fee_multiplier(now: BlockNumberFor<T>) -> Weight {
+        let depth = T::FeeMultiplierHistoryDepth::get();
+        if depth.is_zero() {
+            return Weight::zero();
+        }
+
+        let multiplier = pallet_transaction_payment::Pallet::<T>::next_fee_multiplier();
+        FeeMultiplierHistory::<T>::insert(now, multiplier);
+        if now >= depth {
+            FeeMultiplierHistory::<T>::remove(now.saturating_sub(depth));
+        }
+
+        T::DbWeight::get().reads_writes(1, 2)
+    }
+
+    /// Snapshot the `EnergyPrice` every `PriceSnapshotInterval` blocks, dropping the oldest
+    /// snapshot once `MaxPriceSnapshots` are kept.
+    fn record_price_snapshot(now: BlockNumberFor<T>) -> Weight {
+        let interval = T::PriceSnapshotInterval::get();
+        if interval.is_zero() || T::MaxPriceSnapshots::get() == 0 || !(now % interval).is_zero() {
+            return Weight::zero();
+        }
+
+        let Some(price) = T::EnergyPrice::get() else {
+            return T::DbWeight::get().reads(2);
+        };
+        PriceSnapshots::<T>::mutate(|snapshots| {
+            if snapshots.is_full() {
+                snapshots.remove(0);
+            }
+            let _ = snapshots.try_push((now, price));
+        });
+
+        T::DbWeight::get().reads_writes(3, 1)
+    }
+
+    /// Up to `samples` price snapshots at least `interval` blocks apart, oldest first and ending
+    /// with the latest snapshot.
+    ///
+    /// Fewer samples are returned when not enough snapshots are kept, none when `interval` is
+    /// shorter than the `PriceSnapshotInterval`.
+    pub fn energy_price_history(
+        samples: u32,
+        interval: BlockNumberFor<T>,
+    ) -> Vec<(BlockNumberFor<T>, BalanceOf<T>)> {
+        let cadence = T::PriceSnapshotInterval::get();
+        if cadence.is_zero() || interval < cadence {
+            return Vec::new();
+        }
+
+        let mut history: Vec<(BlockNumberFor<T>, BalanceOf<T>)> = Vec::new();
+        for (block, price) in PriceSnapshots::<T>::get().into_iter().rev() {
+            if history.len() >= samples as usize {
+                break;
+            }
+            if history.last().map_or(true, |(last, _)| block.saturating_add(interval) <= *last) {
+                history.push((block, price));
+            }
+        }
+        history.reverse();
+        history
+    }
+
+    /// Evaluate `f` as if `base_fee` and `multiplier` were the current fee parameters.
+    ///
+    /// Every storage change, including the ones made by `f`, is rolled back afterwards.
+    pub fn with_fee_parameters<R>(
+        base_fee: BalanceOf<T>,
+        multiplier: Multiplier,
+        f: impl FnOnce() -> R,
+    ) -> R {
+        frame_support::storage::with_transaction_unchecked(|| {
+            BaseFee::<T>::put(base_fee);
+            pallet_transaction_payment::NextFeeMultiplier::<T>::put(multiplier);
+            TransactionOutcome::Rollback(f())
+        })
+    }
+
+    /// Lowest and highest fee `fee` evaluates to under the current base fee, i.e. with the
+    /// default and the upper fee multiplier.
+    pub fn fee_range(fee: impl Fn() -> BalanceOf<T>) -> (BalanceOf<T>, BalanceOf<T>) {
+        let base_fee = Self::base_fee();
+        let low = Self::with_fee_parameters(base_fee, DefaultFeeMultiplier::<T>::get(), &fee);
+        let high = Self::with_fee_parameters(base_fee, Self::upper_fee_multiplier(), &fee);
+        (low.min(high), low.max(high))
+    }
+
     fn update_burned_energy(amount: BalanceOf<T>) -> Result<(), DispatchError> {
         BurnedEnergy::<T>::mutate(|current_burned| {
             *current_burned =
@@ -482,7 +2304,8 @@ impl<T: Config> Pallet<T> {
 }
 
 impl<T: Config> Convert<Multiplier, Multiplier> for Pallet<T> {
-    fn convert(_previous: Multiplier) -> Multiplier {
+    /// Deposits `MultiplierUpdated` when the multiplier changes.
+    fn convert(previous: Multiplier) -> Multiplier {
         let min_multiplier = DefaultFeeMultiplier::<T>::get();
         let max_multiplier = Self::upper_fee_multiplier();
 
@@ -518,11 +2341,19 @@ impl<T: Config> Convert<Multiplier, Multiplier> for Pallet<T> {
         let threshold_weight = (block_fullness_threshold * max_limiting_dimension) as u128;
         let block_weight = normal_limiting_dimension as u128;
 
-        if threshold_weight <= block_weight {
-            max_multiplier
-        } else {
-            min_multiplier
+        let multiplier =
+            if threshold_weight <= block_weight { max_multiplier } else { min_multiplier };
+        if multiplier != previous {
+            Self::deposit_event(Event::<T>::MultiplierUpdated {
+                old: previous,
+                new: multiplier,
+                block_fullness: Perquintill::from_rational(
+                    normal_limiting_dimension,
+                    max_limiting_dimension.max(1),
+                ),
+            });
         }
+        multiplier
     }
 }
 
@@ -558,6 +2389,21 @@ impl<T: Config> MultiplierUpdate for Pallet<T> {
     }
 }
 
+/// Queues new accounts for their starter energy. The fee token can't be granted while the
+/// account is being created, so it is granted at the start of the next block.
+impl<T: Config> OnNewAccount<T::AccountId> for Pallet<T> {
+    fn on_new_account(who: &T::AccountId) {
+        let amount = Self::starter_energy_amount();
+        if amount.is_zero()
+            || Self::starter_energy_budget() < amount
+            || StarterEnergyGranted::<T>::contains_key(who)
+        {
+            return;
+        }
+        PendingStarterEnergy::<T>::insert(who, ());
+    }
+}
+
 /// Handler for when a fee has been withdrawn.
 pub trait OnWithdrawFeeHandler<AccountId> {
     fn on_withdraw_fee(who: &AccountId);
@@ -1,14 +1,14 @@
 use core::marker::PhantomData;
 
-use crate::traits::{AssetsBalancesConverter, NativeExchange};
+use crate::traits::{AssetsBalancesConverter, BootstrapExchange, NativeExchange};
 use crate::{self as pallet_energy_fee, FeeCreditOf};
-use crate::{CallFee, CustomFee};
+use crate::{CallFee, CustomFee, GetByKey, TokenExchange};
 use fp_account::AccountId20;
 
 use frame_support::dispatch::GetDispatchInfo;
 use frame_support::traits::fungible::{Balanced, ItemOf};
 use frame_support::traits::tokens::imbalance::SplitTwoWays;
-use frame_support::traits::{Currency, OnUnbalanced};
+use frame_support::traits::{Contains, Currency, OnUnbalanced};
 use frame_support::weights::{ConstantMultiplier, IdentityFee};
 use frame_support::{
     derive_impl,
@@ -26,8 +26,8 @@ use sp_arithmetic::{FixedPointNumber, FixedU128, Perbill, Perquintill};
 use sp_core::{Get, H256, U256};
 
 use sp_runtime::{
-    traits::{BlakeTwo256, DispatchInfoOf, IdentityLookup, Zero},
-    BuildStorage, Permill,
+    traits::{BlakeTwo256, Convert, DispatchInfoOf, IdentityLookup, Zero},
+    ArithmeticError, BuildStorage, DispatchError, Permill,
 };
 
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -37,18 +37,26 @@ pub(crate) type AssetId = u32;
 pub(crate) type Nonce = u64;
 pub(crate) type Balance = u128;
 pub(crate) type BalancesVNRG = ItemOf<Assets, GetVNRG, AccountId>;
-pub(crate) type EnergyRate = AssetsBalancesConverter<Test, AssetRate>;
+pub(crate) type EnergyRate = AssetsBalancesConverter<Test, AssetRate, FallbackEnergyRate>;
 
 pub(crate) const VNRG: AssetId = 1;
 pub(crate) const ALICE: AccountId = AccountId20([1u8; 20]);
 pub(crate) const BOB: AccountId = AccountId20([2u8; 20]);
 pub(crate) const FEE_DEST: AccountId = AccountId20([3u8; 20]);
 pub(crate) const MAIN_DEST: AccountId = AccountId20([4u8; 20]);
+pub(crate) const FEE_ASSET_DEST: AccountId = AccountId20([5u8; 20]);
+pub(crate) const REBATE_POT: AccountId = AccountId20([6u8; 20]);
+pub(crate) const TREASURY: AccountId = AccountId20([7u8; 20]);
+pub(crate) const SURCHARGE_POT: AccountId = AccountId20([8u8; 20]);
+pub(crate) const STARTER_POT: AccountId = AccountId20([9u8; 20]);
 
 /// 10^9 with 18 decimals
 /// 1 VNRG = VNRG_TO_VTRS_RATE VTRS
 pub(crate) const VNRG_TO_VTRS_RATE: FixedU128 =
     FixedU128::from_inner(1_000_000_000_000_000_000_000_000_000);
+/// Price of VNRG in the mocked broker pool, twice the oracle rate
+pub(crate) const POOL_VNRG_TO_VTRS_RATE: FixedU128 =
+    FixedU128::from_inner(2_000_000_000_000_000_000_000_000_000);
 pub(crate) const VTRS_INITIAL_BALANCE: u128 = 2_000_000_000_000_000_000_000_000_000;
 
 // Configure a mock runtime to test the pallet.
@@ -79,6 +87,7 @@ parameter_types! {
     pub const AssetAccountDeposit: Balance = 0;
     pub const ApprovalDeposit: Balance = 0;
     pub const AssetsStringLimit: u32 = 50;
+    pub const FeeDiscountDelegationDeposit: Balance = 100;
     pub const MetadataDepositBase: Balance = 0;
     pub const MetadataDepositPerByte: Balance = 0;
     pub BlockWeights: frame_system::limits::BlockWeights = frame_system::limits::BlockWeights
@@ -109,7 +118,7 @@ impl frame_system::Config for Test {
     type Version = ();
     type PalletInfo = PalletInfo;
     type AccountData = pallet_balances::AccountData<Balance>;
-    type OnNewAccount = ();
+    type OnNewAccount = EnergyFee;
     type OnKilledAccount = ();
     type SystemWeightInfo = ();
     type SS58Prefix = ();
@@ -144,8 +153,18 @@ impl pallet_asset_rate::Config for Test {
 }
 
 parameter_types! {
+    pub const FallbackEnergyRate: Option<FixedU128> = Some(VNRG_TO_VTRS_RATE);
     pub const FeeBurnAccount: AccountId = FEE_DEST;
     pub const MainBurnAccount: AccountId = MAIN_DEST;
+    pub const FeeAssetAccount: AccountId = FEE_ASSET_DEST;
+    pub const RebatePotAccount: AccountId = REBATE_POT;
+    pub const TreasuryAccount: AccountId = TREASURY;
+    pub static RebateEra: u32 = 0;
+    pub static MinimumFee: Balance = 0;
+    pub static EnergyPrice: Option<Balance> = None;
+    pub static PoolLiquid: bool = false;
+    pub const SurchargePotAccount: AccountId = SURCHARGE_POT;
+    pub const StarterPotAccount: AccountId = STARTER_POT;
 }
 
 pub struct FeeBurnDestination<GetAccountId: Get<AccountId>>(PhantomData<GetAccountId>);
@@ -173,6 +192,32 @@ impl<GetAccountId: Get<AccountId>> OnUnbalanced<NegativeImbalance<Test>>
 pub(crate) type EnergyExchange =
     NativeExchange<AssetId, BalancesVTRS, BalancesVNRG, EnergyRate, GetVNRG>;
 
+/// Stands in for the broker pool, exchanging at `POOL_VNRG_TO_VTRS_RATE`.
+pub struct PoolExchange;
+
+impl
+    TokenExchange<
+        AccountId,
+        BalancesVTRS,
+        BalancesVNRG,
+        MainBurnDestination<MainBurnAccount>,
+        Balance,
+    > for PoolExchange
+{
+    fn convert_from_input(amount: Balance) -> Result<Balance, DispatchError> {
+        POOL_VNRG_TO_VTRS_RATE
+            .reciprocal()
+            .and_then(|rate| rate.checked_mul_int(amount))
+            .ok_or(ArithmeticError::Overflow.into())
+    }
+
+    fn convert_from_output(amount: Balance) -> Result<Balance, DispatchError> {
+        POOL_VNRG_TO_VTRS_RATE
+            .checked_mul_int(amount)
+            .ok_or(ArithmeticError::Overflow.into())
+    }
+}
+
 impl pallet_energy_fee::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type ManageOrigin = EnsureRoot<AccountId>;
@@ -180,12 +225,102 @@ impl pallet_energy_fee::Config for Test {
     type CustomFee = EnergyFee;
     type FeeTokenBalanced = BalancesVNRG;
     type MainTokenBalanced = BalancesVTRS;
-    type EnergyExchange = EnergyExchange;
+    type EnergyExchange = BootstrapExchange<Test, PoolExchange, EnergyExchange, PoolLiquid>;
+    type EnergyRedemption = EnergyExchange;
     type EnergyAssetId = GetVNRG;
     type MainRecycleDestination = MainBurnDestination<MainBurnAccount>;
     type FeeRecycleDestination =
         SplitTwoWays<Balance, FeeCreditOf<Test>, FeeBurnDestination<FeeBurnAccount>, (), 2, 8>;
     type OnWithdrawFee = ();
+    type DefaultBlockGasLimit = ConstU64<75_000_000>;
+    type MinBlockGasLimit = ConstU64<15_000_000>;
+    type MaxBlockGasLimit = ConstU64<150_000_000>;
+    type FeeMultiplierHistoryDepth = ConstU64<3>;
+    type NacLevel = MockNacLevel;
+    type NacFeeDiscount = NacFeeDiscount;
+    type MinimumFee = MinimumFee;
+    type FeeExemptCalls = SudoCalls;
+    type EvmFeeReceiptRetention = ConstU64<3>;
+    type MaxEvmFeeReceiptsPerBlock = ConstU32<2>;
+    type FeePaymentPathRetention = ConstU64<3>;
+    type MaxFeePaymentPathRemovalsPerBlock = ConstU32<1>;
+    type FeeAssets = Assets;
+    type FeeAssetRate = AssetsBalancesConverter<Test, AssetRate>;
+    type FeeAssetCollector = FeeAssetAccount;
+    type RebateEra = RebateEra;
+    type FeeSpendHistoryEras = ConstU32<2>;
+    type RebatePot = RebatePotAccount;
+    type RebateClaimEras = ConstU32<2>;
+    type UnclaimedRebateDestination = TreasuryAccount;
+    type CrossChainCalls = AssetsCalls;
+    type SurchargePot = SurchargePotAccount;
+    type MaxSurchargeRefundsPerBlock = ConstU32<1>;
+    type EnergyPrice = EnergyPrice;
+    type PriceSnapshotInterval = ConstU64<2>;
+    type MaxPriceSnapshots = ConstU32<3>;
+    type StarterEnergyPot = StarterPotAccount;
+    type MaxStarterEnergyGrantsPerBlock = ConstU32<2>;
+    type FeeSwapLimitBonus = MockFeeSwapLimitBonus;
+    type MaxEnergyAllowances = ConstU32<2>;
+    type MaxFeeContributionRemovalsPerBlock = ConstU32<1>;
+    type FeeDiscountDelegationDeposit = FeeDiscountDelegationDeposit;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub static NacLevels: Vec<(AccountId, u8)> = vec![];
+    pub static FeeSwapLimitBonuses: Vec<(AccountId, u32)> = vec![];
+}
+
+pub struct SudoCalls;
+
+impl Contains<RuntimeCall> for SudoCalls {
+    fn contains(call: &RuntimeCall) -> bool {
+        matches!(call, RuntimeCall::Sudo(..))
+    }
+}
+
+/// Stand-in for the calls interacting with cross-chain state
+pub struct AssetsCalls;
+
+impl Contains<RuntimeCall> for AssetsCalls {
+    fn contains(call: &RuntimeCall) -> bool {
+        matches!(call, RuntimeCall::Assets(..))
+    }
+}
+
+pub struct MockNacLevel;
+
+impl Convert<&AccountId, Option<u8>> for MockNacLevel {
+    fn convert(who: &AccountId) -> Option<u8> {
+        NacLevels::get()
+            .into_iter()
+            .find(|(account, _)| account == who)
+            .map(|(_, level)| level)
+    }
+}
+
+pub struct MockFeeSwapLimitBonus;
+
+impl Convert<&AccountId, u32> for MockFeeSwapLimitBonus {
+    fn convert(who: &AccountId) -> u32 {
+        FeeSwapLimitBonuses::get()
+            .into_iter()
+            .find(|(account, _)| account == who)
+            .map_or(0, |(_, bonus)| bonus)
+    }
+}
+
+/// 10% off per NAC level above 1, level 0 is configured with a discount to check it is ignored
+pub struct NacFeeDiscount;
+
+impl GetByKey<u8, Perbill> for NacFeeDiscount {
+    fn get(level: &u8) -> Perbill {
+        match level {
+            0 => Perbill::from_percent(50),
+            level => Perbill::from_percent(10 * (*level as u32 - 1)),
+        }
+    }
 }
 
 impl pallet_timestamp::Config for Test {
@@ -383,6 +518,14 @@ pub fn new_test_ext(energy_balance: Balance) -> sp_io::TestExternalities {
     t.into()
 }
 
+/// Externalities for the benchmarks, in the rebate era after the first, so that the rebates of
+/// an ended era can be claimed.
+#[cfg(feature = "runtime-benchmarks")]
+pub fn new_benchmark_ext() -> sp_io::TestExternalities {
+    RebateEra::set(1);
+    new_test_ext(0)
+}
+
 pub(crate) fn calculate_block_weight_based_on_threshold(threshold: Perquintill) -> Weight {
     let max_block_weight = <Test as frame_system::Config>::BlockWeights::get().max_block;
     let (ref_time, proof_size) = (
@@ -1,12 +1,21 @@
 //! Tests for the module.
 
-use crate::{mock::*, BurnedEnergy, BurnedEnergyThreshold, CheckEnergyFee, Event, TokenExchange};
+use crate::{
+    mock::*, BurnedEnergy, BurnedEnergyThreshold, CheckEnergyFee, CustomFee, EnergyAllowanceCount,
+    EnergyAllowances, Error, EscrowedSurcharges, Event, EvmFeeReceipts, EvmFeeReceiptsByBlock,
+    ExpiredFeeContributions, FeeContributions, FeeCredit, FeePaymentPathPruning, FeePaymentPaths,
+    FeePaymentStep, FeeSource, KeepAliveOptOut, PendingStarterEnergy, PriceSnapshots, RebatePools,
+    StarterEnergyGranted, TokenExchange,
+};
 use frame_support::{
-    dispatch::{DispatchInfo, GetDispatchInfo},
+    assert_noop, assert_ok,
+    dispatch::{DispatchClass, DispatchInfo, GetDispatchInfo},
     traits::{
-        fungible::Inspect, Hooks, LockIdentifier, LockableCurrency, NamedReservableCurrency,
-        WithdrawReasons,
+        fungible::{Inspect, Mutate},
+        Currency, Hooks, LockIdentifier, LockableCurrency, NamedReservableCurrency, OnNewAccount,
+        PalletInfoAccess, WithdrawReasons,
     },
+    weights::Weight,
 };
 use frame_system::{
     mocking::MockUncheckedExtrinsic,
@@ -17,11 +26,12 @@ use pallet_assets::{weights::SubstrateWeight as AssetsWeight, WeightInfo as _};
 use pallet_evm::{Config as EVMConfig, GasWeightMapping, OnChargeEVMTransaction};
 use pallet_transaction_payment::{Multiplier, OnChargeTransaction};
 use parity_scale_codec::Encode;
-use sp_arithmetic::Perbill;
+use sp_arithmetic::{FixedU128, Perbill};
+use sp_core::H256;
 use sp_runtime::{
     traits::{One, SignedExtension},
     transaction_validity::{InvalidTransaction, TransactionValidityError},
-    DispatchError, FixedPointNumber, Perquintill,
+    DispatchError, FixedPointNumber, Perquintill, TokenError, TransactionOutcome,
 };
 
 type Extrinsic = MockUncheckedExtrinsic<Test>;
@@ -475,6 +485,60 @@ fn fee_multiplier_works() {
     });
 }
 
+#[test]
+fn fee_multiplier_changes_are_announced() {
+    new_test_ext(INITIAL_ENERGY_BALANCE).execute_with(|| {
+        System::set_block_number(1);
+        let upper_multiplier = Multiplier::from(2);
+        assert_ok!(EnergyFee::update_upper_fee_multiplier(RuntimeOrigin::root(), upper_multiplier));
+        let multiplier_updates = || {
+            System::events()
+                .into_iter()
+                .filter_map(|record| match record.event {
+                    RuntimeEvent::EnergyFee(event @ Event::MultiplierUpdated { .. }) => Some(event),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // an empty block keeps the lower multiplier
+        TransactionPayment::on_finalize(1);
+        assert!(multiplier_updates().is_empty());
+
+        let weights = <Test as frame_system::Config>::BlockWeights::get();
+        let normal_max_weight =
+            weights.get(DispatchClass::Normal).max_total.unwrap_or(weights.max_block);
+        System::set_block_consumed_resources(normal_max_weight, 0);
+        TransactionPayment::on_finalize(1);
+        assert_eq!(TransactionPayment::next_fee_multiplier(), upper_multiplier);
+        assert_eq!(
+            multiplier_updates(),
+            vec![Event::MultiplierUpdated {
+                old: Multiplier::one(),
+                new: upper_multiplier,
+                block_fullness: Perquintill::one(),
+            }]
+        );
+
+        // unchanged multipliers aren't announced
+        TransactionPayment::on_finalize(1);
+        assert_eq!(multiplier_updates().len(), 1);
+
+        System::reset_events();
+        System::set_block_consumed_resources(Weight::zero(), 0);
+        TransactionPayment::on_finalize(1);
+        assert_eq!(TransactionPayment::next_fee_multiplier(), Multiplier::one());
+        assert_eq!(
+            multiplier_updates(),
+            vec![Event::MultiplierUpdated {
+                old: upper_multiplier,
+                new: Multiplier::one(),
+                block_fullness: Perquintill::zero(),
+            }]
+        );
+    });
+}
+
 #[test]
 fn fee_multiplier_works_for_evm() {
     new_test_ext(INITIAL_ENERGY_BALANCE).execute_with(|| {
@@ -597,3 +661,1365 @@ fn exchange_should_not_withdraw_reserved_balance() {
         .is_ok());
     })
 }
+
+#[test]
+fn with_fee_parameters_does_not_change_storage() {
+    new_test_ext(INITIAL_ENERGY_BALANCE).execute_with(|| {
+        let call = RuntimeCall::Assets(pallet_assets::Call::transfer {
+            id: VNRG.into(),
+            target: BOB,
+            amount: 1_000,
+        });
+        let current_fee = EnergyFee::dispatch_info_to_fee(&call, None, None).into_inner();
+        assert_eq!(current_fee, GetConstantEnergyFee::get());
+
+        let new_base_fee = 100;
+        let new_multiplier = Multiplier::from(3);
+        let simulated_fee = EnergyFee::with_fee_parameters(new_base_fee, new_multiplier, || {
+            EnergyFee::dispatch_info_to_fee(&call, None, None).into_inner()
+        });
+
+        assert_eq!(simulated_fee, new_multiplier.saturating_mul_int(new_base_fee));
+        assert_eq!(EnergyFee::base_fee(), GetConstantEnergyFee::get());
+        assert_eq!(TransactionPayment::next_fee_multiplier(), Multiplier::one());
+        assert_eq!(EnergyFee::dispatch_info_to_fee(&call, None, None).into_inner(), current_fee);
+    });
+}
+
+#[test]
+fn update_block_gas_limit_works() {
+    new_test_ext(INITIAL_ENERGY_BALANCE).execute_with(|| {
+        System::set_block_number(1);
+        assert_eq!(EnergyFee::block_gas_limit(), 75_000_000);
+
+        assert_noop!(
+            EnergyFee::update_block_gas_limit(RuntimeOrigin::root(), 150_000_001),
+            Error::<Test>::BlockGasLimitOutOfBounds
+        );
+        assert_noop!(
+            EnergyFee::update_block_gas_limit(RuntimeOrigin::root(), 14_999_999),
+            Error::<Test>::BlockGasLimitOutOfBounds
+        );
+        assert_noop!(
+            EnergyFee::update_block_gas_limit(RuntimeOrigin::signed(ALICE), 100_000_000),
+            DispatchError::BadOrigin
+        );
+
+        let new_limit = 100_000_000;
+        assert_ok!(EnergyFee::update_block_gas_limit(RuntimeOrigin::root(), new_limit));
+        System::assert_last_event(Event::<Test>::BlockGasLimitScheduled { new_limit }.into());
+        assert_eq!(EnergyFee::block_gas_limit(), 75_000_000);
+
+        EnergyFee::on_initialize(2);
+        System::assert_last_event(Event::<Test>::BlockGasLimitUpdated { new_limit }.into());
+        assert_eq!(EnergyFee::block_gas_limit(), new_limit);
+        assert_eq!(EnergyFee::pending_block_gas_limit(), None);
+    });
+}
+
+#[test]
+fn multiplier_history_works() {
+    new_test_ext(INITIAL_ENERGY_BALANCE).execute_with(|| {
+        let multipliers = [Multiplier::from(1), Multiplier::from(2), Multiplier::from(3)];
+        for (block, multiplier) in (1..).zip(multipliers) {
+            System::set_block_number(block);
+            pallet_transaction_payment::NextFeeMultiplier::<Test>::put(multiplier);
+            EnergyFee::on_initialize(block);
+        }
+
+        assert_eq!(EnergyFee::multiplier_at(1), Some(Multiplier::from(1)));
+        assert_eq!(EnergyFee::multiplier_at(2), Some(Multiplier::from(2)));
+        assert_eq!(EnergyFee::multiplier_at(3), Some(Multiplier::from(3)));
+        assert_eq!(EnergyFee::multiplier_at(4), None);
+
+        // The history depth is 3 blocks, so block 1 is pruned once block 4 starts.
+        System::set_block_number(4);
+        EnergyFee::on_initialize(4);
+        assert_eq!(EnergyFee::multiplier_at(1), None);
+        assert_eq!(EnergyFee::multiplier_at(4), Some(Multiplier::from(3)));
+    });
+}
+
+#[test]
+fn swap_funded_fee_keeps_account_alive() {
+    new_test_ext(0).execute_with(|| {
+        System::set_block_number(1);
+        let existential_deposit = <BalancesVTRS as Currency<AccountId>>::minimum_balance();
+        let vtrs_fee = VNRG_TO_VTRS_RATE
+            .checked_mul_int(GetConstantEnergyFee::get())
+            .expect("Expected to calculate missing fee in VTRS");
+
+        // one unit short of keeping the existential deposit after the swap
+        BalancesVTRS::make_free_balance_be(&ALICE, vtrs_fee + existential_deposit - 1);
+        assert!(<EnergyFee as OnChargeEVMTransaction<Test>>::withdraw_fee(
+            &ALICE.into(),
+            1_234_567_890.into(),
+        )
+        .is_err());
+        assert_eq!(BalancesVTRS::balance(&ALICE), vtrs_fee + existential_deposit - 1);
+        assert_eq!(BalancesVNRG::balance(&ALICE), 0);
+
+        // exactly enough to pay the fee and keep the existential deposit
+        BalancesVTRS::make_free_balance_be(&ALICE, vtrs_fee + existential_deposit);
+        assert!(<EnergyFee as OnChargeEVMTransaction<Test>>::withdraw_fee(
+            &ALICE.into(),
+            1_234_567_890.into(),
+        )
+        .is_ok());
+        assert_eq!(BalancesVTRS::balance(&ALICE), existential_deposit);
+        assert!(System::account_exists(&ALICE));
+    });
+}
+
+#[test]
+fn set_fee_keep_alive_works() {
+    new_test_ext(0).execute_with(|| {
+        System::set_block_number(1);
+        assert!(!KeepAliveOptOut::<Test>::contains_key(ALICE));
+
+        assert_ok!(EnergyFee::set_fee_keep_alive(RuntimeOrigin::signed(ALICE), false));
+        assert!(KeepAliveOptOut::<Test>::contains_key(ALICE));
+        System::assert_last_event(
+            Event::<Test>::FeeKeepAliveUpdated { who: ALICE, keep_alive: false }.into(),
+        );
+
+        assert_ok!(EnergyFee::set_fee_keep_alive(RuntimeOrigin::signed(ALICE), true));
+        assert!(!KeepAliveOptOut::<Test>::contains_key(ALICE));
+        System::assert_last_event(
+            Event::<Test>::FeeKeepAliveUpdated { who: ALICE, keep_alive: true }.into(),
+        );
+
+        assert_noop!(
+            EnergyFee::set_fee_keep_alive(RawOrigin::None.into(), false),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn fees_use_fallback_rate_when_energy_rate_is_missing() {
+    new_test_ext(0).execute_with(|| {
+        System::set_block_number(1);
+        pallet_asset_rate::ConversionRateToNative::<Test>::remove(VNRG);
+
+        EnergyFee::on_initialize(1);
+        System::assert_has_event(Event::<Test>::EnergyRateMissing.into());
+
+        let initial_vtrs_balance = BalancesVTRS::balance(&ALICE);
+        assert!(<EnergyFee as OnChargeEVMTransaction<Test>>::withdraw_fee(
+            &ALICE.into(),
+            1_234_567_890.into(),
+        )
+        .is_ok());
+
+        let vtrs_fee = FallbackEnergyRate::get()
+            .expect("Expected the mock to configure a fallback rate")
+            .checked_mul_int(GetConstantEnergyFee::get())
+            .expect("Expected to calculate missing fee in VTRS");
+        assert_eq!(BalancesVTRS::balance(&ALICE), initial_vtrs_balance - vtrs_fee);
+    });
+}
+
+#[test]
+fn missing_energy_rate_is_reported_once_per_change() {
+    new_test_ext(0).execute_with(|| {
+        let rate = pallet_asset_rate::ConversionRateToNative::<Test>::get(VNRG).unwrap();
+        let rate_events = || {
+            System::events()
+                .into_iter()
+                .filter(|record| {
+                    matches!(
+                        record.event,
+                        RuntimeEvent::EnergyFee(
+                            Event::EnergyRateMissing | Event::EnergyRateRestored
+                        )
+                    )
+                })
+                .count()
+        };
+
+        System::set_block_number(1);
+        pallet_asset_rate::ConversionRateToNative::<Test>::remove(VNRG);
+        EnergyFee::on_initialize(1);
+        System::assert_has_event(Event::<Test>::EnergyRateMissing.into());
+
+        System::reset_events();
+        System::set_block_number(2);
+        EnergyFee::on_initialize(2);
+        assert_eq!(rate_events(), 0);
+
+        pallet_asset_rate::ConversionRateToNative::<Test>::insert(VNRG, rate);
+        System::set_block_number(3);
+        EnergyFee::on_initialize(3);
+        System::assert_has_event(Event::<Test>::EnergyRateRestored.into());
+
+        System::reset_events();
+        System::set_block_number(4);
+        EnergyFee::on_initialize(4);
+        assert_eq!(rate_events(), 0);
+    });
+}
+
+#[test]
+fn approve_and_transfer_energy_from_works() {
+    new_test_ext(INITIAL_ENERGY_BALANCE).execute_with(|| {
+        System::set_block_number(1);
+        let allowance = 1_000;
+
+        assert_ok!(EnergyFee::approve_energy(RuntimeOrigin::signed(ALICE), BOB, allowance));
+        assert_eq!(EnergyFee::energy_allowance(ALICE, BOB), allowance);
+        System::assert_last_event(
+            Event::<Test>::EnergyApproval { owner: ALICE, spender: BOB, amount: allowance }.into(),
+        );
+
+        assert_ok!(EnergyFee::transfer_energy_from(
+            RuntimeOrigin::signed(BOB),
+            ALICE,
+            MAIN_DEST,
+            400
+        ));
+        assert_eq!(EnergyFee::energy_allowance(ALICE, BOB), 600);
+        assert_eq!(BalancesVNRG::balance(&ALICE), INITIAL_ENERGY_BALANCE - 400);
+        assert_eq!(BalancesVNRG::balance(&MAIN_DEST), 400);
+        System::assert_last_event(
+            Event::<Test>::EnergyTransferredFrom {
+                spender: BOB,
+                from: ALICE,
+                to: MAIN_DEST,
+                amount: 400,
+            }
+            .into(),
+        );
+
+        // over-spending the allowance is rejected
+        assert_noop!(
+            EnergyFee::transfer_energy_from(RuntimeOrigin::signed(BOB), ALICE, BOB, 601),
+            Error::<Test>::InsufficientEnergyAllowance
+        );
+        // spending without an allowance is rejected
+        assert_noop!(
+            EnergyFee::transfer_energy_from(RuntimeOrigin::signed(ALICE), BOB, ALICE, 1),
+            Error::<Test>::InsufficientEnergyAllowance
+        );
+
+        // spending the whole allowance clears it
+        assert_ok!(EnergyFee::transfer_energy_from(RuntimeOrigin::signed(BOB), ALICE, BOB, 600));
+        assert!(!EnergyAllowances::<Test>::contains_key(ALICE, BOB));
+
+        // approving zero resets the allowance
+        assert_ok!(EnergyFee::approve_energy(RuntimeOrigin::signed(ALICE), BOB, allowance));
+        assert_ok!(EnergyFee::approve_energy(RuntimeOrigin::signed(ALICE), BOB, 0));
+        assert!(!EnergyAllowances::<Test>::contains_key(ALICE, BOB));
+    });
+}
+
+#[test]
+fn energy_allowances_are_capped_per_owner() {
+    new_test_ext(INITIAL_ENERGY_BALANCE).execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(EnergyFee::approve_energy(RuntimeOrigin::signed(ALICE), BOB, 100));
+        assert_ok!(EnergyFee::approve_energy(RuntimeOrigin::signed(ALICE), MAIN_DEST, 100));
+        assert_eq!(EnergyAllowanceCount::<Test>::get(ALICE), 2);
+
+        assert_noop!(
+            EnergyFee::approve_energy(RuntimeOrigin::signed(ALICE), FEE_DEST, 100),
+            Error::<Test>::TooManyEnergyAllowances
+        );
+        // updating an existing allowance doesn't take another slot
+        assert_ok!(EnergyFee::approve_energy(RuntimeOrigin::signed(ALICE), BOB, 200));
+        assert_eq!(EnergyAllowanceCount::<Test>::get(ALICE), 2);
+
+        // revoked and spent allowances free their slot
+        assert_ok!(EnergyFee::approve_energy(RuntimeOrigin::signed(ALICE), BOB, 0));
+        assert_ok!(EnergyFee::transfer_energy_from(
+            RuntimeOrigin::signed(MAIN_DEST),
+            ALICE,
+            MAIN_DEST,
+            100
+        ));
+        assert!(!EnergyAllowanceCount::<Test>::contains_key(ALICE));
+
+        assert_ok!(EnergyFee::approve_energy(RuntimeOrigin::signed(ALICE), FEE_DEST, 100));
+        assert_eq!(EnergyAllowanceCount::<Test>::get(ALICE), 1);
+    });
+}
+
+#[test]
+fn fee_paused_pallet_calls_are_not_charged() {
+    new_test_ext(INITIAL_ENERGY_BALANCE).execute_with(|| {
+        System::set_block_number(1);
+        let system_index = <System as PalletInfoAccess>::index() as u8;
+        let remark_call: RuntimeCall =
+            RuntimeCall::System(frame_system::Call::remark { remark: [1u8; 32].to_vec() });
+        let dispatch_info: DispatchInfo =
+            DispatchInfo { weight: SystemWeight::<Test>::remark(32), ..Default::default() };
+        let fee = TransactionPayment::compute_fee(100, &dispatch_info, 0);
+
+        assert_noop!(
+            EnergyFee::set_fee_paused(RuntimeOrigin::signed(ALICE), system_index, true),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(EnergyFee::set_fee_paused(RuntimeOrigin::root(), system_index, true));
+        System::assert_last_event(
+            Event::<Test>::FeePauseUpdated { pallet_index: system_index, paused: true }.into(),
+        );
+
+        let withdrawn = <EnergyFee as OnChargeTransaction<Test>>::withdraw_fee(
+            &ALICE,
+            &remark_call,
+            &dispatch_info,
+            fee,
+            0,
+        )
+        .expect("Expected to skip the fee");
+        assert!(withdrawn.is_none());
+        assert_eq!(BalancesVNRG::balance(&ALICE), INITIAL_ENERGY_BALANCE);
+
+        // calls of other pallets are still charged
+        let other_call: RuntimeCall =
+            RuntimeCall::EnergyFee(crate::Call::set_fee_keep_alive { keep_alive: true });
+        assert!(<EnergyFee as OnChargeTransaction<Test>>::withdraw_fee(
+            &ALICE,
+            &other_call,
+            &dispatch_info,
+            fee,
+            0,
+        )
+        .expect("Expected to withdraw fee")
+        .is_some());
+
+        assert_ok!(EnergyFee::set_fee_paused(RuntimeOrigin::root(), system_index, false));
+        assert!(!EnergyFee::is_fee_paused(&remark_call));
+    });
+}
+
+#[test]
+fn nac_level_discounts_fees() {
+    new_test_ext(INITIAL_ENERGY_BALANCE).execute_with(|| {
+        System::set_block_number(1);
+        let call: RuntimeCall =
+            RuntimeCall::BalancesVTRS(pallet_balances::Call::transfer_allow_death {
+                dest: BOB,
+                value: 1,
+            });
+        let dispatch_info = call.get_dispatch_info();
+        let fee = EnergyFee::dispatch_info_to_fee(&call, None, None).into_inner();
+
+        let native_fee_paid = |level: Option<u8>| {
+            NacLevels::set(level.map(|level| vec![(ALICE, level)]).unwrap_or_default());
+            let balance_before = BalancesVNRG::balance(&ALICE);
+            <EnergyFee as OnChargeTransaction<Test>>::withdraw_fee(
+                &ALICE,
+                &call,
+                &dispatch_info,
+                fee,
+                0,
+            )
+            .expect("Expected to withdraw fee");
+            balance_before - BalancesVNRG::balance(&ALICE)
+        };
+        let evm_fee_paid = |level: Option<u8>| {
+            NacLevels::set(level.map(|level| vec![(ALICE, level)]).unwrap_or_default());
+            let balance_before = BalancesVNRG::balance(&ALICE);
+            <EnergyFee as OnChargeEVMTransaction<Test>>::withdraw_fee(&ALICE.into(), 1.into())
+                .expect("Expected to withdraw fee");
+            balance_before - BalancesVNRG::balance(&ALICE)
+        };
+
+        // unverified accounts and level 0 pay the full fee
+        assert_eq!(native_fee_paid(None), fee);
+        assert_eq!(native_fee_paid(Some(0)), fee);
+        assert_eq!(native_fee_paid(Some(1)), fee);
+        assert_eq!(native_fee_paid(Some(2)), Perbill::from_percent(90).mul_floor(fee));
+        assert_eq!(native_fee_paid(Some(3)), Perbill::from_percent(80).mul_floor(fee));
+        System::assert_last_event(
+            Event::<Test>::EnergyFeePaid {
+                who: ALICE,
+                amount: Perbill::from_percent(80).mul_floor(fee),
+            }
+            .into(),
+        );
+
+        assert_eq!(evm_fee_paid(None), fee);
+        assert_eq!(evm_fee_paid(Some(0)), fee);
+        assert_eq!(evm_fee_paid(Some(3)), Perbill::from_percent(80).mul_floor(fee));
+        assert_eq!(EnergyFee::discounted_fee(&BOB, fee), fee);
+    });
+}
+
+#[test]
+fn evm_fee_receipts_are_kept_within_retention_window() {
+    new_test_ext(INITIAL_ENERGY_BALANCE).execute_with(|| {
+        System::set_block_number(1);
+        let charge_evm_fee = || {
+            let _ = <EnergyFee as OnChargeEVMTransaction<Test>>::withdraw_fee(
+                &ALICE.into(),
+                1_234_567_890.into(),
+            )
+            .expect("Expected to withdraw fee");
+        };
+        let [first, second, third] = [1, 2, 3].map(H256::repeat_byte);
+
+        charge_evm_fee();
+        EnergyFee::note_evm_fee_receipt(first);
+        assert_eq!(EnergyFee::evm_fee_paid(first), Some(GetConstantEnergyFee::get()));
+
+        // a transaction which didn't withdraw a fee gets no receipt
+        EnergyFee::note_evm_fee_receipt(second);
+        assert_eq!(EnergyFee::evm_fee_paid(second), None);
+
+        // at most two receipts are kept per block
+        charge_evm_fee();
+        EnergyFee::note_evm_fee_receipt(second);
+        charge_evm_fee();
+        EnergyFee::note_evm_fee_receipt(third);
+        assert_eq!(EnergyFee::evm_fee_paid(second), Some(GetConstantEnergyFee::get()));
+        assert_eq!(EnergyFee::evm_fee_paid(third), None);
+
+        // The retention is 3 blocks, so receipts of block 1 are pruned once block 4 starts.
+        System::set_block_number(3);
+        EnergyFee::on_initialize(3);
+        assert_eq!(EnergyFee::evm_fee_paid(first), Some(GetConstantEnergyFee::get()));
+        System::set_block_number(4);
+        EnergyFee::on_initialize(4);
+        assert_eq!(EnergyFee::evm_fee_paid(first), None);
+        assert!(!EvmFeeReceipts::<Test>::contains_key(first));
+        assert!(EvmFeeReceiptsByBlock::<Test>::get(1).is_empty());
+    });
+}
+
+#[test]
+fn evm_fee_receipts_of_reverted_blocks_are_discarded() {
+    new_test_ext(INITIAL_ENERGY_BALANCE).execute_with(|| {
+        let fee = GetConstantEnergyFee::get();
+        let apply_evm_transaction = |tx_hash: H256| {
+            let _ = <EnergyFee as OnChargeEVMTransaction<Test>>::withdraw_fee(
+                &ALICE.into(),
+                1_234_567_890.into(),
+            )
+            .expect("Expected to withdraw fee");
+            EnergyFee::note_evm_fee_receipt(tx_hash);
+        };
+        let [first, reverted, second] = [1, 2, 3].map(H256::repeat_byte);
+
+        System::set_block_number(1);
+        apply_evm_transaction(first);
+
+        // block 2 of a fork which is reverted by a reorg
+        frame_support::storage::with_transaction_unchecked(|| {
+            System::set_block_number(2);
+            EnergyFee::on_initialize(2);
+            apply_evm_transaction(reverted);
+            assert_eq!(EnergyFee::evm_fee_receipt(reverted), Some((2, fee)));
+            TransactionOutcome::Rollback(())
+        });
+
+        // block 2 of the canonical chain
+        System::set_block_number(2);
+        EnergyFee::on_initialize(2);
+        apply_evm_transaction(second);
+        assert_eq!(EnergyFee::evm_fee_receipt(reverted), None);
+        assert_eq!(EnergyFee::evm_fee_receipt(first), Some((1, fee)));
+        assert_eq!(EnergyFee::evm_fee_receipt(second), Some((2, fee)));
+        assert_eq!(EvmFeeReceiptsByBlock::<Test>::get(2).into_inner(), vec![second]);
+
+        // pruning only removes the receipts of the canonical blocks
+        for block in 3..=5 {
+            System::set_block_number(block);
+            EnergyFee::on_initialize(block);
+        }
+        assert_eq!(EnergyFee::evm_fee_receipt(second), None);
+        assert!(EvmFeeReceipts::<Test>::iter().next().is_none());
+        assert!(EvmFeeReceiptsByBlock::<Test>::iter().next().is_none());
+    });
+}
+
+#[test]
+fn fee_credit_is_spent_before_fee_token_across_transactions() {
+    new_test_ext(0).execute_with(|| {
+        System::set_block_number(1);
+        let fee = GetConstantEnergyFee::get();
+        let credit = fee * 3 / 2;
+        let price = VNRG_TO_VTRS_RATE.checked_mul_int(credit).expect("Expected to price credit");
+        let charge_evm_fee = || {
+            let _ = <EnergyFee as OnChargeEVMTransaction<Test>>::withdraw_fee(
+                &ALICE.into(),
+                1_234_567_890.into(),
+            )
+            .expect("Expected to withdraw fee");
+        };
+
+        assert_noop!(
+            EnergyFee::buy_fee_credit(RuntimeOrigin::signed(ALICE), credit, price - 1),
+            Error::<Test>::FeeCreditPriceTooHigh
+        );
+        assert_ok!(EnergyFee::buy_fee_credit(RuntimeOrigin::signed(ALICE), credit, price));
+        System::assert_last_event(
+            Event::<Test>::FeeCreditBought { who: ALICE, amount: credit, price }.into(),
+        );
+        assert_eq!(EnergyFee::fee_credit(ALICE), credit);
+        // the credit isn't fee token the account could transfer
+        assert_eq!(BalancesVNRG::balance(&ALICE), 0);
+        let vtrs_balance = BalancesVTRS::balance(&ALICE);
+
+        // the first fee is paid with the credit only
+        charge_evm_fee();
+        assert_eq!(EnergyFee::fee_credit(ALICE), credit - fee);
+        assert_eq!(BalancesVTRS::balance(&ALICE), vtrs_balance);
+        System::assert_has_event(Event::<Test>::FeeCreditSpent { who: ALICE, amount: fee }.into());
+        System::assert_has_event(Event::<Test>::EnergyFeePaid { who: ALICE, amount: fee }.into());
+        assert_eq!(BurnedEnergy::<Test>::get(), fee);
+
+        // the rest of the credit covers half of the second fee, the other half is exchanged
+        charge_evm_fee();
+        assert!(!FeeCredit::<Test>::contains_key(ALICE));
+        let missing_fee = fee - (credit - fee);
+        assert_eq!(
+            BalancesVTRS::balance(&ALICE),
+            vtrs_balance - VNRG_TO_VTRS_RATE.checked_mul_int(missing_fee).unwrap()
+        );
+        assert_eq!(BalancesVNRG::balance(&ALICE), 0);
+        assert_eq!(BurnedEnergy::<Test>::get(), 2 * fee);
+    });
+}
+
+#[test]
+fn fee_is_paid_from_first_sufficient_fee_source() {
+    const ASSET: AssetId = 2;
+
+    let fee = GetConstantEnergyFee::get();
+    new_test_ext(fee / 2).execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Assets::force_create(RuntimeOrigin::root(), ASSET.into(), BOB, true, 1));
+        assert_ok!(Assets::mint(RuntimeOrigin::signed(BOB), ASSET.into(), ALICE, fee));
+        // the asset is worth twice as much as the fee token
+        assert_ok!(AssetRate::create(
+            RuntimeOrigin::root(),
+            Box::new(ASSET),
+            VNRG_TO_VTRS_RATE * FixedU128::from_u32(2)
+        ));
+
+        let call = RuntimeCall::Assets(pallet_assets::Call::transfer {
+            id: VNRG.into(),
+            target: BOB,
+            amount: 1,
+        });
+        let info = DispatchInfo { weight: AssetsWeight::<Test>::transfer(), ..Default::default() };
+        let pay_fee = |preferences: Vec<FeeSource<AssetId>>| {
+            CheckEnergyFee::<Test>::with_preferences(preferences.try_into().unwrap())
+                .pre_dispatch(&ALICE, &call, &info, 100)?;
+            <EnergyFee as OnChargeTransaction<Test>>::withdraw_fee(&ALICE, &call, &info, fee, 0)
+                .map(|_| ())
+        };
+        let vtrs_balance = BalancesVTRS::balance(&ALICE);
+
+        // the fee token isn't sufficient, so what it's missing is paid with the asset
+        assert_ok!(pay_fee(vec![FeeSource::FeeToken, FeeSource::Asset(ASSET)]));
+        System::assert_has_event(
+            Event::<Test>::FeeAssetExchanged {
+                who: ALICE,
+                asset: ASSET,
+                amount: fee / 2,
+                price: fee / 4,
+            }
+            .into(),
+        );
+        assert_eq!(Assets::balance(ASSET, ALICE), fee - fee / 4);
+        assert_eq!(Assets::balance(ASSET, FEE_ASSET_DEST), fee / 4);
+        assert_eq!(BalancesVNRG::balance(&ALICE), 0);
+        assert_eq!(BalancesVTRS::balance(&ALICE), vtrs_balance);
+
+        // the remaining asset doesn't cover the next fee, the main token is only exchanged if
+        // the transaction allows it
+        assert_ok!(Assets::burn(RuntimeOrigin::signed(BOB), ASSET.into(), ALICE, fee / 4 + 1));
+        assert_eq!(
+            pay_fee(vec![FeeSource::Asset(ASSET), FeeSource::FeeToken]),
+            Err(TransactionValidityError::Invalid(InvalidTransaction::Payment))
+        );
+        assert_eq!(BalancesVTRS::balance(&ALICE), vtrs_balance);
+
+        assert_ok!(pay_fee(vec![
+            FeeSource::MainToken,
+            FeeSource::Asset(ASSET),
+            FeeSource::FeeToken
+        ]));
+        assert_eq!(Assets::balance(ASSET, ALICE), fee / 2 - 1);
+        assert_eq!(BalancesVNRG::balance(&ALICE), 0);
+        assert_eq!(
+            BalancesVTRS::balance(&ALICE),
+            vtrs_balance - VNRG_TO_VTRS_RATE.checked_mul_int(fee).unwrap()
+        );
+    });
+}
+
+#[test]
+fn fee_sources_cover_the_tip_and_the_dispute_surcharge() {
+    const ASSET: AssetId = 2;
+
+    let fee = GetConstantEnergyFee::get();
+    new_test_ext(fee).execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Assets::force_create(RuntimeOrigin::root(), ASSET.into(), BOB, true, 1));
+        assert_ok!(Assets::mint(RuntimeOrigin::signed(BOB), ASSET.into(), ALICE, fee));
+        // the asset is worth twice as much as the fee token
+        assert_ok!(AssetRate::create(
+            RuntimeOrigin::root(),
+            Box::new(ASSET),
+            VNRG_TO_VTRS_RATE * FixedU128::from_u32(2)
+        ));
+        let pay_fee = |call: &RuntimeCall, fee: Balance, tip: Balance| {
+            let info = call.get_dispatch_info();
+            CheckEnergyFee::<Test>::with_preferences(
+                vec![FeeSource::FeeToken, FeeSource::Asset(ASSET)].try_into().unwrap(),
+            )
+            .pre_dispatch(&ALICE, call, &info, 100)?;
+            <EnergyFee as OnChargeTransaction<Test>>::withdraw_fee(&ALICE, call, &info, fee, tip)
+                .map(|_| ())
+        };
+        let vtrs_balance = BalancesVTRS::balance(&ALICE);
+
+        // the fee token covers the fee, but not the tip on top of it
+        let remark = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+        assert_ok!(pay_fee(&remark, fee + fee / 2, fee / 2));
+        System::assert_has_event(
+            Event::<Test>::FeeAssetExchanged {
+                who: ALICE,
+                asset: ASSET,
+                amount: fee / 2,
+                price: fee / 4,
+            }
+            .into(),
+        );
+        assert_eq!(BalancesVNRG::balance(&ALICE), 0);
+        assert_eq!(BalancesVTRS::balance(&ALICE), vtrs_balance);
+
+        // the fee token covers the fee, but not the dispute surcharge on top of it
+        assert_ok!(EnergyFee::update_dispute_surcharge(
+            RuntimeOrigin::root(),
+            Perbill::from_percent(50)
+        ));
+        EnergyFee::note_open_dispute(1, H256::repeat_byte(1));
+        assert_ok!(BalancesVNRG::mint_into(&ALICE, fee));
+        let transfer = RuntimeCall::Assets(pallet_assets::Call::transfer {
+            id: VNRG.into(),
+            target: BOB,
+            amount: 1,
+        });
+        assert_ok!(pay_fee(&transfer, fee, 0));
+        System::assert_has_event(
+            Event::<Test>::FeeAssetExchanged {
+                who: ALICE,
+                asset: ASSET,
+                amount: fee / 2,
+                price: fee / 4,
+            }
+            .into(),
+        );
+        assert_eq!(EnergyFee::escrowed_surcharge(ALICE), fee / 2);
+        assert_eq!(Assets::balance(ASSET, ALICE), fee / 2);
+        assert_eq!(BalancesVNRG::balance(&ALICE), 0);
+        assert_eq!(BalancesVTRS::balance(&ALICE), vtrs_balance);
+    });
+}
+
+#[test]
+fn fee_rebates_are_shared_in_proportion_to_fees_paid() {
+    let fee = GetConstantEnergyFee::get();
+    new_test_ext(INITIAL_ENERGY_BALANCE).execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(EnergyFee::update_rebate_fraction(
+            RuntimeOrigin::root(),
+            Perbill::from_percent(50)
+        ));
+
+        let call = RuntimeCall::Assets(pallet_assets::Call::transfer {
+            id: VNRG.into(),
+            target: FEE_DEST,
+            amount: 1,
+        });
+        let info = DispatchInfo { weight: AssetsWeight::<Test>::transfer(), ..Default::default() };
+        let pay_fee = |who: AccountId| {
+            let credit =
+                <EnergyFee as OnChargeTransaction<Test>>::withdraw_fee(&who, &call, &info, fee, 0)
+                    .expect("Expected to withdraw fee");
+            assert_ok!(<EnergyFee as OnChargeTransaction<Test>>::correct_and_deposit_fee(
+                &who,
+                &info,
+                &From::from(()),
+                0,
+                0,
+                credit
+            ));
+        };
+
+        // BOB pays three times as much fees as ALICE during era 0
+        pay_fee(ALICE);
+        for _ in 0..3 {
+            pay_fee(BOB);
+        }
+        let pool = EnergyFee::rebate_pool(0).unwrap();
+        assert_eq!(pool.amount, 2 * fee);
+        assert_eq!(pool.contributions, 4 * fee);
+        assert_eq!(BalancesVNRG::balance(&REBATE_POT), 2 * fee);
+        assert_eq!(FeeContributions::<Test>::get(0, BOB), 3 * fee);
+
+        assert_noop!(
+            EnergyFee::claim_rebate(RuntimeOrigin::signed(ALICE), 0),
+            Error::<Test>::RebateEraNotEnded
+        );
+
+        RebateEra::set(1);
+        EnergyFee::on_initialize(2);
+        assert_eq!(EnergyFee::pending_rebate(&ALICE, 0), fee / 2);
+        assert_eq!(EnergyFee::pending_rebate(&BOB, 0), 3 * fee / 2);
+
+        let alice_balance = BalancesVNRG::balance(&ALICE);
+        assert_ok!(EnergyFee::claim_rebate(RuntimeOrigin::signed(ALICE), 0));
+        System::assert_last_event(
+            Event::<Test>::RebateClaimed { who: ALICE, era: 0, amount: fee / 2 }.into(),
+        );
+        assert_eq!(BalancesVNRG::balance(&ALICE), alice_balance + fee / 2);
+        assert_noop!(
+            EnergyFee::claim_rebate(RuntimeOrigin::signed(ALICE), 0),
+            Error::<Test>::NoRebate
+        );
+
+        // BOB's rebate expires to the treasury once it can't be claimed anymore
+        RebateEra::set(2);
+        EnergyFee::on_initialize(3);
+        assert_eq!(EnergyFee::pending_rebate(&BOB, 0), 3 * fee / 2);
+        FeeContributions::<Test>::insert(0, MAIN_DEST, fee);
+        RebateEra::set(3);
+        EnergyFee::on_initialize(4);
+        System::assert_has_event(
+            Event::<Test>::RebatesExpired { era: 0, amount: 3 * fee / 2 }.into(),
+        );
+        assert!(!RebatePools::<Test>::contains_key(0));
+        // the contributions of the expired era are removed one per block
+        assert_eq!(FeeContributions::<Test>::iter_prefix(0).count(), 1);
+        EnergyFee::on_initialize(5);
+        assert_eq!(FeeContributions::<Test>::iter_prefix(0).count(), 0);
+        assert!(!ExpiredFeeContributions::<Test>::contains_key(0));
+        assert_eq!(BalancesVNRG::balance(&TREASURY), 3 * fee / 2);
+        assert_noop!(
+            EnergyFee::claim_rebate(RuntimeOrigin::signed(BOB), 0),
+            Error::<Test>::NoRebate
+        );
+    });
+}
+
+#[test]
+fn minimal_call_pays_at_least_the_minimum_fee() {
+    new_test_ext(INITIAL_ENERGY_BALANCE).execute_with(|| {
+        System::set_block_number(1);
+        let floor = 1_000;
+        MinimumFee::set(floor);
+        NacLevels::set(vec![(ALICE, 3)]);
+
+        let pay_fee = |call: RuntimeCall, fee: Balance| {
+            let info = call.get_dispatch_info();
+            let balance = BalancesVNRG::balance(&ALICE);
+            <EnergyFee as OnChargeTransaction<Test>>::withdraw_fee(&ALICE, &call, &info, fee, 0)
+                .expect("Expected to withdraw fee");
+            balance - BalancesVNRG::balance(&ALICE)
+        };
+
+        // a near-zero weight call pays the floor, even after the NAC discount
+        let remark = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+        assert_eq!(pay_fee(remark.clone(), 1), floor);
+        assert_eq!(pay_fee(remark.clone(), floor + 100), floor);
+        assert_eq!(EnergyFee::charged_fee(&ALICE, &remark, 10 * floor), 8 * floor);
+
+        // exempt calls bypass the floor
+        let sudo = RuntimeCall::Sudo(pallet_sudo::Call::sudo { call: Box::new(remark) });
+        assert_eq!(pay_fee(sudo, 1), 1);
+    });
+}
+
+#[test]
+fn dispute_surcharge_is_escrowed_while_disputes_are_active() {
+    new_test_ext(INITIAL_ENERGY_BALANCE).execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(EnergyFee::update_dispute_surcharge(
+            RuntimeOrigin::root(),
+            Perbill::from_percent(50)
+        ));
+
+        let transfer = RuntimeCall::Assets(pallet_assets::Call::transfer {
+            id: VNRG.into(),
+            target: BOB,
+            amount: 1_000,
+        });
+        let remark = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+        let pay_fee = |who: AccountId, call: &RuntimeCall| {
+            let info = call.get_dispatch_info();
+            let balance = BalancesVNRG::balance(&who);
+            <EnergyFee as OnChargeTransaction<Test>>::withdraw_fee(&who, call, &info, 1_000, 0)
+                .expect("Expected to withdraw fee");
+            balance - BalancesVNRG::balance(&who)
+        };
+
+        // without active disputes no surcharge is charged
+        let fee = pay_fee(ALICE, &transfer);
+        let remark_fee = pay_fee(ALICE, &remark);
+        assert!(EscrowedSurcharges::<Test>::iter().next().is_none());
+
+        // while disputes are active, only cross-chain calls pay the surcharge
+        EnergyFee::note_open_dispute(1, H256::repeat_byte(1));
+        EnergyFee::note_open_dispute(1, H256::repeat_byte(1));
+        EnergyFee::note_open_dispute(1, H256::repeat_byte(2));
+        assert_eq!(EnergyFee::open_dispute_count(), 2);
+        assert_eq!(pay_fee(ALICE, &transfer), fee + fee / 2);
+        assert_eq!(pay_fee(ALICE, &remark), remark_fee);
+        assert_eq!(pay_fee(BOB, &transfer), fee + fee / 2);
+        System::assert_last_event(
+            Event::<Test>::DisputeSurchargeCharged { who: BOB, amount: fee / 2 }.into(),
+        );
+        assert_eq!(EnergyFee::escrowed_surcharge(ALICE), fee / 2);
+        assert_eq!(BalancesVNRG::balance(&SURCHARGE_POT), 2 * (fee / 2));
+
+        // nothing is refunded until the disputes end
+        EnergyFee::on_initialize(2);
+        assert_eq!(EnergyFee::escrowed_surcharge(ALICE), fee / 2);
+
+        // disputes are active until all of them concluded
+        EnergyFee::prune_open_disputes(|_, candidate| *candidate == H256::repeat_byte(2));
+        assert_eq!(EnergyFee::open_dispute_count(), 1);
+        EnergyFee::on_initialize(3);
+        assert_eq!(EnergyFee::escrowed_surcharge(ALICE), fee / 2);
+
+        // refunds are bounded per block
+        EnergyFee::prune_open_disputes(|_, _| false);
+        assert_eq!(EnergyFee::open_dispute_count(), 0);
+        let alice_balance = BalancesVNRG::balance(&ALICE);
+        let bob_balance = BalancesVNRG::balance(&BOB);
+        EnergyFee::on_initialize(4);
+        assert_eq!(EscrowedSurcharges::<Test>::iter().count(), 1);
+        EnergyFee::on_initialize(5);
+        assert!(EscrowedSurcharges::<Test>::iter().next().is_none());
+        assert_eq!(BalancesVNRG::balance(&ALICE), alice_balance + fee / 2);
+        assert_eq!(BalancesVNRG::balance(&BOB), bob_balance + fee / 2);
+        assert_eq!(BalancesVNRG::balance(&SURCHARGE_POT), 0);
+    });
+}
+
+#[test]
+fn disabled_auto_swap_rejects_fees_without_enough_fee_token() {
+    new_test_ext(0).execute_with(|| {
+        System::set_block_number(1);
+        let remark = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+        let info = remark.get_dispatch_info();
+        let pay_fee = || {
+            <EnergyFee as OnChargeTransaction<Test>>::withdraw_fee(&ALICE, &remark, &info, 1_000, 0)
+        };
+        let pay_evm_fee =
+            || <EnergyFee as OnChargeEVMTransaction<Test>>::withdraw_fee(&ALICE.into(), 1.into());
+
+        assert_noop!(
+            EnergyFee::set_auto_swap_enabled(RuntimeOrigin::signed(ALICE), false),
+            DispatchError::BadOrigin
+        );
+        assert!(EnergyFee::auto_swap_enabled());
+
+        // enabled, the missing fee token is exchanged
+        let vtrs_balance = BalancesVTRS::balance(&ALICE);
+        assert!(pay_fee().is_ok());
+        assert!(pay_evm_fee().is_ok());
+        assert!(BalancesVTRS::balance(&ALICE) < vtrs_balance);
+        assert_eq!(BalancesVNRG::balance(&ALICE), 0);
+
+        assert_ok!(EnergyFee::set_auto_swap_enabled(RuntimeOrigin::root(), false));
+        System::assert_last_event(Event::<Test>::AutoSwapUpdated { enabled: false }.into());
+
+        // disabled, substrate and Ethereum transactions are rejected without touching VTRS
+        let vtrs_balance = BalancesVTRS::balance(&ALICE);
+        assert_eq!(
+            pay_fee().err(),
+            Some(TransactionValidityError::Invalid(InvalidTransaction::Payment))
+        );
+        assert!(pay_evm_fee().is_err());
+        assert_eq!(
+            EnergyFee::calculate_fee_parts(&ALICE, 1_000),
+            Err(Error::<Test>::AutoSwapDisabled.into())
+        );
+        assert_eq!(BalancesVTRS::balance(&ALICE), vtrs_balance);
+
+        // enough fee token is still spent
+        assert_ok!(BalancesVNRG::mint_into(&ALICE, INITIAL_ENERGY_BALANCE));
+        assert!(pay_fee().is_ok());
+        assert!(pay_evm_fee().is_ok());
+        assert_eq!(BalancesVTRS::balance(&ALICE), vtrs_balance);
+    });
+}
+
+#[test]
+fn fee_swaps_are_limited_per_account_and_era() {
+    new_test_ext(0).execute_with(|| {
+        System::set_block_number(1);
+        // holds no fee token, unlike BOB
+        let charlie = AccountId::from([10u8; 20]);
+        BalancesVTRS::make_free_balance_be(&charlie, VTRS_INITIAL_BALANCE);
+        let remark = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+        let info = remark.get_dispatch_info();
+        let pay_fee = |who: AccountId| {
+            <EnergyFee as OnChargeTransaction<Test>>::withdraw_fee(&who, &remark, &info, 1_000, 0)
+        };
+
+        assert_noop!(
+            EnergyFee::update_fee_swap_limit(RuntimeOrigin::signed(ALICE), Some(2)),
+            DispatchError::BadOrigin
+        );
+        assert_eq!(EnergyFee::remaining_fee_swaps(&ALICE), None);
+        assert_ok!(EnergyFee::update_fee_swap_limit(RuntimeOrigin::root(), Some(2)));
+        System::assert_last_event(Event::<Test>::FeeSwapLimitUpdated { new_limit: Some(2) }.into());
+        // charlie may swap once more than the limit
+        FeeSwapLimitBonuses::set(vec![(charlie, 1)]);
+
+        // every fee needs a swap as no fee token is held
+        for _ in 0..2 {
+            assert!(pay_fee(ALICE).is_ok());
+            assert!(pay_fee(charlie).is_ok());
+        }
+        assert_eq!(EnergyFee::remaining_fee_swaps(&ALICE), Some(0));
+        assert_eq!(EnergyFee::remaining_fee_swaps(&charlie), Some(1));
+
+        // the quota is used up, the fee must be paid with fee token
+        let vtrs_balance = BalancesVTRS::balance(&ALICE);
+        assert_eq!(
+            pay_fee(ALICE).err(),
+            Some(TransactionValidityError::Invalid(InvalidTransaction::Payment))
+        );
+        assert_eq!(
+            EnergyFee::calculate_fee_parts(&ALICE, 1_000),
+            Err(Error::<Test>::FeeSwapLimitReached.into())
+        );
+        assert_ok!(CheckEnergyFee::<Test>::with_preferences(
+            vec![FeeSource::MainToken].try_into().unwrap()
+        )
+        .validate(&ALICE, &remark, &info, 100));
+        assert!(pay_fee(ALICE).is_err());
+        assert_eq!(BalancesVTRS::balance(&ALICE), vtrs_balance);
+        assert!(pay_fee(charlie).is_ok());
+        assert!(pay_fee(charlie).is_err());
+
+        assert_ok!(BalancesVNRG::mint_into(&ALICE, INITIAL_ENERGY_BALANCE));
+        assert!(pay_fee(ALICE).is_ok());
+        assert_eq!(BalancesVTRS::balance(&ALICE), vtrs_balance);
+
+        // the quota resets in the next era
+        RebateEra::set(1);
+        assert_eq!(EnergyFee::remaining_fee_swaps(&charlie), Some(3));
+        assert!(pay_fee(charlie).is_ok());
+        assert_eq!(EnergyFee::remaining_fee_swaps(&charlie), Some(2));
+
+        // lifting the limit lets every account swap again
+        assert_ok!(EnergyFee::update_fee_swap_limit(RuntimeOrigin::root(), None));
+        assert_eq!(EnergyFee::remaining_fee_swaps(&charlie), None);
+    });
+}
+
+#[test]
+fn energy_price_history_samples_snapshots() {
+    new_test_ext(INITIAL_ENERGY_BALANCE).execute_with(|| {
+        // no snapshot is taken while the price can't be quoted
+        EnergyFee::on_initialize(2);
+        assert!(PriceSnapshots::<Test>::get().is_empty());
+        assert!(EnergyFee::energy_price_history(10, 2).is_empty());
+
+        for block in 3..=10 {
+            EnergyPrice::set(Some(block as Balance * 100));
+            EnergyFee::on_initialize(block);
+        }
+
+        // snapshots are taken every 2 blocks, only the 3 latest ones are kept
+        assert_eq!(
+            PriceSnapshots::<Test>::get().into_inner(),
+            vec![(6, 600), (8, 800), (10, 1000)]
+        );
+
+        // fewer snapshots than requested
+        assert_eq!(EnergyFee::energy_price_history(10, 2), vec![(6, 600), (8, 800), (10, 1000)]);
+        assert_eq!(EnergyFee::energy_price_history(2, 2), vec![(8, 800), (10, 1000)]);
+        assert_eq!(EnergyFee::energy_price_history(10, 3), vec![(6, 600), (10, 1000)]);
+        assert_eq!(EnergyFee::energy_price_history(10, 4), vec![(6, 600), (10, 1000)]);
+        assert_eq!(EnergyFee::energy_price_history(10, 5), vec![(10, 1000)]);
+        assert!(EnergyFee::energy_price_history(0, 2).is_empty());
+
+        // the interval can't be shorter than the snapshot cadence
+        assert!(EnergyFee::energy_price_history(10, 1).is_empty());
+    });
+}
+
+#[test]
+fn minimal_evm_transaction_pays_at_least_the_min_evm_fee() {
+    new_test_ext(INITIAL_ENERGY_BALANCE).execute_with(|| {
+        System::set_block_number(1);
+        let constant_fee = GetConstantEnergyFee::get();
+        let pay_evm_fee = || {
+            let balance = BalancesVNRG::balance(&ALICE);
+            <EnergyFee as OnChargeEVMTransaction<Test>>::withdraw_fee(&ALICE.into(), 21_000.into())
+                .expect("Expected to withdraw fee");
+            balance - BalancesVNRG::balance(&ALICE)
+        };
+
+        assert_noop!(
+            EnergyFee::update_min_evm_fee(RuntimeOrigin::signed(ALICE), 2 * constant_fee),
+            DispatchError::BadOrigin
+        );
+        assert_eq!(EnergyFee::evm_fee_floor(), 0);
+        assert_eq!(pay_evm_fee(), constant_fee);
+
+        // the floor applies to the computed fee, also after the NAC discount
+        assert_ok!(EnergyFee::update_min_evm_fee(RuntimeOrigin::root(), 2 * constant_fee));
+        System::assert_last_event(
+            Event::<Test>::MinEvmFeeUpdated { new_fee: 2 * constant_fee }.into(),
+        );
+        NacLevels::set(vec![(ALICE, 3)]);
+        assert_eq!(EnergyFee::evm_fee_floor(), 2 * constant_fee);
+        assert_eq!(pay_evm_fee(), 2 * constant_fee);
+
+        // the native floor applies when it's higher
+        MinimumFee::set(3 * constant_fee);
+        assert_eq!(EnergyFee::evm_fee_floor(), 3 * constant_fee);
+        assert_eq!(pay_evm_fee(), 3 * constant_fee);
+    });
+}
+
+#[test]
+fn gas_estimation_buffer_is_added_to_dry_run_gas() {
+    new_test_ext(INITIAL_ENERGY_BALANCE).execute_with(|| {
+        System::set_block_number(1);
+        assert_eq!(EnergyFee::gas_estimation_buffer(), Perbill::from_percent(10));
+        assert_eq!(EnergyFee::buffered_gas(50_000), 55_000);
+        // rounded up, never below the dry run gas
+        assert_eq!(EnergyFee::buffered_gas(21_001), 23_102);
+        assert_eq!(EnergyFee::buffered_gas(u64::MAX), u64::MAX);
+
+        assert_noop!(
+            EnergyFee::update_gas_estimation_buffer(
+                RuntimeOrigin::signed(ALICE),
+                Perbill::from_percent(25)
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(EnergyFee::update_gas_estimation_buffer(
+            RuntimeOrigin::root(),
+            Perbill::from_percent(25)
+        ));
+        System::assert_last_event(
+            Event::<Test>::GasEstimationBufferUpdated { new_buffer: Perbill::from_percent(25) }
+                .into(),
+        );
+        assert_eq!(EnergyFee::buffered_gas(50_000), 62_500);
+
+        assert_ok!(EnergyFee::update_gas_estimation_buffer(RuntimeOrigin::root(), Perbill::zero()));
+        assert_eq!(EnergyFee::buffered_gas(50_000), 50_000);
+    });
+}
+
+#[test]
+fn new_accounts_receive_starter_energy_until_the_budget_is_spent() {
+    new_test_ext(INITIAL_ENERGY_BALANCE).execute_with(|| {
+        System::set_block_number(1);
+        BalancesVTRS::make_free_balance_be(&STARTER_POT, 1);
+        assert_ok!(BalancesVNRG::mint_into(&STARTER_POT, 1_000));
+        let create_account = |seed: u8| {
+            let who = AccountId::from([0x40 + seed; 20]);
+            BalancesVTRS::make_free_balance_be(&who, VTRS_INITIAL_BALANCE);
+            who
+        };
+
+        // disabled by default
+        let early = create_account(0);
+        assert!(!PendingStarterEnergy::<Test>::contains_key(early));
+
+        assert_noop!(
+            EnergyFee::set_starter_energy(RuntimeOrigin::signed(ALICE), 100, 250),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(EnergyFee::set_starter_energy(RuntimeOrigin::root(), 100, 250));
+        System::assert_last_event(
+            Event::<Test>::StarterEnergySet { amount: 100, budget: 250 }.into(),
+        );
+
+        // granted at the start of the next block, two accounts per block
+        let accounts = [create_account(1), create_account(2), create_account(3)];
+        assert!(accounts.iter().all(|who| PendingStarterEnergy::<Test>::contains_key(who)));
+        EnergyFee::on_initialize(2);
+        let granted: Vec<_> =
+            accounts.iter().filter(|who| BalancesVNRG::balance(who) == 100).collect();
+        assert_eq!(granted.len(), 2);
+        assert!(granted.iter().all(|who| StarterEnergyGranted::<Test>::contains_key(who)));
+        assert_eq!(EnergyFee::starter_energy_budget(), 50);
+        assert_eq!(BalancesVNRG::balance(&STARTER_POT), 800);
+
+        // the rest of the budget doesn't cover another grant
+        EnergyFee::on_initialize(3);
+        assert_eq!(PendingStarterEnergy::<Test>::iter().count(), 0);
+        assert_eq!(accounts.iter().filter(|who| BalancesVNRG::balance(who) == 0).count(), 1);
+        assert!(!PendingStarterEnergy::<Test>::contains_key(create_account(4)));
+
+        // accounts are granted once
+        assert_ok!(EnergyFee::set_starter_energy(RuntimeOrigin::root(), 100, 1_000));
+        EnergyFee::on_new_account(granted[0]);
+        assert!(!PendingStarterEnergy::<Test>::contains_key(granted[0]));
+
+        // a zero amount disables the grants
+        assert_ok!(EnergyFee::set_starter_energy(RuntimeOrigin::root(), 0, 1_000));
+        assert!(!PendingStarterEnergy::<Test>::contains_key(create_account(5)));
+    });
+}
+
+#[test]
+fn delegated_fee_discount_applies_to_the_delegate() {
+    new_test_ext(INITIAL_ENERGY_BALANCE).execute_with(|| {
+        System::set_block_number(1);
+        let charlie = AccountId::from([3u8; 20]);
+        BalancesVTRS::make_free_balance_be(&charlie, VTRS_INITIAL_BALANCE);
+        let deposit = FeeDiscountDelegationDeposit::get();
+        let fee = 10_000;
+        NacLevels::set(vec![(ALICE, 3), (charlie, 2)]);
+        assert_eq!(EnergyFee::discounted_fee(&BOB, fee), fee);
+
+        assert_noop!(
+            EnergyFee::delegate_fee_discount(RuntimeOrigin::signed(ALICE), ALICE),
+            Error::<Test>::SelfDelegation
+        );
+        assert_ok!(EnergyFee::delegate_fee_discount(RuntimeOrigin::signed(ALICE), BOB));
+        System::assert_last_event(
+            Event::<Test>::FeeDiscountDelegated { delegator: ALICE, delegate: BOB }.into(),
+        );
+        assert_eq!(BalancesVTRS::reserved_balance(ALICE), deposit);
+
+        // the discount applies once the delegate accepts it
+        assert_eq!(EnergyFee::discounted_fee(&BOB, fee), fee);
+        assert_noop!(
+            EnergyFee::accept_fee_discount_delegation(RuntimeOrigin::signed(charlie), ALICE),
+            Error::<Test>::NoFeeDiscountDelegation
+        );
+        assert_ok!(EnergyFee::accept_fee_discount_delegation(RuntimeOrigin::signed(BOB), ALICE));
+        System::assert_last_event(
+            Event::<Test>::FeeDiscountDelegationAccepted { delegator: ALICE, delegate: BOB }.into(),
+        );
+        assert_eq!(EnergyFee::discounted_fee(&BOB, fee), Perbill::from_percent(80) * fee);
+        // the delegator keeps its discount
+        assert_eq!(EnergyFee::discounted_fee(&ALICE, fee), Perbill::from_percent(80) * fee);
+
+        // a delegate receives a single discount, which is never lower than its own
+        assert_ok!(EnergyFee::delegate_fee_discount(RuntimeOrigin::signed(charlie), BOB));
+        assert_noop!(
+            EnergyFee::accept_fee_discount_delegation(RuntimeOrigin::signed(BOB), charlie),
+            Error::<Test>::DelegateTaken
+        );
+        // delegating again replaces the delegation, the deposit is reserved once
+        assert_ok!(EnergyFee::delegate_fee_discount(RuntimeOrigin::signed(ALICE), charlie));
+        assert_eq!(BalancesVTRS::reserved_balance(ALICE), deposit);
+        assert_eq!(EnergyFee::discounted_fee(&BOB, fee), fee);
+        assert_ok!(EnergyFee::accept_fee_discount_delegation(
+            RuntimeOrigin::signed(charlie),
+            ALICE
+        ));
+        assert_eq!(EnergyFee::discounted_fee(&charlie, fee), Perbill::from_percent(80) * fee);
+        assert_ok!(EnergyFee::accept_fee_discount_delegation(RuntimeOrigin::signed(BOB), charlie));
+        assert_eq!(EnergyFee::discounted_fee(&BOB, fee), Perbill::from_percent(90) * fee);
+
+        // the minimum fee still applies
+        MinimumFee::set(fee);
+        let remark = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+        assert_eq!(EnergyFee::charged_fee(&BOB, &remark, fee), fee);
+
+        assert_ok!(EnergyFee::revoke_fee_discount_delegation(RuntimeOrigin::signed(ALICE)));
+        System::assert_last_event(
+            Event::<Test>::FeeDiscountDelegationRevoked { delegator: ALICE, delegate: charlie }
+                .into(),
+        );
+        assert_eq!(BalancesVTRS::reserved_balance(ALICE), 0);
+        assert_eq!(EnergyFee::discounted_fee(&charlie, fee), Perbill::from_percent(90) * fee);
+        assert_noop!(
+            EnergyFee::revoke_fee_discount_delegation(RuntimeOrigin::signed(ALICE)),
+            Error::<Test>::NoFeeDiscountDelegation
+        );
+    });
+}
+
+#[test]
+fn fee_discount_delegation_needs_the_deposit() {
+    new_test_ext(0).execute_with(|| {
+        let charlie = AccountId::from([3u8; 20]);
+        assert_noop!(
+            EnergyFee::delegate_fee_discount(RuntimeOrigin::signed(charlie), BOB),
+            pallet_balances::Error::<Test>::InsufficientBalance
+        );
+    });
+}
+
+#[test]
+fn fee_payment_paths_record_how_fees_were_paid() {
+    new_test_ext(0).execute_with(|| {
+        System::set_block_number(1);
+        let fee = GetConstantEnergyFee::get();
+        let path =
+            |block, index| EnergyFee::fee_payment_path(block, index).map(|path| path.into_inner());
+
+        // the Ethereum transaction's fee is swapped for VTRS, the account having no VNRG
+        System::set_extrinsic_index(0);
+        let _ = <EnergyFee as OnChargeEVMTransaction<Test>>::withdraw_fee(
+            &ALICE.into(),
+            1_234_567_890.into(),
+        )
+        .expect("Expected to withdraw fee");
+        let price = VNRG_TO_VTRS_RATE.checked_mul_int(fee).unwrap();
+        assert_eq!(
+            path(1, 0),
+            Some(vec![
+                FeePaymentStep::Swapped { amount: fee, price },
+                FeePaymentStep::FeeToken { amount: fee },
+            ])
+        );
+
+        // the substrate transaction's fee is paid with VNRG directly
+        assert_ok!(BalancesVNRG::mint_into(&ALICE, INITIAL_ENERGY_BALANCE));
+        System::set_extrinsic_index(1);
+        let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![1u8; 32] });
+        let dispatch_info =
+            DispatchInfo { weight: SystemWeight::<Test>::remark(32), ..Default::default() };
+        let computed_fee = TransactionPayment::compute_fee(100, &dispatch_info, 0);
+        assert_ok!(<EnergyFee as OnChargeTransaction<Test>>::withdraw_fee(
+            &ALICE,
+            &call,
+            &dispatch_info,
+            computed_fee,
+            0,
+        ));
+        let paid = INITIAL_ENERGY_BALANCE - BalancesVNRG::balance(&ALICE);
+        assert_eq!(path(1, 1), Some(vec![FeePaymentStep::FeeToken { amount: paid }]));
+
+        // extrinsics which paid no fee and future blocks have no path
+        assert_eq!(path(1, 2), None);
+        assert_eq!(path(2, 0), None);
+
+        // The retention is 3 blocks, so the paths of block 1 are pruned once block 4 starts, one
+        // path per block.
+        System::set_block_number(3);
+        EnergyFee::on_initialize(3);
+        assert!(path(1, 0).is_some());
+        System::set_block_number(4);
+        EnergyFee::on_initialize(4);
+        assert_eq!(path(1, 0), None);
+        assert_eq!(FeePaymentPaths::<Test>::iter_prefix(1).count(), 1);
+        System::set_block_number(5);
+        EnergyFee::on_initialize(5);
+        assert!(FeePaymentPaths::<Test>::iter_prefix(1).next().is_none());
+        assert_eq!(FeePaymentPathPruning::<Test>::get().map(|(block, _)| block), Some(2));
+    });
+}
+
+#[test]
+fn energy_is_redeemed_for_main_token_within_slippage() {
+    new_test_ext(INITIAL_ENERGY_BALANCE).execute_with(|| {
+        System::set_block_number(1);
+        let amount = INITIAL_ENERGY_BALANCE / 2;
+        let price = VNRG_TO_VTRS_RATE.checked_mul_int(amount).expect("Expected to price energy");
+        let vtrs_balance = BalancesVTRS::balance(&ALICE);
+
+        assert_noop!(
+            EnergyFee::redeem_energy(RuntimeOrigin::signed(ALICE), amount, price + 1),
+            Error::<Test>::RedemptionPriceTooLow
+        );
+
+        assert_ok!(EnergyFee::redeem_energy(RuntimeOrigin::signed(ALICE), amount, price));
+        System::assert_last_event(
+            Event::<Test>::EnergyRedeemed { who: ALICE, amount, price }.into(),
+        );
+        assert_eq!(BalancesVNRG::balance(&ALICE), INITIAL_ENERGY_BALANCE - amount);
+        assert_eq!(BalancesVTRS::balance(&ALICE), vtrs_balance + price);
+
+        // only the fee token the account holds can be redeemed
+        assert_noop!(
+            EnergyFee::redeem_energy(RuntimeOrigin::signed(ALICE), INITIAL_ENERGY_BALANCE, 0),
+            TokenError::FundsUnavailable
+        );
+    });
+}
+
+#[test]
+fn fee_spend_history_accumulates_fees_per_era() {
+    new_test_ext(INITIAL_ENERGY_BALANCE).execute_with(|| {
+        System::set_block_number(1);
+        let evm_fee = GetConstantEnergyFee::get();
+        let charge_evm_fee = || {
+            let _ = <EnergyFee as OnChargeEVMTransaction<Test>>::withdraw_fee(
+                &ALICE.into(),
+                1_234_567_890.into(),
+            )
+            .expect("Expected to withdraw fee");
+        };
+        let charge_native_fee = || {
+            let balance = BalancesVNRG::balance(&ALICE);
+            let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![1u8; 32] });
+            let dispatch_info =
+                DispatchInfo { weight: SystemWeight::<Test>::remark(32), ..Default::default() };
+            let computed_fee = TransactionPayment::compute_fee(100, &dispatch_info, 0);
+            assert_ok!(<EnergyFee as OnChargeTransaction<Test>>::withdraw_fee(
+                &ALICE,
+                &call,
+                &dispatch_info,
+                computed_fee,
+                0,
+            ));
+            balance - BalancesVNRG::balance(&ALICE)
+        };
+
+        // both Ethereum and substrate transactions are counted
+        RebateEra::set(1);
+        charge_evm_fee();
+        let native_fee = charge_native_fee();
+        assert_eq!(EnergyFee::fee_spend_history(&ALICE), vec![(1, evm_fee + native_fee)]);
+
+        RebateEra::set(2);
+        charge_evm_fee();
+        assert_eq!(
+            EnergyFee::fee_spend_history(&ALICE),
+            vec![(1, evm_fee + native_fee), (2, evm_fee)]
+        );
+
+        // eras without fees are skipped and only the latest 2 eras with fees are kept
+        RebateEra::set(4);
+        charge_evm_fee();
+        charge_evm_fee();
+        assert_eq!(EnergyFee::fee_spend_history(&ALICE), vec![(2, evm_fee), (4, 2 * evm_fee)]);
+
+        assert!(EnergyFee::fee_spend_history(&BOB).is_empty());
+    });
+}
+
+#[test]
+fn fees_are_exchanged_at_the_oracle_rate_until_the_pool_is_liquid() {
+    new_test_ext(0).execute_with(|| {
+        System::set_block_number(1);
+        let constant_fee = GetConstantEnergyFee::get();
+        let withdraw_fee = || {
+            let vtrs_balance = BalancesVTRS::balance(&ALICE);
+            assert_ok!(<EnergyFee as OnChargeEVMTransaction<Test>>::withdraw_fee(
+                &ALICE.into(),
+                1_234_567_890.into(),
+            ));
+            assert_eq!(BalancesVNRG::balance(&ALICE), 0);
+            vtrs_balance - BalancesVTRS::balance(&ALICE)
+        };
+
+        // the pool has no liquidity yet, the oracle rate applies
+        assert_eq!(withdraw_fee(), VNRG_TO_VTRS_RATE.checked_mul_int(constant_fee).unwrap());
+
+        // the pool reached its minimum reserve, the next fee is swapped through it
+        assert!(!EnergyFee::fee_pool_liquid());
+        PoolLiquid::set(true);
+        assert_eq!(withdraw_fee(), POOL_VNRG_TO_VTRS_RATE.checked_mul_int(constant_fee).unwrap());
+        assert!(EnergyFee::fee_pool_liquid());
+        System::assert_has_event(Event::FeePoolBecameLiquid.into());
+
+        // the switch is latched, fees don't fall back to the oracle rate once the pool drains
+        PoolLiquid::set(false);
+        assert_eq!(withdraw_fee(), POOL_VNRG_TO_VTRS_RATE.checked_mul_int(constant_fee).unwrap());
+    });
+}
@@ -1,13 +1,18 @@
-use crate::CallFee;
+use crate::{CallFee, Config, Pallet};
 use frame_support::ensure;
 use frame_support::traits::{
     fungible::{Balanced, Inspect},
-    tokens::{Balance, ConversionFromAssetBalance, ConversionToAssetBalance, Precision},
+    tokens::{
+        Balance, ConversionFromAssetBalance, ConversionToAssetBalance, Fortitude, Precision,
+        Preservation,
+    },
     Get, Imbalance,
 };
 use frame_support::traits::{Currency, ExistenceRequirement, OnUnbalanced, WithdrawReasons};
 use pallet_asset_rate::{Config as AssetRateConfig, Error as AssetRateError};
-use sp_runtime::{DispatchError, FixedPointNumber, FixedPointOperand, TokenError};
+use sp_runtime::{
+    DispatchError, FixedPointNumber, FixedPointOperand, FixedU128, Perbill, TokenError,
+};
 use sp_std::marker::PhantomData;
 
 type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
@@ -35,6 +40,24 @@ where
     fn ethereum_fee() -> Balance {
         Self::custom_fee()
     }
+
+    /// Whether the fee of `runtime_call` covers EVM calls it wraps, which then aren't charged
+    /// again by the EVM runner
+    fn wraps_evm_calls(_runtime_call: &RuntimeCall) -> bool {
+        false
+    }
+}
+
+/// A value that depends on a key, e.g. a fee discount depending on the NAC level
+pub trait GetByKey<Key, Value> {
+    fn get(key: &Key) -> Value;
+}
+
+/// No discount for any key
+impl<Key> GetByKey<Key, Perbill> for () {
+    fn get(_key: &Key) -> Perbill {
+        Perbill::zero()
+    }
 }
 
 pub trait TokenExchange<AccountId, SourceToken, TargetToken, SourceTokenRecycleDest, TokenBalance>
@@ -76,6 +99,17 @@ where
         Self::exchange_inner(who, resulting_amount, amount)
     }
 
+    /// Exchange `SourceToken` -> `TargetToken` based on the `amount` of `TargetToken` to pay a
+    /// fee of user `who`
+    ///
+    /// Exchanges may bound swaps paying fees more tightly than the ones users initiate.
+    fn exchange_fee_from_output(
+        who: &AccountId,
+        amount: TokenBalance,
+    ) -> Result<TokenBalance, DispatchError> {
+        Self::exchange_from_output(who, amount)
+    }
+
     fn exchange_inner(
         who: &AccountId,
         amount_in: TokenBalance,
@@ -97,10 +131,36 @@ where
     }
 }
 
-pub struct AssetsBalancesConverter<T, P>(PhantomData<(T, P)>);
+/// Exchange of the fee token back to the main token
+pub trait TokenRedemption<AccountId, TokenBalance> {
+    /// Calculate the amount of main token `amount` of fee token is redeemed for
+    fn quote_redemption(amount: TokenBalance) -> Result<TokenBalance, DispatchError>;
+
+    /// Redeem `amount` of fee token for main token on behalf of user `who`, failing if it yields
+    /// less than `min_out` of main token. Returns the main token received
+    fn redeem(
+        who: &AccountId,
+        amount: TokenBalance,
+        min_out: TokenBalance,
+    ) -> Result<TokenBalance, DispatchError>;
+}
+
+/// Converts balances using the rates of `pallet_asset_rate`.
+///
+/// When an asset has no rate, e.g. because of a misconfiguration, the rate provided by `F` is
+/// used instead, so that fees keep working. `F = ()` disables the fallback.
+pub struct AssetsBalancesConverter<T, P, F = ()>(PhantomData<(T, P, F)>);
 
-impl<T: AssetRateConfig, P> ConversionFromAssetBalance<BalanceOf<T>, AssetIdOf<T>, BalanceOf<T>>
-    for AssetsBalancesConverter<T, P>
+impl<T: AssetRateConfig, P, F: Get<Option<FixedU128>>> AssetsBalancesConverter<T, P, F> {
+    fn rate(asset_id: AssetIdOf<T>) -> Result<FixedU128, DispatchError> {
+        pallet_asset_rate::ConversionRateToNative::<T>::get(asset_id)
+            .or_else(F::get)
+            .ok_or(AssetRateError::<T>::UnknownAssetKind.into())
+    }
+}
+
+impl<T: AssetRateConfig, P, F> ConversionFromAssetBalance<BalanceOf<T>, AssetIdOf<T>, BalanceOf<T>>
+    for AssetsBalancesConverter<T, P, F>
 where
     P: ConversionFromAssetBalance<
         BalanceOf<T>,
@@ -108,6 +168,7 @@ where
         BalanceOf<T>,
         Error = AssetRateError<T>,
     >,
+    F: Get<Option<FixedU128>>,
     BalanceOf<T>: FixedPointOperand,
 {
     type Error = DispatchError;
@@ -116,12 +177,17 @@ where
         balance: BalanceOf<T>,
         asset_id: AssetIdOf<T>,
     ) -> Result<BalanceOf<T>, Self::Error> {
-        P::from_asset_balance(balance, asset_id).map_err(|e| e.into())
+        match P::from_asset_balance(balance, asset_id) {
+            Err(AssetRateError::<T>::UnknownAssetKind) => F::get()
+                .map(|rate| rate.saturating_mul_int(balance))
+                .ok_or(AssetRateError::<T>::UnknownAssetKind.into()),
+            result => result.map_err(|e| e.into()),
+        }
     }
 }
 
-impl<T: AssetRateConfig, P> ConversionToAssetBalance<BalanceOf<T>, AssetIdOf<T>, BalanceOf<T>>
-    for AssetsBalancesConverter<T, P>
+impl<T: AssetRateConfig, P, F> ConversionToAssetBalance<BalanceOf<T>, AssetIdOf<T>, BalanceOf<T>>
+    for AssetsBalancesConverter<T, P, F>
 where
     P: ConversionFromAssetBalance<
         BalanceOf<T>,
@@ -129,6 +195,7 @@ where
         BalanceOf<T>,
         Error = AssetRateError<T>,
     >,
+    F: Get<Option<FixedU128>>,
     BalanceOf<T>: FixedPointOperand,
 {
     type Error = DispatchError;
@@ -137,8 +204,7 @@ where
         balance: BalanceOf<T>,
         asset_id: AssetIdOf<T>,
     ) -> Result<BalanceOf<T>, Self::Error> {
-        let rate = pallet_asset_rate::ConversionRateToNative::<T>::get(asset_id)
-            .ok_or::<Self::Error>(AssetRateError::<T>::UnknownAssetKind.into())?;
+        let rate = Self::rate(asset_id)?;
         let result = rate
             .reciprocal()
             .ok_or(DispatchError::Other("Asset rate too low"))?
@@ -148,6 +214,92 @@ where
     }
 }
 
+/// Exchanges through `Pool` once `IsPoolLiquid` reports enough liquidity, through `Bootstrap`
+/// until then.
+///
+/// Before the broker pool is funded after genesis, swapping for fees would fail. `Bootstrap` is
+/// meant to exchange at the oracle rate meanwhile. The first time the pool is liquid, the switch
+/// is latched in [`FeePoolLiquid`](crate::FeePoolLiquid): fees keep going through the pool even
+/// if its liquidity later drops, they never fall back to the oracle rate.
+pub struct BootstrapExchange<T, Pool, Bootstrap, IsPoolLiquid>(
+    PhantomData<(T, Pool, Bootstrap, IsPoolLiquid)>,
+);
+
+impl<T: Config, P, E, L: Get<bool>> BootstrapExchange<T, P, E, L> {
+    /// Whether to exchange through the pool, latching the switch once it's liquid.
+    fn use_pool() -> bool {
+        if Pallet::<T>::fee_pool_liquid() {
+            return true;
+        }
+        let liquid = L::get();
+        if liquid {
+            Pallet::<T>::note_fee_pool_liquid();
+        }
+        liquid
+    }
+}
+
+impl<AC, ST, TT, STD, B, T, P, E, L> TokenExchange<AC, ST, TT, STD, B>
+    for BootstrapExchange<T, P, E, L>
+where
+    ST: Currency<AC, Balance = B>,
+    TT: Balanced<AC> + Inspect<AC, Balance = B>,
+    STD: OnUnbalanced<<ST as Currency<AC>>::NegativeImbalance>,
+    B: Balance,
+    T: Config,
+    P: TokenExchange<AC, ST, TT, STD, B>,
+    E: TokenExchange<AC, ST, TT, STD, B>,
+    L: Get<bool>,
+{
+    fn convert_from_input(amount: B) -> Result<B, DispatchError> {
+        if Self::use_pool() {
+            P::convert_from_input(amount)
+        } else {
+            E::convert_from_input(amount)
+        }
+    }
+
+    fn convert_from_output(amount: B) -> Result<B, DispatchError> {
+        if Self::use_pool() {
+            P::convert_from_output(amount)
+        } else {
+            E::convert_from_output(amount)
+        }
+    }
+
+    fn exchange_from_input(who: &AC, amount: B) -> Result<B, DispatchError> {
+        if Self::use_pool() {
+            P::exchange_from_input(who, amount)
+        } else {
+            E::exchange_from_input(who, amount)
+        }
+    }
+
+    fn exchange_from_output(who: &AC, amount: B) -> Result<B, DispatchError> {
+        if Self::use_pool() {
+            P::exchange_from_output(who, amount)
+        } else {
+            E::exchange_from_output(who, amount)
+        }
+    }
+
+    fn exchange_fee_from_output(who: &AC, amount: B) -> Result<B, DispatchError> {
+        if Self::use_pool() {
+            P::exchange_fee_from_output(who, amount)
+        } else {
+            E::exchange_fee_from_output(who, amount)
+        }
+    }
+
+    fn exchange_inner(who: &AC, amount_in: B, amount_out: B) -> Result<B, DispatchError> {
+        if Self::use_pool() {
+            P::exchange_inner(who, amount_in, amount_out)
+        } else {
+            E::exchange_inner(who, amount_in, amount_out)
+        }
+    }
+}
+
 pub struct NativeExchange<AssetId, SourceToken, TargetToken, Rate, GetAssetId>(
     PhantomData<(AssetId, SourceToken, TargetToken, Rate, GetAssetId)>,
 );
@@ -174,3 +326,28 @@ where
         R::from_asset_balance(amount, asset_id)
     }
 }
+
+impl<AC, AS, TT, ST, B, G, R> TokenRedemption<AC, B> for NativeExchange<AS, ST, TT, R, G>
+where
+    TT: Balanced<AC> + Inspect<AC, Balance = B>,
+    ST: Currency<AC, Balance = B>,
+    B: Balance,
+    G: Get<AS>,
+    R: ConversionFromAssetBalance<B, AS, B, Error = DispatchError>,
+{
+    fn quote_redemption(amount: B) -> Result<B, DispatchError> {
+        let asset_id = G::get();
+        R::from_asset_balance(amount, asset_id)
+    }
+
+    fn redeem(who: &AC, amount: B, min_out: B) -> Result<B, DispatchError> {
+        let amount_out = Self::quote_redemption(amount)?;
+        ensure!(amount_out >= min_out, DispatchError::Other("Redemption below minimum"));
+
+        // the redeemed fee token is burned and the main token minted at the rate
+        let _ =
+            TT::withdraw(who, amount, Precision::Exact, Preservation::Preserve, Fortitude::Polite)?;
+        let _ = ST::deposit_creating(who, amount_out);
+        Ok(amount_out)
+    }
+}
@@ -0,0 +1,148 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for `pallet-energy-fee`.
+pub trait WeightInfo {
+    fn delegate_fee_discount() -> Weight;
+    fn accept_fee_discount_delegation() -> Weight;
+    fn revoke_fee_discount_delegation() -> Weight;
+    fn update_fee_swap_limit() -> Weight;
+    fn approve_energy() -> Weight;
+    fn transfer_energy_from() -> Weight;
+    fn buy_fee_credit() -> Weight;
+    fn claim_rebate() -> Weight;
+    fn redeem_energy() -> Weight;
+}
+
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    fn delegate_fee_discount() -> Weight {
+        Weight::from_parts(31_547_000, 3581)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(3_u64))
+    }
+
+    fn accept_fee_discount_delegation() -> Weight {
+        Weight::from_parts(17_832_000, 3554)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+
+    fn revoke_fee_discount_delegation() -> Weight {
+        Weight::from_parts(27_105_000, 3581)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(3_u64))
+    }
+
+    fn update_fee_swap_limit() -> Weight {
+        Weight::from_parts(6_871_000, 0)
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    fn approve_energy() -> Weight {
+        Weight::from_parts(18_932_000, 3582)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+
+    fn transfer_energy_from() -> Weight {
+        Weight::from_parts(64_718_000, 6208)
+            .saturating_add(T::DbWeight::get().reads(6_u64))
+            .saturating_add(T::DbWeight::get().writes(6_u64))
+    }
+
+    fn buy_fee_credit() -> Weight {
+        Weight::from_parts(246_381_000, 16644)
+            .saturating_add(T::DbWeight::get().reads(13_u64))
+            .saturating_add(T::DbWeight::get().writes(12_u64))
+    }
+
+    fn claim_rebate() -> Weight {
+        Weight::from_parts(71_264_000, 3593)
+            .saturating_add(T::DbWeight::get().reads(7_u64))
+            .saturating_add(T::DbWeight::get().writes(6_u64))
+    }
+
+    fn redeem_energy() -> Weight {
+        Weight::from_parts(208_473_000, 16644)
+            .saturating_add(T::DbWeight::get().reads(11_u64))
+            .saturating_add(T::DbWeight::get().writes(10_u64))
+    }
+}
+
+impl WeightInfo for () {
+    fn delegate_fee_discount() -> Weight {
+        Weight::from_parts(31_547_000, 3581)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(3_u64))
+    }
+
+    fn accept_fee_discount_delegation() -> Weight {
+        Weight::from_parts(17_832_000, 3554)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+
+    fn revoke_fee_discount_delegation() -> Weight {
+        Weight::from_parts(27_105_000, 3581)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(3_u64))
+    }
+
+    fn update_fee_swap_limit() -> Weight {
+        Weight::from_parts(6_871_000, 0)
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn approve_energy() -> Weight {
+        Weight::from_parts(18_932_000, 3582)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+
+    fn transfer_energy_from() -> Weight {
+        Weight::from_parts(64_718_000, 6208)
+            .saturating_add(RocksDbWeight::get().reads(6_u64))
+            .saturating_add(RocksDbWeight::get().writes(6_u64))
+    }
+
+    fn buy_fee_credit() -> Weight {
+        Weight::from_parts(246_381_000, 16644)
+            .saturating_add(RocksDbWeight::get().reads(13_u64))
+            .saturating_add(RocksDbWeight::get().writes(12_u64))
+    }
+
+    fn claim_rebate() -> Weight {
+        Weight::from_parts(71_264_000, 3593)
+            .saturating_add(RocksDbWeight::get().reads(7_u64))
+            .saturating_add(RocksDbWeight::get().writes(6_u64))
+    }
+
+    fn redeem_energy() -> Weight {
+        Weight::from_parts(208_473_000, 16644)
+            .saturating_add(RocksDbWeight::get().reads(11_u64))
+            .saturating_add(RocksDbWeight::get().writes(10_u64))
+    }
+}
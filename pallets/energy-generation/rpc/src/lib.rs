@@ -3,6 +3,7 @@ use jsonrpsee::{
     proc_macros::rpc,
     types::{ErrorCode, ErrorObject},
 };
+use parity_scale_codec::Codec;
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
 use sp_runtime::{traits::Block as BlockT, Perbill};
@@ -14,7 +15,7 @@ use pallet_reputation::ReputationTier;
 pub use energy_generation_runtime_api::EnergyGenerationApi as EnergyGenerationRuntimeApi;
 
 #[rpc(server, client)]
-pub trait EnergyGenerationApi<BlockHash> {
+pub trait EnergyGenerationApi<BlockHash, AccountId> {
     #[method(name = "energyGeneration_reputationTierAdditionalReward")]
     fn reputation_tier_additional_reward(
         &self,
@@ -24,6 +25,17 @@ pub trait EnergyGenerationApi<BlockHash> {
 
     #[method(name = "energyGeneration_currentEnergyPerStakeCurrency")]
     fn current_energy_per_stake_currency(&self, at: Option<BlockHash>) -> RpcResult<u128>;
+
+    #[method(name = "energyGeneration_energyPerReputationPoint")]
+    fn energy_per_reputation_point(&self, at: Option<BlockHash>) -> RpcResult<u128>;
+
+    #[method(name = "energyGeneration_validatorUptime")]
+    fn validator_uptime(
+        &self,
+        who: AccountId,
+        era: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<Perbill>>;
 }
 
 pub struct EnergyGeneration<C, B> {
@@ -37,12 +49,14 @@ impl<C, B> EnergyGeneration<C, B> {
     }
 }
 
-impl<C, Block> EnergyGenerationApiServer<<Block as BlockT>::Hash> for EnergyGeneration<C, Block>
+impl<C, Block, AccountId> EnergyGenerationApiServer<<Block as BlockT>::Hash, AccountId>
+    for EnergyGeneration<C, Block>
 where
     Block: BlockT,
+    AccountId: Codec,
     C: Send + Sync + 'static,
     C: ProvideRuntimeApi<Block> + HeaderBackend<Block>,
-    C::Api: EnergyGenerationRuntimeApi<Block>,
+    C::Api: EnergyGenerationRuntimeApi<Block, AccountId>,
 {
     fn reputation_tier_additional_reward(
         &self,
@@ -80,4 +94,39 @@ where
             )
         })
     }
+
+    fn energy_per_reputation_point(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<u128> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        );
+        api.energy_per_reputation_point(at).map_err(|e| {
+            ErrorObject::owned(
+                ErrorCode::InternalError.code(),
+                "Unable to query energy_per_reputation_point.",
+                Some(e.to_string()),
+            )
+        })
+    }
+
+    fn validator_uptime(
+        &self,
+        who: AccountId,
+        era: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<Perbill>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        );
+        api.validator_uptime(at, who, era).map_err(|e| {
+            ErrorObject::owned(
+                ErrorCode::InternalError.code(),
+                "Unable to query validator_uptime.",
+                Some(e.to_string()),
+            )
+        })
+    }
 }
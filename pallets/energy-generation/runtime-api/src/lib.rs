@@ -1,13 +1,75 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use pallet_energy_generation::{
+    ElectionScore, ElectionSolutionError, IssuanceReport, RewardBreakdown, StakingOverview,
+    ValidatorStats,
+};
 use pallet_reputation::ReputationTier;
+use parity_scale_codec::Codec;
 use sp_runtime::Perbill;
+use sp_std::vec::Vec;
 
 sp_api::decl_runtime_apis! {
-    pub trait EnergyGenerationApi
+    #[api_version(13)]
+    pub trait EnergyGenerationApi<AccountId>
+    where
+        AccountId: Codec,
     {
         fn reputation_tier_additional_reward(tier: ReputationTier) -> Perbill;
 
         fn current_energy_per_stake_currency() -> u128;
+
+        #[api_version(2)]
+        fn era_issuance_report(era: u32) -> Option<IssuanceReport<u128, u128>>;
+
+        #[api_version(3)]
+        fn reward_curve_points() -> Vec<(Perbill, Perbill)>;
+
+        #[api_version(4)]
+        fn validator_uptime(who: AccountId, era: u32) -> Option<Perbill>;
+
+        /// Energy minted minus energy burned in the last ended era, in the smallest VNRG unit.
+        #[api_version(5)]
+        fn net_energy_inflation() -> i128;
+
+        /// How the energy reward of `validator` for the active era is made up.
+        #[api_version(6)]
+        fn reward_breakdown(validator: AccountId) -> RewardBreakdown<u128>;
+
+        /// Own stake `validator` needs to earn `target_energy_per_era` at the current parameters,
+        /// `u128::MAX` if it can't be reached.
+        #[api_version(7)]
+        fn stake_for_target_reward(validator: AccountId, target_energy_per_era: u128) -> u128;
+
+        /// Stake, cooperators, commission, reputation tier, uptime and last reward of `who`,
+        /// `None` if it isn't a validator.
+        #[api_version(8)]
+        fn validator_stats(who: AccountId) -> Option<ValidatorStats<u128, u128>>;
+
+        /// Energy per reputation point the validator rewards of the next era are calculated with.
+        #[api_version(9)]
+        fn energy_per_reputation_point() -> u128;
+
+        /// Score `validators` proposed as the validator set of the next era, without electing
+        /// them, or tell why they can't be elected.
+        #[api_version(10)]
+        fn check_election_solution(
+            validators: Vec<AccountId>,
+        ) -> Result<ElectionScore<u128>, ElectionSolutionError>;
+
+        /// Stake of the active era, total stake currency issuance, their ratio and the number of
+        /// active validators.
+        #[api_version(11)]
+        fn staking_overview() -> StakingOverview<u128>;
+
+        /// Own stake `who` has to bond to validate, the trust bond above NAC level 1 and the
+        /// common bond otherwise.
+        #[api_version(12)]
+        fn min_validator_bond(who: AccountId) -> u128;
+
+        /// Projected yearly energy issuance valued in stake currency, relative to the total
+        /// stake of the active era, assuming the current energy rate holds.
+        #[api_version(13)]
+        fn network_energy_apy() -> Perbill;
     }
 }
@@ -219,7 +219,7 @@ benchmarks! {
         let stash = create_funded_user::<T>("stash", USER_SEED, 100);
         let controller = create_funded_user::<T>("controller", USER_SEED, 100);
         let controller_lookup = T::Lookup::unlookup(controller.clone());
-        let reward_destination = RewardDestination::Staked;
+        let reward_destination = RewardDestination::Stash;
         let amount = T::Currency::minimum_balance() * 10u32.into();
         whitelist_account!(stash);
     }: _(RawOrigin::Signed(stash.clone()), controller_lookup, amount, reward_destination)
@@ -462,7 +462,7 @@ benchmarks! {
 
     set_payee {
         let (stash, controller) = create_stash_controller::<T>(USER_SEED, 100, Default::default())?;
-        assert_eq!(Payee::<T>::get(&stash), RewardDestination::Staked);
+        assert_eq!(Payee::<T>::get(&stash), RewardDestination::Controller);
         whitelist_account!(controller);
     }: _(RawOrigin::Signed(controller), RewardDestination::Controller)
     verify {
@@ -584,7 +584,7 @@ benchmarks! {
             n,
             T::MaxCooperatorRewardedPerValidator::get() as u32,
             false,
-            RewardDestination::Staked,
+            RewardDestination::Stash,
         )?;
 
         let current_era = CurrentEra::<T>::get().unwrap();
@@ -899,7 +899,7 @@ benchmarks! {
 
         // Create a validator with a commission of 50%
         let (stash, controller) =
-            create_stash_controller::<T>(1, 1, RewardDestination::Staked)?;
+            create_stash_controller::<T>(1, 1, RewardDestination::Stash)?;
         let validator_prefs =
             ValidatorPrefs { commission: Perbill::from_percent(50), ..Default::default() };
         Staking::<T>::validate(RawOrigin::Signed(controller).into(), validator_prefs)?;
@@ -929,6 +929,212 @@ benchmarks! {
         assert_eq!(MinCommission::<T>::get(), Perbill::from_percent(100));
     }
 
+    consolidate_unlocking {
+        let (_, controller, _) = create_stash_controller::<T>(0, 100, Default::default())?;
+        // the worst case, all chunks unlock in the same era and are merged into one
+        let mut ledger = Ledger::<T>::get(&controller).unwrap();
+        let unlock_chunk = UnlockChunk::<StakeOf<T>> {
+            value: 1u32.into(),
+            era: EraIndex::zero(),
+        };
+        for _ in 0 .. T::MaxUnlockingChunks::get() {
+            ledger.unlocking.try_push(unlock_chunk.clone()).unwrap();
+        }
+        Ledger::<T>::insert(&controller, ledger);
+        whitelist_account!(controller);
+    }: _(RawOrigin::Signed(controller.clone()))
+    verify {
+        assert_eq!(Ledger::<T>::get(&controller).unwrap().unlocking.len(), 1);
+    }
+
+    update_prefs {
+        let (stash, controller, _) =
+            create_stash_controller::<T>(USER_SEED, 100, Default::default())?;
+        Staking::<T>::do_add_validator(&stash, Default::default());
+        let commission = Perbill::from_percent(50);
+        // the worst case, paying into an account checks that it can receive energy
+        let payee = RewardDestination::Account(create_funded_user::<T>("payee", USER_SEED, 100));
+        whitelist_account!(controller);
+    }: _(RawOrigin::Signed(controller), Some(commission), Some(payee.clone()))
+    verify {
+        assert_eq!(Validators::<T>::get(&stash).commission, commission);
+        assert_eq!(Payee::<T>::get(&stash), payee);
+    }
+
+    set_max_slash {
+        let (stash, controller, _) =
+            create_stash_controller::<T>(USER_SEED, 100, Default::default())?;
+        let max_slash = Perbill::from_percent(50);
+        whitelist_account!(controller);
+    }: _(RawOrigin::Signed(controller), Some(max_slash))
+    verify {
+        assert_eq!(CooperatorMaxSlash::<T>::get(&stash), Some(max_slash));
+    }
+
+    report_client_version {
+        let (stash, controller, _) =
+            create_stash_controller::<T>(USER_SEED, 100, Default::default())?;
+        Staking::<T>::do_add_validator(&stash, Default::default());
+        let version = ClientVersion { major: 1, minor: 0, patch: 0 };
+        whitelist_account!(controller);
+    }: _(RawOrigin::Signed(controller), version)
+    verify {
+        assert_eq!(ValidatorClientVersion::<T>::get(&stash), Some(version));
+    }
+
+    set_minimum_client_version {
+        let minimum = ClientVersion { major: 1, minor: 0, patch: 0 };
+    }: _(RawOrigin::Root, Some(minimum))
+    verify {
+        assert_eq!(MinimumClientVersion::<T>::get().map(|requirement| requirement.minimum), Some(minimum));
+    }
+
+    sweep_stale_rewards {
+        // eras with unclaimed rewards, all of them iterated, at most `MaxStaleRewardErasPerSweep`
+        // of them swept
+        let e in 1 .. T::HistoryDepth::get() + T::MaxStaleRewardErasPerSweep::get();
+        let current_era = e + T::HistoryDepth::get();
+        CurrentEra::<T>::put(current_era);
+        ActiveEra::<T>::put(ActiveEraInfo { index: current_era, start: None });
+        let reward = pallet_assets::Pallet::<T>::minimum_balance(T::EnergyAssetId::get());
+        for era in 0 .. e {
+            ErasUnclaimedRewards::<T>::insert(era, reward);
+        }
+        let caller = whitelisted_caller();
+    }: _(RawOrigin::Signed(caller))
+    verify {
+        let swept = e.min(T::MaxStaleRewardErasPerSweep::get());
+        assert_eq!(ErasUnclaimedRewards::<T>::iter().count() as u32, e - swept);
+    }
+
+    claim_all_cooperations {
+        // validators the cooperator cooperates with, each of them rewarding the maximum of
+        // stakers
+        let v in 1 .. T::MaxCooperations::get();
+        clear_validators_and_cooperators::<T>();
+
+        let mut validators = Vec::new();
+        for i in 0 .. v {
+            let (stash, _, _) = create_stash_controller::<T>(i, 100, Default::default())?;
+            Staking::<T>::do_add_validator(&stash, Default::default());
+            validators.push(stash);
+        }
+        // the bond of `create_stash_controller` is split among the validators
+        let stake = T::StakeCurrency::minimum_balance() * 10u32.into() / v.into();
+        let targets: Vec<_> = validators
+            .iter()
+            .map(|stash| (T::Lookup::unlookup(stash.clone()), stake))
+            .collect();
+        for i in 0 .. T::MaxCooperatorRewardedPerValidator::get() - 1 {
+            let (_, controller, _) =
+                create_stash_controller::<T>(USER_SEED + 1 + i, 100, RewardDestination::Stash)?;
+            Staking::<T>::cooperate(RawOrigin::Signed(controller).into(), targets.clone())?;
+        }
+        let (cooperator, controller, _) =
+            create_stash_controller::<T>(USER_SEED, 100, RewardDestination::Stash)?;
+        Staking::<T>::cooperate(RawOrigin::Signed(controller.clone()).into(), targets)?;
+
+        ValidatorCount::<T>::put(v);
+        let new_validators = Staking::<T>::try_trigger_new_era(SessionIndex::one()).unwrap();
+        assert_eq!(new_validators.len(), v as usize);
+
+        let current_era = CurrentEra::<T>::get().unwrap();
+        ErasEnergyPerStakeCurrency::<T>::insert(current_era, EnergyOf::<T>::one());
+        let energy = || pallet_assets::Pallet::<T>::balance(T::EnergyAssetId::get(), &cooperator);
+        let balance_before = energy();
+    }: _(RawOrigin::Signed(controller), current_era, v)
+    verify {
+        ensure!(
+            balance_before < energy(),
+            "Energy of cooperator stash should have increased after claiming.",
+        );
+        for stash in &validators {
+            let ledger = Staking::<T>::ledger(<Bonded<T>>::get(stash).unwrap()).unwrap();
+            ensure!(ledger.claimed_rewards.contains(&current_era), "Validator was not paid out.");
+        }
+    }
+
+    force_set_validators {
+        let v in 1 .. MaxValidators::<T>::get();
+        clear_validators_and_cooperators::<T>();
+
+        let mut validators = Vec::new();
+        for i in 0 .. v {
+            let (stash, _, _) = create_stash_controller::<T>(i, 100, Default::default())?;
+            T::SessionInterface::set_session_keys(&stash);
+            validators.push(stash);
+        }
+        ValidatorCount::<T>::put(v);
+        MinimumValidatorCount::<T>::put(1);
+    }: _(RawOrigin::Root, validators.clone())
+    verify {
+        validators.sort();
+        assert_eq!(ForcedValidators::<T>::get(), Some(validators));
+        assert_eq!(ForceEra::<T>::get(), Forcing::ForceNew);
+    }
+
+    set_max_cooperations {
+        let max = T::MaxCooperations::get();
+    }: _(RawOrigin::Root, max)
+    verify {
+        assert_eq!(MaxCooperationsPerCooperator::<T>::get(), Some(max));
+    }
+
+    set_battery_slot_capacity {
+        let capacity = T::MaxBatterySlotCapacity::get();
+    }: _(RawOrigin::Root, capacity)
+    verify {
+        assert_eq!(BatterySlotCapacityOverride::<T>::get(), Some(capacity));
+    }
+
+    set_nac_revocation_policy {
+        let policy = NacRevocationPolicy::ChillAll;
+    }: _(RawOrigin::Root, policy)
+    verify {
+        assert_eq!(NacRevocationChillPolicy::<T>::get(), policy);
+    }
+
+    set_offending_threshold {
+        let threshold = MAX_OFFENDING_VALIDATORS_THRESHOLD;
+    }: _(RawOrigin::Root, threshold)
+    verify {
+        assert_eq!(PendingOffendingValidatorsThreshold::<T>::get(), Some(threshold));
+    }
+
+    set_energy_per_reputation_point {
+        let rate = T::MaxEnergyPerReputationPoint::get();
+    }: _(RawOrigin::Root, rate)
+    verify {
+        assert_eq!(EnergyPerReputationPointOverride::<T>::get(), Some(rate));
+    }
+
+    set_tier_bonus {
+        // a tier with both neighbours, so that both bounds are read
+        let tier = ReputationTier::Trailblazer(2);
+        let bonus = Staking::<T>::tier_energy_bonus(&tier);
+    }: _(RawOrigin::Root, tier, bonus)
+    verify {
+        assert_eq!(TierEnergyBonus::<T>::get(tier), Some(bonus));
+    }
+
+    commit_next_era_intent {
+        let (stash, controller, _) =
+            create_stash_controller::<T>(USER_SEED, 100, Default::default())?;
+        Staking::<T>::do_add_validator(&stash, Default::default());
+        whitelist_account!(controller);
+    }: _(RawOrigin::Signed(controller), true)
+    verify {
+        let era = Staking::<T>::next_elected_era();
+        assert_eq!(NextEraIntent::<T>::get(era, &stash), Some(true));
+    }
+
+    set_rewards_paused {
+        RewardsPaused::<T>::put(false);
+    }: _(RawOrigin::Root, true)
+    verify {
+        assert!(RewardsPaused::<T>::get());
+    }
+
     impl_benchmark_test_suite!(
         Staking,
         crate::mock::ExtBuilder::default().has_stakers(true),
@@ -978,7 +1184,7 @@ mod tests {
                 n,
                 <<Test as Config>::MaxCooperatorRewardedPerValidator as Get<_>>::get(),
                 false,
-                RewardDestination::Staked,
+                RewardDestination::Stash,
             )
             .unwrap();
 
@@ -1007,7 +1213,7 @@ mod tests {
                 n,
                 <<Test as Config>::MaxCooperatorRewardedPerValidator as Get<_>>::get(),
                 false,
-                RewardDestination::Staked,
+                RewardDestination::Stash,
             )
             .unwrap();
 
@@ -100,10 +100,26 @@
 //!   to the blockchain's economic model. Developers must carefully consider the impact of slashing
 //!   and rebonding on overall token supply, staking incentives, and validator behavior. Misaligned
 //!   penalties could lead to excessive validator exits or reduced participation.
+//! - **Reputation Reward**: Besides the energy generated by their stake, validators are rewarded
+//!   the era's energy per reputation point for each reputation point they had when the era ended.
+//!   Governance tunes the rate with `set_energy_per_reputation_point`, the new rate applies from
+//!   the next era on.
+//!   Their energy reward is raised by the bonus of their reputation tier, which governance sets
+//!   per tier with `set_tier_bonus`.
+//! - **Next Era Intent**: Validators may commit with `commit_next_era_intent` whether they'll
+//!   validate in the next era. Oversubscribed elections still go by stake, committed validators
+//!   are only taken first among equally staked ones, and those that committed not to validate
+//!   are chilled. Intents only count for the era committed for and are removed once it's elected.
+//! - **Reward Pause**: In an economic emergency governance can stop the minting of energy rewards
+//!   with `set_rewards_paused` while eras keep rotating. Rewards claimed meanwhile are minted once
+//!   rewards resume, or forfeited if the runtime doesn't defer them.
 //! - **Customizing Disabling Strategies**: The disabling strategy can be customized by adjusting
 //!   the `DISABLING_LIMIT_FACTOR` to change how many validators can be disabled relative to the
 //!   active set size. This flexibility allows different networks to enforce validator accountability
 //!   according to their governance models and risk tolerance.
+//! - **Offending Validators Threshold**: Once more of the active set is disabled than the
+//!   offending validators threshold allows, a new era is forced. Governance can tune it with
+//!   `set_offending_threshold`, the new threshold applies from the next era on.
 //! - **Logging and Monitoring**: Logs generated by disabling decisions and slashing operations should
 //!   be monitored by network operators to ensure that validator penalties are being applied fairly
 //!   and in accordance with network rules. Misuse or errors in these operations could undermine
@@ -146,7 +162,7 @@ use frame_support::{
     traits::{tokens::fungibles::Debt, Currency, Defensive, Get},
     BoundedVec, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
 };
-use pallet_reputation::Reputation;
+use pallet_reputation::{Reputation, ReputationPoint, ReputationTier};
 use parity_scale_codec::{Decode, Encode, HasCompact, MaxEncodedLen};
 use scale_info::TypeInfo;
 use sp_runtime::{
@@ -193,6 +209,9 @@ pub type StakeNegativeImbalanceOf<T> = <<T as Config>::StakeCurrency as Currency
 pub type EnergyDebtOf<T> =
     Debt<<T as frame_system::Config>::AccountId, pallet_assets::pallet::Pallet<T>>;
 
+/// The issuance report of this pallet.
+pub type IssuanceReportOf<T> = IssuanceReport<EnergyOf<T>, StakeOf<T>>;
+
 type AccountIdLookupOf<T> = <<T as frame_system::Config>::Lookup as StaticLookup>::Source;
 
 /// Representation of the status of a staker.
@@ -218,6 +237,175 @@ pub struct ActiveEraInfo {
     start: Option<u64>,
 }
 
+/// How the energy reward of a validator for an era is made up.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct RewardBreakdown<Energy> {
+    /// Energy generated by the validator's own stake, after commission.
+    pub base: Energy,
+    /// Commission taken from the energy generated by its cooperators' stake.
+    pub commission: Energy,
+    /// Additional reward percent of the validator's current reputation tier.
+    pub reputation_bonus_percent: Perbill,
+    /// Energy rewarded for the validator's reputation points at the era's energy per reputation
+    /// point.
+    pub reputation_reward: Energy,
+    /// Energy added by the reputation tier, on top of `base`, `commission` and
+    /// `reputation_reward`.
+    pub reputation_bonus: Energy,
+    /// Energy the validator receives for the era.
+    pub total: Energy,
+}
+
+/// Score of a validator set proposed for the next era, see [`Pallet::check_election_solution`].
+///
+/// A solution is better than another if its `minimal_stake` is higher, then if its `sum_stake`
+/// is higher, then if its `sum_stake_squared` is lower, i.e. its stake is spread more evenly.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct ElectionScore<Stake> {
+    /// Lowest stake backing a validator of the set.
+    pub minimal_stake: Stake,
+    /// Stake backing the whole set.
+    pub sum_stake: Stake,
+    /// Sum of the squared stakes backing each validator, saturating.
+    pub sum_stake_squared: Stake,
+}
+
+/// Why a validator set proposed for the next era can't be elected.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum ElectionSolutionError {
+    /// The validator at the index doesn't intend to validate or lacks the reputation to.
+    NotCandidate(u32),
+    /// The validator at the index is already part of the set.
+    DuplicateValidator(u32),
+    /// The set has more validators than `ValidatorCount`.
+    TooManyValidators,
+    /// The set has fewer validators than `MinimumValidatorCount`, or none.
+    TooFewValidators,
+}
+
+/// Statistics of a validator aggregated for explorers.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct ValidatorStats<Stake, Energy> {
+    /// Active bond of the validator.
+    pub own_stake: Stake,
+    /// Stake backing the validator in the active era, zero if it isn't active.
+    pub total_stake: Stake,
+    /// Number of cooperators cooperating with the validator.
+    pub cooperators: u32,
+    /// Commission the validator takes from its cooperators' rewards.
+    pub commission: Perbill,
+    /// Current reputation tier of the validator, if it has reached one.
+    pub reputation_tier: Option<ReputationTier>,
+    /// Uptime of the validator in the active era, if it's been recorded.
+    pub uptime: Option<Perbill>,
+    /// Era and energy of the last reward paid to the validator, if any.
+    pub last_reward: Option<(EraIndex, Energy)>,
+}
+
+/// Energy parameters the chain was launched with, see [`Pallet::genesis_parameters`].
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct GenesisParameters<Stake, Energy> {
+    /// Energy generated per stake currency.
+    pub energy_per_stake_currency: Energy,
+    /// Minimum bond of cooperators.
+    pub min_cooperator_bond: Stake,
+    /// Minimum bond of validators below the trusted NAC level.
+    pub min_common_validator_bond: Stake,
+    /// Minimum bond of validators with the trusted NAC level.
+    pub min_trust_validator_bond: Stake,
+    /// Name of the energy asset.
+    pub energy_name: Vec<u8>,
+    /// Symbol of the energy asset.
+    pub energy_symbol: Vec<u8>,
+    /// Decimals of the energy asset.
+    pub energy_decimals: u8,
+}
+
+/// Key staking figures driving the validator rewards, see [`Pallet::staking_overview`].
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct StakingOverview<Stake> {
+    /// Stake backing the validators of the active era.
+    pub total_staked: Stake,
+    /// Total issuance of the staking currency.
+    pub total_issuance: Stake,
+    /// Share of `total_issuance` that is staked.
+    pub staking_ratio: Perbill,
+    /// Number of validators in the current session.
+    pub active_validators: u32,
+}
+
+/// Issuance and slashing accounted during a single era.
+///
+/// Everything is attributed to the era that was active when it happened, e.g. rewards for an
+/// older era paid out now count towards the active era.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct IssuanceReport<Energy, Stake> {
+    /// Energy minted by reward payouts.
+    pub energy_minted: Energy,
+    /// Reputation granted to validators, block authors and offence reporters.
+    pub reputation_granted: ReputationPoint,
+    /// Stake slashed from stakers.
+    pub stake_slashed: Stake,
+    /// Reputation slashed from stakers.
+    pub reputation_slashed: ReputationPoint,
+    /// Slashed stake handed over to `Config::Slash`, i.e. not paid to reporters.
+    pub treasury_inflow: Stake,
+}
+
+/// Liveness of a validator over the sessions of an era.
+#[derive(
+    PartialEq, Eq, Clone, Copy, Default, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub struct UptimeRecord {
+    /// Sessions of the era that ended with the account in the validator set.
+    pub sessions: SessionIndex,
+    /// Sessions in which the account was reported unresponsive.
+    pub offline_sessions: SessionIndex,
+}
+
+/// Age of the session keys of a validator, observed at session ends.
+#[derive(
+    PartialEq, Eq, Clone, Copy, Default, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub struct SessionKeysAge {
+    /// Hash of the keys as last observed.
+    pub keys_hash: [u8; 32],
+    /// Session in which the keys were first observed.
+    pub since: SessionIndex,
+    /// Whether a rotation reminder was already emitted for these keys.
+    pub reminded: bool,
+}
+
+/// Version of the client software run by a validator, ordered by `major`, `minor`, `patch`.
+#[derive(
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Clone,
+    Copy,
+    Default,
+    Encode,
+    Decode,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub struct ClientVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+/// Minimum client version validators have to report to receive their full rewards.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ClientVersionRequirement {
+    /// The oldest accepted version.
+    pub minimum: ClientVersion,
+    /// First era whose rewards are reduced for validators running an older version.
+    pub enforced_from: EraIndex,
+}
+
 /// Reward points of an era. Used to split era total payout between validators.
 ///
 /// This points will be used to reward validators and their respective cooperators.
@@ -236,6 +424,9 @@ impl<AccountId: Ord> Default for EraRewardPoints<AccountId> {
 }
 
 /// A destination account for payment.
+///
+/// Rewards are paid in the energy asset, while stake is held in the stake currency, so rewards
+/// cannot be added to the stake directly, see [`RewardDestination::Staked`].
 #[derive(
     PartialEq, Eq, Copy, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen,
 )]
@@ -249,6 +440,11 @@ pub enum RewardDestination<AccountId> {
     Account(AccountId),
     /// Receive no reward.
     None,
+    /// Add the rewards to the stake.
+    ///
+    /// Not supported since rewards are paid in the energy asset: it can't be chosen anymore
+    /// and the rewards of stashes which still have it are paid into the stash.
+    Staked,
 }
 
 /// Preference of what happens regarding validation.
@@ -285,6 +481,18 @@ pub struct UnlockChunk<Balance: HasCompact + MaxEncodedLen> {
     era: EraIndex,
 }
 
+impl<Balance: HasCompact + MaxEncodedLen + Copy> UnlockChunk<Balance> {
+    /// Amount of funds to be unlocked.
+    pub fn value(&self) -> Balance {
+        self.value
+    }
+
+    /// Era number at which point it'll be unlocked.
+    pub fn era(&self) -> EraIndex {
+        self.era
+    }
+}
+
 /// The ledger of a (bonded) stash.
 #[derive(
     PartialEqNoBound,
@@ -359,6 +567,28 @@ impl<T: Config> StakingLedger<T> {
         }
     }
 
+    /// Merge unlocking chunks that unlock in the same era into a single chunk.
+    ///
+    /// `unlocking` is a FiFo queue ordered by era, so chunks of the same era are adjacent.
+    /// Returns the number of chunks removed by merging.
+    fn merge_unlocking_chunks(&mut self) -> u32 {
+        let chunks = sp_std::mem::take(&mut self.unlocking).into_inner();
+        let initial_len = chunks.len();
+        let mut merged: Vec<UnlockChunk<StakeOf<T>>> = Vec::with_capacity(initial_len);
+        for chunk in chunks {
+            match merged.last_mut() {
+                Some(last) if last.era == chunk.era => {
+                    last.value = last.value.saturating_add(chunk.value)
+                },
+                _ => merged.push(chunk),
+            }
+        }
+        let removed = initial_len.saturating_sub(merged.len()) as u32;
+        self.unlocking =
+            merged.try_into().expect("merging chunks never increases their number. qed");
+        removed
+    }
+
     /// Re-bond funds that were scheduled for unlocking.
     ///
     /// Returns the updated ledger, and the amount actually rebonded.
@@ -631,6 +861,11 @@ pub trait SessionInterface<AccountId> {
     fn validators() -> Vec<AccountId>;
     /// Prune historical session tries up to but not including the given index.
     fn prune_historical_up_to(up_to: SessionIndex);
+    /// Hash of the session keys currently registered by the validator, if any.
+    fn session_keys_hash(validator: &AccountId) -> Option<[u8; 32]>;
+    /// Register some session keys for the validator.
+    #[cfg(feature = "runtime-benchmarks")]
+    fn set_session_keys(_validator: &AccountId) {}
 }
 
 impl<T: Config> SessionInterface<<T as frame_system::Config>::AccountId> for T
@@ -658,6 +893,18 @@ where
     fn prune_historical_up_to(up_to: SessionIndex) {
         <pallet_session::historical::Pallet<T>>::prune_up_to(up_to);
     }
+
+    fn session_keys_hash(validator: &<T as frame_system::Config>::AccountId) -> Option<[u8; 32]> {
+        pallet_session::NextKeys::<T>::get(validator)
+            .map(|keys| keys.using_encoded(sp_io::hashing::blake2_256))
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn set_session_keys(validator: &<T as frame_system::Config>::AccountId) {
+        let keys = T::Keys::decode(&mut sp_runtime::traits::TrailingZeroInput::zeroes())
+            .expect("session keys decode from an infinite zero input; qed");
+        pallet_session::NextKeys::<T>::insert(validator, keys);
+    }
 }
 
 impl<AccountId> SessionInterface<AccountId> for () {
@@ -668,6 +915,9 @@ impl<AccountId> SessionInterface<AccountId> for () {
         Vec::new()
     }
     fn prune_historical_up_to(_: SessionIndex) {}
+    fn session_keys_hash(_: &AccountId) -> Option<[u8; 32]> {
+        None
+    }
 }
 
 /// Handler for determining the energy demand on the current era.
@@ -724,6 +974,20 @@ pub enum Forcing {
     ForceAlways,
 }
 
+/// What happens to a validator whose NAC access is revoked.
+#[derive(
+    Copy, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen, Default,
+)]
+pub enum NacRevocationPolicy {
+    /// The validator keeps validating.
+    Ignore,
+    /// The validator is chilled before the next era election, unless it's invulnerable.
+    #[default]
+    ChillExceptInvulnerables,
+    /// The validator is chilled before the next era election, invulnerables included.
+    ChillAll,
+}
+
 /// A `Convert` implementation that finds the stash of the given controller account,
 /// if any.
 pub struct StashOf<T>(sp_std::marker::PhantomData<T>);
@@ -9,6 +9,46 @@ use crate::{Config, Pallet};
 #[cfg(feature = "try-runtime")]
 use sp_runtime::TryRuntimeError;
 
+/// Seeding `TierEnergyBonus` with the bonuses of
+/// `Config::ReputationTierEnergyRewardAdditionalPercentMapping`, so that governance adjusts the
+/// current bonuses rather than zero.
+pub mod v16 {
+    use super::*;
+    use pallet_reputation::RANKS_PER_TIER;
+
+    pub struct VersionUncheckedMigrateV15ToV16<T>(core::marker::PhantomData<T>);
+    impl<T: Config> UncheckedOnRuntimeUpgrade for VersionUncheckedMigrateV15ToV16<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut seeded = 0;
+            for tier in (1..=RANKS_PER_TIER * 3).filter_map(ReputationTier::try_from_rank) {
+                let bonus = T::ReputationTierEnergyRewardAdditionalPercentMapping::convert(&tier);
+                TierEnergyBonus::<T>::insert(tier, bonus);
+                seeded += 1;
+            }
+
+            log!(info, "v16 applied successfully, {} tier bonuses seeded.", seeded);
+            T::DbWeight::get().writes(seeded)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: Vec<u8>) -> Result<(), TryRuntimeError> {
+            frame_support::ensure!(
+                TierEnergyBonus::<T>::iter().count() == usize::from(RANKS_PER_TIER) * 3,
+                "TierEnergyBonus isn't seeded after the migration"
+            );
+            Ok(())
+        }
+    }
+
+    pub type MigrateV15ToV16<T> = VersionedMigration<
+        15,
+        16,
+        VersionUncheckedMigrateV15ToV16<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
 /// Migrating `OffendingValidators` from `Vec<(u32, bool)>` to `Vec<u32>`
 pub mod v15 {
     use super::*;
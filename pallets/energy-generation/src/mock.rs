@@ -102,6 +102,7 @@ impl FindAuthor<AccountId> for Author11 {
 
 parameter_types! {
     pub static SessionsPerEra: SessionIndex = 3;
+    pub static SessionKeysRotationAge: SessionIndex = 4;
     pub static ExistentialDeposit: Balance = 1;
     pub static SlashDeferDuration: EraIndex = 0;
     pub static Period: BlockNumber = 5;
@@ -151,9 +152,17 @@ impl pallet_balances::Config for Test {
     type RuntimeHoldReason = ();
 }
 
+parameter_types! {
+    pub const MaxReputationPerEra: ReputationPoint = ReputationPoint(u64::MAX);
+}
+
 impl pallet_reputation::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = ();
+    type DecayBatchSize = ConstU32<1_000>;
+    type MaxReputationPerEra = MaxReputationPerEra;
+    type CurrentEra = ConstU32<0>;
+    type ReputationActionCooldown = ConstU64<0>;
 }
 
 parameter_types! {
@@ -240,10 +249,15 @@ parameter_types! {
     pub const BondingDuration: EraIndex = 3;
     pub const RewardCurve: &'static PiecewiseLinear<'static> = &I_NPOS;
     pub const OffendingValidatorsThreshold: Perbill = Perbill::from_percent(75);
+    pub const SlashCapRewardPenalty: Perbill = Perbill::from_percent(50);
+    pub const OutdatedClientRewardPenalty: Perbill = Perbill::from_percent(50);
+    pub const ClientVersionGracePeriod: EraIndex = 1;
+    pub const StaleRewardsDestination: AccountId = 9999;
 }
 
 parameter_types! {
     pub static RewardRemainderUnbalanced: u128 = 0;
+    pub static BackingBoost: Perbill = Perbill::from_percent(10);
 }
 
 pub struct RewardRemainderMock;
@@ -260,6 +274,11 @@ impl OnUnbalanced<StakeNegativeImbalanceOf<Test>> for RewardRemainderMock {
 parameter_types! {
     pub const VNRG: AssetId = 1;
     pub static BatterySlotCapacity: EnergyOf<Test> = EnergyOf::<Test>::from(100_000_000_000u128);
+    pub static MinBatterySlotCapacity: EnergyOf<Test> = EnergyOf::<Test>::from(10_000_000_000u128);
+    pub static MaxBatterySlotCapacity: EnergyOf<Test> = EnergyOf::<Test>::from(1_000_000_000_000u128);
+    pub static EnergyPerReputationPoint: EnergyOf<Test> = EnergyOf::<Test>::zero();
+    pub static MaxEnergyPerReputationPoint: EnergyOf<Test> = EnergyOf::<Test>::from(1_000u128);
+    pub static MaxTierEnergyBonus: Perbill = Perbill::from_percent(50);
     pub static MaxCooperations: u32 = 16;
     pub static HistoryDepth: u32 = 80;
     pub static MaxUnlockingChunks: u32 = 32;
@@ -268,6 +287,12 @@ parameter_types! {
     pub static MaxWinners: u32 = 100;
     pub static ValidatorReputationTier: ReputationTier = ReputationTier::Vanguard(1);
     pub static CollaborativeValidatorReputationTier: ReputationTier = ReputationTier::Trailblazer(1);
+    pub static MaxEnergyMintPerBlock: Option<EnergyOf<Test>> = None;
+    pub static DeferRewardsWhilePaused: bool = true;
+    pub static MinEnergyPerStakeCurrency: EnergyOf<Test> = 1_000;
+    pub static OperationalCallFee: EnergyOf<Test> = EnergyOf::<Test>::zero();
+    pub static ReservedOperationalCalls: u32 = 0;
+    pub static CooperationWarmupEras: EraIndex = 0;
 }
 
 pub struct MockReward;
@@ -319,6 +344,10 @@ pub(crate) const DISABLING_LIMIT_FACTOR: usize = 3;
 impl pallet_energy_generation::Config for Test {
     type AdminOrigin = EnsureOneOrRoot;
     type BatterySlotCapacity = BatterySlotCapacity;
+    type MinBatterySlotCapacity = MinBatterySlotCapacity;
+    type MaxBatterySlotCapacity = MaxBatterySlotCapacity;
+    type EnergyPerReputationPoint = EnergyPerReputationPoint;
+    type MaxEnergyPerReputationPoint = MaxEnergyPerReputationPoint;
     type BenchmarkingConfig = TestBenchmarkingConfig;
     type BondingDuration = BondingDuration;
     type CollaborativeValidatorReputationTier = CollaborativeValidatorReputationTier;
@@ -327,17 +356,33 @@ impl pallet_energy_generation::Config for Test {
     type HistoryDepth = HistoryDepth;
     type MaxCooperations = MaxCooperations;
     type MaxCooperatorRewardedPerValidator = ConstU32<64>;
+    type SlashCapRewardPenalty = SlashCapRewardPenalty;
+    type OutdatedClientRewardPenalty = OutdatedClientRewardPenalty;
+    type BackingBoost = BackingBoost;
+    type ClientVersionGracePeriod = ClientVersionGracePeriod;
+    type OnboardingGraceEras = ConstU32<1>;
+    type CooperationWarmupEras = CooperationWarmupEras;
+    type StaleRewardsDestination = StaleRewardsDestination;
+    type MaxStaleRewardErasPerSweep = ConstU32<2>;
+    type MaxEnergyMintPerBlock = MaxEnergyMintPerBlock;
+    type DeferRewardsWhilePaused = DeferRewardsWhilePaused;
+    type MinEnergyPerStakeCurrency = MinEnergyPerStakeCurrency;
+    type OperationalCallFee = OperationalCallFee;
+    type ReservedOperationalCalls = ReservedOperationalCalls;
     type MaxUnlockingChunks = MaxUnlockingChunks;
     type NextNewSession = Session;
     type EventListeners = EventListenerMock;
     type DisablingStrategy =
         pallet_energy_generation::UpToLimitDisablingStrategy<DISABLING_LIMIT_FACTOR>;
+    type OffendingValidatorsThreshold = OffendingValidatorsThreshold;
     type ReputationTierEnergyRewardAdditionalPercentMapping =
         ReputationTierEnergyRewardAdditionalPercentMapping;
+    type MaxTierEnergyBonus = MaxTierEnergyBonus;
     type Reward = MockReward;
     type RewardRemainder = RewardRemainderMock;
     type RuntimeEvent = RuntimeEvent;
     type SessionInterface = Self;
+    type SessionKeysRotationAge = SessionKeysRotationAge;
     type SessionsPerEra = SessionsPerEra;
     type Slash = ();
     type SlashDeferDuration = SlashDeferDuration;
@@ -346,10 +391,26 @@ impl pallet_energy_generation::Config for Test {
     type ThisWeightInfo = ();
     type UnixTime = Timestamp;
     type ValidatorReputationTier = ValidatorReputationTier;
-    type ValidatorNacLevel = ();
+    type ValidatorNacLevel = MockNacLevel;
     type OnVipMembershipHandler = TestVipMembershipHandler;
 }
 
+parameter_types! {
+    /// NAC levels of accounts, others have no NAC.
+    pub static NacLevels: Vec<(AccountId, u8)> = vec![];
+}
+
+pub struct MockNacLevel;
+
+impl Convert<&AccountId, Option<u8>> for MockNacLevel {
+    fn convert(who: &AccountId) -> Option<u8> {
+        NacLevels::get()
+            .into_iter()
+            .find(|(account, _)| account == who)
+            .map(|(_, level)| level)
+    }
+}
+
 // Implement the trait for a specific type
 pub struct TestVipMembershipHandler;
 
@@ -503,6 +564,10 @@ impl ExtBuilder {
         self.stakers.push((stash, ctrl, stake, status));
         self
     }
+    pub fn energy_per_stake_currency(mut self, rate: Balance) -> Self {
+        self.energy_per_stake_currency = rate;
+        self
+    }
     pub fn balance_factor(mut self, factor: Balance) -> Self {
         self.balance_factor = factor;
         self
@@ -723,6 +788,7 @@ pub(crate) fn run_to_block(n: BlockNumber) {
     PowerPlant::on_finalize(System::block_number());
     for b in (System::block_number() + 1)..=n {
         System::set_block_number(b);
+        ReputationPallet::on_initialize(b);
         Session::on_initialize(b);
         <PowerPlant as Hooks<u64>>::on_initialize(b);
         Timestamp::set_timestamp(System::block_number() * BLOCK_TIME + INIT_TIMESTAMP);
@@ -8,7 +8,11 @@ use frame_support::{
     pallet_prelude::*,
     storage::bounded_btree_set::BoundedBTreeSet,
     traits::{
-        tokens::{fungibles::Balanced, Precision},
+        tokens::{
+            fungibles::{Balanced, Inspect},
+            Precision,
+            Provenance::Minted,
+        },
         Currency, DefensiveResult, Get, LockableCurrency, OnUnbalanced, WithdrawReasons,
     },
     weights::Weight,
@@ -18,11 +22,12 @@ use frame_system::pallet_prelude::BlockNumberFor;
 use scale_info::prelude::*;
 
 use crate::OnVipMembershipHandler;
-use pallet_reputation::{ReputationPoint, ReputationRecord};
+use pallet_reputation::{ReputationPoint, ReputationRecord, ReputationTier};
 use pallet_session::historical;
 use sp_runtime::{
-    traits::{Convert, One, Saturating, Zero},
-    Perbill,
+    helpers_128bit::multiply_by_rational_with_rounding,
+    traits::{Bounded, CheckedDiv, Convert, One, SaturatedConversion, Saturating, Zero},
+    Perbill, Rounding,
 };
 use sp_staking::{
     offence::{OffenceDetails, OnOffenceHandler},
@@ -32,12 +37,14 @@ use sp_std::prelude::*;
 
 use crate::slashing::NegativeImbalanceOf;
 use crate::{
-    log, slashing, weights::WeightInfo, ActiveEraInfo, Cooperations, EnergyDebtOf, EnergyOf,
-    EnergyRateCalculator, Exposure, ExposureOf, Forcing, IndividualExposure, RewardDestination,
-    SessionInterface, StakeOf, StakingLedger, ValidatorPrefs,
+    log, slashing, weights::WeightInfo, ActiveEraInfo, Cooperations, ElectionScore,
+    ElectionSolutionError, EnergyDebtOf, EnergyOf, EnergyRateCalculator, Exposure, ExposureOf,
+    Forcing, IndividualExposure, IssuanceReportOf, NacRevocationPolicy, RewardBreakdown,
+    RewardDestination, SessionInterface, SessionKeysAge, StakeOf, StakingLedger, StakingOverview,
+    ValidatorPrefs, ValidatorStats,
 };
 
-use super::{pallet::*, STAKING_ID};
+use super::{pallet::*, MAX_DEFERRED_REWARD_PAYOUTS_PER_BLOCK, STAKING_ID};
 
 impl<T: Config> Pallet<T> {
     /// The total balance that can be slashed from a stash account as of right now.
@@ -46,6 +53,52 @@ impl<T: Config> Pallet<T> {
         Self::bonded(stash).and_then(Self::ledger).map(|l| l.active).unwrap_or_default()
     }
 
+    /// The maximum number of validators a cooperator can cooperate with right now.
+    pub fn max_cooperations() -> u32 {
+        Self::max_cooperations_override()
+            .map_or(T::MaxCooperations::get(), |max| max.min(T::MaxCooperations::get()))
+    }
+
+    /// The battery slot capacity the energy rate of the next era is calculated with.
+    pub fn battery_slot_capacity() -> EnergyOf<T> {
+        Self::battery_slot_capacity_override().unwrap_or_else(T::BatterySlotCapacity::get)
+    }
+
+    /// The additional energy reward ratio of validators with the `tier` reputation.
+    pub fn tier_energy_bonus(tier: &ReputationTier) -> Perbill {
+        TierEnergyBonus::<T>::get(tier)
+            .unwrap_or_else(|| T::ReputationTierEnergyRewardAdditionalPercentMapping::convert(tier))
+    }
+
+    /// The energy per reputation point the rewards of the next era are calculated with.
+    pub fn energy_per_reputation_point() -> EnergyOf<T> {
+        Self::energy_per_reputation_point_override()
+            .unwrap_or_else(T::EnergyPerReputationPoint::get)
+    }
+
+    /// Energy rewarded to `validator` for its reputation points at the rate of `era`.
+    ///
+    /// The points are the ones snapshotted when `era` ended. While `era` is still active, its
+    /// current points are used as an estimate.
+    pub(crate) fn reputation_reward(validator: &T::AccountId, era: EraIndex) -> EnergyOf<T> {
+        let rate = Self::eras_energy_per_reputation_point(era).unwrap_or_default();
+        let points = Self::eras_validator_reputation_points(era, validator)
+            .or_else(|| {
+                Self::active_era()
+                    .filter(|active_era| active_era.index == era)
+                    .and_then(|_| pallet_reputation::AccountReputation::<T>::get(validator))
+                    .map(|record| record.reputation.points())
+            })
+            .unwrap_or_default();
+        rate.saturating_mul(points.0.into())
+    }
+
+    /// The share of the active set that can be disabled before a new era is forced.
+    pub fn offending_validators_threshold() -> Perbill {
+        Self::offending_validators_threshold_override()
+            .unwrap_or_else(T::OffendingValidatorsThreshold::get)
+    }
+
     /// Slash account stake (reason: exit VIP).
     pub fn slash_vip_account(account: &T::AccountId, tax_percent: Perbill) -> DispatchResult {
         let mut ledger_info = Self::ledger(account).ok_or(Error::<T>::NotController)?;
@@ -106,6 +159,10 @@ impl<T: Config> Pallet<T> {
         if !stake_value.is_zero() {
             let (imbalance, _) = T::StakeCurrency::slash(account, stake_value);
             slashed_imbalance.subsume(imbalance);
+            Self::note_issuance(|report| {
+                report.stake_slashed.saturating_accrue(slashed_imbalance.peek());
+                report.treasury_inflow.saturating_accrue(slashed_imbalance.peek());
+            });
             T::Slash::on_unbalanced(slashed_imbalance);
 
             <Pallet<T>>::update_ledger(account, &ledger_info);
@@ -160,7 +217,8 @@ impl<T: Config> Pallet<T> {
         }
     }
 
-    /// Minimum stake to be a validator depends on NAC level.
+    /// Minimum stake to be a validator depends on NAC level: accounts above level 1 need the
+    /// trust bond, the rest and accounts without a NAC the common one.
     pub fn min_bond_for_validator(stash: &T::AccountId) -> StakeOf<T> {
         match T::ValidatorNacLevel::convert(stash) {
             Some(level) => {
@@ -186,6 +244,36 @@ impl<T: Config> Pallet<T> {
         }
     }
 
+    /// Schedule the chill of a validator whose NAC access was revoked, as the
+    /// `NacRevocationChillPolicy` says. Accounts that aren't validators are ignored.
+    pub fn note_nac_revoked(stash: &T::AccountId) {
+        if !Validators::<T>::contains_key(stash) {
+            return;
+        }
+
+        let chill = match Self::nac_revocation_policy() {
+            NacRevocationPolicy::Ignore => false,
+            NacRevocationPolicy::ChillExceptInvulnerables => !Self::invulnerables().contains(stash),
+            NacRevocationPolicy::ChillAll => true,
+        };
+        if chill {
+            PendingNacChills::<T>::insert(stash, ());
+            Self::deposit_event(Event::<T>::NacRevocationChillScheduled { stash: stash.clone() });
+        }
+    }
+
+    /// Chill the validators scheduled by `note_nac_revoked`, freeing their cooperators' stake,
+    /// unless their NAC access was restored in the meantime.
+    fn apply_nac_revocation_chills() {
+        for (stash, ()) in PendingNacChills::<T>::drain() {
+            if T::ValidatorNacLevel::convert(&stash).map_or(false, |level| level > 0) {
+                continue;
+            }
+            Self::do_remove_validator_from_cooperators_target(&stash);
+            Self::chill_stash(&stash);
+        }
+    }
+
     pub(crate) fn check_reputation_validator(acc: &T::AccountId) {
         if !Self::is_legit_for_validator(acc) {
             Self::chill_stash(acc);
@@ -257,7 +345,8 @@ impl<T: Config> Pallet<T> {
         validators_points: impl IntoIterator<Item = (T::AccountId, ReputationPoint)>,
     ) {
         for (validator, points) in validators_points.into_iter() {
-            pallet_reputation::Pallet::<T>::increase_creating(&validator, points);
+            let granted = pallet_reputation::Pallet::<T>::earn_creating(&validator, points);
+            Self::note_reputation_granted(granted);
         }
     }
 
@@ -321,6 +410,9 @@ impl<T: Config> Pallet<T> {
         <Ledger<T>>::insert(&controller, &ledger);
 
         let validator_total_payout = exposure.total.into() / era_energy_rate;
+        ErasUnclaimedRewards::<T>::mutate(era, |unclaimed| {
+            *unclaimed = unclaimed.saturating_sub(validator_total_payout)
+        });
 
         let validator_prefs = Self::eras_validator_prefs(era, &validator_stash);
         // Validator first gets a cut off the top.
@@ -332,6 +424,43 @@ impl<T: Config> Pallet<T> {
         let validator_exposure_part = Perbill::from_rational(exposure.own, exposure.total);
         let validator_staking_payout = validator_exposure_part * validator_leftover_payout;
 
+        // Cooperators with a slash cap leave part of their reward to the validator.
+        let cooperator_rewards = exposure
+            .others
+            .iter()
+            // Cooperations still warming up earn nothing, their share isn't minted.
+            .filter(|cooperator| {
+                !Self::is_cooperation_warming_up(&cooperator.who, &ledger.stash, era)
+            })
+            .map(|cooperator| {
+                let cooperator_exposure_part =
+                    Perbill::from_rational(cooperator.value, exposure.total);
+                let reward: EnergyOf<T> = cooperator_exposure_part * validator_leftover_payout;
+                let forfeited = ErasCooperatorMaxSlash::<T>::get(era, &cooperator.who)
+                    .map(|max_slash| {
+                        (T::SlashCapRewardPenalty::get() * max_slash.left_from_one()) * reward
+                    })
+                    .unwrap_or_else(Zero::zero);
+                (cooperator, reward - forfeited, forfeited)
+            })
+            .collect::<Vec<_>>();
+        let validator_forfeited_payout = cooperator_rewards
+            .iter()
+            .fold(EnergyOf::<T>::zero(), |total, (_, _, forfeited)| {
+                total.saturating_add(*forfeited)
+            });
+
+        let mut validator_payout = validator_staking_payout
+            + validator_commission_payout
+            + validator_forfeited_payout
+            + Self::reputation_reward(&ledger.stash, era);
+        if ErasBackingBoostedValidators::<T>::contains_key(era, &ledger.stash) {
+            validator_payout += T::BackingBoost::get() * validator_payout;
+        }
+        if ErasOutdatedValidators::<T>::contains_key(era, &ledger.stash) {
+            validator_payout -= T::OutdatedClientRewardPenalty::get() * validator_payout;
+        }
+
         Self::deposit_event(Event::<T>::PayoutStarted {
             era_index: era,
             validator_stash: ledger.stash.clone(),
@@ -339,9 +468,8 @@ impl<T: Config> Pallet<T> {
 
         let mut total_imbalance = EnergyDebtOf::<T>::zero(T::EnergyAssetId::get());
         // We can now make total validator payout:
-        if let Some(imbalance) =
-            Self::make_payout(&ledger.stash, validator_staking_payout + validator_commission_payout)
-        {
+        if let Some(imbalance) = Self::make_validator_payout(&ledger.stash, validator_payout) {
+            LastValidatorReward::<T>::insert(&ledger.stash, (era, imbalance.peek()));
             Self::deposit_event(Event::<T>::Rewarded {
                 stash: ledger.stash,
                 amount: imbalance.peek(),
@@ -356,11 +484,7 @@ impl<T: Config> Pallet<T> {
 
         // Lets now calculate how this is split to the cooperators.
         // Reward only the clipped exposures. Note this is not necessarily sorted.
-        for cooperator in exposure.others.iter() {
-            let cooperator_exposure_part = Perbill::from_rational(cooperator.value, exposure.total);
-
-            let cooperator_reward: EnergyOf<T> =
-                cooperator_exposure_part * validator_leftover_payout;
+        for (cooperator, cooperator_reward, _) in cooperator_rewards {
             // We can now make cooperator payout:
             if let Some(imbalance) = Self::make_payout(&cooperator.who, cooperator_reward) {
                 // Note: this logic does not count payouts for `RewardDestination::None`.
@@ -374,40 +498,586 @@ impl<T: Config> Pallet<T> {
             }
         }
 
+        Self::note_issuance(|report| {
+            report.energy_minted.saturating_accrue(total_imbalance.peek())
+        });
         T::Reward::on_unbalanced(total_imbalance);
         debug_assert!(cooperator_payout_count <= T::MaxCooperatorRewardedPerValidator::get());
         Ok(Some(T::ThisWeightInfo::payout_stakers_alive_staked(cooperator_payout_count)).into())
     }
 
+    /// Boost the reward of `validators` for the active era, as they backed a candidate of an
+    /// under-subscribed parachain.
+    ///
+    /// The boost is granted once per era, however many candidates a validator backed.
+    pub fn note_backing_boost(validators: impl IntoIterator<Item = T::AccountId>) {
+        if T::BackingBoost::get().is_zero() {
+            return;
+        }
+        let Some(era) = Self::active_era().map(|era| era.index) else { return };
+        for validator in validators {
+            ErasBackingBoostedValidators::<T>::insert(era, validator, ());
+        }
+    }
+
+    /// Whether `stash` is within `OnboardingGraceEras` eras of the first era it was elected for.
+    ///
+    /// Unresponsiveness of validators in their onboarding grace isn't reported, other offences
+    /// are slashed as usual.
+    pub fn in_onboarding_grace(stash: &T::AccountId) -> bool {
+        let Some(active_era) = Self::active_era().map(|e| e.index) else { return false };
+        ValidatorFirstActiveEra::<T>::get(stash).is_some_and(|first_era| {
+            (first_era..first_era.saturating_add(T::OnboardingGraceEras::get()))
+                .contains(&active_era)
+        })
+    }
+
+    /// Whether `stash` runs a client older than the minimum version enforced in `era`.
+    ///
+    /// Validators that never reported a version are considered outdated once a minimum is
+    /// enforced.
+    pub fn is_client_outdated(stash: &T::AccountId, era: EraIndex) -> bool {
+        MinimumClientVersion::<T>::get()
+            .filter(|requirement| requirement.enforced_from <= era)
+            .is_some_and(|requirement| {
+                ValidatorClientVersion::<T>::get(stash)
+                    .map_or(true, |version| version < requirement.minimum)
+            })
+    }
+
+    /// Sweep the unclaimed rewards of up to `MaxStaleRewardErasPerSweep` eras older than
+    /// `HistoryDepth` into `StaleRewardsDestination`.
+    pub(crate) fn do_sweep_stale_rewards() -> DispatchResult {
+        ensure!(!RewardsPaused::<T>::get(), Error::<T>::RewardsArePaused);
+        let Some(current_era) = CurrentEra::<T>::get() else { return Ok(()) };
+        // Same bound as the one checked by `payout_stakers`.
+        let oldest_claimable = current_era.saturating_sub(T::HistoryDepth::get());
+
+        let stale_eras = ErasUnclaimedRewards::<T>::iter_keys()
+            .filter(|era| *era < oldest_claimable)
+            .take(T::MaxStaleRewardErasPerSweep::get() as usize)
+            .collect::<Vec<_>>();
+        if stale_eras.is_empty() {
+            return Ok(());
+        }
+
+        let amount = stale_eras.iter().fold(EnergyOf::<T>::zero(), |total, era| {
+            total.saturating_add(ErasUnclaimedRewards::<T>::take(era))
+        });
+        if !amount.is_zero() {
+            let imbalance = pallet_assets::Pallet::<T>::deposit(
+                T::EnergyAssetId::get(),
+                &T::StaleRewardsDestination::get(),
+                amount,
+                Precision::Exact,
+            )?;
+            Self::note_issuance(|report| report.energy_minted.saturating_accrue(amount));
+            T::Reward::on_unbalanced(imbalance);
+        }
+
+        Self::deposit_event(Event::<T>::StaleRewardsSwept {
+            eras: stale_eras.len() as u32,
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Pay out `era` for every validator whose rewarded stakers of `era` include the cooperator
+    /// `stash` and which wasn't paid out yet.
+    pub(super) fn do_claim_all_cooperations(
+        stash: T::AccountId,
+        era: EraIndex,
+        limit: u32,
+    ) -> DispatchResultWithPostInfo {
+        let cooperations = Self::cooperators(&stash).ok_or(Error::<T>::NotCooperator)?;
+
+        let mut weight = T::DbWeight::get().reads(2);
+        let mut validators = 0;
+        for validator in cooperations.targets.keys().cloned() {
+            if validators >= limit {
+                break;
+            }
+            weight.saturating_accrue(T::DbWeight::get().reads(3));
+            let already_claimed = Self::bonded(&validator)
+                .and_then(Self::ledger)
+                .map_or(true, |ledger| ledger.claimed_rewards.contains(&era));
+            let rewarded = <ErasStakersClipped<T>>::get(era, &validator)
+                .others
+                .iter()
+                .any(|exposure| exposure.who == stash);
+            if already_claimed || !rewarded {
+                continue;
+            }
+
+            let post_info = Self::do_payout_stakers(validator, era).map_err(|e| {
+                e.error.with_weight(
+                    weight.saturating_add(e.post_info.actual_weight.unwrap_or_default()),
+                )
+            })?;
+            weight.saturating_accrue(post_info.actual_weight.unwrap_or_default());
+            validators += 1;
+        }
+
+        Self::deposit_event(Event::<T>::CooperationsClaimed { stash, era, validators });
+        Ok(Some(weight).into())
+    }
+
+    /// The issuance report of `era`.
+    ///
+    /// Returns `None` for eras that are not active yet or are older than `HistoryDepth`.
+    pub fn era_issuance_report(era: EraIndex) -> Option<IssuanceReportOf<T>> {
+        let active_era = Self::active_era()?.index;
+        if era > active_era || era < active_era.saturating_sub(T::HistoryDepth::get()) {
+            return None;
+        }
+        Some(ErasIssuance::<T>::get(era))
+    }
+
+    /// The share of the ended sessions of `era` in which `who` was a responsive validator.
+    ///
+    /// The active era only accounts the sessions ended so far. Returns `None` if `who` was not a
+    /// validator in any ended session of `era` or `era` is older than `HistoryDepth`.
+    pub fn validator_uptime(who: &T::AccountId, era: EraIndex) -> Option<Perbill> {
+        let active_era = Self::active_era()?.index;
+        if era < active_era.saturating_sub(T::HistoryDepth::get()) {
+            return None;
+        }
+        let record = ErasValidatorUptime::<T>::get(era, who).filter(|r| r.sessions > 0)?;
+        let online_sessions = record.sessions.saturating_sub(record.offline_sessions);
+        Some(Perbill::from_rational(online_sessions, record.sessions))
+    }
+
+    /// Stake, cooperators, commission, reputation tier, uptime and last reward of `validator`.
+    ///
+    /// Returns `None` if `validator` doesn't intend to validate. The stake backing it and its
+    /// uptime are taken from the active era and are zero or `None` while it isn't active.
+    pub fn validator_stats(
+        validator: &T::AccountId,
+    ) -> Option<ValidatorStats<StakeOf<T>, EnergyOf<T>>> {
+        let prefs = Validators::<T>::try_get(validator).ok()?;
+        let active_era = Self::active_era().map(|era| era.index);
+
+        Some(ValidatorStats {
+            own_stake: Self::bonded(validator)
+                .and_then(Self::ledger)
+                .map(|ledger| ledger.active)
+                .unwrap_or_default(),
+            total_stake: active_era
+                .map(|era| ErasStakersClipped::<T>::get(era, validator).total)
+                .unwrap_or_default(),
+            cooperators: Self::collaborations(validator)
+                .map_or(0, |cooperators| cooperators.len() as u32),
+            commission: prefs.commission,
+            reputation_tier: pallet_reputation::AccountReputation::<T>::get(validator)
+                .and_then(|record| record.reputation.tier()),
+            uptime: active_era.and_then(|era| Self::validator_uptime(validator, era)),
+            last_reward: Self::last_validator_reward(validator),
+        })
+    }
+
+    /// Total stake of the active era, total issuance of the staking currency, their ratio and the
+    /// number of active validators.
+    ///
+    /// The staked total is zero until the first era is active.
+    pub fn staking_overview() -> StakingOverview<StakeOf<T>> {
+        let total_staked = Self::active_era()
+            .map(|era| ErasTotalStake::<T>::get(era.index))
+            .unwrap_or_default();
+        let total_issuance = T::StakeCurrency::total_issuance();
+        StakingOverview {
+            total_staked,
+            total_issuance,
+            staking_ratio: Perbill::from_rational(total_staked, total_issuance),
+            active_validators: T::SessionInterface::validators().len() as u32,
+        }
+    }
+
+    /// How the energy reward of `validator` for the active era is computed from its exposure,
+    /// commission and current reputation tier.
+    ///
+    /// The slash cap forfeits of its cooperators and the outdated client penalty aren't
+    /// included. All amounts are zero if `validator` isn't active.
+    pub fn reward_breakdown(validator: &T::AccountId) -> RewardBreakdown<EnergyOf<T>> {
+        let reputation_bonus_percent = Self::calculate_energy_reward_multiplier(validator);
+        let inactive = RewardBreakdown { reputation_bonus_percent, ..Default::default() };
+        let Some(era) = Self::active_era().map(|era| era.index) else {
+            return inactive;
+        };
+        let exposure = ErasStakersClipped::<T>::get(era, validator);
+        let rate = Self::eras_energy_per_stake_cur(era).unwrap_or_default();
+        if exposure.total.is_zero() || rate.is_zero() {
+            return inactive;
+        }
+
+        let total_payout = exposure.total.into() / rate;
+        let commission = Self::eras_validator_prefs(era, validator).commission * total_payout;
+        let base = Perbill::from_rational(exposure.own, exposure.total)
+            * total_payout.saturating_sub(commission);
+        let reputation_reward = Self::reputation_reward(validator, era);
+        let before_bonus = base.saturating_add(commission).saturating_add(reputation_reward);
+        let reputation_bonus = reputation_bonus_percent.mul_floor(before_bonus);
+        RewardBreakdown {
+            base,
+            commission,
+            reputation_bonus_percent,
+            reputation_reward,
+            reputation_bonus,
+            total: before_bonus.saturating_add(reputation_bonus),
+        }
+    }
+
+    /// The own stake `validator` needs to earn `target` energy per era, given the current energy
+    /// rate, its commission, reputation tier and reputation reward, and the stake of its
+    /// cooperators in the active era.
+    ///
+    /// Returns the maximum stake if the target can't be reached, e.g. without an energy rate.
+    pub fn stake_for_target_reward(validator: &T::AccountId, target: EnergyOf<T>) -> StakeOf<T> {
+        let active_era = Self::active_era().map(|era| era.index);
+        let rate: u128 = active_era
+            .and_then(Self::eras_energy_per_stake_cur)
+            .or_else(Self::current_energy_per_stake_currency)
+            .unwrap_or_default()
+            .saturated_into();
+        if rate.is_zero() {
+            return StakeOf::<T>::max_value();
+        }
+
+        // The reward is `(1 + bonus) * ((own + commission * cooperated) / rate + reputation)`.
+        let bonus = Self::calculate_energy_reward_multiplier(validator);
+        let reputation_reward: u128 = active_era
+            .map(|era| Self::reputation_reward(validator, era))
+            .unwrap_or_default()
+            .saturated_into();
+        let reward_before_bonus = multiply_by_rational_with_rounding(
+            target.saturated_into(),
+            Perbill::ACCURACY.into(),
+            u128::from(Perbill::ACCURACY).saturating_add(bonus.deconstruct().into()),
+            Rounding::Up,
+        )
+        .map(|reward| reward.saturating_sub(reputation_reward));
+        let Some(stake) = reward_before_bonus.and_then(|reward| reward.checked_mul(rate)) else {
+            return StakeOf::<T>::max_value();
+        };
+
+        let cooperated = active_era
+            .map(|era| {
+                let exposure = ErasStakersClipped::<T>::get(era, validator);
+                exposure.total.saturating_sub(exposure.own)
+            })
+            .unwrap_or_default();
+        let commission = Self::validators(validator).commission * cooperated;
+        stake.saturated_into::<StakeOf<T>>().saturating_sub(commission)
+    }
+
+    /// Account that `stash` was unresponsive in the current session, e.g. as reported by
+    /// `pallet_im_online`.
+    pub fn note_unresponsive(stash: &T::AccountId) {
+        if let Some(active_era) = Self::active_era() {
+            ErasValidatorUptime::<T>::mutate(active_era.index, stash, |record| {
+                let record = record.get_or_insert_with(Default::default);
+                record.offline_sessions.saturating_inc();
+            });
+        }
+    }
+
+    /// Account an ended session for every validator of the session.
+    fn note_session_ended(session_index: SessionIndex) {
+        let validators = T::SessionInterface::validators();
+        if let Some(active_era) = Self::active_era() {
+            for validator in &validators {
+                ErasValidatorUptime::<T>::mutate(active_era.index, validator, |record| {
+                    record.get_or_insert_with(Default::default).sessions.saturating_inc();
+                });
+            }
+        }
+        for validator in validators {
+            Self::note_session_keys_age(validator, session_index);
+        }
+    }
+
+    /// Track how long `validator` has been running its current session keys and emit a
+    /// reminder once they reach `SessionKeysRotationAge` sessions.
+    fn note_session_keys_age(validator: T::AccountId, session_index: SessionIndex) {
+        let Some(keys_hash) = T::SessionInterface::session_keys_hash(&validator) else {
+            ValidatorSessionKeysAge::<T>::remove(&validator);
+            return;
+        };
+        let rotation_age = T::SessionKeysRotationAge::get();
+        ValidatorSessionKeysAge::<T>::mutate(&validator, |record| {
+            let record = match record {
+                Some(record) if record.keys_hash == keys_hash => record,
+                _ => record.insert(SessionKeysAge {
+                    keys_hash,
+                    since: session_index,
+                    reminded: false,
+                }),
+            };
+            let age = session_index.saturating_sub(record.since);
+            if rotation_age > 0 && !record.reminded && age >= rotation_age {
+                record.reminded = true;
+                Self::deposit_event(Event::<T>::SessionKeysRotationDue {
+                    stash: validator.clone(),
+                    age,
+                });
+            }
+        });
+    }
+
+    /// Update the issuance report of the active era.
+    pub(crate) fn note_issuance(f: impl FnOnce(&mut IssuanceReportOf<T>)) {
+        if let Some(active_era) = Self::active_era() {
+            ErasIssuance::<T>::mutate(active_era.index, f);
+        }
+    }
+
+    /// Account energy burned in the active era, e.g. by transaction fees.
+    pub fn note_energy_burned(amount: EnergyOf<T>) {
+        if let Some(active_era) = Self::active_era() {
+            ErasEnergyBurned::<T>::mutate(active_era.index, |burned| {
+                burned.saturating_accrue(amount)
+            });
+        }
+    }
+
+    /// Energy minted minus energy burned in the last ended era, in the smallest unit of the
+    /// energy asset. A negative value means the energy supply shrank.
+    ///
+    /// Until the first era has ended, the figures of the active era so far are used.
+    pub fn net_energy_inflation() -> i128 {
+        let Some(active_era) = Self::active_era() else {
+            return 0;
+        };
+        let era = active_era.index.checked_sub(1).unwrap_or(active_era.index);
+        let signed = |amount: EnergyOf<T>| {
+            i128::try_from(amount.saturated_into::<u128>()).unwrap_or(i128::MAX)
+        };
+        signed(ErasIssuance::<T>::get(era).energy_minted)
+            .saturating_sub(signed(ErasEnergyBurned::<T>::get(era)))
+    }
+
+    /// Account reputation granted in the active era.
+    pub(crate) fn note_reputation_granted(points: ReputationPoint) {
+        Self::note_issuance(|report| {
+            report.reputation_granted = report.reputation_granted.saturating_add(*points).into()
+        });
+    }
+
+    /// Ensure that energy rewards can be paid into `payee`.
+    ///
+    /// `RewardDestination::Account` must be able to receive the energy asset, e.g. it must not
+    /// be blocked for it, and `RewardDestination::Staked` can't be chosen.
+    pub(crate) fn ensure_valid_payee(payee: &RewardDestination<T::AccountId>) -> DispatchResult {
+        match payee {
+            RewardDestination::Account(account) => {
+                let asset_id = T::EnergyAssetId::get();
+                let min_balance = pallet_assets::Pallet::<T>::minimum_balance(asset_id.clone());
+                pallet_assets::Pallet::<T>::can_deposit(asset_id, account, min_balance, Minted)
+                    .into_result()
+                    .map_err(|_| Error::<T>::InvalidPayee)?;
+            },
+            RewardDestination::Staked => return Err(Error::<T>::RewardsCannotBeStaked.into()),
+            _ => {},
+        }
+        Ok(())
+    }
+
     /// Actually make a payment to a staker. This uses the currency's reward function
     /// to pay the right payee for the given staker account.
     fn make_payout(stash: &T::AccountId, amount: EnergyOf<T>) -> Option<EnergyDebtOf<T>> {
-        let dest = Self::payee(stash);
-        let asset_id = T::EnergyAssetId::get();
-        let amount = Self::calculate_energy_reward_multiplier(stash)
+        let amount = Self::with_reward_multiplier(stash, amount);
+        let account = Self::payout_account(stash)?;
+        Self::mint_reward(&account, amount)
+    }
+
+    /// Actually make a payment to the validator `stash`, like [`Self::make_payout`].
+    ///
+    /// When the reward goes to another account than the controller, the part of it the
+    /// controller lacks to pay for `ReservedOperationalCalls` operational calls is paid to the
+    /// controller instead. Nothing is paid with [`RewardDestination::None`].
+    fn make_validator_payout(stash: &T::AccountId, amount: EnergyOf<T>) -> Option<EnergyDebtOf<T>> {
+        let amount = Self::with_reward_multiplier(stash, amount);
+        let account = Self::payout_account(stash)?;
+        let controller = Self::bonded(stash)?;
+        if account == controller {
+            return Self::mint_reward(&account, amount);
+        }
+
+        let controller_energy =
+            pallet_assets::Pallet::<T>::balance(T::EnergyAssetId::get(), &controller);
+        let top_up =
+            Self::operational_energy_reserve().saturating_sub(controller_energy).min(amount);
+        let mut total_imbalance = EnergyDebtOf::<T>::zero(T::EnergyAssetId::get());
+        let mut rest = amount;
+        if !top_up.is_zero() {
+            if let Some(imbalance) = Self::mint_reward(&controller, top_up) {
+                total_imbalance.subsume(imbalance).unwrap_or_default();
+                rest = rest.saturating_sub(top_up);
+                Self::deposit_event(Event::<T>::EnergyReserveToppedUp {
+                    controller,
+                    amount: top_up,
+                });
+            }
+        }
+        if !rest.is_zero() {
+            let imbalance = Self::mint_reward(&account, rest)?;
+            total_imbalance.subsume(imbalance).unwrap_or_default();
+        }
+        Some(total_imbalance)
+    }
+
+    /// `amount` raised by the energy reward multiplier of `stash`'s reputation tier.
+    fn with_reward_multiplier(stash: &T::AccountId, amount: EnergyOf<T>) -> EnergyOf<T> {
+        Self::calculate_energy_reward_multiplier(stash)
             .mul_floor(amount)
-            .saturating_add(amount);
+            .saturating_add(amount)
+    }
 
-        match dest {
-            RewardDestination::Controller => Self::bonded(stash).and_then(|controller| {
-                pallet_assets::Pallet::<T>::deposit(asset_id, &controller, amount, Precision::Exact)
-                    .ok()
-            }),
+    /// The account the rewards of `stash` are paid to, `None` if it doesn't want any.
+    fn payout_account(stash: &T::AccountId) -> Option<T::AccountId> {
+        match Self::payee(stash) {
+            RewardDestination::Controller => Self::bonded(stash),
+            RewardDestination::Stash | RewardDestination::Staked => Some(stash.clone()),
+            RewardDestination::Account(dest_account) => Some(dest_account),
+            RewardDestination::None => None,
+        }
+    }
 
-            RewardDestination::Stash => {
-                pallet_assets::Pallet::<T>::deposit(asset_id, stash, amount, Precision::Exact).ok()
-            },
+    /// The energy the controller of a validator is kept holding to pay for
+    /// `ReservedOperationalCalls` operational calls.
+    pub fn operational_energy_reserve() -> EnergyOf<T> {
+        T::OperationalCallFee::get().saturating_mul(T::ReservedOperationalCalls::get().into())
+    }
+
+    /// Mint `amount` of energy into `account`, deferring the part that exceeds what is left of
+    /// `MaxEnergyMintPerBlock` in this block.
+    ///
+    /// The returned imbalance only holds the minted part. Nothing is minted while rewards are
+    /// paused, `amount` is then deferred or forfeited according to `DeferRewardsWhilePaused`.
+    fn mint_reward(account: &T::AccountId, amount: EnergyOf<T>) -> Option<EnergyDebtOf<T>> {
+        if RewardsPaused::<T>::get() {
+            if T::DeferRewardsWhilePaused::get() {
+                DeferredEnergyRewards::<T>::mutate(account, |total| {
+                    total.saturating_accrue(amount)
+                });
+                Self::deposit_event(Event::<T>::EnergyRewardDeferred {
+                    account: account.clone(),
+                    amount,
+                });
+            } else {
+                Self::deposit_event(Event::<T>::EnergyRewardForfeited {
+                    account: account.clone(),
+                    amount,
+                });
+            }
+            return None;
+        }
 
-            RewardDestination::Account(dest_account) => pallet_assets::Pallet::<T>::deposit(
+        let asset_id = T::EnergyAssetId::get();
+        let Some(max_mint) = T::MaxEnergyMintPerBlock::get() else {
+            return pallet_assets::Pallet::<T>::deposit(
                 asset_id,
-                &dest_account,
+                account,
                 amount,
                 Precision::Exact,
             )
-            .ok(),
+            .ok();
+        };
 
-            RewardDestination::None => None,
+        let minted = amount.min(max_mint.saturating_sub(BlockEnergyMinted::<T>::get()));
+        let imbalance = match pallet_assets::Pallet::<T>::deposit(
+            asset_id.clone(),
+            account,
+            minted,
+            Precision::Exact,
+        ) {
+            Ok(imbalance) => imbalance,
+            // e.g. the minted part alone is below the minimum balance, defer all of it
+            Err(_) if minted < amount => EnergyDebtOf::<T>::zero(asset_id),
+            Err(_) => return None,
+        };
+        BlockEnergyMinted::<T>::mutate(|total| total.saturating_accrue(imbalance.peek()));
+
+        let deferred = amount.saturating_sub(imbalance.peek());
+        if !deferred.is_zero() {
+            DeferredEnergyRewards::<T>::mutate(account, |total| total.saturating_accrue(deferred));
+            Self::deposit_event(Event::<T>::EnergyRewardDeferred {
+                account: account.clone(),
+                amount: deferred,
+            });
         }
+        Some(imbalance)
+    }
+
+    /// Start a new block's `MaxEnergyMintPerBlock` allowance and spend it on deferred rewards.
+    ///
+    /// Nothing is minted while rewards are paused. Without `MaxEnergyMintPerBlock` only the
+    /// rewards deferred during a pause are left, they are minted without limit.
+    ///
+    /// Returns the weight consumed.
+    pub(crate) fn mint_deferred_rewards() -> Weight {
+        BlockEnergyMinted::<T>::kill();
+        if RewardsPaused::<T>::get() {
+            return T::DbWeight::get().reads_writes(1, 1);
+        }
+        let max_mint = T::MaxEnergyMintPerBlock::get().unwrap_or_else(EnergyOf::<T>::max_value);
+
+        let asset_id = T::EnergyAssetId::get();
+        let mut allowance = max_mint;
+        let mut total_imbalance = EnergyDebtOf::<T>::zero(asset_id.clone());
+        let mut accounts: u64 = 0;
+        let deferred_rewards = DeferredEnergyRewards::<T>::iter()
+            .take(MAX_DEFERRED_REWARD_PAYOUTS_PER_BLOCK as usize)
+            .collect::<Vec<_>>();
+        if deferred_rewards.is_empty() {
+            return T::DbWeight::get().reads_writes(2, 1);
+        }
+
+        for (account, amount) in deferred_rewards {
+            if allowance.is_zero() {
+                break;
+            }
+            accounts += 1;
+
+            let minted = amount.min(allowance);
+            match pallet_assets::Pallet::<T>::deposit(
+                asset_id.clone(),
+                &account,
+                minted,
+                Precision::Exact,
+            ) {
+                Ok(imbalance) => {
+                    allowance -= minted;
+                    if minted == amount {
+                        DeferredEnergyRewards::<T>::remove(&account);
+                    } else {
+                        DeferredEnergyRewards::<T>::insert(&account, amount - minted);
+                    }
+                    Self::deposit_event(Event::<T>::DeferredEnergyRewardPaid {
+                        account,
+                        amount: minted,
+                    });
+                    total_imbalance.subsume(imbalance).unwrap_or_default();
+                },
+                // retry in a later block, without holding up the rewards of the other accounts
+                Err(_) if minted < amount => continue,
+                // the account can't receive energy, like in `make_payout` the reward is dropped
+                Err(_) => {
+                    DeferredEnergyRewards::<T>::remove(&account);
+                    Self::deposit_event(Event::<T>::DeferredEnergyRewardDropped {
+                        account,
+                        amount,
+                    });
+                },
+            }
+        }
+
+        BlockEnergyMinted::<T>::put(max_mint - allowance);
+        Self::note_issuance(|report| {
+            report.energy_minted.saturating_accrue(total_imbalance.peek())
+        });
+        T::Reward::on_unbalanced(total_imbalance);
+
+        T::DbWeight::get().reads_writes(2 + 3 * accounts, 3 + 3 * accounts)
     }
 
     /// Update the ledger for a controller.
@@ -430,10 +1100,6 @@ impl<T: Config> Pallet<T> {
 
     /// Plan a new session potentially trigger a new era.
     fn new_session(session_index: SessionIndex) -> Option<Vec<T::AccountId>> {
-        // In any case we update reputation per each session.
-        // TODO: replace with an associated type in Config
-        pallet_reputation::Pallet::<T>::update_points_for_time();
-
         if let Some(current_era) = Self::current_era() {
             // Initial era has been set.
             let current_era_start_session_index = Self::eras_start_session_index(current_era)
@@ -510,6 +1176,8 @@ impl<T: Config> Pallet<T> {
 
     /// End a session potentially ending an era.
     fn end_session(session_index: SessionIndex) {
+        Self::note_session_ended(session_index);
+
         if let Some(active_era) = Self::active_era() {
             if let Some(next_active_era_start_session_index) =
                 Self::eras_start_session_index(active_era.index + 1)
@@ -540,6 +1208,10 @@ impl<T: Config> Pallet<T> {
 
         Self::store_energy_rate(active_era);
 
+        if let Some(threshold) = PendingOffendingValidatorsThreshold::<T>::take() {
+            OffendingValidatorsThresholdOverride::<T>::put(threshold);
+        }
+
         let bonding_duration = T::BondingDuration::get();
 
         BondedEras::<T>::mutate(|bonded| {
@@ -571,23 +1243,52 @@ impl<T: Config> Pallet<T> {
         let staked = Self::eras_total_stake(era_index);
         let issuance = pallet_assets::Pallet::<T>::total_supply(T::EnergyAssetId::get());
         let core_nodes_num = Self::core_nodes_count();
-        let battery_slot_cap = T::BatterySlotCapacity::get();
+        let battery_slot_cap = Self::battery_slot_capacity();
 
-        let energy_per_stake_currency = T::EnergyPerStakeCurrency::calculate_energy_rate(
+        let mut energy_per_stake_currency = T::EnergyPerStakeCurrency::calculate_energy_rate(
             staked,
             issuance,
             core_nodes_num,
             battery_slot_cap,
         );
 
+        // A zero rate pays no rewards for the whole era. Unless governance paused them, that's
+        // a broken calculator rather than a decision.
+        let floor = T::MinEnergyPerStakeCurrency::get();
+        if energy_per_stake_currency.is_zero() && !floor.is_zero() && !RewardsPaused::<T>::get() {
+            log!(warn, "energy rate of era {} is zero, falling back to {:?}", era_index, floor);
+            energy_per_stake_currency = floor;
+            Self::deposit_event(Event::<T>::EnergyRateFloorApplied {
+                era_index,
+                energy_rate: floor,
+            });
+        }
+
         <ErasEnergyPerStakeCurrency<T>>::insert(era_index, energy_per_stake_currency);
+        <ErasEnergyPerReputationPoint<T>>::insert(era_index, Self::energy_per_reputation_point());
+        // Every validator is paid `exposure.total / rate`, and the exposures add up to the total
+        // stake.
+        let staked: EnergyOf<T> = staked.into();
+        let rewards = staked.checked_div(&energy_per_stake_currency).unwrap_or_default();
+        ErasUnclaimedRewards::<T>::insert(era_index, rewards);
         Self::deposit_event(Event::<T>::EraEnergyPerStakeCurrencySet {
             era_index,
             energy_rate: energy_per_stake_currency,
         });
     }
 
-    fn end_era(_active_era: ActiveEraInfo, _session_index: SessionIndex) {
+    fn end_era(active_era: ActiveEraInfo, _session_index: SessionIndex) {
+        // Snapshot the reputation the era's validators are rewarded for.
+        for validator in <ErasStakersClipped<T>>::iter_key_prefix(active_era.index) {
+            if let Some(record) = pallet_reputation::AccountReputation::<T>::get(&validator) {
+                <ErasValidatorReputationPoints<T>>::insert(
+                    active_era.index,
+                    &validator,
+                    record.reputation.points(),
+                );
+            }
+        }
+
         // Clear disabled validators.
         <DisabledValidators<T>>::kill();
     }
@@ -630,7 +1331,15 @@ impl<T: Config> Pallet<T> {
     pub(crate) fn try_trigger_new_era(
         start_session_index: SessionIndex,
     ) -> Option<Vec<T::AccountId>> {
-        let exposures = Self::ellect_and_collect_exposures();
+        Self::apply_nac_revocation_chills();
+
+        // The forced set is only consumed once its era is planned, so it's retried otherwise.
+        let forced_validators = ForcedValidators::<T>::get();
+        let is_forced = forced_validators.is_some();
+        let exposures = match forced_validators {
+            Some(validators) => Self::collect_forced_exposures(validators),
+            None => Self::ellect_and_collect_exposures(),
+        };
         if (exposures.len() as u32) < Self::minimum_validator_count().max(1) {
             // Session will panic if we ever return an empty validator set, thus max(1) ^^.
             match CurrentEra::<T>::get() {
@@ -657,6 +1366,9 @@ impl<T: Config> Pallet<T> {
             return None;
         }
 
+        if is_forced {
+            ForcedValidators::<T>::kill();
+        }
         Self::deposit_event(Event::StakersElected);
         Some(Self::trigger_new_era(start_session_index, exposures))
     }
@@ -675,17 +1387,31 @@ impl<T: Config> Pallet<T> {
         if exposures.len() > max_validators {
             let invulnerables = Self::invulnerables();
 
+            let committed: Vec<_> = exposures
+                .iter()
+                .filter(|(stash, _)| Self::is_committed_for(stash, new_planned_era))
+                .map(|(stash, _)| stash.clone())
+                .collect();
+
             exposures.select_nth_unstable_by(max_validators, |a, b| {
                 // If `a` < `b`, then validator `a` will be elected
-                match (invulnerables.contains(&a.0), invulnerables.contains(&b.0)) {
-                    (true, false) => Ordering::Less,
-                    (false, true) => Ordering::Greater,
-                    _ => a.1.total.cmp(&b.1.total).reverse(),
-                }
+                invulnerables
+                    .contains(&b.0)
+                    .cmp(&invulnerables.contains(&a.0))
+                    .then_with(|| a.1.total.cmp(&b.1.total).reverse())
+                    // the intent to validate only breaks ties in stake
+                    .then_with(|| committed.contains(&b.0).cmp(&committed.contains(&a.0)))
             });
         }
+        // The intents of the elected era are spent, at most one per validator.
+        let _ = NextEraIntent::<T>::clear_prefix(new_planned_era, u32::MAX, None);
         let elected_stashes: Vec<_> =
             exposures.iter().take(max_validators).map(|(x, _)| x.clone()).collect();
+        for stash in &elected_stashes {
+            if !ValidatorFirstActiveEra::<T>::contains_key(stash) {
+                ValidatorFirstActiveEra::<T>::insert(stash, new_planned_era);
+            }
+        }
 
         // Populate stakers, exposures, and the snapshot of validator prefs.
         let mut total_stake: StakeOf<T> = Zero::zero();
@@ -693,6 +1419,16 @@ impl<T: Config> Pallet<T> {
             total_stake = total_stake.saturating_add(exposure.total);
             <ErasStakers<T>>::insert(new_planned_era, &stash, &exposure);
 
+            for cooperator in &exposure.others {
+                if let Some(max_slash) = CooperatorMaxSlash::<T>::get(&cooperator.who) {
+                    ErasCooperatorMaxSlash::<T>::insert(
+                        new_planned_era,
+                        &cooperator.who,
+                        max_slash,
+                    );
+                }
+            }
+
             let mut exposure_clipped = exposure;
             let clipped_max_len = T::MaxCooperatorRewardedPerValidator::get() as usize;
             if exposure_clipped.others.len() > clipped_max_len {
@@ -701,6 +1437,10 @@ impl<T: Config> Pallet<T> {
             }
             <ErasStakersClipped<T>>::insert(new_planned_era, &stash, exposure_clipped);
 
+            if Self::is_client_outdated(&stash, new_planned_era) {
+                ErasOutdatedValidators::<T>::insert(new_planned_era, &stash, ());
+            }
+
             let pref = Self::validators(&stash);
             <ErasValidatorPrefs<T>>::insert(new_planned_era, stash, pref);
         });
@@ -720,6 +1460,54 @@ impl<T: Config> Pallet<T> {
         elected_stashes
     }
 
+    /// Score `validators` proposed as the validator set of the next era, without electing them.
+    ///
+    /// Every validator must be a distinct candidate with enough reputation to validate, and the
+    /// set must hold between `MinimumValidatorCount` and `ValidatorCount` validators. Each is
+    /// backed by its own stake and its cooperators, like in the election.
+    pub fn check_election_solution(
+        validators: Vec<T::AccountId>,
+    ) -> Result<ElectionScore<StakeOf<T>>, ElectionSolutionError> {
+        let count = validators.len() as u32;
+        ensure!(count <= Self::validator_count(), ElectionSolutionError::TooManyValidators);
+        ensure!(
+            count >= Self::minimum_validator_count().max(1),
+            ElectionSolutionError::TooFewValidators
+        );
+
+        let mut score = ElectionScore::<StakeOf<T>>::default();
+        for (index, validator) in validators.iter().enumerate() {
+            let index = index as u32;
+            ensure!(
+                !validators[..index as usize].contains(validator),
+                ElectionSolutionError::DuplicateValidator(index)
+            );
+            let (prefs, own) = Validators::<T>::try_get(validator)
+                .ok()
+                .filter(|_| Self::is_legit_for_validator(validator))
+                .zip(Self::bonded(validator).and_then(Self::ledger))
+                .map(|(prefs, ledger)| (prefs, ledger.active))
+                .ok_or(ElectionSolutionError::NotCandidate(index))?;
+
+            let stake = Self::collect_exposure(validator, &prefs, own).total;
+            score.minimal_stake = if index == 0 { stake } else { score.minimal_stake.min(stake) };
+            score.sum_stake = score.sum_stake.saturating_add(stake);
+            score.sum_stake_squared =
+                score.sum_stake_squared.saturating_add(stake.saturating_mul(stake));
+        }
+        Ok(score)
+    }
+
+    /// The era the next election plans.
+    pub(crate) fn next_elected_era() -> EraIndex {
+        CurrentEra::<T>::get().map_or(0, |era| era.saturating_add(1))
+    }
+
+    /// Whether `stash` committed to validate in `era`, see `commit_next_era_intent`.
+    fn is_committed_for(stash: &T::AccountId, era: EraIndex) -> bool {
+        NextEraIntent::<T>::get(era, stash) == Some(true)
+    }
+
     /// Ellect validators and collect them into a [`Exposure`].
     #[allow(clippy::type_complexity)]
     fn ellect_and_collect_exposures() -> Vec<(T::AccountId, Exposure<T::AccountId, StakeOf<T>>)> {
@@ -730,46 +1518,113 @@ impl<T: Config> Pallet<T> {
                 let controller = Self::bonded(&validator).unwrap();
                 // Build `struct exposure` from `support`.
                 let own: StakeOf<T> = Self::ledger(&controller).unwrap().active;
-                let others = match Collaborations::<T>::get(&validator) {
-                    Some(coops) => coops
-                        .iter()
-                        .cloned()
-                        .filter_map(|who| {
-                            match Self::cooperators(&who)
-                                .and_then(|collab| collab.targets.get(&validator).cloned())
-                            {
-                                Some(value) => {
-                                    let record = pallet_reputation::Pallet::<T>::reputation(&who)
-                                        .unwrap_or_else(ReputationRecord::with_now::<T>);
-                                    if record.reputation >= prefs.min_coop_reputation {
-                                        Some(IndividualExposure { who, value })
-                                    } else {
-                                        None
-                                    }
-                                },
-                                None => None,
-                            }
-                        })
-                        .collect(),
-                    None => Vec::new(),
-                };
-                let total = own
-                    + others
-                        .iter()
-                        .fold(Zero::zero(), |acc: StakeOf<T>, x| acc.saturating_add(x.value));
-
-                let exposure = Exposure { own, others, total };
+                let exposure = Self::collect_exposure(&validator, &prefs, own);
 
                 (validator, exposure)
             })
             .collect()
     }
 
+    /// Collect the [`Exposure`]s of the validator set forced by governance, skipping the election.
+    ///
+    /// Validators that got unbonded or lost their session keys since are left out.
+    #[allow(clippy::type_complexity)]
+    fn collect_forced_exposures(
+        validators: Vec<T::AccountId>,
+    ) -> Vec<(T::AccountId, Exposure<T::AccountId, StakeOf<T>>)> {
+        log!(warn, "bypassing the election with a validator set forced by governance");
+        validators
+            .into_iter()
+            .filter(|validator| T::SessionInterface::session_keys_hash(validator).is_some())
+            .filter_map(|validator| {
+                let own = Self::bonded(&validator).and_then(Self::ledger)?.active;
+                let exposure =
+                    Self::collect_exposure(&validator, &Self::validators(&validator), own);
+                Some((validator, exposure))
+            })
+            .collect()
+    }
+
+    /// Build the [`Exposure`] of `validator` backed by `own` stake and its cooperators.
+    fn collect_exposure(
+        validator: &T::AccountId,
+        prefs: &ValidatorPrefs,
+        own: StakeOf<T>,
+    ) -> Exposure<T::AccountId, StakeOf<T>> {
+        let others = match Collaborations::<T>::get(validator) {
+            Some(coops) => coops
+                .iter()
+                .cloned()
+                .filter_map(|who| {
+                    match Self::cooperators(&who)
+                        .and_then(|collab| collab.targets.get(validator).cloned())
+                    {
+                        Some(value) => {
+                            let record = pallet_reputation::Pallet::<T>::reputation(&who)
+                                .unwrap_or_else(ReputationRecord::with_now::<T>);
+                            if record.reputation >= prefs.min_coop_reputation {
+                                Some(IndividualExposure { who, value })
+                            } else {
+                                None
+                            }
+                        },
+                        None => None,
+                    }
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+        let total = own
+            + others
+                .iter()
+                .fold(Zero::zero(), |acc: StakeOf<T>, x| acc.saturating_add(x.value));
+
+        Exposure { own, others, total }
+    }
+
     // filter out illegit validators
     fn ellect_validators() {
+        // validators that committed not to validate in the elected era step down
+        let era = Self::next_elected_era();
+        let declined: Vec<_> = NextEraIntent::<T>::iter_prefix(era)
+            .filter(|(stash, validate)| !validate && Validators::<T>::contains_key(stash))
+            .map(|(stash, _)| stash)
+            .collect();
+        for stash in declined {
+            Self::do_remove_validator_from_cooperators_target(&stash);
+            Self::chill_stash(&stash);
+        }
+
         // filter out by min reputation for validator
-        let (legit, should_chill): (Vec<_>, Vec<_>) =
+        let (mut legit, mut should_chill): (Vec<_>, Vec<_>) =
             Validators::<T>::iter().partition(|(acc, _)| Self::is_legit_for_validator(acc));
+
+        // Keep the validator set from collapsing: if too few validators have enough reputation,
+        // retain the most reputable of the others to reach the minimum validator count.
+        let minimum = Self::minimum_validator_count() as usize;
+        let missing = minimum.saturating_sub(legit.len()).min(should_chill.len());
+        if missing > 0 {
+            should_chill.sort_by_cached_key(|(acc, _)| {
+                sp_std::cmp::Reverse(
+                    pallet_reputation::AccountReputation::<T>::get(acc)
+                        .map(|record| *record.reputation)
+                        .unwrap_or_default(),
+                )
+            });
+            legit.extend(should_chill.drain(..missing));
+            let retained = missing as u32;
+            log!(
+                warn,
+                "retaining {} validators without enough reputation to keep the minimum validator \
+                count of {}",
+                retained,
+                minimum,
+            );
+            Self::deposit_event(Event::<T>::MinimumValidatorCountEnforced {
+                retained,
+                minimum: minimum as u32,
+            });
+        }
         // if validators don't have enough reputation to be validators, we force chill them
         // in this keys they would need to call Self::validate again, and their reputation will be
         // checked. So at the very list they will be blocked until the next era
@@ -807,6 +1662,11 @@ impl<T: Config> Pallet<T> {
         <Ledger<T>>::remove(&controller);
 
         <Payee<T>>::remove(stash);
+        CooperatorMaxSlash::<T>::remove(stash);
+        ValidatorSessionKeysAge::<T>::remove(stash);
+        ValidatorClientVersion::<T>::remove(stash);
+        LastValidatorReward::<T>::remove(stash);
+        NextEraIntent::<T>::remove(Self::next_elected_era(), stash);
         Self::do_remove_validator(stash);
         Self::do_remove_cooperator(stash);
 
@@ -823,8 +1683,23 @@ impl<T: Config> Pallet<T> {
         <ErasStakersClipped<T>>::remove_prefix(era_index, None);
         #[allow(deprecated)]
         <ErasValidatorPrefs<T>>::remove_prefix(era_index, None);
+        #[allow(deprecated)]
+        <ErasCooperatorMaxSlash<T>>::remove_prefix(era_index, None);
+        #[allow(deprecated)]
+        <ErasOutdatedValidators<T>>::remove_prefix(era_index, None);
+        #[allow(deprecated)]
+        <ErasBackingBoostedValidators<T>>::remove_prefix(era_index, None);
+        #[allow(deprecated)]
+        <ErasValidatorUptime<T>>::remove_prefix(era_index, None);
+        #[allow(deprecated)]
+        <ErasValidatorReputationPoints<T>>::remove_prefix(era_index, None);
+        #[allow(deprecated)]
+        <NextEraIntent<T>>::remove_prefix(era_index, None);
         <ErasEnergyPerStakeCurrency<T>>::remove(era_index);
+        <ErasEnergyPerReputationPoint<T>>::remove(era_index);
         <ErasTotalStake<T>>::remove(era_index);
+        <ErasIssuance<T>>::remove(era_index);
+        <ErasEnergyBurned<T>>::remove(era_index);
         ErasStartSessionIndex::<T>::remove(era_index);
     }
 
@@ -852,6 +1727,14 @@ impl<T: Config> Pallet<T> {
         Self::deposit_event(Event::<T>::ForceEra { mode });
     }
 
+    /// Ensure that a new era is triggered at the next session, whatever the `ForceEra` mode is.
+    pub(crate) fn ensure_new_era() {
+        match ForceEra::<T>::get() {
+            Forcing::ForceAlways | Forcing::ForceNew => (),
+            _ => Self::set_force_era(Forcing::ForceNew),
+        }
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     pub fn add_era_stakers(
         current_era: EraIndex,
@@ -874,6 +1757,7 @@ impl<T: Config> Pallet<T> {
     /// to `Cooperators` or `Collaborations` outside of this function is almost certainly
     /// wrong.
     pub fn do_add_cooperator(who: &T::AccountId, cooperations: Cooperations<T>) -> DispatchResult {
+        Self::note_cooperation_activation(who, &cooperations);
         for target in &cooperations.targets {
             if !Collaborations::<T>::contains_key(target.0) {
                 let mut set = BoundedBTreeSet::new();
@@ -894,6 +1778,37 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// Record the activation era of the cooperations of `who` that are new, and forget the ones
+    /// that were withdrawn. Genesis cooperations are not recorded.
+    fn note_cooperation_activation(who: &T::AccountId, cooperations: &Cooperations<T>) {
+        let old = Cooperators::<T>::get(who).map(|c| c.targets.into_inner()).unwrap_or_default();
+        for target in old.keys().filter(|target| !cooperations.targets.contains_key(*target)) {
+            CooperationActivation::<T>::remove(who, target);
+        }
+
+        let Some(current_era) = Self::current_era() else {
+            return;
+        };
+        if T::CooperationWarmupEras::get().is_zero() {
+            return;
+        }
+        for target in cooperations.targets.keys().filter(|target| !old.contains_key(*target)) {
+            CooperationActivation::<T>::insert(who, target, current_era.saturating_add(1));
+        }
+    }
+
+    /// Whether the cooperation of `cooperator` with `validator` is still warming up in `era`,
+    /// i.e. earns no energy reward.
+    pub fn is_cooperation_warming_up(
+        cooperator: &T::AccountId,
+        validator: &T::AccountId,
+        era: EraIndex,
+    ) -> bool {
+        CooperationActivation::<T>::get(cooperator, validator).map_or(false, |activation| {
+            era < activation.saturating_add(T::CooperationWarmupEras::get())
+        })
+    }
+
     /// This function will remove a cooperator from the `Cooperators` storage map,
     /// and `VoterList`.
     ///
@@ -914,6 +1829,8 @@ impl<T: Config> Pallet<T> {
                 }
                 T::OnVipMembershipHandler::update_active_stake(who);
                 Cooperators::<T>::remove(who);
+                let _ =
+                    CooperationActivation::<T>::clear_prefix(who, T::MaxCooperations::get(), None);
                 true
             },
             None => false,
@@ -980,7 +1897,7 @@ impl<T: Config> Pallet<T> {
         };
 
         if let Some(tier) = reputation.tier() {
-            T::ReputationTierEnergyRewardAdditionalPercentMapping::convert(&tier)
+            Self::tier_energy_bonus(&tier)
         } else {
             Perbill::zero()
         }
@@ -1214,26 +2131,42 @@ where
 {
     fn note_author(author: T::AccountId) {
         let reward = Self::calculate_block_authoring_reward();
-        if let Err(e) = <pallet_reputation::Pallet<T>>::do_increase_points(&author, reward) {
-            pallet_reputation::Pallet::<T>::deposit_event(
+        match <pallet_reputation::Pallet<T>>::do_increase_points(&author, reward) {
+            Ok(granted) => Self::note_reputation_granted(granted),
+            Err(e) => pallet_reputation::Pallet::<T>::deposit_event(
                 pallet_reputation::Event::<T>::ReputationIncreaseFailed {
                     account: author,
                     error: e,
                     points: reward,
                 },
-            );
+            ),
         }
     }
 }
 
+/// The energy rate set by governance, scaled by the battery slot capacity relative to the
+/// configured `BatterySlotCapacity`.
 impl<T: Config> EnergyRateCalculator<StakeOf<T>, EnergyOf<T>> for Pallet<T> {
     fn calculate_energy_rate(
         _total_staked: StakeOf<T>,
         _total_issuance: EnergyOf<T>,
         _core_nodes_num: u32,
-        _battery_slot_cap: EnergyOf<T>,
+        battery_slot_cap: EnergyOf<T>,
     ) -> EnergyOf<T> {
-        Pallet::<T>::current_energy_per_stake_currency().unwrap_or(EnergyOf::<T>::zero())
+        let rate =
+            Pallet::<T>::current_energy_per_stake_currency().unwrap_or(EnergyOf::<T>::zero());
+        let configured_cap = T::BatterySlotCapacity::get();
+        if configured_cap.is_zero() || battery_slot_cap == configured_cap {
+            return rate;
+        }
+
+        multiply_by_rational_with_rounding(
+            rate.saturated_into(),
+            battery_slot_cap.saturated_into(),
+            configured_cap.saturated_into(),
+            Rounding::Down,
+        )
+        .map_or_else(EnergyOf::<T>::max_value, |rate| rate.saturated_into())
     }
 }
 
@@ -44,9 +44,10 @@ pub use impls::*;
 
 use crate::{
     slashing, slashing::NegativeImbalanceOf, weights::WeightInfo, AccountIdLookupOf, ActiveEraInfo,
-    Cooperations, DisablingStrategy, EnergyDebtOf, EnergyRateCalculator, Exposure, Forcing,
-    RewardDestination, SessionInterface, StakeNegativeImbalanceOf, StakeOf, StakingLedger,
-    UnappliedSlash, UnlockChunk, ValidatorPrefs,
+    ClientVersion, ClientVersionRequirement, Cooperations, DisablingStrategy, EnergyDebtOf,
+    EnergyRateCalculator, Exposure, Forcing, IssuanceReportOf, NacRevocationPolicy,
+    RewardDestination, SessionInterface, SessionKeysAge, StakeNegativeImbalanceOf, StakeOf,
+    StakingLedger, UnappliedSlash, UnlockChunk, UptimeRecord, ValidatorPrefs,
 };
 
 #[cfg(feature = "try-runtime")]
@@ -57,18 +58,26 @@ const STAKING_ID: LockIdentifier = *b"staking ";
 // [`Call::unbond`], as the post dipatch weight may depend on the number of slashing span on the
 // account which is not provided as an input. The value set should be conservative but sensible.
 pub(crate) const SPECULATIVE_NUM_SPANS: u32 = 32;
+// The maximum number of accounts whose deferred energy rewards are minted in a single block, which
+// keeps `on_initialize` bounded however small the rewards are.
+pub(crate) const MAX_DEFERRED_REWARD_PAYOUTS_PER_BLOCK: u32 = 64;
+// The highest offending validators threshold governance may set. Above it a majority of the active
+// set could be disabled before a new era is forced.
+pub(crate) const MAX_OFFENDING_VALIDATORS_THRESHOLD: Perbill = Perbill::from_percent(50);
 
 #[allow(clippy::module_inception)]
 #[frame_support::pallet]
 pub mod pallet {
     use crate::{
-        slashing::StorageEssentials, BenchmarkingConfig, EnergyOf, OnVipMembershipHandler,
+        slashing::StorageEssentials, BenchmarkingConfig, EnergyOf, GenesisParameters,
+        OnVipMembershipHandler,
     };
+    use frame_support::traits::fungibles::metadata::Inspect as MetadataInspect;
 
     use super::*;
 
     /// The current storage version.
-    const STORAGE_VERSION: StorageVersion = StorageVersion::new(15);
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(16);
 
     #[pallet::pallet]
     #[pallet::storage_version(STORAGE_VERSION)]
@@ -113,9 +122,25 @@ pub mod pallet {
         /// Energy asset ID.
         type EnergyAssetId: Get<Self::AssetId>;
 
-        /// Battery slot capacity.
+        /// Battery slot capacity, until governance sets another one.
         type BatterySlotCapacity: Get<EnergyOf<Self>>;
 
+        /// Lowest battery slot capacity governance may set.
+        #[pallet::constant]
+        type MinBatterySlotCapacity: Get<EnergyOf<Self>>;
+
+        /// Highest battery slot capacity governance may set.
+        #[pallet::constant]
+        type MaxBatterySlotCapacity: Get<EnergyOf<Self>>;
+
+        /// Energy a validator is rewarded per era for each of its reputation points, until
+        /// governance sets another rate.
+        type EnergyPerReputationPoint: Get<EnergyOf<Self>>;
+
+        /// Highest energy per reputation point governance may set.
+        #[pallet::constant]
+        type MaxEnergyPerReputationPoint: Get<EnergyOf<Self>>;
+
         /// Time used for computing era duration.
         ///
         /// It is guaranteed to start being called from the first `on_finalize`. Thus value at
@@ -187,9 +212,21 @@ pub mod pallet {
         /// Interface for interacting with a session pallet.
         type SessionInterface: SessionInterface<Self::AccountId>;
 
+        /// Number of sessions after which a validator still running the same session keys is
+        /// reminded to rotate them. Zero disables the reminders.
+        #[pallet::constant]
+        type SessionKeysRotationAge: Get<SessionIndex>;
+
         /// Energy per stake currency rate calculation callback.
         type EnergyPerStakeCurrency: EnergyRateCalculator<StakeOf<Self>, EnergyOf<Self>>;
 
+        /// The rate an era falls back to when `EnergyPerStakeCurrency` returns zero, which would
+        /// stop all rewards. Zero disables the fallback.
+        ///
+        /// Not applied while `RewardsPaused` is set, where a zero rate is expected.
+        #[pallet::constant]
+        type MinEnergyPerStakeCurrency: Get<EnergyOf<Self>>;
+
         /// Something that can estimate the next session change, accurately or as a best effort
         /// guess.
         type NextNewSession: EstimateNextNewSession<BlockNumberFor<Self>>;
@@ -201,6 +238,69 @@ pub mod pallet {
         #[pallet::constant]
         type MaxCooperatorRewardedPerValidator: Get<u32>;
 
+        /// The share of the reward forfeited by a cooperator with a slash cap of zero.
+        ///
+        /// A cooperator capping its slash at `max_slash` forfeits `(1 - max_slash)` of this share,
+        /// which is paid to the validator instead. The part of a slash above the cap isn't moved
+        /// to the validator, it is dropped.
+        #[pallet::constant]
+        type SlashCapRewardPenalty: Get<Perbill>;
+
+        /// The share of its own reward a validator forfeits for an era in which it didn't run
+        /// the client version required by `MinimumClientVersion`.
+        #[pallet::constant]
+        type OutdatedClientRewardPenalty: Get<Perbill>;
+
+        /// The share of its own reward a validator earns on top for an era in which it backed
+        /// candidates of an under-subscribed parachain, see `note_backing_boost`. Zero disables
+        /// the boost.
+        #[pallet::constant]
+        type BackingBoost: Get<Perbill>;
+
+        /// Number of eras validators are given to upgrade after a new minimum client version is
+        /// set.
+        #[pallet::constant]
+        type ClientVersionGracePeriod: Get<EraIndex>;
+
+        /// Number of eras, starting with the first era a validator is elected for, during which
+        /// it isn't reported for being unresponsive, see [`Pallet::in_onboarding_grace`]. Zero
+        /// disables the grace.
+        #[pallet::constant]
+        type OnboardingGraceEras: Get<EraIndex>;
+
+        /// Number of eras, starting with the first era a new cooperation can be exposed in,
+        /// during which the cooperation earns no energy reward. Zero disables the warm-up.
+        #[pallet::constant]
+        type CooperationWarmupEras: Get<EraIndex>;
+
+        /// The account receiving the energy rewards left unclaimed for longer than
+        /// `HistoryDepth` eras.
+        type StaleRewardsDestination: Get<Self::AccountId>;
+
+        /// The maximum number of eras whose unclaimed rewards are swept by a single
+        /// `sweep_stale_rewards` call.
+        #[pallet::constant]
+        type MaxStaleRewardErasPerSweep: Get<u32>;
+
+        /// The maximum amount of energy minted as staking rewards in a single block, `None` for
+        /// no limit. Rewards above it are deferred and minted in the following blocks.
+        type MaxEnergyMintPerBlock: Get<Option<EnergyOf<Self>>>;
+
+        /// Whether the rewards paid out while `RewardsPaused` is set are deferred and minted once
+        /// rewards resume, rather than forfeited.
+        type DeferRewardsWhilePaused: Get<bool>;
+
+        /// The energy fee of a signed call keeping a validator operational, e.g. `validate` or
+        /// `set_keys`, which the energy reserve of validators is priced in. `im-online`
+        /// heartbeats are unsigned and free, they don't need the reserve.
+        type OperationalCallFee: Get<EnergyOf<Self>>;
+
+        /// Number of operational calls the controller of a validator is kept able to pay for.
+        /// When the validator reward is paid to another account, it first tops the controller up
+        /// to this reserve. Zero disables the reserve.
+        #[pallet::constant]
+        type ReservedOperationalCalls: Get<u32>;
+
         /// The maximum number of `unlocking` chunks a [`StakingLedger`] can
         /// have. Effectively determines how many unique eras a staker may be
         /// unbonding in.
@@ -223,6 +323,11 @@ pub mod pallet {
         /// `DisablingStragegy` controls how validators are disabled
         type DisablingStrategy: DisablingStrategy<Self>;
 
+        /// Share of the active set that can be disabled before a new era is forced, until
+        /// governance sets another one.
+        #[pallet::constant]
+        type OffendingValidatorsThreshold: Get<Perbill>;
+
         /// The minimum reputation to be a validator.
         #[pallet::constant]
         type ValidatorReputationTier: Get<ReputationTier>;
@@ -233,11 +338,17 @@ pub mod pallet {
         type CollaborativeValidatorReputationTier: Get<ReputationTier>;
 
         /// `ReputationTier` -> `Perbill` mapping, depicting additional energy reward ratio per tier.
+        ///
+        /// Applies to the tiers governance hasn't set a bonus for.
         type ReputationTierEnergyRewardAdditionalPercentMapping: for<'a> Convert<
             &'a ReputationTier,
             Perbill,
         >;
 
+        /// Highest additional energy reward ratio governance may set for a tier.
+        #[pallet::constant]
+        type MaxTierEnergyBonus: Get<Perbill>;
+
         /// A conversion from account ID to NAC level.
         type ValidatorNacLevel: for<'a> Convert<&'a Self::AccountId, Option<u8>>;
 
@@ -272,6 +383,81 @@ pub mod pallet {
     #[pallet::unbounded]
     pub type Invulnerables<T: Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
 
+    /// The validator set forced by governance for the next planned era, bypassing the election
+    /// once.
+    #[pallet::storage]
+    #[pallet::getter(fn forced_validators)]
+    #[pallet::unbounded]
+    pub type ForcedValidators<T: Config> = StorageValue<_, Vec<T::AccountId>, OptionQuery>;
+
+    /// The first era each cooperation of a cooperator, by validator, can be exposed in. Used to
+    /// withhold rewards during `Config::CooperationWarmupEras`.
+    ///
+    /// Cooperations without an entry, e.g. genesis ones, have no warm-up.
+    #[pallet::storage]
+    pub type CooperationActivation<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        Twox64Concat,
+        T::AccountId,
+        EraIndex,
+        OptionQuery,
+    >;
+
+    /// The maximum number of validators a cooperator can cooperate with, set by governance.
+    ///
+    /// `Config::MaxCooperations` applies while it's not set, and is always the upper bound.
+    #[pallet::storage]
+    #[pallet::getter(fn max_cooperations_override)]
+    pub type MaxCooperationsPerCooperator<T: Config> = StorageValue<_, u32, OptionQuery>;
+
+    /// The battery slot capacity the energy rate is calculated with, set by governance.
+    ///
+    /// `Config::BatterySlotCapacity` applies while it's not set.
+    #[pallet::storage]
+    #[pallet::getter(fn battery_slot_capacity_override)]
+    pub type BatterySlotCapacityOverride<T: Config> = StorageValue<_, EnergyOf<T>, OptionQuery>;
+
+    /// The energy per reputation point set by governance, the rate of the next eras.
+    ///
+    /// `Config::EnergyPerReputationPoint` applies while it's not set.
+    #[pallet::storage]
+    #[pallet::getter(fn energy_per_reputation_point_override)]
+    pub type EnergyPerReputationPointOverride<T: Config> =
+        StorageValue<_, EnergyOf<T>, OptionQuery>;
+
+    /// The additional energy reward ratio of reputation tiers, set by governance.
+    ///
+    /// `Config::ReputationTierEnergyRewardAdditionalPercentMapping` applies to the tiers without
+    /// one.
+    #[pallet::storage]
+    pub type TierEnergyBonus<T: Config> =
+        StorageMap<_, Twox64Concat, ReputationTier, Perbill, OptionQuery>;
+
+    /// The offending validators threshold of the active era, set by governance.
+    ///
+    /// `Config::OffendingValidatorsThreshold` applies while it's not set.
+    #[pallet::storage]
+    #[pallet::getter(fn offending_validators_threshold_override)]
+    pub type OffendingValidatorsThresholdOverride<T: Config> =
+        StorageValue<_, Perbill, OptionQuery>;
+
+    /// The offending validators threshold set by governance, applied when the next era starts.
+    #[pallet::storage]
+    pub type PendingOffendingValidatorsThreshold<T: Config> = StorageValue<_, Perbill, OptionQuery>;
+
+    /// What happens to validators whose NAC access is revoked.
+    #[pallet::storage]
+    #[pallet::getter(fn nac_revocation_policy)]
+    pub type NacRevocationChillPolicy<T: Config> = StorageValue<_, NacRevocationPolicy, ValueQuery>;
+
+    /// Validators whose NAC access was revoked, chilled before the next era election unless
+    /// their access is restored by then.
+    #[pallet::storage]
+    pub type PendingNacChills<T: Config> =
+        StorageMap<_, Twox64Concat, T::AccountId, (), OptionQuery>;
+
     /// Map from all locked "stash" accounts to the controller account.
     ///
     /// TWOX-NOTE: SAFE since `AccountId` is a secure hash.
@@ -314,6 +500,15 @@ pub mod pallet {
     pub type Payee<T: Config> =
         StorageMap<_, Twox64Concat, T::AccountId, RewardDestination<T::AccountId>, ValueQuery>;
 
+    /// The maximum proportion of their exposure cooperators agreed to lose in a slash. Keyed by
+    /// stash.
+    ///
+    /// Cooperators without an entry are slashed in full.
+    #[pallet::storage]
+    #[pallet::getter(fn cooperator_max_slash)]
+    pub type CooperatorMaxSlash<T: Config> =
+        StorageMap<_, Twox64Concat, T::AccountId, Perbill, OptionQuery>;
+
     /// The map from (wannabe) validator stash key to the preferences of that validator.
     ///
     /// TWOX-NOTE: SAFE since `AccountId` is a secure hash.
@@ -455,12 +650,139 @@ pub mod pallet {
         ValueQuery,
     >;
 
+    /// Snapshot of the slash caps of the cooperators exposed in an era.
+    ///
+    /// Slashes and rewards of an era use the caps in effect when its exposures were taken, so
+    /// changing the cap only affects the following eras.
+    ///
+    /// Is it removed after `HISTORY_DEPTH` eras.
+    #[pallet::storage]
+    #[pallet::getter(fn eras_cooperator_max_slash)]
+    pub type ErasCooperatorMaxSlash<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        EraIndex,
+        Twox64Concat,
+        T::AccountId,
+        Perbill,
+        OptionQuery,
+    >;
+
+    /// Energy rewards of an era, before reward multipliers, that validators haven't paid out
+    /// yet.
+    ///
+    /// Unlike other era information it outlives `HISTORY_DEPTH`, until the rewards that can no
+    /// longer be claimed are moved to `StaleRewardsDestination` by `sweep_stale_rewards`.
+    #[pallet::storage]
+    pub type ErasUnclaimedRewards<T: Config> =
+        StorageMap<_, Twox64Concat, EraIndex, EnergyOf<T>, ValueQuery>;
+
+    /// Energy minted as staking rewards in the current block, while `MaxEnergyMintPerBlock` is
+    /// set.
+    #[pallet::storage]
+    pub type BlockEnergyMinted<T: Config> = StorageValue<_, EnergyOf<T>, ValueQuery>;
+
+    /// Energy rewards that exceeded `MaxEnergyMintPerBlock`, or were paid out while rewards were
+    /// paused, by the account they are paid into.
+    #[pallet::storage]
+    pub type DeferredEnergyRewards<T: Config> =
+        StorageMap<_, Twox64Concat, T::AccountId, EnergyOf<T>, ValueQuery>;
+
+    /// Whether governance paused the minting of energy rewards.
+    ///
+    /// Eras keep rotating and rewards can still be claimed, but nothing is minted: the rewards
+    /// are deferred or forfeited, depending on `DeferRewardsWhilePaused`.
+    #[pallet::storage]
+    pub type RewardsPaused<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+    /// The first era a validator was elected for.
+    ///
+    /// It is kept when the stash is reaped, so the onboarding grace is granted once per
+    /// validator.
+    #[pallet::storage]
+    pub type ValidatorFirstActiveEra<T: Config> =
+        StorageMap<_, Twox64Concat, T::AccountId, EraIndex, OptionQuery>;
+
+    /// Whether a validator intends to keep validating in the era it committed for.
+    ///
+    /// The intents of an era are removed once it's elected.
+    #[pallet::storage]
+    #[pallet::getter(fn next_era_intent)]
+    pub type NextEraIntent<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, EraIndex, Twox64Concat, T::AccountId, bool, OptionQuery>;
+
+    /// The era and energy of the last reward paid to a validator.
+    #[pallet::storage]
+    #[pallet::getter(fn last_validator_reward)]
+    pub type LastValidatorReward<T: Config> =
+        StorageMap<_, Twox64Concat, T::AccountId, (EraIndex, EnergyOf<T>), OptionQuery>;
+
+    /// The client version last reported by a validator.
+    #[pallet::storage]
+    pub type ValidatorClientVersion<T: Config> =
+        StorageMap<_, Twox64Concat, T::AccountId, ClientVersion, OptionQuery>;
+
+    /// The client version validators are required to run, if any.
+    #[pallet::storage]
+    pub type MinimumClientVersion<T: Config> =
+        StorageValue<_, ClientVersionRequirement, OptionQuery>;
+
+    /// Validators elected for an era while running an outdated client, see
+    /// `OutdatedClientRewardPenalty`.
+    ///
+    /// Is it removed after `HISTORY_DEPTH` eras.
+    #[pallet::storage]
+    pub type ErasOutdatedValidators<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, EraIndex, Twox64Concat, T::AccountId, (), OptionQuery>;
+
+    /// Energy parameters the chain was launched with.
+    ///
+    /// Written once at genesis and never changed, chains launched before it was introduced
+    /// have none.
+    #[pallet::storage]
+    #[pallet::unbounded]
+    #[pallet::getter(fn genesis_parameters)]
+    pub type GenesisEnergyParameters<T: Config> =
+        StorageValue<_, GenesisParameters<StakeOf<T>, EnergyOf<T>>, OptionQuery>;
+
+    /// Validators that backed candidates of under-subscribed parachains in an era, see
+    /// `BackingBoost`.
+    ///
+    /// It is removed after `HISTORY_DEPTH` eras.
+    #[pallet::storage]
+    pub type ErasBackingBoostedValidators<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, EraIndex, Twox64Concat, T::AccountId, (), OptionQuery>;
+
     /// Eras energy rate per stake currency (VNRG per 1 VTRS)
     #[pallet::storage]
     #[pallet::getter(fn eras_energy_per_stake_cur)]
     pub type ErasEnergyPerStakeCurrency<T: Config> =
         StorageMap<_, Twox64Concat, EraIndex, EnergyOf<T>>;
 
+    /// Eras energy rewarded to validators per reputation point.
+    ///
+    /// Is it removed after `HISTORY_DEPTH` eras.
+    #[pallet::storage]
+    #[pallet::getter(fn eras_energy_per_reputation_point)]
+    pub type ErasEnergyPerReputationPoint<T: Config> =
+        StorageMap<_, Twox64Concat, EraIndex, EnergyOf<T>>;
+
+    /// The reputation points of the era's validators when the era ended.
+    ///
+    /// Reputation rewards of an era are paid for these points, not for the points at payout.
+    /// Is it removed after `HISTORY_DEPTH` eras.
+    #[pallet::storage]
+    #[pallet::getter(fn eras_validator_reputation_points)]
+    pub type ErasValidatorReputationPoints<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        EraIndex,
+        Twox64Concat,
+        T::AccountId,
+        ReputationPoint,
+        OptionQuery,
+    >;
+
     /// The total amount staked for the last `HISTORY_DEPTH` eras.
     /// If total hasn't been set or has been removed then 0 stake is returned.
     #[pallet::storage]
@@ -468,6 +790,39 @@ pub mod pallet {
     pub type ErasTotalStake<T: Config> =
         StorageMap<_, Twox64Concat, EraIndex, StakeOf<T>, ValueQuery>;
 
+    /// Issuance accounted per era for the last `HISTORY_DEPTH` eras.
+    ///
+    /// The key is the era that was active when the issuance happened.
+    #[pallet::storage]
+    pub type ErasIssuance<T: Config> =
+        StorageMap<_, Twox64Concat, EraIndex, IssuanceReportOf<T>, ValueQuery>;
+
+    /// Energy burned per era for the last `HISTORY_DEPTH` eras, see
+    /// [`Pallet::note_energy_burned`].
+    ///
+    /// The key is the era that was active when the energy was burned.
+    #[pallet::storage]
+    pub type ErasEnergyBurned<T: Config> =
+        StorageMap<_, Twox64Concat, EraIndex, EnergyOf<T>, ValueQuery>;
+
+    /// Liveness of the validators of an era, tracked for the last `HISTORY_DEPTH` eras.
+    #[pallet::storage]
+    pub type ErasValidatorUptime<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        EraIndex,
+        Twox64Concat,
+        T::AccountId,
+        UptimeRecord,
+        OptionQuery,
+    >;
+
+    /// Age of the session keys of the validators that took part in a session, used to remind
+    /// them to rotate their keys.
+    #[pallet::storage]
+    pub type ValidatorSessionKeysAge<T: Config> =
+        StorageMap<_, Twox64Concat, T::AccountId, SessionKeysAge, OptionQuery>;
+
     /// Mode of era forcing.
     #[pallet::storage]
     #[pallet::getter(fn force_era)]
@@ -569,6 +924,7 @@ pub mod pallet {
     /// cooperators. The threshold is compared to the actual number of validators / cooperators
     /// (`CountFor*`) in the system compared to the configured max (`Max*Count`).
     #[pallet::storage]
+    #[pallet::getter(fn chill_threshold)]
     pub(crate) type ChillThreshold<T: Config> = StorageValue<_, Percent, OptionQuery>;
 
     /// The current constant value of energy per stake currency.
@@ -624,6 +980,17 @@ pub mod pallet {
             MinCommission::<T>::put(self.min_commission);
             MinCommonValidatorBond::<T>::put(self.min_common_validator_bond);
             MinTrustValidatorBond::<T>::put(self.min_trust_validator_bond);
+            // the energy asset is created by the assets genesis, built before this one
+            let energy_asset = T::EnergyAssetId::get();
+            GenesisEnergyParameters::<T>::put(GenesisParameters {
+                energy_per_stake_currency: self.energy_per_stake_currency,
+                min_cooperator_bond: self.min_cooperator_bond,
+                min_common_validator_bond: self.min_common_validator_bond,
+                min_trust_validator_bond: self.min_trust_validator_bond,
+                energy_name: pallet_assets::Pallet::<T>::name(energy_asset.clone()),
+                energy_symbol: pallet_assets::Pallet::<T>::symbol(energy_asset.clone()),
+                energy_decimals: pallet_assets::Pallet::<T>::decimals(energy_asset),
+            });
             if let Some(x) = self.max_validator_count {
                 MaxValidatorsCount::<T>::put(x);
             }
@@ -676,6 +1043,9 @@ pub mod pallet {
     pub enum Event<T: Config> {
         /// The era energy per stake currency has been set.
         EraEnergyPerStakeCurrencySet { era_index: EraIndex, energy_rate: EnergyOf<T> },
+        /// The energy rate calculator returned zero for the era, which was given the
+        /// `MinEnergyPerStakeCurrency` rate instead. The calculator is likely misconfigured.
+        EnergyRateFloorApplied { era_index: EraIndex, energy_rate: EnergyOf<T> },
         /// The cooperator has been rewarded by this amount.
         Rewarded { stash: T::AccountId, amount: EnergyOf<T> },
         /// A staker (validator or cooperator) has been slashed by the given amount.
@@ -704,6 +1074,9 @@ pub mod pallet {
         Kicked { cooperator: T::AccountId, stash: T::AccountId },
         /// The election failed. No new era is planned.
         StakingElectionFailed,
+        /// Too few validators had enough reputation, `retained` validators without it were kept
+        /// in the validator set to reach the `minimum` validator count.
+        MinimumValidatorCountEnforced { retained: u32, minimum: u32 },
         /// An account has stopped participating as either a validator or cooperator.
         Chilled { stash: T::AccountId },
         /// The stakers' rewards are getting paid.
@@ -712,6 +1085,58 @@ pub mod pallet {
         ValidatorPrefsSet { stash: T::AccountId, prefs: ValidatorPrefs },
         /// A new force era mode was set.
         ForceEra { mode: Forcing },
+        /// Unlocking chunks of an account maturing in the same era have been merged.
+        UnlockingConsolidated { stash: T::AccountId, merged_chunks: u32 },
+        /// A cooperator has set or removed the cap of their slashes.
+        MaxSlashSet { stash: T::AccountId, max_slash: Option<Perbill> },
+        /// The session keys of an active validator reached the recommended rotation age, in
+        /// sessions. Purely informational.
+        SessionKeysRotationDue { stash: T::AccountId, age: SessionIndex },
+        /// A validator reported the version of its client.
+        ClientVersionReported { stash: T::AccountId, version: ClientVersion },
+        /// The minimum client version was set or removed.
+        MinimumClientVersionSet { requirement: Option<ClientVersionRequirement> },
+        /// Rewards of `eras` eras that can no longer be claimed were moved to
+        /// `StaleRewardsDestination`.
+        StaleRewardsSwept { eras: u32, amount: EnergyOf<T> },
+        /// `amount` of the energy reward of `account` exceeded `MaxEnergyMintPerBlock`, or rewards
+        /// are paused, and will be minted in a later block.
+        EnergyRewardDeferred { account: T::AccountId, amount: EnergyOf<T> },
+        /// `amount` of the deferred energy reward of `account` was minted.
+        DeferredEnergyRewardPaid { account: T::AccountId, amount: EnergyOf<T> },
+        /// The cooperator claimed the rewards of `era` from `validators` validators at once.
+        CooperationsClaimed { stash: T::AccountId, era: EraIndex, validators: u32 },
+        /// Governance forced the validator set of the next era.
+        ValidatorSetForced { validators: Vec<T::AccountId> },
+        /// The maximum number of cooperations per cooperator was set.
+        MaxCooperationsSet { max: u32 },
+        /// The battery slot capacity was set, the energy rate of the next era uses it.
+        BatterySlotCapacitySet { capacity: EnergyOf<T> },
+        /// The policy for validators whose NAC access is revoked was set.
+        NacRevocationPolicySet { policy: NacRevocationPolicy },
+        /// The validator lost its NAC access and is chilled before the next era election.
+        NacRevocationChillScheduled { stash: T::AccountId },
+        /// The offending validators threshold was set, it applies from the next era on.
+        OffendingValidatorsThresholdSet { threshold: Perbill },
+        /// The energy per reputation point was set, the rewards of the next era use it.
+        EnergyPerReputationPointSet { rate: EnergyOf<T> },
+        /// The validator committed whether it intends to keep validating in `era`.
+        NextEraIntentCommitted { stash: T::AccountId, era: EraIndex, validate: bool },
+        /// The additional energy reward ratio of `tier` was set.
+        TierEnergyBonusSet { tier: ReputationTier, bonus: Perbill },
+        /// `amount` of the validator reward was paid to the controller to restore its energy
+        /// reserve for operational calls.
+        EnergyReserveToppedUp { controller: T::AccountId, amount: EnergyOf<T> },
+        /// The stash moved its ledger to a new controller.
+        ControllerSet { stash: T::AccountId, controller: T::AccountId },
+        /// Governance paused or resumed the minting of energy rewards.
+        RewardsPauseSet { paused: bool },
+        /// `amount` of the energy reward of `account` was paid out while rewards were paused and
+        /// won't be minted.
+        EnergyRewardForfeited { account: T::AccountId, amount: EnergyOf<T> },
+        /// The deferred energy reward of `account` was dropped, as the account can't receive
+        /// energy.
+        DeferredEnergyRewardDropped { account: T::AccountId, amount: EnergyOf<T> },
     }
 
     #[pallet::error]
@@ -748,6 +1173,8 @@ pub mod pallet {
         InvalidNumberOfCooperations,
         /// Items are not sorted and unique.
         NotSortedAndUnique,
+        /// The stash of the controller is not a validator.
+        NotValidator,
         /// Rewards for this era have already been claimed for this validator.
         AlreadyClaimed,
         /// Incorrect previous history depth input provided.
@@ -777,13 +1204,38 @@ pub mod pallet {
         ReputationTooLow,
         /// New validator count exceeds maximum allowed validators.
         IncorrectValidatorCount,
+        /// Energy rewards cannot be paid into the given payee.
+        InvalidPayee,
+        /// Energy rewards cannot be added to the stake.
+        RewardsCannotBeStaked,
+        /// The stash of the controller is not a cooperator.
+        NotCooperator,
+        /// The validator has no session keys set.
+        NoSessionKeys,
+        /// The cooperation cap is zero or above `MaxCooperations`.
+        InvalidCooperationCap,
+        /// The battery slot capacity is outside of `MinBatterySlotCapacity..=MaxBatterySlotCapacity`.
+        BatterySlotCapacityOutOfBounds,
+        /// The offending validators threshold is zero or above 50%.
+        InvalidOffendingThreshold,
+        /// The energy per reputation point is above `MaxEnergyPerReputationPoint`.
+        EnergyPerReputationPointTooHigh,
+        /// The tier bonus is above `MaxTierEnergyBonus`, below the bonus of the tier under it or
+        /// above the bonus of the tier over it.
+        TierEnergyBonusOutOfBounds,
+        /// The new controller has no NAC access.
+        ControllerWithoutAccess,
+        /// Rewards are already paused or already resumed.
+        RewardsPauseUnchanged,
+        /// Energy rewards can't be minted while they are paused.
+        RewardsArePaused,
     }
 
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
         fn on_initialize(_now: BlockNumberFor<T>) -> Weight {
-            // just return the weight of the on_finalize.
-            T::DbWeight::get().reads(1)
+            // the weight of the on_finalize and of minting the deferred rewards.
+            T::DbWeight::get().reads(1).saturating_add(Self::mint_deferred_rewards())
         }
 
         fn on_finalize(_n: BlockNumberFor<T>) {
@@ -803,6 +1255,15 @@ pub mod pallet {
         fn integrity_test() {
             // and that MaxCooperations is always greater than 1, since we count on this.
             assert!(!T::MaxCooperations::get().is_zero());
+            assert!(
+                T::MinBatterySlotCapacity::get() <= T::BatterySlotCapacity::get()
+                    && T::BatterySlotCapacity::get() <= T::MaxBatterySlotCapacity::get(),
+                "BatterySlotCapacity must be within MinBatterySlotCapacity..=MaxBatterySlotCapacity"
+            );
+            assert!(
+                T::EnergyPerReputationPoint::get() <= T::MaxEnergyPerReputationPoint::get(),
+                "EnergyPerReputationPoint must not exceed MaxEnergyPerReputationPoint"
+            );
 
             sp_std::if_std! {
                 sp_io::TestExternalities::new_empty().execute_with(||
@@ -859,6 +1320,8 @@ pub mod pallet {
                 return Err(Error::<T>::AlreadyPaired.into());
             }
 
+            Self::ensure_valid_payee(&payee)?;
+
             // Reject a bond which is considered to be _dust_.
             if value < T::StakeCurrency::minimum_balance() {
                 return Err(Error::<T>::InsufficientBond.into());
@@ -1037,6 +1500,10 @@ pub mod pallet {
                     let mut slashed_imbalance = NegativeImbalanceOf::<T>::zero();
                     let (imbalance, _) = T::StakeCurrency::slash(&ledger.stash, slashed_stake);
                     slashed_imbalance.subsume(imbalance);
+                    Self::note_issuance(|report| {
+                        report.stake_slashed.saturating_accrue(slashed_imbalance.peek());
+                        report.treasury_inflow.saturating_accrue(slashed_imbalance.peek());
+                    });
                     T::Slash::on_unbalanced(slashed_imbalance);
                     ledger.total -= slashed_stake;
                 }
@@ -1186,10 +1653,7 @@ pub mod pallet {
             }
 
             ensure!(!targets.is_empty(), Error::<T>::EmptyTargets);
-            ensure!(
-                targets.len() <= T::MaxCooperations::get() as usize,
-                Error::<T>::TooManyTargets
-            );
+            ensure!(targets.len() <= Self::max_cooperations() as usize, Error::<T>::TooManyTargets);
 
             let old =
                 Cooperators::<T>::get(stash).map_or_else(BTreeMap::new, |x| x.targets.into_inner());
@@ -1275,6 +1739,7 @@ pub mod pallet {
         ) -> DispatchResult {
             let controller = ensure_signed(origin)?;
             let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
+            Self::ensure_valid_payee(&payee)?;
             let stash = &ledger.stash;
             <Payee<T>>::insert(stash, payee);
             Ok(())
@@ -1285,6 +1750,7 @@ pub mod pallet {
         /// Effects will be felt instantly (as soon as this function is completed successfully).
         ///
         /// The dispatch origin for this call must be _Signed_ by the stash, not the controller.
+        /// The new controller must have NAC access and must not control another stash.
         ///
         /// ## Complexity
         /// O(1)
@@ -1303,12 +1769,15 @@ pub mod pallet {
             if <Ledger<T>>::contains_key(&controller) {
                 return Err(Error::<T>::AlreadyPaired.into());
             }
-            if controller != old_controller {
-                <Bonded<T>>::insert(&stash, &controller);
-                if let Some(l) = <Ledger<T>>::take(&old_controller) {
-                    <Ledger<T>>::insert(&controller, l);
-                }
-            }
+            // a controller without access couldn't sign any staking operation
+            ensure!(
+                T::ValidatorNacLevel::convert(&controller).map_or(false, |level| level > 0),
+                Error::<T>::ControllerWithoutAccess
+            );
+            let ledger = <Ledger<T>>::take(&old_controller).ok_or(Error::<T>::NotController)?;
+            <Bonded<T>>::insert(&stash, &controller);
+            <Ledger<T>>::insert(&controller, ledger);
+            Self::deposit_event(Event::<T>::ControllerSet { stash, controller });
             Ok(())
         }
 
@@ -1588,7 +2057,7 @@ pub mod pallet {
         /// 2. or, the `ledger.total` of the stash is below existential deposit.
         ///
         /// The former can happen in cases like a slash; the latter when a fully unbonded account
-        /// is still receiving staking rewards in `RewardDestination::Staked`.
+        /// is still receiving staking rewards.
         ///
         /// It can be called by anyone, as long as `stash` meets the above requirements.
         ///
@@ -1898,6 +2367,391 @@ pub mod pallet {
             Self::chill_stash(&stash);
             Ok(())
         }
+
+        /// Merge the unlocking chunks of the caller which unlock in the same era, freeing up
+        /// unlocking slots for further `unbond` calls.
+        ///
+        /// The dispatch origin must be signed by the controller. Does nothing if no two chunks
+        /// unlock in the same era.
+        #[pallet::call_index(31)]
+        #[pallet::weight(T::ThisWeightInfo::consolidate_unlocking())]
+        pub fn consolidate_unlocking(origin: OriginFor<T>) -> DispatchResult {
+            let controller = ensure_signed(origin)?;
+            let mut ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
+
+            let merged_chunks = ledger.merge_unlocking_chunks();
+            if merged_chunks > 0 {
+                Self::update_ledger(&controller, &ledger);
+                Self::deposit_event(Event::<T>::UnlockingConsolidated {
+                    stash: ledger.stash,
+                    merged_chunks,
+                });
+            }
+            Ok(())
+        }
+
+        /// Update the commission and the reward destination of the origin controller at once.
+        ///
+        /// `None` leaves the corresponding setting unchanged. A new commission is only accepted
+        /// from a validator and must respect `MinCommission`, while the payee is checked as in
+        /// `set_payee`. Nothing is changed unless both updates are valid.
+        ///
+        /// The dispatch origin for this call must be _Signed_ by the controller, not the stash.
+        #[pallet::call_index(32)]
+        #[pallet::weight(T::ThisWeightInfo::update_prefs())]
+        pub fn update_prefs(
+            origin: OriginFor<T>,
+            commission: Option<Perbill>,
+            payee: Option<RewardDestination<T::AccountId>>,
+        ) -> DispatchResult {
+            let controller = ensure_signed(origin)?;
+            let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
+            let stash = ledger.stash;
+
+            let prefs = match commission {
+                Some(commission) => {
+                    ensure!(Validators::<T>::contains_key(&stash), Error::<T>::NotStash);
+                    ensure!(commission >= MinCommission::<T>::get(), Error::<T>::CommissionTooLow);
+                    Some(ValidatorPrefs { commission, ..Validators::<T>::get(&stash) })
+                },
+                None => None,
+            };
+            if let Some(payee) = &payee {
+                Self::ensure_valid_payee(payee)?;
+            }
+
+            if let Some(prefs) = prefs {
+                Validators::<T>::insert(&stash, &prefs);
+                Self::deposit_event(Event::<T>::ValidatorPrefsSet { stash: stash.clone(), prefs });
+            }
+            if let Some(payee) = payee {
+                <Payee<T>>::insert(&stash, payee);
+            }
+            Ok(())
+        }
+
+        /// Cap the proportion of the exposure the origin controller's stash loses when a
+        /// cooperated validator is slashed, or remove the cap with `None`.
+        ///
+        /// The part of a slash above the cap is dropped, not moved to the validator. Capped
+        /// cooperators forfeit part of their rewards to it, see `SlashCapRewardPenalty`. The cap
+        /// takes effect from the next era.
+        ///
+        /// The dispatch origin for this call must be _Signed_ by the controller, not the stash.
+        #[pallet::call_index(33)]
+        #[pallet::weight(T::ThisWeightInfo::set_max_slash())]
+        pub fn set_max_slash(origin: OriginFor<T>, max_slash: Option<Perbill>) -> DispatchResult {
+            let controller = ensure_signed(origin)?;
+            let stash = Self::ledger(&controller).ok_or(Error::<T>::NotController)?.stash;
+
+            CooperatorMaxSlash::<T>::set(&stash, max_slash);
+
+            Self::deposit_event(Event::<T>::MaxSlashSet { stash, max_slash });
+            Ok(())
+        }
+
+        /// Report the version of the client run by the origin controller's validator.
+        ///
+        /// Validators that haven't reported a version of at least `MinimumClientVersion` when
+        /// an era is planned lose `OutdatedClientRewardPenalty` of their own reward for that era.
+        ///
+        /// The dispatch origin for this call must be _Signed_ by the controller, not the stash.
+        #[pallet::call_index(34)]
+        #[pallet::weight(T::ThisWeightInfo::report_client_version())]
+        pub fn report_client_version(
+            origin: OriginFor<T>,
+            version: ClientVersion,
+        ) -> DispatchResult {
+            let controller = ensure_signed(origin)?;
+            let stash = Self::ledger(&controller).ok_or(Error::<T>::NotController)?.stash;
+            ensure!(Validators::<T>::contains_key(&stash), Error::<T>::NotValidator);
+
+            ValidatorClientVersion::<T>::insert(&stash, version);
+
+            Self::deposit_event(Event::<T>::ClientVersionReported { stash, version });
+            Ok(())
+        }
+
+        /// Set the client version validators have to run, or remove the requirement with `None`.
+        ///
+        /// The requirement is enforced `ClientVersionGracePeriod` eras after the active one.
+        ///
+        /// The dispatch origin must be Root, or must be authorized by the `T::AdminOrigin`.
+        #[pallet::call_index(35)]
+        #[pallet::weight(T::ThisWeightInfo::set_minimum_client_version())]
+        pub fn set_minimum_client_version(
+            origin: OriginFor<T>,
+            minimum: Option<ClientVersion>,
+        ) -> DispatchResult {
+            <T as Config>::AdminOrigin::ensure_origin(origin)?;
+            let active_era = Self::active_era().map(|e| e.index).unwrap_or_default();
+            let requirement = minimum.map(|minimum| ClientVersionRequirement {
+                minimum,
+                enforced_from: active_era.saturating_add(T::ClientVersionGracePeriod::get()),
+            });
+
+            MinimumClientVersion::<T>::set(requirement);
+
+            Self::deposit_event(Event::<T>::MinimumClientVersionSet { requirement });
+            Ok(())
+        }
+
+        /// Move the energy rewards of eras older than `HistoryDepth`, which can no longer be
+        /// paid out, to `StaleRewardsDestination`.
+        ///
+        /// At most `MaxStaleRewardErasPerSweep` eras are swept per call. Calling it when no
+        /// expired era is left does nothing.
+        ///
+        /// The dispatch origin for this call must be _Signed_.
+        #[pallet::call_index(36)]
+        #[pallet::weight(T::ThisWeightInfo::sweep_stale_rewards(
+            T::HistoryDepth::get().saturating_add(T::MaxStaleRewardErasPerSweep::get()),
+        ))]
+        pub fn sweep_stale_rewards(origin: OriginFor<T>) -> DispatchResult {
+            ensure_signed(origin)?;
+            Self::do_sweep_stale_rewards()
+        }
+
+        /// Pay out the rewards of `era` from all validators the stash of the origin controller
+        /// cooperates with, like `payout_stakers` does for a single validator.
+        ///
+        /// Validators that were already paid out for `era`, or whose rewarded stakers of `era`
+        /// don't include the stash, are skipped. At most `limit` validators are paid per call,
+        /// so calling it again resumes where the previous call stopped.
+        ///
+        /// The dispatch origin for this call must be _Signed_ by the controller, not the stash.
+        #[pallet::call_index(37)]
+        #[pallet::weight(T::ThisWeightInfo::claim_all_cooperations(
+            (*limit).min(T::MaxCooperations::get()),
+        ).saturating_add(
+            // skipped validators
+            T::DbWeight::get().reads(3).saturating_mul(T::MaxCooperations::get().into()),
+        ))]
+        pub fn claim_all_cooperations(
+            origin: OriginFor<T>,
+            era: EraIndex,
+            limit: u32,
+        ) -> DispatchResultWithPostInfo {
+            let controller = ensure_signed(origin)?;
+            let stash = Self::ledger(&controller).ok_or(Error::<T>::NotController)?.stash;
+            Self::do_claim_all_cooperations(stash, era, limit)
+        }
+
+        /// Force `validators` to be the validator set of the next era, bypassing the election.
+        ///
+        /// This is a last-resort recovery tool, e.g. after a mass equivocation. A new era is
+        /// forced at the end of the next session; the override lasts for that era only and the
+        /// normal election resumes afterwards.
+        ///
+        /// Every validator must be a bonded stash with session keys set, and there must be at
+        /// least `MinimumValidatorCount` and at most `ValidatorCount` of them. The set is kept
+        /// until an era is planned with it.
+        ///
+        /// The dispatch origin must be Root.
+        #[pallet::call_index(38)]
+        #[pallet::weight(T::ThisWeightInfo::force_set_validators(validators.len() as u32))]
+        pub fn force_set_validators(
+            origin: OriginFor<T>,
+            mut validators: Vec<T::AccountId>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            validators.sort();
+            validators.dedup();
+            let count = validators.len() as u32;
+            ensure!(
+                count >= Self::minimum_validator_count().max(1) && count <= Self::validator_count(),
+                Error::<T>::IncorrectValidatorCount
+            );
+            for validator in &validators {
+                ensure!(Bonded::<T>::contains_key(validator), Error::<T>::NotStash);
+                ensure!(
+                    T::SessionInterface::session_keys_hash(validator).is_some(),
+                    Error::<T>::NoSessionKeys
+                );
+            }
+
+            ForcedValidators::<T>::put(&validators);
+            Self::set_force_era(Forcing::ForceNew);
+            Self::deposit_event(Event::<T>::ValidatorSetForced { validators });
+            Ok(())
+        }
+
+        /// Set the maximum number of validators a cooperator can cooperate with.
+        ///
+        /// It can't exceed `MaxCooperations`. Lowering it doesn't affect existing cooperations,
+        /// but they can't be resubmitted with more targets than the new maximum.
+        ///
+        /// The dispatch origin must be Root, or must be authorized by the `T::AdminOrigin`.
+        #[pallet::call_index(39)]
+        #[pallet::weight(T::ThisWeightInfo::set_max_cooperations())]
+        pub fn set_max_cooperations(origin: OriginFor<T>, max: u32) -> DispatchResult {
+            <T as Config>::AdminOrigin::ensure_origin(origin)?;
+            ensure!(
+                !max.is_zero() && max <= T::MaxCooperations::get(),
+                Error::<T>::InvalidCooperationCap
+            );
+
+            MaxCooperationsPerCooperator::<T>::put(max);
+            Self::deposit_event(Event::<T>::MaxCooperationsSet { max });
+            Ok(())
+        }
+
+        /// Set the battery slot capacity the energy rate is calculated with.
+        ///
+        /// It must be within `MinBatterySlotCapacity..=MaxBatterySlotCapacity`. The energy rate
+        /// of the current era is kept, the new capacity applies from the next era on.
+        ///
+        /// The dispatch origin must be Root, or must be authorized by the `T::AdminOrigin`.
+        #[pallet::call_index(40)]
+        #[pallet::weight(T::ThisWeightInfo::set_battery_slot_capacity())]
+        pub fn set_battery_slot_capacity(
+            origin: OriginFor<T>,
+            capacity: EnergyOf<T>,
+        ) -> DispatchResult {
+            <T as Config>::AdminOrigin::ensure_origin(origin)?;
+            ensure!(
+                capacity >= T::MinBatterySlotCapacity::get()
+                    && capacity <= T::MaxBatterySlotCapacity::get(),
+                Error::<T>::BatterySlotCapacityOutOfBounds
+            );
+
+            BatterySlotCapacityOverride::<T>::put(capacity);
+            Self::deposit_event(Event::<T>::BatterySlotCapacitySet { capacity });
+            Ok(())
+        }
+
+        /// Set what happens to validators whose NAC access is revoked.
+        ///
+        /// Only validators revoked after this call are affected, chills already scheduled still
+        /// apply.
+        #[pallet::call_index(41)]
+        #[pallet::weight(T::ThisWeightInfo::set_nac_revocation_policy())]
+        pub fn set_nac_revocation_policy(
+            origin: OriginFor<T>,
+            policy: NacRevocationPolicy,
+        ) -> DispatchResult {
+            <T as Config>::AdminOrigin::ensure_origin(origin)?;
+
+            NacRevocationChillPolicy::<T>::put(policy);
+            Self::deposit_event(Event::<T>::NacRevocationPolicySet { policy });
+            Ok(())
+        }
+
+        /// Set the share of the active set that can be disabled for offences before a new era is
+        /// forced.
+        ///
+        /// It must be non-zero and at most 50%. The threshold of the active era is kept, the new
+        /// one applies from the next era on.
+        ///
+        /// The dispatch origin must be Root, or must be authorized by the `T::AdminOrigin`.
+        #[pallet::call_index(42)]
+        #[pallet::weight(T::ThisWeightInfo::set_offending_threshold())]
+        pub fn set_offending_threshold(origin: OriginFor<T>, value: Perbill) -> DispatchResult {
+            <T as Config>::AdminOrigin::ensure_origin(origin)?;
+            ensure!(
+                !value.is_zero() && value <= MAX_OFFENDING_VALIDATORS_THRESHOLD,
+                Error::<T>::InvalidOffendingThreshold
+            );
+
+            PendingOffendingValidatorsThreshold::<T>::put(value);
+            Self::deposit_event(Event::<T>::OffendingValidatorsThresholdSet { threshold: value });
+            Ok(())
+        }
+
+        /// Set the energy a validator is rewarded per era for each of its reputation points.
+        ///
+        /// It must be at most `MaxEnergyPerReputationPoint`, zero disables the reputation
+        /// reward. The rate of the current era is kept, the new one applies from the next era on.
+        ///
+        /// The dispatch origin must be Root, or must be authorized by the `T::AdminOrigin`.
+        #[pallet::call_index(43)]
+        #[pallet::weight(T::ThisWeightInfo::set_energy_per_reputation_point())]
+        pub fn set_energy_per_reputation_point(
+            origin: OriginFor<T>,
+            rate: EnergyOf<T>,
+        ) -> DispatchResult {
+            <T as Config>::AdminOrigin::ensure_origin(origin)?;
+            ensure!(
+                rate <= T::MaxEnergyPerReputationPoint::get(),
+                Error::<T>::EnergyPerReputationPointTooHigh
+            );
+
+            EnergyPerReputationPointOverride::<T>::put(rate);
+            Self::deposit_event(Event::<T>::EnergyPerReputationPointSet { rate });
+            Ok(())
+        }
+
+        /// Commit whether the validator intends to keep validating in the next elected era.
+        ///
+        /// When there are more candidates than validator slots, validators are elected by stake
+        /// and, among equally staked validators, those that committed to validate are elected
+        /// first. Validators that committed not to validate are chilled at the election. The
+        /// intent only applies to the next election.
+        ///
+        /// The dispatch origin for this call must be _Signed_ by the controller, not the stash.
+        #[pallet::call_index(45)]
+        #[pallet::weight(T::ThisWeightInfo::commit_next_era_intent())]
+        pub fn commit_next_era_intent(origin: OriginFor<T>, validate: bool) -> DispatchResult {
+            let controller = ensure_signed(origin)?;
+            let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
+            let stash = ledger.stash;
+            ensure!(Validators::<T>::contains_key(&stash), Error::<T>::NotValidator);
+
+            let era = Self::next_elected_era();
+            NextEraIntent::<T>::insert(era, &stash, validate);
+            Self::deposit_event(Event::<T>::NextEraIntentCommitted { stash, era, validate });
+            Ok(())
+        }
+
+        /// Set the additional energy reward ratio of validators with the `tier` reputation.
+        ///
+        /// It must be at most `MaxTierEnergyBonus`, and keep the bonuses ordered by tier: at least
+        /// the bonus of the tier below and at most the bonus of the tier above. Rewards paid from
+        /// then on use it.
+        ///
+        /// The dispatch origin must be Root, or must be authorized by the `T::AdminOrigin`.
+        #[pallet::call_index(44)]
+        #[pallet::weight(T::ThisWeightInfo::set_tier_bonus())]
+        pub fn set_tier_bonus(
+            origin: OriginFor<T>,
+            tier: ReputationTier,
+            bonus: Perbill,
+        ) -> DispatchResult {
+            <T as Config>::AdminOrigin::ensure_origin(origin)?;
+
+            let rank = tier.rank();
+            let lower = ReputationTier::try_from_rank(rank.saturating_sub(1))
+                .map_or(Perbill::zero(), |lower| Self::tier_energy_bonus(&lower));
+            let upper = rank
+                .checked_add(1)
+                .and_then(ReputationTier::try_from_rank)
+                .map_or(T::MaxTierEnergyBonus::get(), |upper| Self::tier_energy_bonus(&upper))
+                .min(T::MaxTierEnergyBonus::get());
+            ensure!(lower <= bonus && bonus <= upper, Error::<T>::TierEnergyBonusOutOfBounds);
+
+            TierEnergyBonus::<T>::insert(tier, bonus);
+            Self::deposit_event(Event::<T>::TierEnergyBonusSet { tier, bonus });
+            Ok(())
+        }
+
+        /// Pause or resume the minting of energy rewards.
+        ///
+        /// While paused eras keep rotating and rewards can still be claimed, but nothing is
+        /// minted. Claimed rewards are deferred and minted once rewards resume if
+        /// `DeferRewardsWhilePaused` is set, and forfeited otherwise. Stale rewards can't be
+        /// swept while paused.
+        ///
+        /// The dispatch origin must be Root, or must be authorized by the `T::AdminOrigin`.
+        #[pallet::call_index(46)]
+        #[pallet::weight(T::ThisWeightInfo::set_rewards_paused())]
+        pub fn set_rewards_paused(origin: OriginFor<T>, paused: bool) -> DispatchResult {
+            <T as Config>::AdminOrigin::ensure_origin(origin)?;
+            ensure!(RewardsPaused::<T>::get() != paused, Error::<T>::RewardsPauseUnchanged);
+
+            RewardsPaused::<T>::put(paused);
+            Self::deposit_event(Event::<T>::RewardsPauseSet { paused });
+            Ok(())
+        }
     }
 }
 
@@ -35,8 +35,9 @@
 use core::ops::{Add, Mul};
 
 use crate::{
-    Config, CooperatorSlashInEra, DisabledValidators, DisablingStrategy, Error, Exposure, Pallet,
-    Perbill, SessionInterface, SpanSlash, StakeOf, UnappliedSlash, ValidatorSlashInEra,
+    Config, CooperatorSlashInEra, DisabledValidators, DisablingStrategy, ErasCooperatorMaxSlash,
+    Error, Exposure, Pallet, Perbill, SessionInterface, SpanSlash, StakeOf, UnappliedSlash,
+    ValidatorSlashInEra,
 };
 use frame_support::{
     ensure,
@@ -270,6 +271,10 @@ impl SlashEntityPerbill {
         }
     }
 
+    fn capped(self, cap: Perbill) -> Self {
+        Self { reputation: self.reputation.min(cap), stake: self.stake.min(cap) }
+    }
+
     fn compare_with(&self, prior: Self) -> UpdateDecision {
         if prior.reputation.deconstruct() < self.reputation.deconstruct()
             || prior.stake.deconstruct() < self.stake.deconstruct()
@@ -518,8 +523,11 @@ fn kick_out_if_recent<T: Config>(params: SlashParams<T>) {
 
 /// Inform the [`DisablingStrategy`] implementation about the new offender and disable the list of
 /// validators provided by [`make_disabling_decision`].
+///
+/// A new era is forced once more of the active set is disabled than the offending validators
+/// threshold allows.
 fn add_offending_validator<T: Config>(params: &SlashParams<T>) {
-    DisabledValidators::<T>::mutate(|disabled| {
+    let disabled_count = DisabledValidators::<T>::mutate(|disabled| {
         if let Some(offender) =
             T::DisablingStrategy::decision(params.stash, params.slash_era, disabled)
         {
@@ -530,14 +538,24 @@ fn add_offending_validator<T: Config>(params: &SlashParams<T>) {
                 T::SessionInterface::disable_validator(offender);
             }
         }
+        disabled.len() as u32
     });
 
+    let active_set_len = T::SessionInterface::validators().len() as u32;
+    if disabled_count > <Pallet<T>>::offending_validators_threshold() * active_set_len {
+        <Pallet<T>>::ensure_new_era();
+    }
+
     // `DisabledValidators` should be kept sorted
     debug_assert!(DisabledValidators::<T>::get().windows(2).all(|pair| pair[0] < pair[1]));
 }
 
 /// Slash cooperators. Accepts general parameters and the prior slash percentage of the validator.
 ///
+/// Cooperators which capped their slash in [`ErasCooperatorMaxSlash`] lose at most the capped
+/// proportion of their exposure. The part of the slash above the cap is not slashed from anyone,
+/// the validator only ever loses its own slash proportion.
+///
 /// Returns the amount of reward to pay out.
 fn slash_cooperators<T: Config>(
     params: SlashParams<T>,
@@ -558,9 +576,15 @@ fn slash_cooperators<T: Config>(
             let reputation = pallet_reputation::AccountReputation::<T>::get(stash)
                 .map(|r| r.reputation.points())
                 .unwrap_or_default();
-            let own_slash_prior =
-                SlashEntityOf::<T>::new(reputation, cooperator.value) * prior_slash_p;
-            let own_slash = SlashEntityOf::<T>::new(reputation, cooperator.value) * validator_loss;
+            let exposed = SlashEntityOf::<T>::new(reputation, cooperator.value);
+
+            let (prior_slash_p, validator_loss) =
+                match ErasCooperatorMaxSlash::<T>::get(params.slash_era, stash) {
+                    Some(cap) => (prior_slash_p.capped(cap), validator_loss.capped(cap)),
+                    None => (prior_slash_p, validator_loss),
+                };
+            let own_slash_prior = exposed * prior_slash_p;
+            let own_slash = exposed * validator_loss;
             let own_slash_difference = own_slash.saturating_sub(own_slash_prior);
 
             let era_slash = CooperatorSlashInEra::<T>::get(params.slash_era, stash)
@@ -786,6 +810,9 @@ pub fn do_slash<T: Config>(
 
     if !stake_value.is_zero() {
         let (imbalance, missing) = T::StakeCurrency::slash(stash, stake_value);
+        <Pallet<T>>::note_issuance(|report| {
+            report.stake_slashed.saturating_accrue(imbalance.peek())
+        });
         slashed_imbalance.subsume(imbalance);
 
         if !missing.is_zero() {
@@ -797,6 +824,10 @@ pub fn do_slash<T: Config>(
     }
     value.stake = stake_value;
     <pallet_reputation::Pallet<T>>::do_slash(stash, value.reputation)?;
+    <Pallet<T>>::note_issuance(|report| {
+        report.reputation_slashed =
+            report.reputation_slashed.saturating_add(*value.reputation).into()
+    });
 
     // trigger the event
     <Pallet<T>>::deposit_event(super::Event::<T>::Slashed { staker: stash.clone(), amount: value });
@@ -845,6 +876,9 @@ fn pay_reporters<T: Config>(
     reporters: &[T::AccountId],
 ) -> DispatchResult {
     if reward_payout.is_zero() || reporters.is_empty() {
+        <Pallet<T>>::note_issuance(|report| {
+            report.treasury_inflow.saturating_accrue(slashed_imbalance.peek())
+        });
         T::Slash::on_unbalanced(slashed_imbalance);
         return Ok(());
     }
@@ -859,7 +893,9 @@ fn pay_reporters<T: Config>(
     let reputation_per_reporter: ReputationPoint = (prop * *reputation_reward).into();
     let stake_per_reporter = prop * stake_reward.peek();
     for reporter in reporters {
-        pallet_reputation::Pallet::<T>::increase_creating(reporter, reputation_per_reporter);
+        let granted =
+            pallet_reputation::Pallet::<T>::earn_creating(reporter, reputation_per_reporter);
+        <Pallet<T>>::note_reputation_granted(granted);
 
         let (reporter_reward, rest) = stake_reward.split(stake_per_reporter);
         stake_reward = rest;
@@ -870,6 +906,9 @@ fn pay_reporters<T: Config>(
     }
 
     value_slashed.subsume(stake_reward); // remainder of reward division remains.
+    <Pallet<T>>::note_issuance(|report| {
+        report.treasury_inflow.saturating_accrue(value_slashed.peek())
+    });
     T::Slash::on_unbalanced(value_slashed);
 
     Ok(())
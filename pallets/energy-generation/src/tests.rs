@@ -233,6 +233,7 @@ fn change_controller_works() {
         assert_ok!(PowerPlant::chill(RuntimeOrigin::signed(10)));
 
         // change controller
+        NacLevels::set(vec![(5, 1)]);
         assert_ok!(PowerPlant::set_controller(RuntimeOrigin::signed(11), 5));
         assert_eq!(PowerPlant::bonded(11), Some(5));
         mock::start_active_era(1);
@@ -246,6 +247,55 @@ fn change_controller_works() {
     })
 }
 
+#[test]
+fn set_controller_requires_a_free_controller_with_access() {
+    ExtBuilder::default().build_and_execute(|| {
+        // 20 controls 21
+        assert_noop!(
+            PowerPlant::set_controller(RuntimeOrigin::signed(11), 20),
+            Error::<Test>::AlreadyPaired
+        );
+        assert_noop!(
+            PowerPlant::set_controller(RuntimeOrigin::signed(10), 5),
+            Error::<Test>::NotStash
+        );
+        // 5 has no NAC, then one without access
+        assert_noop!(
+            PowerPlant::set_controller(RuntimeOrigin::signed(11), 5),
+            Error::<Test>::ControllerWithoutAccess
+        );
+        NacLevels::set(vec![(5, 0)]);
+        assert_noop!(
+            PowerPlant::set_controller(RuntimeOrigin::signed(11), 5),
+            Error::<Test>::ControllerWithoutAccess
+        );
+
+        NacLevels::set(vec![(5, 2), (10, 1)]);
+        let ledger = PowerPlant::ledger(10).unwrap();
+        assert_ok!(PowerPlant::set_controller(RuntimeOrigin::signed(11), 5));
+        assert_eq!(
+            *staking_events_since_last_call().last().unwrap(),
+            Event::ControllerSet { stash: 11, controller: 5 }
+        );
+        assert_eq!(PowerPlant::ledger(5), Some(ledger));
+        assert_eq!(PowerPlant::ledger(10), None);
+
+        // the old controller may now control another stash
+        assert_ok!(PowerPlant::set_controller(RuntimeOrigin::signed(21), 10));
+        assert_eq!(PowerPlant::bonded(21), Some(10));
+
+        // the rotated controllers keep managing the stake
+        assert_ok!(PowerPlant::unbond(RuntimeOrigin::signed(5), 100));
+        assert_eq!(PowerPlant::ledger(5).unwrap().active, 900);
+        assert_ok!(PowerPlant::chill(RuntimeOrigin::signed(10)));
+        assert!(!Validators::<Test>::contains_key(21));
+        assert_noop!(
+            PowerPlant::unbond(RuntimeOrigin::signed(20), 100),
+            Error::<Test>::NotController
+        );
+    })
+}
+
 #[ignore]
 #[test]
 fn rewards_should_work() {
@@ -1534,6 +1584,128 @@ fn auto_withdraw_may_not_unlock_all_chunks() {
     })
 }
 
+#[test]
+fn consolidate_unlocking_merges_chunks_of_same_era() {
+    ExtBuilder::default().no_cooperate().build_and_execute(|| {
+        Ledger::<Test>::insert(
+            10,
+            StakingLedger {
+                stash: 11,
+                total: 1000,
+                active: 400,
+                unlocking: bounded_vec![
+                    UnlockChunk { value: 100, era: 5 },
+                    UnlockChunk { value: 300, era: 5 },
+                    UnlockChunk { value: 200, era: 6 },
+                ],
+                claimed_rewards: bounded_vec![],
+            },
+        );
+        let _ = staking_events_since_last_call();
+
+        assert_ok!(PowerPlant::consolidate_unlocking(RuntimeOrigin::signed(10)));
+        assert_eq!(
+            PowerPlant::ledger(10),
+            Some(StakingLedger {
+                stash: 11,
+                total: 1000,
+                active: 400,
+                unlocking: bounded_vec![
+                    UnlockChunk { value: 400, era: 5 },
+                    UnlockChunk { value: 200, era: 6 },
+                ],
+                claimed_rewards: bounded_vec![],
+            })
+        );
+        assert_eq!(
+            staking_events_since_last_call(),
+            vec![Event::UnlockingConsolidated { stash: 11, merged_chunks: 1 }]
+        );
+
+        // already compact, nothing changes
+        assert_storage_noop!(assert_ok!(PowerPlant::consolidate_unlocking(RuntimeOrigin::signed(
+            10
+        ))));
+
+        assert_noop!(
+            PowerPlant::consolidate_unlocking(RuntimeOrigin::signed(1234)),
+            Error::<Test>::NotController
+        );
+    });
+}
+
+#[test]
+fn update_prefs_sets_commission_and_payee() {
+    ExtBuilder::default().build_and_execute(|| {
+        let _ = staking_events_since_last_call();
+
+        // both at once
+        assert_ok!(PowerPlant::update_prefs(
+            RuntimeOrigin::signed(10),
+            Some(Perbill::from_percent(10)),
+            Some(RewardDestination::Controller),
+        ));
+        assert_eq!(PowerPlant::validators(11).commission, Perbill::from_percent(10));
+        assert_eq!(PowerPlant::payee(11), RewardDestination::Controller);
+        assert_eq!(
+            staking_events_since_last_call(),
+            vec![Event::ValidatorPrefsSet { stash: 11, prefs: PowerPlant::validators(11) }]
+        );
+
+        // only the payee
+        assert_ok!(PowerPlant::update_prefs(
+            RuntimeOrigin::signed(10),
+            None,
+            Some(RewardDestination::Stash),
+        ));
+        assert_eq!(PowerPlant::validators(11).commission, Perbill::from_percent(10));
+        assert_eq!(PowerPlant::payee(11), RewardDestination::Stash);
+        assert!(staking_events_since_last_call().is_empty());
+
+        // only the commission
+        assert_ok!(PowerPlant::update_prefs(
+            RuntimeOrigin::signed(10),
+            Some(Perbill::from_percent(20)),
+            None,
+        ));
+        assert_eq!(PowerPlant::validators(11).commission, Perbill::from_percent(20));
+        assert_eq!(PowerPlant::payee(11), RewardDestination::Stash);
+    });
+}
+
+#[test]
+fn update_prefs_applies_nothing_if_any_update_is_invalid() {
+    ExtBuilder::default().build_and_execute(|| {
+        MinCommission::<Test>::put(Perbill::from_percent(5));
+        let payee = PowerPlant::payee(11);
+
+        assert_noop!(
+            PowerPlant::update_prefs(
+                RuntimeOrigin::signed(10),
+                Some(Perbill::from_percent(1)),
+                Some(RewardDestination::Controller),
+            ),
+            Error::<Test>::CommissionTooLow
+        );
+        assert_eq!(PowerPlant::payee(11), payee);
+
+        // cooperators have no commission to update
+        assert_noop!(
+            PowerPlant::update_prefs(
+                RuntimeOrigin::signed(100),
+                Some(Perbill::from_percent(10)),
+                None
+            ),
+            Error::<Test>::NotStash
+        );
+
+        assert_noop!(
+            PowerPlant::update_prefs(RuntimeOrigin::signed(1234), None, None),
+            Error::<Test>::NotController
+        );
+    });
+}
+
 #[test]
 fn rebond_works() {
     //
@@ -2396,6 +2568,114 @@ fn offence_deselects_validator_even_when_slash_is_zero() {
         });
 }
 
+#[test]
+fn capped_cooperators_are_slashed_up_to_their_cap() {
+    ExtBuilder::default().has_stakers(false).build_and_execute(|| {
+        make_validator(10, 11, 1000);
+        bond_cooperator(1000, 100, 1000, vec![(11, 1000)]);
+        bond_cooperator(2000, 200, 1000, vec![(11, 1000)]);
+
+        assert_ok!(PowerPlant::set_max_slash(
+            RuntimeOrigin::signed(200),
+            Some(Perbill::from_percent(10))
+        ));
+        assert_eq!(
+            *staking_events_since_last_call().last().unwrap(),
+            Event::MaxSlashSet { stash: 2000, max_slash: Some(Perbill::from_percent(10)) }
+        );
+        assert_noop!(
+            PowerPlant::set_max_slash(RuntimeOrigin::signed(2000), None),
+            Error::<Test>::NotController
+        );
+
+        mock::start_active_era(1);
+        assert_eq!(PowerPlant::eras_cooperator_max_slash(1, 2000), Some(Perbill::from_percent(10)));
+        assert_eq!(PowerPlant::eras_cooperator_max_slash(1, 1000), None);
+
+        on_offence_now(
+            &[OffenceDetails {
+                offender: (11, PowerPlant::eras_stakers(active_era(), 11)),
+                reporters: vec![],
+            }],
+            &[Perbill::from_percent(50)],
+        );
+
+        // the uncapped cooperator loses half of its stake, the capped one only a tenth
+        assert_eq!(PowerPlant::ledger(100).unwrap().active, 500);
+        assert_eq!(PowerPlant::ledger(200).unwrap().active, 900);
+        // the validator only loses half of its own stake, the slash above the cap is dropped
+        assert_eq!(PowerPlant::ledger(10).unwrap().active, 1000 - 500);
+    });
+}
+
+#[test]
+fn cooperators_capping_their_slash_at_zero_dont_raise_the_validator_slash() {
+    ExtBuilder::default().has_stakers(false).build_and_execute(|| {
+        make_validator(10, 11, 1000);
+        bond_cooperator(1000, 100, 1000, vec![(11, 1000)]);
+        bond_cooperator(2000, 200, 5000, vec![(11, 5000)]);
+        assert_ok!(PowerPlant::set_max_slash(RuntimeOrigin::signed(100), Some(Perbill::zero())));
+        assert_ok!(PowerPlant::set_max_slash(RuntimeOrigin::signed(200), Some(Perbill::zero())));
+
+        mock::start_active_era(1);
+        on_offence_now(
+            &[OffenceDetails {
+                offender: (11, PowerPlant::eras_stakers(active_era(), 11)),
+                reporters: vec![],
+            }],
+            &[Perbill::from_percent(20)],
+        );
+
+        // the cooperators lose nothing and the validator no more than its own slash fraction
+        assert_eq!(PowerPlant::ledger(100).unwrap().active, 1000);
+        assert_eq!(PowerPlant::ledger(200).unwrap().active, 5000);
+        assert_eq!(PowerPlant::ledger(10).unwrap().active, 1000 - 200);
+    });
+}
+
+#[test]
+fn capped_cooperators_forfeit_part_of_reward_to_validator() {
+    ExtBuilder::default()
+        .has_stakers(false)
+        .energy_per_stake_currency(1)
+        .build_and_execute(|| {
+            make_validator(10, 11, 1000);
+            bond_cooperator(1000, 100, 1000, vec![(11, 1000)]);
+            bond_cooperator(2000, 200, 1000, vec![(11, 1000)]);
+
+            // with no slashing at all the whole `SlashCapRewardPenalty` is forfeited
+            assert_ok!(PowerPlant::set_max_slash(
+                RuntimeOrigin::signed(200),
+                Some(Perbill::zero())
+            ));
+
+            mock::start_active_era(1);
+            let _ = current_total_payout_for_duration(reward_time_per_era());
+            mock::start_active_era(2);
+            assert_ok!(PowerPlant::payout_stakers(RuntimeOrigin::signed(1337), 11, 1));
+
+            let uncapped_reward = Assets::balance(VNRG::get(), 100);
+            let capped_reward = Assets::balance(VNRG::get(), 200);
+            assert!(uncapped_reward > 0);
+            assert_eq_error_rate!(capped_reward, SlashCapRewardPenalty::get() * uncapped_reward, 2);
+
+            // the validator is rewarded for its own stake and the forfeited part
+            let validator_reward = uncapped_reward + (uncapped_reward - capped_reward);
+            let validator_multiplier = PowerPlant::calculate_energy_reward_multiplier(&11);
+            assert_eq_error_rate!(
+                Assets::balance(VNRG::get(), 10),
+                validator_reward + validator_multiplier * validator_reward,
+                4
+            );
+
+            // removing the cap restores the full reward from the next era
+            assert_ok!(PowerPlant::set_max_slash(RuntimeOrigin::signed(200), None));
+            assert_eq!(PowerPlant::eras_cooperator_max_slash(2, 2000), Some(Perbill::zero()));
+            mock::start_active_era(3);
+            assert_eq!(PowerPlant::eras_cooperator_max_slash(3, 2000), None);
+        });
+}
+
 #[test]
 fn slashing_performed_according_exposure() {
     // This test checks that slashing is performed according the exposure (or more precisely,
@@ -4651,6 +4931,61 @@ fn payout_to_any_account_works() {
     })
 }
 
+#[test]
+fn set_payee_rejects_account_that_cannot_receive_rewards() {
+    ExtBuilder::default().build_and_execute(|| {
+        // 42 holds some energy but is blocked from receiving more
+        assert_ok!(Assets::mint(RuntimeOrigin::signed(1), VNRG::get().into(), 42, 10));
+        assert_ok!(Assets::block(RuntimeOrigin::signed(1), VNRG::get().into(), 42));
+
+        assert_noop!(
+            PowerPlant::set_payee(RuntimeOrigin::signed(10), RewardDestination::Account(42)),
+            Error::<Test>::InvalidPayee
+        );
+        assert_noop!(
+            PowerPlant::bond(RuntimeOrigin::signed(3), 4, 1000, RewardDestination::Account(42)),
+            Error::<Test>::InvalidPayee
+        );
+
+        // accounts without energy can still receive rewards
+        assert_ok!(PowerPlant::set_payee(
+            RuntimeOrigin::signed(10),
+            RewardDestination::Account(43)
+        ));
+        assert_eq!(PowerPlant::payee(11), RewardDestination::Account(43));
+    })
+}
+
+#[test]
+fn staked_payee_cannot_be_chosen_and_pays_into_stash() {
+    ExtBuilder::default().has_stakers(false).build_and_execute(|| {
+        make_validator(10, 11, 10000000);
+        bond_cooperator(1234, 1337, 1000000, vec![(11, 1000000)]);
+
+        assert_noop!(
+            PowerPlant::set_payee(RuntimeOrigin::signed(1337), RewardDestination::Staked),
+            Error::<Test>::RewardsCannotBeStaked
+        );
+        assert_noop!(
+            PowerPlant::bond(RuntimeOrigin::signed(3), 4, 1000, RewardDestination::Staked),
+            Error::<Test>::RewardsCannotBeStaked
+        );
+
+        // a payee set before `Staked` was rejected is paid into the stash
+        Payee::<Test>::insert(1234, RewardDestination::Staked);
+        let stash_energy = Assets::balance(VNRG::get(), 1234);
+        let controller_energy = Assets::balance(VNRG::get(), 1337);
+
+        mock::start_active_era(1);
+        let _ = current_total_payout_for_duration(reward_time_per_era());
+        mock::start_active_era(2);
+        assert_ok!(PowerPlant::payout_stakers(RuntimeOrigin::signed(1337), 11, 1));
+
+        assert!(Assets::balance(VNRG::get(), 1234) > stash_energy);
+        assert_eq!(Assets::balance(VNRG::get(), 1337), controller_energy);
+    })
+}
+
 #[test]
 fn session_buffering_with_offset() {
     // similar to live-chains, have some offset for the first session
@@ -5870,6 +6205,1337 @@ fn set_min_commission_works_with_admin_origin() {
     })
 }
 
+#[test]
+fn era_issuance_report_reconciles_with_issuance() {
+    ExtBuilder::default().energy_per_stake_currency(1).build_and_execute(|| {
+        mock::start_active_era(1);
+        PowerPlant::reward_by_ids(vec![(11, 1.into())]);
+        mock::start_active_era(2);
+
+        let report_before = PowerPlant::era_issuance_report(2).unwrap();
+        let energy_before = Assets::total_supply(VNRG::get());
+        let stake_before = Balances::total_issuance();
+
+        mock::make_all_reward_payment(1);
+        PowerPlant::reward_by_ids(vec![(21, 50.into())]);
+        on_offence_now(
+            &[OffenceDetails {
+                offender: (11, PowerPlant::eras_stakers(active_era(), 11)),
+                reporters: vec![],
+            }],
+            &[Perbill::from_percent(10)],
+        );
+
+        let report = PowerPlant::era_issuance_report(2).unwrap();
+        let energy_minted = Assets::total_supply(VNRG::get()) - energy_before;
+        assert!(energy_minted > 0);
+        assert_eq!(report.energy_minted - report_before.energy_minted, energy_minted);
+        assert_eq!(*report.reputation_granted - *report_before.reputation_granted, 50,);
+
+        // `Slash` drops the imbalance in the mock, so everything slashed is burnt.
+        let stake_slashed = stake_before - Balances::total_issuance();
+        assert!(stake_slashed > 0);
+        assert_eq!(report.stake_slashed, stake_slashed);
+        assert_eq!(report.treasury_inflow, stake_slashed);
+        assert!(*report.reputation_slashed > 0);
+
+        // reports are bounded by the active era and the history depth
+        assert_eq!(PowerPlant::era_issuance_report(3), None);
+        mock::start_active_era(HistoryDepth::get() + 3);
+        assert_eq!(PowerPlant::era_issuance_report(2), None);
+        assert!(PowerPlant::era_issuance_report(3).is_some());
+    });
+}
+
+#[test]
+fn validator_uptime_accounts_unresponsive_sessions() {
+    ExtBuilder::default().build_and_execute(|| {
+        mock::start_active_era(1);
+        // no session of the era has ended yet
+        assert_eq!(PowerPlant::validator_uptime(&11, 1), None);
+
+        // 11 misses the heartbeat of the first session of era 1
+        PowerPlant::note_unresponsive(&11);
+        advance_session();
+        assert_eq!(PowerPlant::validator_uptime(&11, 1), Some(Perbill::zero()));
+        assert_eq!(PowerPlant::validator_uptime(&21, 1), Some(Perbill::one()));
+
+        mock::start_active_era(2);
+        assert_eq!(PowerPlant::validator_uptime(&11, 1), Some(Perbill::from_rational(2u32, 3)));
+        assert_eq!(PowerPlant::validator_uptime(&21, 1), Some(Perbill::one()));
+        // not elected
+        assert_eq!(PowerPlant::validator_uptime(&31, 1), None);
+
+        mock::start_active_era(HistoryDepth::get() + 2);
+        assert_eq!(PowerPlant::validator_uptime(&11, 1), None);
+        assert_eq!(ErasValidatorUptime::<Test>::get(1, 11), None);
+    });
+}
+
+#[test]
+fn session_keys_rotation_reminder_fires_once_keys_are_old_enough() {
+    ExtBuilder::default().build_and_execute(|| {
+        let rotation_reminders = || {
+            staking_events_since_last_call()
+                .into_iter()
+                .filter(|e| matches!(e, Event::SessionKeysRotationDue { .. }))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(SessionKeysRotationAge::get(), 4);
+
+        // keys are first observed when session 0 ends
+        start_session(4);
+        assert_eq!(rotation_reminders(), vec![]);
+
+        start_session(5);
+        assert_eq!(
+            rotation_reminders(),
+            vec![
+                Event::SessionKeysRotationDue { stash: 11, age: 4 },
+                Event::SessionKeysRotationDue { stash: 21, age: 4 },
+            ]
+        );
+
+        // 11 rotates its keys, the age restarts from the session in which they are observed
+        assert_ok!(Session::set_keys(
+            RuntimeOrigin::signed(10),
+            SessionKeys { other: 110.into() },
+            vec![]
+        ));
+        start_session(9);
+        // 21 is reminded only once for the same keys
+        assert_eq!(rotation_reminders(), vec![]);
+        assert_eq!(ValidatorSessionKeysAge::<Test>::get(11).map(|r| r.since), Some(5));
+
+        start_session(10);
+        assert_eq!(rotation_reminders(), vec![Event::SessionKeysRotationDue { stash: 11, age: 4 }]);
+    });
+}
+
+#[test]
+fn outdated_client_reduces_validator_reward_after_grace_period() {
+    ExtBuilder::default()
+        .has_stakers(false)
+        .energy_per_stake_currency(1)
+        .build_and_execute(|| {
+            make_validator(10, 11, 1000);
+            make_validator(20, 21, 1000);
+            let version = |major, minor, patch| ClientVersion { major, minor, patch };
+
+            mock::start_active_era(1);
+            assert_ok!(PowerPlant::set_minimum_client_version(
+                RuntimeOrigin::root(),
+                Some(version(1, 2, 0))
+            ));
+            let requirement =
+                ClientVersionRequirement { minimum: version(1, 2, 0), enforced_from: 2 };
+            assert_eq!(MinimumClientVersion::<Test>::get(), Some(requirement));
+
+            // only a validator's controller can report its version
+            assert_noop!(
+                PowerPlant::report_client_version(RuntimeOrigin::signed(11), version(1, 2, 0)),
+                Error::<Test>::NotController
+            );
+            assert_ok!(PowerPlant::report_client_version(
+                RuntimeOrigin::signed(10),
+                version(1, 2, 0)
+            ));
+            assert_ok!(PowerPlant::report_client_version(
+                RuntimeOrigin::signed(20),
+                version(1, 1, 9)
+            ));
+            assert_eq!(
+                *staking_events_since_last_call().last().unwrap(),
+                Event::ClientVersionReported { stash: 21, version: version(1, 1, 9) }
+            );
+            assert!(!PowerPlant::is_client_outdated(&11, 2));
+            assert!(PowerPlant::is_client_outdated(&21, 2));
+            // still within the grace period
+            assert!(!PowerPlant::is_client_outdated(&21, 1));
+
+            mock::start_active_era(3);
+            assert!(!ErasOutdatedValidators::<Test>::contains_key(1, 21));
+            assert!(ErasOutdatedValidators::<Test>::contains_key(2, 21));
+            assert!(!ErasOutdatedValidators::<Test>::contains_key(2, 11));
+
+            let payout = |stash, controller, era| {
+                let before = Assets::balance(VNRG::get(), controller);
+                assert_ok!(PowerPlant::payout_stakers(RuntimeOrigin::signed(1337), stash, era));
+                Assets::balance(VNRG::get(), controller) - before
+            };
+
+            let up_to_date_reward = payout(11, 10, 1);
+            assert!(up_to_date_reward > 0);
+            assert_eq!(payout(21, 20, 1), up_to_date_reward);
+
+            assert_eq!(payout(11, 10, 2), up_to_date_reward);
+            assert_eq_error_rate!(
+                payout(21, 20, 2),
+                OutdatedClientRewardPenalty::get().left_from_one() * up_to_date_reward,
+                2
+            );
+        });
+}
+
+#[test]
+fn backing_under_subscribed_parachains_boosts_validator_reward() {
+    ExtBuilder::default()
+        .has_stakers(false)
+        .energy_per_stake_currency(1)
+        .build_and_execute(|| {
+            make_validator(10, 11, 1000);
+            make_validator(20, 21, 1000);
+
+            mock::start_active_era(1);
+            PowerPlant::note_backing_boost([21]);
+            // the boost is granted once per era
+            PowerPlant::note_backing_boost([21]);
+            assert!(ErasBackingBoostedValidators::<Test>::contains_key(1, 21));
+            assert!(!ErasBackingBoostedValidators::<Test>::contains_key(1, 11));
+
+            mock::start_active_era(2);
+            // no boost is noted while it's disabled
+            BackingBoost::set(Perbill::zero());
+            PowerPlant::note_backing_boost([11]);
+            assert!(!ErasBackingBoostedValidators::<Test>::contains_key(2, 11));
+            BackingBoost::set(Perbill::from_percent(10));
+
+            let payout = |stash, controller, era| {
+                let before = Assets::balance(VNRG::get(), controller);
+                assert_ok!(PowerPlant::payout_stakers(RuntimeOrigin::signed(1337), stash, era));
+                Assets::balance(VNRG::get(), controller) - before
+            };
+            let reward = payout(11, 10, 1);
+            assert!(reward > 0);
+            assert_eq_error_rate!(payout(21, 20, 1), reward + BackingBoost::get() * reward, 2);
+        });
+}
+
+#[test]
+fn sweep_stale_rewards_moves_expired_unclaimed_rewards_to_destination() {
+    ExtBuilder::default().energy_per_stake_currency(1).build_and_execute(|| {
+        HistoryDepth::set(2);
+        let destination = StaleRewardsDestination::get();
+
+        mock::start_active_era(1);
+        let rate = PowerPlant::eras_energy_per_stake_cur(1).unwrap();
+        let era_rewards = ErasUnclaimedRewards::<Test>::get(1);
+        assert!(era_rewards > 0);
+        assert_eq!(era_rewards, PowerPlant::eras_total_stake(1) / rate);
+
+        mock::start_active_era(2);
+        assert_ok!(PowerPlant::payout_stakers(RuntimeOrigin::signed(1337), 11, 1));
+        assert_eq!(
+            ErasUnclaimedRewards::<Test>::get(1),
+            era_rewards - PowerPlant::eras_stakers(1, 11).total / rate
+        );
+
+        // every era can still be claimed
+        assert_ok!(PowerPlant::sweep_stale_rewards(RuntimeOrigin::signed(1337)));
+        assert_eq!(Assets::balance(VNRG::get(), destination), 0);
+
+        mock::start_active_era(5);
+        assert_noop!(
+            PowerPlant::payout_stakers(RuntimeOrigin::signed(1337), 21, 2),
+            Error::<Test>::InvalidEraToReward
+                .with_weight(<Test as Config>::ThisWeightInfo::payout_stakers_alive_staked(0))
+        );
+        let stale_rewards: Balance = (0..=2).map(ErasUnclaimedRewards::<Test>::get).sum();
+        let claimable_rewards: Vec<_> = (3..=5).map(ErasUnclaimedRewards::<Test>::get).collect();
+
+        // at most two eras are swept per call
+        assert_ok!(PowerPlant::sweep_stale_rewards(RuntimeOrigin::signed(1337)));
+        assert_eq!(ErasUnclaimedRewards::<Test>::iter_keys().filter(|era| *era <= 2).count(), 1);
+        let first_sweep = Assets::balance(VNRG::get(), destination);
+
+        assert_ok!(PowerPlant::sweep_stale_rewards(RuntimeOrigin::signed(1337)));
+        assert_eq!(Assets::balance(VNRG::get(), destination), stale_rewards);
+        assert_eq!(
+            *staking_events_since_last_call().last().unwrap(),
+            Event::StaleRewardsSwept { eras: 1, amount: stale_rewards - first_sweep }
+        );
+
+        // sweeping again is a no-op and claimable eras are left untouched
+        assert_ok!(PowerPlant::sweep_stale_rewards(RuntimeOrigin::signed(1337)));
+        assert_eq!(Assets::balance(VNRG::get(), destination), stale_rewards);
+        assert_eq!(staking_events_since_last_call(), vec![]);
+        assert_eq!(
+            (3..=5).map(ErasUnclaimedRewards::<Test>::get).collect::<Vec<_>>(),
+            claimable_rewards
+        );
+    });
+}
+
+#[test]
+fn onboarding_grace_covers_first_elected_era_only() {
+    ExtBuilder::default().build_and_execute(|| {
+        // genesis validators are first elected for era 0
+        assert_eq!(ValidatorFirstActiveEra::<Test>::get(11), Some(0));
+        assert!(PowerPlant::in_onboarding_grace(&11));
+
+        mock::start_active_era(1);
+        assert!(!PowerPlant::in_onboarding_grace(&11));
+
+        // a new validator outstaking the others gets elected for era 2
+        make_validator(40, 41, 10_000);
+        assert!(!PowerPlant::in_onboarding_grace(&41));
+        mock::start_active_era(2);
+        assert!(validator_controllers().contains(&40));
+        assert_eq!(ValidatorFirstActiveEra::<Test>::get(41), Some(2));
+        assert!(PowerPlant::in_onboarding_grace(&41));
+
+        mock::start_active_era(3);
+        assert!(!PowerPlant::in_onboarding_grace(&41));
+        assert_eq!(ValidatorFirstActiveEra::<Test>::get(41), Some(2));
+    });
+}
+
+#[test]
+fn energy_reward_minting_is_spread_across_blocks_under_cap() {
+    ExtBuilder::default().energy_per_stake_currency(1).build_and_execute(|| {
+        mock::start_active_era(1);
+        mock::start_active_era(2);
+        let rate = PowerPlant::eras_energy_per_stake_cur(1).unwrap();
+        let max_mint = PowerPlant::eras_stakers(1, 11).total / rate / 3;
+        MaxEnergyMintPerBlock::set(Some(max_mint));
+        let issuance_before = Assets::total_supply(VNRG::get());
+        let _ = staking_events_since_last_call();
+
+        assert_ok!(PowerPlant::payout_stakers(RuntimeOrigin::signed(1337), 11, 1));
+        let (rewarded, deferred) = staking_events_since_last_call().into_iter().fold(
+            (0, 0),
+            |(rewarded, deferred), event| match event {
+                Event::Rewarded { amount, .. } => (rewarded + amount, deferred),
+                Event::EnergyRewardDeferred { amount, .. } => (rewarded, deferred + amount),
+                _ => (rewarded, deferred),
+            },
+        );
+        assert_eq!(rewarded, max_mint);
+        assert!(deferred > max_mint);
+        assert_eq!(Assets::total_supply(VNRG::get()), issuance_before + max_mint);
+        assert_eq!(DeferredEnergyRewards::<Test>::iter_values().sum::<Balance>(), deferred);
+
+        // the deferred rewards are minted over the following blocks, within the cap
+        let mut block = System::block_number();
+        while DeferredEnergyRewards::<Test>::iter().next().is_some() {
+            let issuance = Assets::total_supply(VNRG::get());
+            block += 1;
+            run_to_block(block);
+            assert!(Assets::total_supply(VNRG::get()) - issuance <= max_mint);
+            assert!(BlockEnergyMinted::<Test>::get() <= max_mint);
+        }
+        assert_eq!(Assets::total_supply(VNRG::get()), issuance_before + rewarded + deferred);
+        let paid: Balance = staking_events_since_last_call()
+            .into_iter()
+            .filter_map(|event| match event {
+                Event::DeferredEnergyRewardPaid { amount, .. } => Some(amount),
+                _ => None,
+            })
+            .sum();
+        assert_eq!(paid, deferred);
+
+        // without a cap rewards are minted at once
+        MaxEnergyMintPerBlock::set(None);
+        assert_ok!(PowerPlant::payout_stakers(RuntimeOrigin::signed(1337), 21, 1));
+        assert!(staking_events_since_last_call()
+            .iter()
+            .all(|event| !matches!(event, Event::EnergyRewardDeferred { .. })));
+        assert_eq!(DeferredEnergyRewards::<Test>::iter().count(), 0);
+    });
+}
+
+#[test]
+fn deferred_rewards_an_account_cannot_receive_dont_hold_up_the_others() {
+    ExtBuilder::default().build_and_execute(|| {
+        MaxEnergyMintPerBlock::set(Some(60));
+        for blocked in [42, 44] {
+            assert_ok!(Assets::mint(RuntimeOrigin::signed(1), VNRG::get().into(), blocked, 10));
+            assert_ok!(Assets::block(RuntimeOrigin::signed(1), VNRG::get().into(), blocked));
+        }
+        DeferredEnergyRewards::<Test>::insert(42, 100);
+        DeferredEnergyRewards::<Test>::insert(43, 50);
+        DeferredEnergyRewards::<Test>::insert(44, 10);
+        let _ = staking_events_since_last_call();
+
+        run_to_block(System::block_number() + 1);
+        let events = staking_events_since_last_call();
+        // only a part of the reward of 42 fits the allowance, it's kept for a later block
+        assert_eq!(DeferredEnergyRewards::<Test>::get(42), 100);
+        assert!(events.contains(&Event::DeferredEnergyRewardPaid { account: 43, amount: 50 }));
+        assert_eq!(Assets::balance(VNRG::get(), 43), 50);
+        // the whole reward of 44 fits, but it can't be received and is dropped
+        assert!(!DeferredEnergyRewards::<Test>::contains_key(44));
+        assert!(events.contains(&Event::DeferredEnergyRewardDropped { account: 44, amount: 10 }));
+        assert_eq!(Assets::balance(VNRG::get(), 44), 10);
+    });
+}
+
+#[test]
+fn paused_rewards_are_deferred_or_forfeited() {
+    ExtBuilder::default().energy_per_stake_currency(1).build_and_execute(|| {
+        assert_noop!(PowerPlant::set_rewards_paused(RuntimeOrigin::signed(11), true), BadOrigin);
+        assert_noop!(
+            PowerPlant::set_rewards_paused(RuntimeOrigin::root(), false),
+            Error::<Test>::RewardsPauseUnchanged
+        );
+        assert_ok!(PowerPlant::set_rewards_paused(RuntimeOrigin::root(), true));
+        assert_eq!(
+            *staking_events_since_last_call().last().unwrap(),
+            Event::RewardsPauseSet { paused: true }
+        );
+
+        // eras keep rotating while paused
+        mock::start_active_era(1);
+        mock::start_active_era(2);
+        assert_eq!(active_era(), 2);
+        let issuance_before = Assets::total_supply(VNRG::get());
+
+        // claimed rewards are deferred, nothing is minted
+        assert_ok!(PowerPlant::payout_stakers(RuntimeOrigin::signed(1337), 11, 1));
+        let events = staking_events_since_last_call();
+        assert!(events.iter().all(|event| !matches!(event, Event::Rewarded { .. })));
+        let deferred: Balance = events
+            .iter()
+            .filter_map(|event| match event {
+                Event::EnergyRewardDeferred { amount, .. } => Some(*amount),
+                _ => None,
+            })
+            .sum();
+        assert!(deferred > 0);
+        assert_eq!(DeferredEnergyRewards::<Test>::iter_values().sum::<Balance>(), deferred);
+        run_to_block(System::block_number() + 1);
+        assert_eq!(Assets::total_supply(VNRG::get()), issuance_before);
+        assert_noop!(
+            PowerPlant::sweep_stale_rewards(RuntimeOrigin::signed(1337)),
+            Error::<Test>::RewardsArePaused
+        );
+
+        // without deferral they are forfeited
+        DeferRewardsWhilePaused::set(false);
+        assert_ok!(PowerPlant::payout_stakers(RuntimeOrigin::signed(1337), 21, 1));
+        let events = staking_events_since_last_call();
+        assert!(events.iter().any(|event| matches!(event, Event::EnergyRewardForfeited { .. })));
+        assert!(events.iter().all(|event| !matches!(event, Event::EnergyRewardDeferred { .. })));
+        assert_eq!(DeferredEnergyRewards::<Test>::iter_values().sum::<Balance>(), deferred);
+        assert_noop!(
+            PowerPlant::payout_stakers(RuntimeOrigin::signed(1337), 21, 1),
+            Error::<Test>::AlreadyClaimed
+                .with_weight(<Test as Config>::ThisWeightInfo::payout_stakers_alive_staked(0))
+        );
+
+        // the deferred rewards are minted once rewards resume
+        assert_ok!(PowerPlant::set_rewards_paused(RuntimeOrigin::root(), false));
+        run_to_block(System::block_number() + 1);
+        assert_eq!(Assets::total_supply(VNRG::get()), issuance_before + deferred);
+        assert_eq!(DeferredEnergyRewards::<Test>::iter().count(), 0);
+        let paid: Balance = staking_events_since_last_call()
+            .into_iter()
+            .filter_map(|event| match event {
+                Event::DeferredEnergyRewardPaid { amount, .. } => Some(amount),
+                _ => None,
+            })
+            .sum();
+        assert_eq!(paid, deferred);
+    });
+}
+
+#[test]
+fn zero_energy_rate_falls_back_to_the_floor() {
+    ExtBuilder::default().build_and_execute(|| {
+        let floor = MinEnergyPerStakeCurrency::get();
+        // the calculator returns zero without a rate set by governance
+        CurrentEnergyPerStakeCurrency::<Test>::kill();
+
+        start_active_era(1);
+        assert_eq!(PowerPlant::eras_energy_per_stake_cur(1), Some(floor));
+        assert!(staking_events_since_last_call()
+            .contains(&Event::EnergyRateFloorApplied { era_index: 1, energy_rate: floor }));
+        assert!(ErasUnclaimedRewards::<Test>::get(1) > 0);
+
+        // a zero rate is intended while rewards are paused
+        assert_ok!(PowerPlant::set_rewards_paused(RuntimeOrigin::root(), true));
+        start_active_era(2);
+        assert_eq!(PowerPlant::eras_energy_per_stake_cur(2), Some(0));
+        assert!(!staking_events_since_last_call()
+            .iter()
+            .any(|event| matches!(event, Event::EnergyRateFloorApplied { .. })));
+
+        // a zero floor disables the fallback
+        assert_ok!(PowerPlant::set_rewards_paused(RuntimeOrigin::root(), false));
+        MinEnergyPerStakeCurrency::set(0);
+        start_active_era(3);
+        assert_eq!(PowerPlant::eras_energy_per_stake_cur(3), Some(0));
+        assert!(!staking_events_since_last_call()
+            .iter()
+            .any(|event| matches!(event, Event::EnergyRateFloorApplied { .. })));
+    });
+}
+
+#[test]
+fn minimum_validator_count_retains_most_reputable_unqualified_validators() {
+    ExtBuilder::default().minimum_validator_count(2).build_and_execute(|| {
+        let threshold: ReputationPoint = ValidatorReputationTier::get().into();
+        let set_points = |stash: AccountId, points: u64| {
+            assert_ok!(ReputationPallet::force_set_points(
+                RuntimeOrigin::root(),
+                stash,
+                ReputationPoint::new(points)
+            ));
+        };
+        set_points(31, *threshold);
+        // 11 and 21 lose their reputation, 21 a bit less than 11
+        set_points(11, 0);
+        set_points(21, *threshold / 2);
+        let _ = staking_events_since_last_call();
+
+        start_active_era(1);
+
+        // 21 is kept to reach the minimum, 11 is chilled
+        assert_eq_uvec!(Validators::<Test>::iter_keys().collect::<Vec<_>>(), vec![21, 31]);
+        assert_eq_uvec!(Session::validators(), vec![21, 31]);
+        let events = staking_events_since_last_call();
+        assert!(events.contains(&Event::MinimumValidatorCountEnforced { retained: 1, minimum: 2 }));
+        assert!(events.contains(&Event::Chilled { stash: 11 }));
+
+        // once there are enough reputable validators, the others are chilled again
+        set_points(11, *threshold);
+        assert_ok!(PowerPlant::validate(RuntimeOrigin::signed(10), ValidatorPrefs::default()));
+        start_active_era(2);
+        assert_eq_uvec!(Validators::<Test>::iter_keys().collect::<Vec<_>>(), vec![11, 31]);
+        assert!(!staking_events_since_last_call()
+            .iter()
+            .any(|event| matches!(event, Event::MinimumValidatorCountEnforced { .. })));
+    });
+}
+
+#[test]
+fn net_energy_inflation_turns_negative_under_heavy_fee_burning() {
+    ExtBuilder::default().energy_per_stake_currency(1).build_and_execute(|| {
+        // the first era has not ended yet, so its figures so far are used
+        assert_eq!(active_era(), 0);
+        PowerPlant::note_energy_burned(100);
+        assert_eq!(PowerPlant::net_energy_inflation(), -100);
+
+        mock::start_active_era(1);
+        PowerPlant::reward_by_ids(vec![(11, 1.into())]);
+        mock::start_active_era(2);
+        let energy_before = Assets::total_supply(VNRG::get());
+        mock::make_all_reward_payment(1);
+        let energy_minted = Assets::total_supply(VNRG::get()) - energy_before;
+        assert!(energy_minted > 0);
+        PowerPlant::note_energy_burned(energy_minted / 2);
+        // era 2 has not ended yet
+        assert_eq!(PowerPlant::net_energy_inflation(), 0);
+
+        mock::start_active_era(3);
+        assert_eq!(PowerPlant::net_energy_inflation(), (energy_minted - energy_minted / 2) as i128);
+
+        // fees burn more energy than is minted
+        PowerPlant::note_energy_burned(3 * energy_minted);
+        mock::start_active_era(4);
+        assert_eq!(PowerPlant::net_energy_inflation(), -3 * energy_minted as i128);
+    });
+}
+
+#[test]
+fn claim_all_cooperations_pays_out_every_cooperated_validator() {
+    ExtBuilder::default()
+        .validator_count(3)
+        .energy_per_stake_currency(1)
+        .build_and_execute(|| {
+            // let 31 accept cooperations and back all three validators from 101.
+            assert_ok!(ReputationPallet::force_set_points(
+                RuntimeOrigin::root(),
+                31,
+                CollaborativeValidatorReputationTier::get().into(),
+            ));
+            assert_ok!(PowerPlant::make_collaborative(RuntimeOrigin::signed(31)));
+            assert_ok!(PowerPlant::cooperate(
+                RuntimeOrigin::signed(100),
+                vec![(11, 200), (21, 200), (31, 100)]
+            ));
+            Payee::<Test>::insert(101, RewardDestination::Controller);
+
+            mock::start_active_era(1);
+            assert_eq_uvec!(validator_controllers(), [10, 20, 30]);
+            PowerPlant::reward_by_ids(vec![(11, 1.into()), (21, 1.into()), (31, 1.into())]);
+            mock::start_active_era(2);
+            let _ = staking_events_since_last_call();
+
+            // only cooperators can claim this way.
+            assert_noop!(
+                PowerPlant::claim_all_cooperations(RuntimeOrigin::signed(10), 1, 3),
+                Error::<Test>::NotCooperator
+            );
+
+            // at most `limit` validators are paid per call.
+            let balance_before = Assets::balance(VNRG::get(), 100);
+            assert_ok!(PowerPlant::claim_all_cooperations(RuntimeOrigin::signed(100), 1, 2));
+            assert!(Assets::balance(VNRG::get(), 100) > balance_before);
+            let claimed = || {
+                [10, 20, 30]
+                    .into_iter()
+                    .filter(|c| PowerPlant::ledger(c).unwrap().claimed_rewards.contains(&1))
+                    .count()
+            };
+            assert_eq!(claimed(), 2);
+            assert_eq!(
+                *staking_events_since_last_call().last().unwrap(),
+                Event::CooperationsClaimed { stash: 101, era: 1, validators: 2 }
+            );
+
+            // the next call resumes with the remaining validator.
+            assert_ok!(PowerPlant::claim_all_cooperations(RuntimeOrigin::signed(100), 1, 2));
+            assert_eq!(claimed(), 3);
+            assert_eq!(
+                *staking_events_since_last_call().last().unwrap(),
+                Event::CooperationsClaimed { stash: 101, era: 1, validators: 1 }
+            );
+
+            // claiming again skips the already paid validators.
+            let balance_after = Assets::balance(VNRG::get(), 100);
+            assert_ok!(PowerPlant::claim_all_cooperations(RuntimeOrigin::signed(100), 1, 2));
+            assert_eq!(Assets::balance(VNRG::get(), 100), balance_after);
+            assert_eq!(
+                *staking_events_since_last_call().last().unwrap(),
+                Event::CooperationsClaimed { stash: 101, era: 1, validators: 0 }
+            );
+        });
+}
+
+#[test]
+fn force_set_validators_overrides_the_election_for_one_era() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_eq_uvec!(validator_controllers(), [10, 20]);
+
+        assert_noop!(
+            PowerPlant::force_set_validators(RuntimeOrigin::signed(10), vec![31, 41]),
+            BadOrigin
+        );
+        assert_noop!(
+            PowerPlant::force_set_validators(RuntimeOrigin::root(), vec![]),
+            Error::<Test>::IncorrectValidatorCount
+        );
+        assert_noop!(
+            PowerPlant::force_set_validators(RuntimeOrigin::root(), vec![11, 31, 41]),
+            Error::<Test>::IncorrectValidatorCount
+        );
+        assert_noop!(
+            PowerPlant::force_set_validators(RuntimeOrigin::root(), vec![31, 1]),
+            Error::<Test>::NotStash
+        );
+        // a bonded stash without session keys can't be forced in.
+        let _ = Balances::make_free_balance_be(&1, 1000);
+        assert_ok!(PowerPlant::bond(RuntimeOrigin::signed(1), 2, 500, RewardDestination::Stash));
+        assert_noop!(
+            PowerPlant::force_set_validators(RuntimeOrigin::root(), vec![31, 1]),
+            Error::<Test>::NoSessionKeys
+        );
+
+        assert_ok!(PowerPlant::force_set_validators(RuntimeOrigin::root(), vec![41, 31]));
+        assert_eq!(PowerPlant::forced_validators(), Some(vec![31, 41]));
+        assert_eq!(ForceEra::<Test>::get(), Forcing::ForceNew);
+        assert!(staking_events_since_last_call()
+            .contains(&Event::ValidatorSetForced { validators: vec![31, 41] }));
+
+        // the forced set is installed without an election, even though 41 is idle.
+        mock::start_active_era(1);
+        assert_eq_uvec!(validator_controllers(), [30, 40]);
+        assert_eq!(PowerPlant::forced_validators(), None);
+        assert_eq!(ForceEra::<Test>::get(), Forcing::NotForcing);
+        assert_eq!(PowerPlant::eras_stakers(1, 41).own, 1000);
+
+        // the normal election resumes in the next era.
+        mock::start_active_era(2);
+        assert_eq_uvec!(validator_controllers(), [10, 20]);
+    });
+}
+
+#[test]
+fn forced_validator_set_is_kept_until_its_era_is_planned() {
+    ExtBuilder::default().minimum_validator_count(2).build_and_execute(|| {
+        assert_noop!(
+            PowerPlant::force_set_validators(RuntimeOrigin::root(), vec![31]),
+            Error::<Test>::IncorrectValidatorCount
+        );
+        assert_ok!(PowerPlant::force_set_validators(RuntimeOrigin::root(), vec![31, 41]));
+
+        // 41 drops its session keys, leaving the forced set below the minimum.
+        let keys = pallet_session::NextKeys::<Test>::take(41).unwrap();
+        mock::advance_session();
+        assert!(staking_events_since_last_call().contains(&Event::StakingElectionFailed));
+        assert_eq!(PowerPlant::forced_validators(), Some(vec![31, 41]));
+        assert_eq!(ForceEra::<Test>::get(), Forcing::ForceNew);
+        assert_eq!(current_era(), 0);
+
+        // the override applies once the set can be installed again.
+        pallet_session::NextKeys::<Test>::insert(41, keys);
+        mock::advance_session();
+        assert_eq!(current_era(), 1);
+        assert_eq!(PowerPlant::forced_validators(), None);
+        mock::advance_session();
+        assert_eq_uvec!(validator_controllers(), [30, 40]);
+    });
+}
+
+#[test]
+fn new_cooperations_earn_no_rewards_during_warmup() {
+    ExtBuilder::default().build_and_execute(|| {
+        CooperationWarmupEras::set(2);
+        let _ = Balances::make_free_balance_be(&1, 1000);
+        assert_ok!(PowerPlant::bond(RuntimeOrigin::signed(1), 2, 1000, RewardDestination::Stash));
+        assert_ok!(PowerPlant::cooperate(RuntimeOrigin::signed(2), vec![(21, 500)]));
+        assert_eq!(CooperationActivation::<Test>::get(1, 21), Some(1));
+        // genesis cooperations have no warm-up.
+        assert_eq!(CooperationActivation::<Test>::get(101, 21), None);
+
+        let rewarded = |era| {
+            let _ = staking_events_since_last_call();
+            assert_ok!(PowerPlant::payout_stakers(RuntimeOrigin::signed(1337), 21, era));
+            let events = staking_events_since_last_call();
+            assert!(events.iter().any(|e| matches!(e, Event::Rewarded { stash: 101, .. })));
+            events.iter().any(|e| matches!(e, Event::Rewarded { stash: 1, .. }))
+        };
+
+        mock::start_active_era(1);
+        assert!(PowerPlant::eras_stakers(1, 21).others.iter().any(|e| e.who == 1));
+        mock::start_active_era(2);
+        assert!(!rewarded(1));
+        mock::start_active_era(3);
+        assert!(!rewarded(2));
+        mock::start_active_era(4);
+        assert!(rewarded(3));
+
+        // cooperating again after a withdrawal restarts the warm-up.
+        assert_ok!(PowerPlant::chill(RuntimeOrigin::signed(2)));
+        assert_eq!(CooperationActivation::<Test>::get(1, 21), None);
+        assert_ok!(PowerPlant::cooperate(RuntimeOrigin::signed(2), vec![(21, 500)]));
+        assert_eq!(CooperationActivation::<Test>::get(1, 21), Some(5));
+        mock::start_active_era(6);
+        assert!(PowerPlant::eras_stakers(5, 21).others.iter().any(|e| e.who == 1));
+        assert!(!rewarded(5));
+    });
+}
+
+#[test]
+fn reward_breakdown_shows_the_reputation_bonus() {
+    ExtBuilder::default().energy_per_stake_currency(1).build_and_execute(|| {
+        assert_ok!(ReputationPallet::force_set_points(
+            RuntimeOrigin::root(),
+            11,
+            ReputationTier::Ultramodern(3).into(),
+        ));
+        assert_ok!(PowerPlant::validate(
+            RuntimeOrigin::signed(10),
+            ValidatorPrefs { commission: Perbill::from_percent(10), ..Default::default() }
+        ));
+        mock::start_active_era(1);
+
+        let exposure = PowerPlant::eras_stakers(1, 11);
+        let total_payout =
+            u128::from(exposure.total) / PowerPlant::eras_energy_per_stake_cur(1).unwrap();
+        let commission = Perbill::from_percent(10) * total_payout;
+        let base =
+            Perbill::from_rational(exposure.own, exposure.total) * (total_payout - commission);
+        let breakdown = PowerPlant::reward_breakdown(&11);
+        assert_eq!(breakdown.base, base);
+        assert_eq!(breakdown.commission, commission);
+        assert_eq!(breakdown.reputation_bonus_percent, Perbill::from_percent(20));
+        assert_eq!(breakdown.reputation_bonus, Perbill::from_percent(20) * (base + commission));
+        assert!(breakdown.reputation_bonus > 0);
+        assert_eq!(breakdown.total, base + commission + breakdown.reputation_bonus);
+
+        // 31 isn't elected.
+        let idle = PowerPlant::reward_breakdown(&31);
+        assert_eq!(idle.total, 0);
+        assert_eq!(idle.base, 0);
+    });
+}
+
+#[test]
+fn stake_for_target_reward_yields_the_target_reward() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(PowerPlant::set_energy_per_stake_currency(RuntimeOrigin::root(), 1));
+        assert_ok!(ReputationPallet::force_set_points(
+            RuntimeOrigin::root(),
+            11,
+            ReputationTier::Ultramodern(3).into(),
+        ));
+        assert_ok!(PowerPlant::validate(
+            RuntimeOrigin::signed(10),
+            ValidatorPrefs { commission: Perbill::from_percent(10), ..Default::default() }
+        ));
+        mock::start_active_era(1);
+
+        let target = 2 * PowerPlant::reward_breakdown(&11).total;
+        let stake = PowerPlant::stake_for_target_reward(&11, target);
+        assert!(stake > PowerPlant::eras_stakers(1, 11).own);
+
+        let _ = Balances::make_free_balance_be(&11, 2 * stake);
+        assert_ok!(PowerPlant::bond_extra(
+            RuntimeOrigin::signed(11),
+            stake - PowerPlant::eras_stakers(1, 11).own
+        ));
+        mock::start_active_era(2);
+        assert_eq!(PowerPlant::eras_stakers(2, 11).own, stake);
+        assert_eq_error_rate!(PowerPlant::reward_breakdown(&11).total, target, target / 1000);
+
+        // without an energy rate no stake is enough.
+        ErasEnergyPerStakeCurrency::<Test>::insert(2, 0);
+        assert_eq!(PowerPlant::stake_for_target_reward(&11, target), Balance::max_value());
+    });
+}
+
+#[test]
+fn set_max_cooperations_caps_new_cooperations() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_eq!(PowerPlant::max_cooperations(), MaxCooperations::get());
+        assert_noop!(PowerPlant::set_max_cooperations(RuntimeOrigin::signed(10), 1), BadOrigin);
+        assert_noop!(
+            PowerPlant::set_max_cooperations(RuntimeOrigin::root(), 0),
+            Error::<Test>::InvalidCooperationCap
+        );
+        assert_noop!(
+            PowerPlant::set_max_cooperations(RuntimeOrigin::root(), MaxCooperations::get() + 1),
+            Error::<Test>::InvalidCooperationCap
+        );
+
+        assert_ok!(PowerPlant::set_max_cooperations(RuntimeOrigin::root(), 1));
+        assert_eq!(PowerPlant::max_cooperations(), 1);
+        assert!(staking_events_since_last_call().contains(&Event::MaxCooperationsSet { max: 1 }));
+
+        // the existing cooperations of 101 are kept, but can't be resubmitted above the cap.
+        assert_eq!(PowerPlant::cooperators(101).unwrap().targets.len(), 2);
+        assert_noop!(
+            PowerPlant::cooperate(RuntimeOrigin::signed(100), vec![(11, 200), (21, 200)]),
+            Error::<Test>::TooManyTargets
+        );
+        assert_ok!(PowerPlant::cooperate(RuntimeOrigin::signed(100), vec![(11, 200)]));
+        assert_eq!(PowerPlant::cooperators(101).unwrap().targets.len(), 1);
+
+        assert_ok!(PowerPlant::set_max_cooperations(RuntimeOrigin::root(), 2));
+        assert_ok!(PowerPlant::cooperate(RuntimeOrigin::signed(100), vec![(11, 200), (21, 200)]));
+    });
+}
+
+#[test]
+fn set_battery_slot_capacity_recalculates_the_energy_rate_next_era() {
+    ExtBuilder::default().build_and_execute(|| {
+        start_active_era(1);
+        assert_eq!(PowerPlant::battery_slot_capacity(), BatterySlotCapacity::get());
+        let rate = PowerPlant::current_energy_per_stake_currency().unwrap();
+        assert_eq!(PowerPlant::eras_energy_per_stake_cur(1), Some(rate));
+
+        assert_noop!(
+            PowerPlant::set_battery_slot_capacity(RuntimeOrigin::signed(10), 1_000),
+            BadOrigin
+        );
+        assert_noop!(
+            PowerPlant::set_battery_slot_capacity(
+                RuntimeOrigin::root(),
+                MinBatterySlotCapacity::get() - 1
+            ),
+            Error::<Test>::BatterySlotCapacityOutOfBounds
+        );
+        assert_noop!(
+            PowerPlant::set_battery_slot_capacity(
+                RuntimeOrigin::root(),
+                MaxBatterySlotCapacity::get() + 1
+            ),
+            Error::<Test>::BatterySlotCapacityOutOfBounds
+        );
+
+        let capacity = BatterySlotCapacity::get() * 2;
+        assert_ok!(PowerPlant::set_battery_slot_capacity(RuntimeOrigin::root(), capacity));
+        assert_eq!(PowerPlant::battery_slot_capacity(), capacity);
+        assert!(
+            staking_events_since_last_call().contains(&Event::BatterySlotCapacitySet { capacity })
+        );
+
+        // the rate of the current era is kept, the next era scales with the new capacity.
+        assert_eq!(PowerPlant::eras_energy_per_stake_cur(1), Some(rate));
+        start_active_era(2);
+        assert_eq!(PowerPlant::eras_energy_per_stake_cur(2), Some(rate * 2));
+    });
+}
+
+#[test]
+fn validators_losing_nac_access_are_chilled_next_era() {
+    ExtBuilder::default().invulnerables(vec![21]).build_and_execute(|| {
+        start_active_era(1);
+        assert_eq!(
+            PowerPlant::nac_revocation_policy(),
+            NacRevocationPolicy::ChillExceptInvulnerables
+        );
+
+        NacLevels::set(vec![(101, 0), (21, 0), (11, 0)]);
+        // cooperators and invulnerables aren't chilled by default
+        PowerPlant::note_nac_revoked(&101);
+        PowerPlant::note_nac_revoked(&21);
+        PowerPlant::note_nac_revoked(&11);
+        assert_eq!(PendingNacChills::<Test>::iter_keys().collect::<Vec<_>>(), vec![11]);
+        assert!(staking_events_since_last_call()
+            .contains(&Event::NacRevocationChillScheduled { stash: 11 }));
+
+        // 11 keeps validating the current era
+        assert!(Validators::<Test>::contains_key(11));
+        assert!(PowerPlant::cooperators(101).unwrap().targets.contains_key(&11));
+
+        start_active_era(2);
+        assert!(!Validators::<Test>::contains_key(11));
+        assert_eq!(PowerPlant::eras_stakers(active_era(), 11).total, 0);
+        assert!(!PowerPlant::cooperators(101).unwrap().targets.contains_key(&11));
+        assert_eq!(PendingNacChills::<Test>::iter().count(), 0);
+
+        // governance may chill invulnerables too
+        assert_noop!(
+            PowerPlant::set_nac_revocation_policy(
+                RuntimeOrigin::signed(10),
+                NacRevocationPolicy::ChillAll
+            ),
+            BadOrigin
+        );
+        assert_ok!(PowerPlant::set_nac_revocation_policy(
+            RuntimeOrigin::root(),
+            NacRevocationPolicy::ChillAll
+        ));
+        PowerPlant::note_nac_revoked(&21);
+        start_active_era(3);
+        assert!(!Validators::<Test>::contains_key(21));
+    });
+}
+
+#[test]
+fn validator_stats_aggregate_validator_data() {
+    ExtBuilder::default().energy_per_stake_currency(1).build_and_execute(|| {
+        assert_ok!(ReputationPallet::force_set_points(
+            RuntimeOrigin::root(),
+            11,
+            ReputationTier::Ultramodern(3).into(),
+        ));
+        assert_ok!(PowerPlant::validate(
+            RuntimeOrigin::signed(10),
+            ValidatorPrefs { commission: Perbill::from_percent(10), ..Default::default() }
+        ));
+        mock::start_active_era(1);
+
+        let stats = PowerPlant::validator_stats(&11).unwrap();
+        assert_eq!(stats.own_stake, 1000);
+        assert_eq!(stats.total_stake, PowerPlant::eras_stakers(1, 11).total);
+        assert_eq!(stats.cooperators, 1);
+        assert_eq!(stats.commission, Perbill::from_percent(10));
+        assert!(matches!(stats.reputation_tier, Some(ReputationTier::Ultramodern(_))));
+        // no session of the era has ended yet and nothing was paid out
+        assert_eq!(stats.uptime, None);
+        assert_eq!(stats.last_reward, None);
+
+        advance_session();
+        assert_eq!(PowerPlant::validator_stats(&11).unwrap().uptime, Some(Perbill::one()));
+
+        mock::start_active_era(2);
+        let _ = staking_events_since_last_call();
+        mock::make_all_reward_payment(1);
+        let reward = staking_events_since_last_call()
+            .into_iter()
+            .find_map(|e| match e {
+                Event::Rewarded { stash: 11, amount } => Some(amount),
+                _ => None,
+            })
+            .unwrap();
+        assert!(reward > 0);
+        assert_eq!(PowerPlant::validator_stats(&11).unwrap().last_reward, Some((1, reward)));
+
+        // 31 wasn't elected and 41 doesn't validate.
+        let loser = PowerPlant::validator_stats(&31).unwrap();
+        assert_eq!(loser.own_stake, 500);
+        assert_eq!(loser.total_stake, 0);
+        assert_eq!(loser.cooperators, 0);
+        assert_eq!(loser.last_reward, None);
+        assert_eq!(PowerPlant::validator_stats(&41), None);
+        assert_eq!(PowerPlant::validator_stats(&101), None);
+    });
+}
+
+#[test]
+fn offending_threshold_forces_new_era_from_the_next_era() {
+    ExtBuilder::default()
+        .validator_count(7)
+        .set_status(41, StakerStatus::Validator)
+        .set_status(51, StakerStatus::Validator)
+        .set_status(201, StakerStatus::Validator)
+        .set_status(202, StakerStatus::Validator)
+        .build_and_execute(|| {
+            mock::start_active_era(1);
+            let offend = |stash: AccountId| {
+                on_offence_now(
+                    &[OffenceDetails {
+                        offender: (stash, PowerPlant::eras_stakers(active_era(), stash)),
+                        reporters: vec![],
+                    }],
+                    &[Perbill::zero()],
+                );
+            };
+
+            assert_noop!(
+                PowerPlant::set_offending_threshold(
+                    RuntimeOrigin::signed(10),
+                    Perbill::from_percent(20)
+                ),
+                BadOrigin
+            );
+            assert_noop!(
+                PowerPlant::set_offending_threshold(RuntimeOrigin::root(), Perbill::zero()),
+                Error::<Test>::InvalidOffendingThreshold
+            );
+            assert_noop!(
+                PowerPlant::set_offending_threshold(
+                    RuntimeOrigin::root(),
+                    Perbill::from_percent(51)
+                ),
+                Error::<Test>::InvalidOffendingThreshold
+            );
+            assert_ok!(PowerPlant::set_offending_threshold(
+                RuntimeOrigin::root(),
+                Perbill::from_percent(20)
+            ));
+            assert_eq!(
+                *staking_events().last().unwrap(),
+                Event::OffendingValidatorsThresholdSet { threshold: Perbill::from_percent(20) }
+            );
+
+            // the threshold of the active era is kept.
+            assert_eq!(
+                PowerPlant::offending_validators_threshold(),
+                OffendingValidatorsThreshold::get()
+            );
+            offend(11);
+            offend(21);
+            assert!(is_disabled(10) && is_disabled(20));
+            assert_eq!(ForceEra::<Test>::get(), Forcing::NotForcing);
+
+            mock::start_active_era(2);
+            assert_eq!(PowerPlant::offending_validators_threshold(), Perbill::from_percent(20));
+
+            // 20% of 7 validators is 1, so the second disabled validator forces a new era.
+            offend(31);
+            assert_eq!(ForceEra::<Test>::get(), Forcing::NotForcing);
+            offend(41);
+            assert_eq!(ForceEra::<Test>::get(), Forcing::ForceNew);
+        });
+}
+
+#[test]
+fn energy_per_reputation_point_scales_the_reputation_reward_from_the_next_era() {
+    ExtBuilder::default().energy_per_stake_currency(1).build_and_execute(|| {
+        start_active_era(1);
+        let points = || u128::from(*ReputationPallet::reputation(11).unwrap().reputation.points());
+        assert_eq!(PowerPlant::energy_per_reputation_point(), 0);
+        assert_eq!(PowerPlant::reward_breakdown(&11).reputation_reward, 0);
+
+        assert_noop!(
+            PowerPlant::set_energy_per_reputation_point(RuntimeOrigin::signed(10), 1),
+            BadOrigin
+        );
+        assert_noop!(
+            PowerPlant::set_energy_per_reputation_point(
+                RuntimeOrigin::root(),
+                MaxEnergyPerReputationPoint::get() + 1
+            ),
+            Error::<Test>::EnergyPerReputationPointTooHigh
+        );
+
+        assert_ok!(PowerPlant::set_energy_per_reputation_point(RuntimeOrigin::root(), 2));
+        assert_eq!(PowerPlant::energy_per_reputation_point(), 2);
+        assert!(staking_events_since_last_call()
+            .contains(&Event::EnergyPerReputationPointSet { rate: 2 }));
+
+        // the rate of the current era is kept, the next era uses the new rate.
+        assert_eq!(PowerPlant::reward_breakdown(&11).reputation_reward, 0);
+        start_active_era(2);
+        assert_eq!(PowerPlant::eras_energy_per_reputation_point(2), Some(2));
+        let breakdown = PowerPlant::reward_breakdown(&11);
+        assert!(breakdown.reputation_reward > 0);
+        assert_eq!(breakdown.reputation_reward, 2 * points());
+        assert_eq!(
+            breakdown.total,
+            breakdown.base
+                + breakdown.commission
+                + breakdown.reputation_reward
+                + breakdown.reputation_bonus
+        );
+
+        assert_ok!(PowerPlant::set_energy_per_reputation_point(RuntimeOrigin::root(), 4));
+        start_active_era(3);
+        assert_eq!(PowerPlant::reward_breakdown(&11).reputation_reward, 4 * points());
+
+        // era 2 is paid for the points 11 had when it ended, not for the points at payout.
+        let era_2_points = PowerPlant::eras_validator_reputation_points(2, 11).unwrap();
+        pallet_reputation::AccountReputation::<Test>::mutate(11, |record| {
+            record.as_mut().unwrap().reputation.increase(1_000u64.into());
+        });
+        assert!(u128::from(*era_2_points) < points());
+        assert_eq!(PowerPlant::reputation_reward(&11, 2), 2 * u128::from(*era_2_points));
+
+        // zero disables the reputation reward.
+        assert_ok!(PowerPlant::set_energy_per_reputation_point(RuntimeOrigin::root(), 0));
+        start_active_era(4);
+        assert_eq!(PowerPlant::reward_breakdown(&11).reputation_reward, 0);
+    });
+}
+
+#[test]
+fn check_election_solution_scores_valid_validator_sets() {
+    ExtBuilder::default().build_and_execute(|| {
+        let stake_11 = PowerPlant::eras_stakers(active_era(), 11).total;
+        let stake_21 = PowerPlant::eras_stakers(active_era(), 21).total;
+        assert_eq!(
+            PowerPlant::check_election_solution(vec![21, 11]),
+            Ok(ElectionScore {
+                minimal_stake: stake_11.min(stake_21),
+                sum_stake: stake_11 + stake_21,
+                sum_stake_squared: stake_11 * stake_11 + stake_21 * stake_21,
+            })
+        );
+        // 31 has less stake, so the set is scored lower
+        let score = PowerPlant::check_election_solution(vec![11, 31]).unwrap();
+        assert!(score.minimal_stake < stake_11);
+
+        // nothing is elected
+        assert_eq!(PowerPlant::current_era(), Some(active_era()));
+    });
+}
+
+#[test]
+fn check_election_solution_rejects_invalid_validator_sets() {
+    ExtBuilder::default().build_and_execute(|| {
+        // 41 is idle and 1 isn't bonded
+        assert_eq!(
+            PowerPlant::check_election_solution(vec![11, 41]),
+            Err(ElectionSolutionError::NotCandidate(1))
+        );
+        assert_eq!(
+            PowerPlant::check_election_solution(vec![1]),
+            Err(ElectionSolutionError::NotCandidate(0))
+        );
+        assert_eq!(
+            PowerPlant::check_election_solution(vec![11, 11]),
+            Err(ElectionSolutionError::DuplicateValidator(1))
+        );
+        // the validator count is 2
+        assert_eq!(
+            PowerPlant::check_election_solution(vec![11, 21, 31]),
+            Err(ElectionSolutionError::TooManyValidators)
+        );
+        assert_eq!(
+            PowerPlant::check_election_solution(vec![]),
+            Err(ElectionSolutionError::TooFewValidators)
+        );
+
+        // validators without enough reputation aren't candidates
+        pallet_reputation::AccountReputation::<Test>::remove(31);
+        assert_eq!(
+            PowerPlant::check_election_solution(vec![31]),
+            Err(ElectionSolutionError::NotCandidate(0))
+        );
+    });
+}
+
+#[test]
+fn validator_payout_tops_up_the_controller_energy_reserve() {
+    ExtBuilder::default().has_stakers(false).build_and_execute(|| {
+        OperationalCallFee::set(10);
+        ReservedOperationalCalls::set(5);
+        assert_eq!(PowerPlant::operational_energy_reserve(), 50);
+
+        make_validator(10, 11, 10_000_000);
+        assert_ok!(PowerPlant::set_payee(
+            RuntimeOrigin::signed(10),
+            RewardDestination::Account(42)
+        ));
+        // the controller holds part of the reserve already
+        assert_ok!(Assets::mint(RuntimeOrigin::signed(1), VNRG::get().into(), 10, 20));
+
+        mock::start_active_era(1);
+        let _ = current_total_payout_for_duration(reward_time_per_era());
+        mock::start_active_era(2);
+        assert_ok!(PowerPlant::payout_stakers(RuntimeOrigin::signed(1337), 11, 1));
+
+        let (_, reward) = PowerPlant::last_validator_reward(11).unwrap();
+        assert_eq!(Assets::balance(VNRG::get(), 10), 50);
+        assert_eq!(Assets::balance(VNRG::get(), 42), reward - 30);
+        assert!(
+            staking_events().contains(&Event::EnergyReserveToppedUp { controller: 10, amount: 30 })
+        );
+
+        // a controller holding the reserve isn't topped up
+        mock::start_active_era(3);
+        assert_ok!(PowerPlant::payout_stakers(RuntimeOrigin::signed(1337), 11, 2));
+        let (_, second_reward) = PowerPlant::last_validator_reward(11).unwrap();
+        assert_eq!(Assets::balance(VNRG::get(), 10), 50);
+        assert_eq!(Assets::balance(VNRG::get(), 42), reward - 30 + second_reward);
+    });
+}
+
+#[test]
+fn tier_energy_bonus_set_by_governance_applies_to_rewards() {
+    ExtBuilder::default().build_and_execute(|| {
+        start_active_era(1);
+        let tier = ReputationTier::Trailblazer(2);
+        pallet_reputation::AccountReputation::<Test>::insert(
+            11,
+            ReputationRecord { reputation: tier.into(), updated: 0 },
+        );
+        let default_bonus = Perbill::from_percent(10);
+        assert_eq!(PowerPlant::tier_energy_bonus(&tier), default_bonus);
+        assert_eq!(PowerPlant::reward_breakdown(&11).reputation_bonus_percent, default_bonus);
+
+        assert_noop!(
+            PowerPlant::set_tier_bonus(RuntimeOrigin::signed(10), tier, default_bonus),
+            BadOrigin
+        );
+        // the bonus must stay between the bonuses of Trailblazer(1) and Trailblazer(3)
+        for out_of_bounds in [Perbill::from_percent(7), Perbill::from_percent(13)] {
+            assert_noop!(
+                PowerPlant::set_tier_bonus(RuntimeOrigin::root(), tier, out_of_bounds),
+                Error::<Test>::TierEnergyBonusOutOfBounds
+            );
+        }
+        // and never above `MaxTierEnergyBonus`
+        let bonus = Perbill::from_percent(12);
+        MaxTierEnergyBonus::set(Perbill::from_percent(11));
+        assert_noop!(
+            PowerPlant::set_tier_bonus(RuntimeOrigin::root(), tier, bonus),
+            Error::<Test>::TierEnergyBonusOutOfBounds
+        );
+        MaxTierEnergyBonus::set(Perbill::from_percent(50));
+
+        assert_ok!(PowerPlant::set_tier_bonus(RuntimeOrigin::root(), tier, bonus));
+        assert!(
+            staking_events_since_last_call().contains(&Event::TierEnergyBonusSet { tier, bonus })
+        );
+        assert_eq!(PowerPlant::tier_energy_bonus(&tier), bonus);
+
+        let breakdown = PowerPlant::reward_breakdown(&11);
+        assert_eq!(breakdown.reputation_bonus_percent, bonus);
+        assert_eq!(
+            breakdown.reputation_bonus,
+            bonus.mul_floor(breakdown.total - breakdown.reputation_bonus)
+        );
+        // other tiers keep their bonus
+        assert_eq!(
+            PowerPlant::tier_energy_bonus(&ReputationTier::Trailblazer(3)),
+            Perbill::from_percent(12)
+        );
+    });
+}
+
+#[test]
+fn validators_committed_for_the_next_era_win_ties_in_stake() {
+    ExtBuilder::default()
+        .validator_count(2)
+        .cooperate(CooperateSelector::CooperateWith(vec![(21, 300)]))
+        .set_stake(31, 1000)
+        .build_and_execute(|| {
+            start_active_era(1);
+            // 11 and 31 are staked alike, 21 has the cooperator's stake on top
+            assert_eq!(PowerPlant::ledger(&10).unwrap().active, 1000);
+            assert_eq!(PowerPlant::ledger(&30).unwrap().active, 1000);
+
+            // only validators may commit, through their controller
+            assert_noop!(
+                PowerPlant::commit_next_era_intent(RuntimeOrigin::signed(31), true),
+                Error::<Test>::NotController
+            );
+            assert_noop!(
+                PowerPlant::commit_next_era_intent(RuntimeOrigin::signed(100), true),
+                Error::<Test>::NotValidator
+            );
+
+            assert_ok!(PowerPlant::commit_next_era_intent(RuntimeOrigin::signed(30), true));
+            assert_ok!(PowerPlant::commit_next_era_intent(RuntimeOrigin::signed(20), true));
+            assert!(staking_events_since_last_call().contains(&Event::NextEraIntentCommitted {
+                stash: 31,
+                era: 2,
+                validate: true
+            }));
+            assert_eq!(PowerPlant::next_era_intent(2, 31), Some(true));
+
+            // 31 is elected ahead of 11, which has as much stake but didn't commit
+            start_active_era(2);
+            assert_eq_uvec!(Session::validators(), [21, 31]);
+            assert_eq!(PowerPlant::eras_stakers(active_era(), 11).total, 0);
+
+            // the intents of the elected era are removed
+            assert_eq!(PowerPlant::next_era_intent(2, 31), None);
+            assert!(NextEraIntent::<Test>::iter_prefix(2).next().is_none());
+
+            // the intent doesn't outweigh stake
+            assert_ok!(PowerPlant::unbond(RuntimeOrigin::signed(30), 100));
+            assert_ok!(PowerPlant::commit_next_era_intent(RuntimeOrigin::signed(30), true));
+            start_active_era(3);
+            assert_eq_uvec!(Session::validators(), [21, 11]);
+        });
+}
+
+#[test]
+fn validators_committed_not_to_validate_are_chilled() {
+    ExtBuilder::default().build_and_execute(|| {
+        start_active_era(1);
+        assert_ok!(PowerPlant::commit_next_era_intent(RuntimeOrigin::signed(10), false));
+
+        // 11 keeps validating until the next election
+        assert!(Validators::<Test>::contains_key(11));
+
+        start_active_era(2);
+        assert!(!Validators::<Test>::contains_key(11));
+        assert!(!Session::validators().contains(&11));
+        assert!(!PowerPlant::cooperators(101).unwrap().targets.contains_key(&11));
+    });
+}
+
+#[test]
+fn staking_overview_reports_the_staking_ratio() {
+    ExtBuilder::default().build_and_execute(|| {
+        start_active_era(1);
+        // 11 and 21 are elected, backed by 1000 own stake each, and 200 and 300 from 101
+        let total_issuance = Balances::total_issuance();
+        assert_eq!(
+            PowerPlant::staking_overview(),
+            StakingOverview {
+                total_staked: 2500,
+                total_issuance,
+                staking_ratio: Perbill::from_rational(2500, total_issuance),
+                active_validators: 2,
+            }
+        );
+
+        // bonding more counts from the era it backs the validators in
+        assert_ok!(PowerPlant::bond_extra(RuntimeOrigin::signed(21), 500));
+        assert_eq!(PowerPlant::staking_overview().total_staked, 2500);
+        start_active_era(2);
+        let overview = PowerPlant::staking_overview();
+        assert_eq!(overview.total_staked, 3000);
+        assert_eq!(overview.staking_ratio, Perbill::from_rational(3000, total_issuance));
+    });
+}
+
+#[test]
+fn min_validator_bond_depends_on_nac_level() {
+    ExtBuilder::default()
+        .min_common_validator_bond(100)
+        .min_trust_validator_bond(300)
+        .build_and_execute(|| {
+            NacLevels::set(vec![(11, 2), (21, 1), (31, 0)]);
+            assert_eq!(PowerPlant::min_bond_for_validator(&11), 300);
+            assert_eq!(PowerPlant::min_bond_for_validator(&21), 100);
+            assert_eq!(PowerPlant::min_bond_for_validator(&31), 100);
+
+            // governance changes apply right away
+            MinTrustValidatorBond::<Test>::put(400);
+            assert_eq!(PowerPlant::min_bond_for_validator(&11), 400);
+        });
+}
+
 mod byzantine_threshold_disabling_strategy {
     use crate::{
         tests::Test, ActiveEra, ActiveEraInfo, DisablingStrategy, UpToLimitDisablingStrategy,
@@ -80,6 +80,22 @@ pub trait WeightInfo {
     fn set_min_commission() -> Weight;
     fn make_collaborative() -> Weight;
     fn force_chill() -> Weight;
+    fn consolidate_unlocking() -> Weight;
+    fn update_prefs() -> Weight;
+    fn set_max_slash() -> Weight;
+    fn report_client_version() -> Weight;
+    fn set_minimum_client_version() -> Weight;
+    fn sweep_stale_rewards(e: u32, ) -> Weight;
+    fn claim_all_cooperations(v: u32, ) -> Weight;
+    fn force_set_validators(v: u32, ) -> Weight;
+    fn set_max_cooperations() -> Weight;
+    fn set_battery_slot_capacity() -> Weight;
+    fn set_nac_revocation_policy() -> Weight;
+    fn set_offending_threshold() -> Weight;
+    fn set_energy_per_reputation_point() -> Weight;
+    fn set_tier_bonus() -> Weight;
+    fn commit_next_era_intent() -> Weight;
+    fn set_rewards_paused() -> Weight;
 }
 
 /// Weights for pallet_staking using the Substrate node and recommended hardware.
@@ -347,13 +363,17 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     /// Proof: Staking Bonded (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
     /// Storage: Staking Ledger (r:2 w:2)
     /// Proof: Staking Ledger (max_values: None, max_size: Some(1091), added: 3566, mode: MaxEncodedLen)
+    /// Storage: Nfts Account (r:1 w:0)
+    /// Proof: Nfts Account (max_values: None, max_size: Some(88), added: 2563, mode: MaxEncodedLen)
+    /// Storage: Nfts Attribute (r:1 w:0)
+    /// Proof: Nfts Attribute (max_values: None, max_size: Some(479), added: 2954, mode: MaxEncodedLen)
     fn set_controller() -> Weight {
         // Proof Size summary in bytes:
-        //  Measured:  `907`
-        //  Estimated: `8122`
-        // Minimum execution time: 23_316_000 picoseconds.
-        Weight::from_parts(23_670_000, 8122)
-            .saturating_add(T::DbWeight::get().reads(3_u64))
+        //  Measured:  `1214`
+        //  Estimated: `13639`
+        // Minimum execution time: 31_048_000 picoseconds.
+        Weight::from_parts(31_562_000, 13639)
+            .saturating_add(T::DbWeight::get().reads(5_u64))
             .saturating_add(T::DbWeight::get().writes(3_u64))
     }
     /// Storage: Staking ValidatorCount (r:0 w:1)
@@ -809,6 +829,279 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     fn force_chill() -> Weight {
         Self::chill()
     }
+    /// Storage: Staking Ledger (r:1 w:1)
+    /// Proof: Staking Ledger (max_values: None, max_size: Some(1091), added: 3566, mode: MaxEncodedLen)
+    /// Storage: Balances Locks (r:1 w:1)
+    /// Proof: Balances Locks (max_values: None, max_size: Some(1299), added: 3774, mode: MaxEncodedLen)
+    /// Storage: Balances Freezes (r:1 w:0)
+    /// Proof: Balances Freezes (max_values: None, max_size: Some(49), added: 2524, mode: MaxEncodedLen)
+    fn consolidate_unlocking() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `1324`
+        //  Estimated: `4764`
+        // Minimum execution time: 31_284_000 picoseconds.
+        Weight::from_parts(32_071_000, 4764)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+    /// Storage: Staking Ledger (r:1 w:0)
+    /// Proof: Staking Ledger (max_values: None, max_size: Some(1091), added: 3566, mode: MaxEncodedLen)
+    /// Storage: Staking Validators (r:1 w:1)
+    /// Proof: Staking Validators (max_values: None, max_size: Some(45), added: 2520, mode: MaxEncodedLen)
+    /// Storage: Staking MinCommission (r:1 w:0)
+    /// Proof: Staking MinCommission (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+    /// Storage: Assets Asset (r:1 w:0)
+    /// Proof: Assets Asset (max_values: None, max_size: Some(210), added: 2685, mode: MaxEncodedLen)
+    /// Storage: Assets Account (r:1 w:0)
+    /// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+    /// Storage: Staking Payee (r:0 w:1)
+    /// Proof: Staking Payee (max_values: None, max_size: Some(73), added: 2548, mode: MaxEncodedLen)
+    fn update_prefs() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `1097`
+        //  Estimated: `4556`
+        // Minimum execution time: 27_918_000 picoseconds.
+        Weight::from_parts(28_647_000, 4556)
+            .saturating_add(T::DbWeight::get().reads(5_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+    /// Storage: Staking Ledger (r:1 w:0)
+    /// Proof: Staking Ledger (max_values: None, max_size: Some(1091), added: 3566, mode: MaxEncodedLen)
+    /// Storage: Staking CooperatorMaxSlash (r:0 w:1)
+    /// Proof: Staking CooperatorMaxSlash (max_values: None, max_size: Some(44), added: 2519, mode: MaxEncodedLen)
+    fn set_max_slash() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `808`
+        //  Estimated: `4556`
+        // Minimum execution time: 14_962_000 picoseconds.
+        Weight::from_parts(15_384_000, 4556)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    /// Storage: Staking Ledger (r:1 w:0)
+    /// Proof: Staking Ledger (max_values: None, max_size: Some(1091), added: 3566, mode: MaxEncodedLen)
+    /// Storage: Staking Validators (r:1 w:0)
+    /// Proof: Staking Validators (max_values: None, max_size: Some(45), added: 2520, mode: MaxEncodedLen)
+    /// Storage: Staking ValidatorClientVersion (r:0 w:1)
+    /// Proof: Staking ValidatorClientVersion (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+    fn report_client_version() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `921`
+        //  Estimated: `4556`
+        // Minimum execution time: 18_117_000 picoseconds.
+        Weight::from_parts(18_603_000, 4556)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    /// Storage: Staking ActiveEra (r:1 w:0)
+    /// Proof: Staking ActiveEra (max_values: Some(1), max_size: Some(13), added: 508, mode: MaxEncodedLen)
+    /// Storage: Staking MinimumClientVersion (r:0 w:1)
+    /// Proof: Staking MinimumClientVersion (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+    fn set_minimum_client_version() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `0`
+        //  Estimated: `1498`
+        // Minimum execution time: 8_204_000 picoseconds.
+        Weight::from_parts(8_526_000, 1498)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    /// Storage: Staking RewardsPaused (r:1 w:0)
+    /// Proof: Staking RewardsPaused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+    /// Storage: Staking CurrentEra (r:1 w:0)
+    /// Proof: Staking CurrentEra (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+    /// Storage: Staking ErasUnclaimedRewards (r:100 w:16)
+    /// Proof: Staking ErasUnclaimedRewards (max_values: None, max_size: Some(28), added: 2503, mode: MaxEncodedLen)
+    /// Storage: Assets Asset (r:1 w:1)
+    /// Proof: Assets Asset (max_values: None, max_size: Some(210), added: 2685, mode: MaxEncodedLen)
+    /// Storage: Assets Account (r:1 w:1)
+    /// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+    /// Storage: Staking ActiveEra (r:1 w:0)
+    /// Proof: Staking ActiveEra (max_values: Some(1), max_size: Some(13), added: 508, mode: MaxEncodedLen)
+    /// Storage: Staking ErasIssuance (r:1 w:1)
+    /// Proof: Staking ErasIssuance (max_values: None, max_size: Some(76), added: 2551, mode: MaxEncodedLen)
+    /// The range of component `e` is `[1, 100]`.
+    fn sweep_stale_rewards(e: u32, ) -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `412 + e * (42 ±0)`
+        //  Estimated: `3675 + e * (2503 ±0)`
+        // Minimum execution time: 24_516_000 picoseconds.
+        Weight::from_parts(25_093_418, 3675)
+            // Standard Error: 1_734
+            .saturating_add(Weight::from_parts(3_671_225, 0).saturating_mul(e.into()))
+            .saturating_add(T::DbWeight::get().reads(6_u64))
+            .saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(e.into())))
+            .saturating_add(T::DbWeight::get().writes(3_u64))
+            .saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(e.into())))
+            .saturating_add(Weight::from_parts(0, 2503).saturating_mul(e.into()))
+    }
+    /// Storage: Staking Ledger (r:257 w:256)
+    /// Proof: Staking Ledger (max_values: None, max_size: Some(1091), added: 3566, mode: MaxEncodedLen)
+    /// Storage: Staking Cooperators (r:1 w:0)
+    /// Proof: Staking Cooperators (max_values: None, max_size: Some(558), added: 3033, mode: MaxEncodedLen)
+    /// Storage: Staking Bonded (r:256 w:0)
+    /// Proof: Staking Bonded (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
+    /// Storage: Staking ErasStakersClipped (r:256 w:0)
+    /// Proof Skipped: Staking ErasStakersClipped (max_values: None, max_size: None, mode: Measured)
+    /// Storage: Staking ActiveEra (r:1 w:0)
+    /// Proof: Staking ActiveEra (max_values: Some(1), max_size: Some(13), added: 508, mode: MaxEncodedLen)
+    /// Storage: Staking CurrentEra (r:1 w:0)
+    /// Proof: Staking CurrentEra (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+    /// Storage: Staking ErasEnergyPerStakeCurrency (r:1 w:0)
+    /// Proof: Staking ErasEnergyPerStakeCurrency (max_values: None, max_size: Some(28), added: 2503, mode: MaxEncodedLen)
+    /// Storage: Staking ErasValidatorPrefs (r:256 w:0)
+    /// Proof: Staking ErasValidatorPrefs (max_values: None, max_size: Some(57), added: 2532, mode: MaxEncodedLen)
+    /// Storage: Staking Payee (r:32768 w:0)
+    /// Proof: Staking Payee (max_values: None, max_size: Some(73), added: 2548, mode: MaxEncodedLen)
+    /// Storage: Assets Asset (r:1 w:1)
+    /// Proof: Assets Asset (max_values: None, max_size: Some(210), added: 2685, mode: MaxEncodedLen)
+    /// Storage: Assets Account (r:32768 w:32768)
+    /// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+    /// Storage: Staking ErasIssuance (r:1 w:1)
+    /// Proof: Staking ErasIssuance (max_values: None, max_size: Some(76), added: 2551, mode: MaxEncodedLen)
+    /// The range of component `v` is `[1, 256]`.
+    fn claim_all_cooperations(v: u32, ) -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `1476 + v * (41218 ±0)`
+        //  Estimated: `4556 + v * (336561 ±0)`
+        // Minimum execution time: 6_493_117_000 picoseconds.
+        Weight::from_parts(213_640_218, 4556)
+            // Standard Error: 1_384_208
+            .saturating_add(Weight::from_parts(6_391_844_017, 0).saturating_mul(v.into()))
+            .saturating_add(T::DbWeight::get().reads(7_u64))
+            .saturating_add(T::DbWeight::get().reads((262_u64).saturating_mul(v.into())))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+            .saturating_add(T::DbWeight::get().writes((130_u64).saturating_mul(v.into())))
+            .saturating_add(Weight::from_parts(0, 336561).saturating_mul(v.into()))
+    }
+    /// Storage: Staking MinimumValidatorCount (r:1 w:0)
+    /// Proof: Staking MinimumValidatorCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+    /// Storage: Staking ValidatorCount (r:1 w:0)
+    /// Proof: Staking ValidatorCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+    /// Storage: Staking Bonded (r:100 w:0)
+    /// Proof: Staking Bonded (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
+    /// Storage: Session NextKeys (r:100 w:0)
+    /// Proof Skipped: Session NextKeys (max_values: None, max_size: None, mode: Measured)
+    /// Storage: Staking ForcedValidators (r:0 w:1)
+    /// Proof Skipped: Staking ForcedValidators (max_values: None, max_size: None, mode: Measured)
+    /// Storage: Staking ForceEra (r:0 w:1)
+    /// Proof: Staking ForceEra (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+    /// The range of component `v` is `[1, 100]`.
+    /// Storage: Staking MinimumValidatorCount (r:1 w:0)
+    /// Proof: Staking MinimumValidatorCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+    /// Storage: Staking ValidatorCount (r:1 w:0)
+    /// Proof: Staking ValidatorCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+    /// Storage: Staking Bonded (r:1000 w:0)
+    /// Proof: Staking Bonded (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
+    /// Storage: Session NextKeys (r:1000 w:0)
+    /// Proof Skipped: Session NextKeys (max_values: None, max_size: None, mode: Measured)
+    /// Storage: Staking ForcedValidators (r:0 w:1)
+    /// Proof Skipped: Staking ForcedValidators (max_values: None, max_size: None, mode: Measured)
+    /// Storage: Staking ForceEra (r:0 w:1)
+    /// Proof: Staking ForceEra (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+    /// The range of component `v` is `[1, 1000]`.
+    fn force_set_validators(v: u32, ) -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `0 + v * (309 ±0)`
+        //  Estimated: `1489 + v * (2547 ±0)`
+        // Minimum execution time: 24_117_000 picoseconds.
+        Weight::from_parts(18_430_511, 1489)
+            // Standard Error: 9_874
+            .saturating_add(Weight::from_parts(11_204_367, 0).saturating_mul(v.into()))
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(v.into())))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+            .saturating_add(Weight::from_parts(0, 2547).saturating_mul(v.into()))
+    }
+    /// Storage: Staking MaxCooperationsPerCooperator (r:0 w:1)
+    /// Proof: Staking MaxCooperationsPerCooperator (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+    fn set_max_cooperations() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `0`
+        //  Estimated: `0`
+        // Minimum execution time: 8_312_000 picoseconds.
+        Weight::from_parts(8_604_000, 0)
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    /// Storage: Staking BatterySlotCapacityOverride (r:0 w:1)
+    /// Proof: Staking BatterySlotCapacityOverride (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+    fn set_battery_slot_capacity() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `0`
+        //  Estimated: `0`
+        // Minimum execution time: 8_497_000 picoseconds.
+        Weight::from_parts(8_815_000, 0)
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    /// Storage: Staking NacRevocationChillPolicy (r:0 w:1)
+    /// Proof: Staking NacRevocationChillPolicy (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+    fn set_nac_revocation_policy() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `0`
+        //  Estimated: `0`
+        // Minimum execution time: 8_106_000 picoseconds.
+        Weight::from_parts(8_392_000, 0)
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    /// Storage: Staking PendingOffendingValidatorsThreshold (r:0 w:1)
+    /// Proof: Staking PendingOffendingValidatorsThreshold (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+    fn set_offending_threshold() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `0`
+        //  Estimated: `0`
+        // Minimum execution time: 8_289_000 picoseconds.
+        Weight::from_parts(8_577_000, 0)
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    /// Storage: Staking EnergyPerReputationPointOverride (r:0 w:1)
+    /// Proof: Staking EnergyPerReputationPointOverride (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+    fn set_energy_per_reputation_point() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `0`
+        //  Estimated: `0`
+        // Minimum execution time: 8_533_000 picoseconds.
+        Weight::from_parts(8_841_000, 0)
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    /// Storage: Staking TierEnergyBonus (r:2 w:1)
+    /// Proof: Staking TierEnergyBonus (max_values: None, max_size: Some(14), added: 2489, mode: MaxEncodedLen)
+    fn set_tier_bonus() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `4`
+        //  Estimated: `3479`
+        // Minimum execution time: 14_752_000 picoseconds.
+        Weight::from_parts(15_238_000, 3479)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    /// Storage: Staking Ledger (r:1 w:0)
+    /// Proof: Staking Ledger (max_values: None, max_size: Some(1091), added: 3566, mode: MaxEncodedLen)
+    /// Storage: Staking Validators (r:1 w:0)
+    /// Proof: Staking Validators (max_values: None, max_size: Some(45), added: 2520, mode: MaxEncodedLen)
+    /// Storage: Staking CurrentEra (r:1 w:0)
+    /// Proof: Staking CurrentEra (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+    /// Storage: Staking NextEraIntent (r:0 w:1)
+    /// Proof: Staking NextEraIntent (max_values: None, max_size: Some(53), added: 2528, mode: MaxEncodedLen)
+    fn commit_next_era_intent() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `1016`
+        //  Estimated: `4556`
+        // Minimum execution time: 21_347_000 picoseconds.
+        Weight::from_parts(22_105_000, 4556)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    /// Storage: Staking RewardsPaused (r:1 w:1)
+    /// Proof: Staking RewardsPaused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+    fn set_rewards_paused() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `0`
+        //  Estimated: `1486`
+        // Minimum execution time: 7_412_000 picoseconds.
+        Weight::from_parts(7_689_000, 1486)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
 }
 
 // For backwards compatibility and tests
@@ -1075,13 +1368,17 @@ impl WeightInfo for () {
     /// Proof: Staking Bonded (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
     /// Storage: Staking Ledger (r:2 w:2)
     /// Proof: Staking Ledger (max_values: None, max_size: Some(1091), added: 3566, mode: MaxEncodedLen)
+    /// Storage: Nfts Account (r:1 w:0)
+    /// Proof: Nfts Account (max_values: None, max_size: Some(88), added: 2563, mode: MaxEncodedLen)
+    /// Storage: Nfts Attribute (r:1 w:0)
+    /// Proof: Nfts Attribute (max_values: None, max_size: Some(479), added: 2954, mode: MaxEncodedLen)
     fn set_controller() -> Weight {
         // Proof Size summary in bytes:
-        //  Measured:  `907`
-        //  Estimated: `8122`
-        // Minimum execution time: 23_316_000 picoseconds.
-        Weight::from_parts(23_670_000, 8122)
-            .saturating_add(RocksDbWeight::get().reads(3_u64))
+        //  Measured:  `1214`
+        //  Estimated: `13639`
+        // Minimum execution time: 31_048_000 picoseconds.
+        Weight::from_parts(31_562_000, 13639)
+            .saturating_add(RocksDbWeight::get().reads(5_u64))
             .saturating_add(RocksDbWeight::get().writes(3_u64))
     }
     /// Storage: Staking ValidatorCount (r:0 w:1)
@@ -1537,4 +1834,277 @@ impl WeightInfo for () {
     fn force_chill() -> Weight {
         Self::chill()
     }
+    /// Storage: Staking Ledger (r:1 w:1)
+    /// Proof: Staking Ledger (max_values: None, max_size: Some(1091), added: 3566, mode: MaxEncodedLen)
+    /// Storage: Balances Locks (r:1 w:1)
+    /// Proof: Balances Locks (max_values: None, max_size: Some(1299), added: 3774, mode: MaxEncodedLen)
+    /// Storage: Balances Freezes (r:1 w:0)
+    /// Proof: Balances Freezes (max_values: None, max_size: Some(49), added: 2524, mode: MaxEncodedLen)
+    fn consolidate_unlocking() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `1324`
+        //  Estimated: `4764`
+        // Minimum execution time: 31_284_000 picoseconds.
+        Weight::from_parts(32_071_000, 4764)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+    /// Storage: Staking Ledger (r:1 w:0)
+    /// Proof: Staking Ledger (max_values: None, max_size: Some(1091), added: 3566, mode: MaxEncodedLen)
+    /// Storage: Staking Validators (r:1 w:1)
+    /// Proof: Staking Validators (max_values: None, max_size: Some(45), added: 2520, mode: MaxEncodedLen)
+    /// Storage: Staking MinCommission (r:1 w:0)
+    /// Proof: Staking MinCommission (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+    /// Storage: Assets Asset (r:1 w:0)
+    /// Proof: Assets Asset (max_values: None, max_size: Some(210), added: 2685, mode: MaxEncodedLen)
+    /// Storage: Assets Account (r:1 w:0)
+    /// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+    /// Storage: Staking Payee (r:0 w:1)
+    /// Proof: Staking Payee (max_values: None, max_size: Some(73), added: 2548, mode: MaxEncodedLen)
+    fn update_prefs() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `1097`
+        //  Estimated: `4556`
+        // Minimum execution time: 27_918_000 picoseconds.
+        Weight::from_parts(28_647_000, 4556)
+            .saturating_add(RocksDbWeight::get().reads(5_u64))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+    /// Storage: Staking Ledger (r:1 w:0)
+    /// Proof: Staking Ledger (max_values: None, max_size: Some(1091), added: 3566, mode: MaxEncodedLen)
+    /// Storage: Staking CooperatorMaxSlash (r:0 w:1)
+    /// Proof: Staking CooperatorMaxSlash (max_values: None, max_size: Some(44), added: 2519, mode: MaxEncodedLen)
+    fn set_max_slash() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `808`
+        //  Estimated: `4556`
+        // Minimum execution time: 14_962_000 picoseconds.
+        Weight::from_parts(15_384_000, 4556)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    /// Storage: Staking Ledger (r:1 w:0)
+    /// Proof: Staking Ledger (max_values: None, max_size: Some(1091), added: 3566, mode: MaxEncodedLen)
+    /// Storage: Staking Validators (r:1 w:0)
+    /// Proof: Staking Validators (max_values: None, max_size: Some(45), added: 2520, mode: MaxEncodedLen)
+    /// Storage: Staking ValidatorClientVersion (r:0 w:1)
+    /// Proof: Staking ValidatorClientVersion (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+    fn report_client_version() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `921`
+        //  Estimated: `4556`
+        // Minimum execution time: 18_117_000 picoseconds.
+        Weight::from_parts(18_603_000, 4556)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    /// Storage: Staking ActiveEra (r:1 w:0)
+    /// Proof: Staking ActiveEra (max_values: Some(1), max_size: Some(13), added: 508, mode: MaxEncodedLen)
+    /// Storage: Staking MinimumClientVersion (r:0 w:1)
+    /// Proof: Staking MinimumClientVersion (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+    fn set_minimum_client_version() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `0`
+        //  Estimated: `1498`
+        // Minimum execution time: 8_204_000 picoseconds.
+        Weight::from_parts(8_526_000, 1498)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    /// Storage: Staking RewardsPaused (r:1 w:0)
+    /// Proof: Staking RewardsPaused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+    /// Storage: Staking CurrentEra (r:1 w:0)
+    /// Proof: Staking CurrentEra (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+    /// Storage: Staking ErasUnclaimedRewards (r:100 w:16)
+    /// Proof: Staking ErasUnclaimedRewards (max_values: None, max_size: Some(28), added: 2503, mode: MaxEncodedLen)
+    /// Storage: Assets Asset (r:1 w:1)
+    /// Proof: Assets Asset (max_values: None, max_size: Some(210), added: 2685, mode: MaxEncodedLen)
+    /// Storage: Assets Account (r:1 w:1)
+    /// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+    /// Storage: Staking ActiveEra (r:1 w:0)
+    /// Proof: Staking ActiveEra (max_values: Some(1), max_size: Some(13), added: 508, mode: MaxEncodedLen)
+    /// Storage: Staking ErasIssuance (r:1 w:1)
+    /// Proof: Staking ErasIssuance (max_values: None, max_size: Some(76), added: 2551, mode: MaxEncodedLen)
+    /// The range of component `e` is `[1, 100]`.
+    fn sweep_stale_rewards(e: u32, ) -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `412 + e * (42 ±0)`
+        //  Estimated: `3675 + e * (2503 ±0)`
+        // Minimum execution time: 24_516_000 picoseconds.
+        Weight::from_parts(25_093_418, 3675)
+            // Standard Error: 1_734
+            .saturating_add(Weight::from_parts(3_671_225, 0).saturating_mul(e.into()))
+            .saturating_add(RocksDbWeight::get().reads(6_u64))
+            .saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(e.into())))
+            .saturating_add(RocksDbWeight::get().writes(3_u64))
+            .saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(e.into())))
+            .saturating_add(Weight::from_parts(0, 2503).saturating_mul(e.into()))
+    }
+    /// Storage: Staking Ledger (r:257 w:256)
+    /// Proof: Staking Ledger (max_values: None, max_size: Some(1091), added: 3566, mode: MaxEncodedLen)
+    /// Storage: Staking Cooperators (r:1 w:0)
+    /// Proof: Staking Cooperators (max_values: None, max_size: Some(558), added: 3033, mode: MaxEncodedLen)
+    /// Storage: Staking Bonded (r:256 w:0)
+    /// Proof: Staking Bonded (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
+    /// Storage: Staking ErasStakersClipped (r:256 w:0)
+    /// Proof Skipped: Staking ErasStakersClipped (max_values: None, max_size: None, mode: Measured)
+    /// Storage: Staking ActiveEra (r:1 w:0)
+    /// Proof: Staking ActiveEra (max_values: Some(1), max_size: Some(13), added: 508, mode: MaxEncodedLen)
+    /// Storage: Staking CurrentEra (r:1 w:0)
+    /// Proof: Staking CurrentEra (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+    /// Storage: Staking ErasEnergyPerStakeCurrency (r:1 w:0)
+    /// Proof: Staking ErasEnergyPerStakeCurrency (max_values: None, max_size: Some(28), added: 2503, mode: MaxEncodedLen)
+    /// Storage: Staking ErasValidatorPrefs (r:256 w:0)
+    /// Proof: Staking ErasValidatorPrefs (max_values: None, max_size: Some(57), added: 2532, mode: MaxEncodedLen)
+    /// Storage: Staking Payee (r:32768 w:0)
+    /// Proof: Staking Payee (max_values: None, max_size: Some(73), added: 2548, mode: MaxEncodedLen)
+    /// Storage: Assets Asset (r:1 w:1)
+    /// Proof: Assets Asset (max_values: None, max_size: Some(210), added: 2685, mode: MaxEncodedLen)
+    /// Storage: Assets Account (r:32768 w:32768)
+    /// Proof: Assets Account (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+    /// Storage: Staking ErasIssuance (r:1 w:1)
+    /// Proof: Staking ErasIssuance (max_values: None, max_size: Some(76), added: 2551, mode: MaxEncodedLen)
+    /// The range of component `v` is `[1, 256]`.
+    fn claim_all_cooperations(v: u32, ) -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `1476 + v * (41218 ±0)`
+        //  Estimated: `4556 + v * (336561 ±0)`
+        // Minimum execution time: 6_493_117_000 picoseconds.
+        Weight::from_parts(213_640_218, 4556)
+            // Standard Error: 1_384_208
+            .saturating_add(Weight::from_parts(6_391_844_017, 0).saturating_mul(v.into()))
+            .saturating_add(RocksDbWeight::get().reads(7_u64))
+            .saturating_add(RocksDbWeight::get().reads((262_u64).saturating_mul(v.into())))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+            .saturating_add(RocksDbWeight::get().writes((130_u64).saturating_mul(v.into())))
+            .saturating_add(Weight::from_parts(0, 336561).saturating_mul(v.into()))
+    }
+    /// Storage: Staking MinimumValidatorCount (r:1 w:0)
+    /// Proof: Staking MinimumValidatorCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+    /// Storage: Staking ValidatorCount (r:1 w:0)
+    /// Proof: Staking ValidatorCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+    /// Storage: Staking Bonded (r:100 w:0)
+    /// Proof: Staking Bonded (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
+    /// Storage: Session NextKeys (r:100 w:0)
+    /// Proof Skipped: Session NextKeys (max_values: None, max_size: None, mode: Measured)
+    /// Storage: Staking ForcedValidators (r:0 w:1)
+    /// Proof Skipped: Staking ForcedValidators (max_values: None, max_size: None, mode: Measured)
+    /// Storage: Staking ForceEra (r:0 w:1)
+    /// Proof: Staking ForceEra (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+    /// The range of component `v` is `[1, 100]`.
+    /// Storage: Staking MinimumValidatorCount (r:1 w:0)
+    /// Proof: Staking MinimumValidatorCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+    /// Storage: Staking ValidatorCount (r:1 w:0)
+    /// Proof: Staking ValidatorCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+    /// Storage: Staking Bonded (r:1000 w:0)
+    /// Proof: Staking Bonded (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
+    /// Storage: Session NextKeys (r:1000 w:0)
+    /// Proof Skipped: Session NextKeys (max_values: None, max_size: None, mode: Measured)
+    /// Storage: Staking ForcedValidators (r:0 w:1)
+    /// Proof Skipped: Staking ForcedValidators (max_values: None, max_size: None, mode: Measured)
+    /// Storage: Staking ForceEra (r:0 w:1)
+    /// Proof: Staking ForceEra (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+    /// The range of component `v` is `[1, 1000]`.
+    fn force_set_validators(v: u32, ) -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `0 + v * (309 ±0)`
+        //  Estimated: `1489 + v * (2547 ±0)`
+        // Minimum execution time: 24_117_000 picoseconds.
+        Weight::from_parts(18_430_511, 1489)
+            // Standard Error: 9_874
+            .saturating_add(Weight::from_parts(11_204_367, 0).saturating_mul(v.into()))
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(v.into())))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+            .saturating_add(Weight::from_parts(0, 2547).saturating_mul(v.into()))
+    }
+    /// Storage: Staking MaxCooperationsPerCooperator (r:0 w:1)
+    /// Proof: Staking MaxCooperationsPerCooperator (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+    fn set_max_cooperations() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `0`
+        //  Estimated: `0`
+        // Minimum execution time: 8_312_000 picoseconds.
+        Weight::from_parts(8_604_000, 0)
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    /// Storage: Staking BatterySlotCapacityOverride (r:0 w:1)
+    /// Proof: Staking BatterySlotCapacityOverride (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+    fn set_battery_slot_capacity() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `0`
+        //  Estimated: `0`
+        // Minimum execution time: 8_497_000 picoseconds.
+        Weight::from_parts(8_815_000, 0)
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    /// Storage: Staking NacRevocationChillPolicy (r:0 w:1)
+    /// Proof: Staking NacRevocationChillPolicy (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+    fn set_nac_revocation_policy() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `0`
+        //  Estimated: `0`
+        // Minimum execution time: 8_106_000 picoseconds.
+        Weight::from_parts(8_392_000, 0)
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    /// Storage: Staking PendingOffendingValidatorsThreshold (r:0 w:1)
+    /// Proof: Staking PendingOffendingValidatorsThreshold (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+    fn set_offending_threshold() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `0`
+        //  Estimated: `0`
+        // Minimum execution time: 8_289_000 picoseconds.
+        Weight::from_parts(8_577_000, 0)
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    /// Storage: Staking EnergyPerReputationPointOverride (r:0 w:1)
+    /// Proof: Staking EnergyPerReputationPointOverride (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+    fn set_energy_per_reputation_point() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `0`
+        //  Estimated: `0`
+        // Minimum execution time: 8_533_000 picoseconds.
+        Weight::from_parts(8_841_000, 0)
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    /// Storage: Staking TierEnergyBonus (r:2 w:1)
+    /// Proof: Staking TierEnergyBonus (max_values: None, max_size: Some(14), added: 2489, mode: MaxEncodedLen)
+    fn set_tier_bonus() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `4`
+        //  Estimated: `3479`
+        // Minimum execution time: 14_752_000 picoseconds.
+        Weight::from_parts(15_238_000, 3479)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    /// Storage: Staking Ledger (r:1 w:0)
+    /// Proof: Staking Ledger (max_values: None, max_size: Some(1091), added: 3566, mode: MaxEncodedLen)
+    /// Storage: Staking Validators (r:1 w:0)
+    /// Proof: Staking Validators (max_values: None, max_size: Some(45), added: 2520, mode: MaxEncodedLen)
+    /// Storage: Staking CurrentEra (r:1 w:0)
+    /// Proof: Staking CurrentEra (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+    /// Storage: Staking NextEraIntent (r:0 w:1)
+    /// Proof: Staking NextEraIntent (max_values: None, max_size: Some(53), added: 2528, mode: MaxEncodedLen)
+    fn commit_next_era_intent() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `1016`
+        //  Estimated: `4556`
+        // Minimum execution time: 21_347_000 picoseconds.
+        Weight::from_parts(22_105_000, 4556)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    /// Storage: Staking RewardsPaused (r:1 w:1)
+    /// Proof: Staking RewardsPaused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+    fn set_rewards_paused() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `0`
+        //  Estimated: `1486`
+        // Minimum execution time: 7_412_000 picoseconds.
+        Weight::from_parts(7_689_000, 1486)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
 }
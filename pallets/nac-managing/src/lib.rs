@@ -23,6 +23,12 @@
 //!
 //! - **Storage Items**:
 //!   - **`UsersNft`**: Tracks the NFT details for each user, including their NAC level.
+//!   - **`AccessExemptions`**: Accounts allowed to submit EVM transactions without the required
+//!     NAC level, managed by the admin origin.
+//!   - **`TargetAccessLevels`**: NAC levels required to interact with specific EVM targets,
+//!     e.g. sensitive contracts, managed by the admin origin.
+//!   - **`AccountNames`**: Optional display names registered by accounts against a deposit, with
+//!     the `AccountByName` reverse index used by explorers to label addresses.
 //!   - **Events**: Several events are defined, such as `NftMinted`, `NftUpdated`, and `VippNftMinted`,
 //!     which provide information about the actions taken within the pallet, such as minting or updating
 //!     NFTs.
@@ -57,6 +63,8 @@
 //!   that allow for seamless interaction with other pallets or components of the blockchain. For example,
 //!   the `on_claim()` function allows minting VIPP NFTs upon a user's successful claim, integrating
 //!   additional utility into the existing reward or staking system.
+//! - **Revocation Hook**: Lowering an account's NAC level to zero calls `Config::OnNacRevoked`, which
+//!   the runtime uses to chill validators that lost their access.
 //!
 //! # Usage Scenarios
 //!
@@ -103,7 +111,7 @@ use frame_support::{
     pallet_prelude::{BoundedVec, DispatchResult},
     traits::{
         tokens::nonfungibles_v2::{Create, Inspect, InspectEnumerable, Mutate},
-        Currency, Get, Incrementable, OnNewAccount,
+        Currency, Get, Incrementable, OnNewAccount, ReservableCurrency,
     },
 };
 use frame_system::pallet_prelude::{BlockNumberFor, OriginFor};
@@ -130,6 +138,11 @@ mod tests;
 
 pub mod weights;
 
+type BalanceOf<T> = <T as pallet_balances::Config>::Balance;
+
+/// Display name registered for an account.
+pub type AccountNameOf<T> = BoundedVec<u8, <T as Config>::AccountNameLimit>;
+
 type CollectionConfigFor<T> = CollectionConfig<
     <T as pallet_balances::Config>::Balance,
     BlockNumberFor<T>,
@@ -156,6 +169,7 @@ pub mod pallet {
     use super::*;
     use frame_support::pallet_prelude::*;
     use frame_support::traits::LockableCurrency;
+    use frame_system::ensure_signed;
 
     #[pallet::pallet]
     pub struct Pallet<T>(_);
@@ -200,10 +214,10 @@ pub mod pallet {
 
         /// The currency.
         type Currency: LockableCurrency<
-            Self::AccountId,
-            Moment = BlockNumberFor<Self>,
-            Balance = <Self as pallet_balances::Config>::Balance,
-        >;
+                Self::AccountId,
+                Moment = BlockNumberFor<Self>,
+                Balance = <Self as pallet_balances::Config>::Balance,
+            > + ReservableCurrency<Self::AccountId>;
 
         /// Handler for VIPP members.
         type OnVIPPChanged: OnVippStatusHandler<
@@ -212,11 +226,26 @@ pub mod pallet {
             Self::ItemId,
         >;
 
+        /// Handler for accounts whose NAC access is revoked.
+        type OnNacRevoked: OnNacRevokedHandler<Self::AccountId>;
+
         /// NFT Collection ID.
         type NftCollectionId: Get<Self::CollectionId>;
 
         /// VIPP NFT Collection ID.
         type VIPPCollectionId: Get<Self::CollectionId>;
+
+        /// The maximum length of an account display name.
+        #[pallet::constant]
+        type AccountNameLimit: Get<u32>;
+
+        /// The amount reserved from an account while it holds a display name.
+        #[pallet::constant]
+        type AccountNameDeposit: Get<BalanceOf<Self>>;
+
+        /// Whether a display name may be held by a single account only.
+        #[pallet::constant]
+        type UniqueAccountNames: Get<bool>;
     }
 
     /// Temp storage: the information about user NFTs and NAC levels.
@@ -224,6 +253,36 @@ pub mod pallet {
     pub type UsersNft<T: Config> =
         StorageMap<_, Blake2_128Concat, T::AccountId, (T::ItemId, u8), OptionQuery>;
 
+    /// Display names registered by accounts along with the reserved deposit.
+    #[pallet::storage]
+    #[pallet::getter(fn account_name)]
+    pub type AccountNames<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        (AccountNameOf<T>, BalanceOf<T>),
+        OptionQuery,
+    >;
+
+    /// Reverse index of display names. Only maintained when `UniqueAccountNames` is enabled.
+    #[pallet::storage]
+    #[pallet::getter(fn account_by_name)]
+    pub type AccountByName<T: Config> =
+        StorageMap<_, Blake2_128Concat, AccountNameOf<T>, T::AccountId, OptionQuery>;
+
+    /// Accounts allowed to submit EVM transactions without holding the required NAC level,
+    /// e.g. oracle feeders and relayers.
+    #[pallet::storage]
+    pub type AccessExemptions<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+    /// NAC level required to interact with a target, e.g. a sensitive EVM contract. Targets
+    /// without a level only require the default one.
+    #[pallet::storage]
+    #[pallet::getter(fn target_access_level)]
+    pub type TargetAccessLevels<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u8, OptionQuery>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -258,6 +317,40 @@ pub mod pallet {
             /// The VIPP NFT unique ID.
             item_id: T::ItemId,
         },
+
+        /// Account display name was set.
+        AccountNameSet {
+            /// Whose name.
+            who: T::AccountId,
+            /// The new display name.
+            name: AccountNameOf<T>,
+        },
+
+        /// Account display name was cleared.
+        AccountNameCleared {
+            /// Whose name.
+            who: T::AccountId,
+        },
+
+        /// Account was exempted from the NAC access check.
+        AccessExemptionAdded {
+            /// The exempted account.
+            who: T::AccountId,
+        },
+
+        /// Account lost its exemption from the NAC access check.
+        AccessExemptionRemoved {
+            /// The account that is no longer exempted.
+            who: T::AccountId,
+        },
+
+        /// NAC level required to interact with a target was set.
+        TargetAccessLevelSet {
+            /// The target, e.g. an EVM contract.
+            target: T::AccountId,
+            /// The required NAC level, `None` if only the default one is required.
+            level: Option<u8>,
+        },
     }
 
     #[pallet::error]
@@ -268,6 +361,18 @@ pub mod pallet {
         NftAlreadyExist,
         /// NAC level is not correct.
         NacLevelIsIncorrect,
+        /// Account name is empty.
+        EmptyAccountName,
+        /// Account name exceeds `AccountNameLimit`.
+        AccountNameTooLong,
+        /// Account name is already registered by another account.
+        AccountNameTaken,
+        /// Account has no registered name.
+        AccountNameNotFound,
+        /// Account is already exempted from the NAC access check.
+        AlreadyExempted,
+        /// Account is not exempted from the NAC access check.
+        NotExempted,
     }
 
     #[pallet::call]
@@ -330,6 +435,106 @@ pub mod pallet {
             Self::deposit_event(Event::UserNacLevel { nac_level, owner });
             Ok(())
         }
+
+        /// Register a display name for the caller's account.
+        ///
+        /// `AccountNameDeposit` is reserved on the first registration and kept while the name
+        /// is set. Renaming reuses the existing deposit.
+        #[pallet::call_index(3)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_account_name())]
+        pub fn set_account_name(origin: OriginFor<T>, name: Vec<u8>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(!name.is_empty(), Error::<T>::EmptyAccountName);
+            let name: AccountNameOf<T> =
+                name.try_into().map_err(|_| Error::<T>::AccountNameTooLong)?;
+
+            let previous = AccountNames::<T>::get(&who);
+
+            if T::UniqueAccountNames::get() {
+                if let Some(owner) = AccountByName::<T>::get(&name) {
+                    ensure!(owner == who, Error::<T>::AccountNameTaken);
+                }
+            }
+
+            let deposit = match previous {
+                Some((old_name, deposit)) => {
+                    AccountByName::<T>::remove(&old_name);
+                    deposit
+                },
+                None => {
+                    let deposit = T::AccountNameDeposit::get();
+                    T::Currency::reserve(&who, deposit)?;
+                    deposit
+                },
+            };
+
+            if T::UniqueAccountNames::get() {
+                AccountByName::<T>::insert(&name, &who);
+            }
+            AccountNames::<T>::insert(&who, (&name, deposit));
+
+            Self::deposit_event(Event::AccountNameSet { who, name });
+            Ok(())
+        }
+
+        /// Clear the caller's display name and unreserve the deposit.
+        #[pallet::call_index(4)]
+        #[pallet::weight(<T as Config>::WeightInfo::clear_account_name())]
+        pub fn clear_account_name(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let (name, deposit) =
+                AccountNames::<T>::take(&who).ok_or(Error::<T>::AccountNameNotFound)?;
+            AccountByName::<T>::remove(&name);
+            T::Currency::unreserve(&who, deposit);
+
+            Self::deposit_event(Event::AccountNameCleared { who });
+            Ok(())
+        }
+
+        /// Allow the account to submit EVM transactions regardless of its NAC level.
+        #[pallet::call_index(5)]
+        #[pallet::weight(<T as Config>::WeightInfo::add_access_exemption())]
+        pub fn add_access_exemption(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            ensure!(!AccessExemptions::<T>::contains_key(&who), Error::<T>::AlreadyExempted);
+            AccessExemptions::<T>::insert(&who, ());
+
+            Self::deposit_event(Event::AccessExemptionAdded { who });
+            Ok(())
+        }
+
+        /// Revoke the exemption of the account. The NAC check applies again to its next
+        /// transaction.
+        #[pallet::call_index(6)]
+        #[pallet::weight(<T as Config>::WeightInfo::remove_access_exemption())]
+        pub fn remove_access_exemption(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            AccessExemptions::<T>::take(&who).ok_or(Error::<T>::NotExempted)?;
+
+            Self::deposit_event(Event::AccessExemptionRemoved { who });
+            Ok(())
+        }
+
+        /// Require the NAC level to interact with the target, or only the default level if
+        /// `level` is `None`. A level below the default one doesn't lower it.
+        #[pallet::call_index(7)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_target_access_level())]
+        pub fn set_target_access_level(
+            origin: OriginFor<T>,
+            target: T::AccountId,
+            level: Option<u8>,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            TargetAccessLevels::<T>::set(&target, level);
+
+            Self::deposit_event(Event::TargetAccessLevelSet { target, level });
+            Ok(())
+        }
     }
 
     #[pallet::genesis_config]
@@ -401,8 +606,13 @@ impl<T: Config> Pallet<T> {
         T::Nfts::set_attribute(collection, item, &key, &nac)?;
 
         // Temporary solution to save NFT id and NAC level by user.
+        let previous = UsersNft::<T>::get(&owner).map(|(_, level)| level);
         UsersNft::<T>::insert(&owner, (&item, &nac_level));
 
+        if nac_level == 0 && previous.map_or(false, |level| level > 0) {
+            T::OnNacRevoked::on_nac_revoked(&owner);
+        }
+
         Self::deposit_event(Event::NftUpdated { owner, nac_level });
 
         Ok(())
@@ -446,6 +656,16 @@ impl<T: Config> Pallet<T> {
         }
     }
 
+    /// Whether the account may bypass the NAC access check.
+    pub fn is_access_exempt(account_id: &T::AccountId) -> bool {
+        AccessExemptions::<T>::contains_key(account_id)
+    }
+
+    /// NAC level required to interact with the target, never below `default_level`.
+    pub fn required_access_level(target: &T::AccountId, default_level: u8) -> u8 {
+        Self::target_access_level(target).map_or(default_level, |level| level.max(default_level))
+    }
+
     /// Get NAC level.
     pub fn get_nac_level(account_id: &T::AccountId) -> Option<(u8, <T as Config>::ItemId)> {
         let collection_id = T::NftCollectionId::get();
@@ -756,6 +976,16 @@ impl<T: Config> OnWithdrawFeeHandler<T::AccountId> for Pallet<T> {
     }
 }
 
+/// Handler for accounts losing their NAC access.
+pub trait OnNacRevokedHandler<AccountId> {
+    /// The NAC level of the account was lowered to zero.
+    fn on_nac_revoked(who: &AccountId);
+}
+
+impl<AccountId> OnNacRevokedHandler<AccountId> for () {
+    fn on_nac_revoked(_who: &AccountId) {}
+}
+
 /// Handler for updating, burning VIPP status.
 pub trait OnVippStatusHandler<AccountId, Balance, ItemId> {
     /// Handle a minting new VIPP NFT.
@@ -21,7 +21,7 @@ use crate::{self as pallet_nac_managing, *};
 
 use frame_support::{
     derive_impl, parameter_types,
-    traits::{AsEnsureOriginWithArg, ConstU32, ConstU64},
+    traits::{AsEnsureOriginWithArg, ConstBool, ConstU32, ConstU64},
     weights::constants::RocksDbWeight,
 };
 use frame_system::{EnsureRoot, EnsureSigned};
@@ -126,6 +126,16 @@ impl pallet_nfts::Config for Test {
 parameter_types! {
     pub const NftCollectionId: CollectionId = 0;
     pub const VIPPCollectionId: CollectionId = 1;
+    pub const AccountNameDeposit: Balance = 10;
+    pub static RevokedNacs: Vec<AccountId> = vec![];
+}
+
+pub struct RecordNacRevocation;
+
+impl crate::OnNacRevokedHandler<AccountId> for RecordNacRevocation {
+    fn on_nac_revoked(who: &AccountId) {
+        RevokedNacs::mutate(|revoked| revoked.push(*who));
+    }
 }
 
 impl crate::Config for Test {
@@ -141,6 +151,10 @@ impl crate::Config for Test {
     type Currency = Balances;
     type VIPPCollectionId = VIPPCollectionId;
     type OnVIPPChanged = ();
+    type OnNacRevoked = RecordNacRevocation;
+    type AccountNameLimit = ConstU32<16>;
+    type AccountNameDeposit = AccountNameDeposit;
+    type UniqueAccountNames = ConstBool<true>;
 }
 
 parameter_types! {
@@ -177,9 +191,17 @@ impl pallet_assets::Config for Test {
     type BenchmarkHelper = ();
 }
 
+parameter_types! {
+    pub const MaxReputationPerEra: pallet_reputation::ReputationPoint = pallet_reputation::ReputationPoint(u64::MAX);
+}
+
 impl pallet_reputation::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = ();
+    type DecayBatchSize = ConstU32<0>;
+    type MaxReputationPerEra = MaxReputationPerEra;
+    type CurrentEra = ConstU32<0>;
+    type ReputationActionCooldown = ConstU64<0>;
 }
 
 #[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
@@ -155,3 +155,140 @@ fn on_claim_should_work() {
         assert_eq!(new_claimed, claimed + 1000);
     });
 }
+
+#[test]
+fn set_account_name_should_work() {
+    new_test_ext().execute_with(|| {
+        let account = 1_u64;
+        Balances::force_set_balance(RuntimeOrigin::root(), account, 100).unwrap();
+
+        assert_ok!(NacManaging::set_account_name(
+            RuntimeOrigin::signed(account),
+            b"alice".to_vec()
+        ));
+
+        let name: AccountNameOf<Test> = b"alice".to_vec().try_into().unwrap();
+        assert_eq!(NacManaging::account_name(account), Some((name.clone(), 10)));
+        assert_eq!(NacManaging::account_by_name(&name), Some(account));
+        assert_eq!(Balances::reserved_balance(account), 10);
+
+        // Renaming keeps the same deposit and frees the previous name.
+        assert_ok!(NacManaging::set_account_name(RuntimeOrigin::signed(account), b"bob".to_vec()));
+        assert_eq!(NacManaging::account_by_name(&name), None);
+        assert_eq!(Balances::reserved_balance(account), 10);
+
+        assert_ok!(NacManaging::clear_account_name(RuntimeOrigin::signed(account)));
+        assert_eq!(NacManaging::account_name(account), None);
+        assert_eq!(Balances::reserved_balance(account), 0);
+        assert_err!(
+            NacManaging::clear_account_name(RuntimeOrigin::signed(account)),
+            Error::<Test>::AccountNameNotFound
+        );
+    });
+}
+
+#[test]
+fn set_account_name_rejects_invalid_names() {
+    new_test_ext().execute_with(|| {
+        let (account, other) = (1_u64, 2_u64);
+        Balances::force_set_balance(RuntimeOrigin::root(), account, 100).unwrap();
+        Balances::force_set_balance(RuntimeOrigin::root(), other, 100).unwrap();
+
+        assert_err!(
+            NacManaging::set_account_name(RuntimeOrigin::signed(account), vec![]),
+            Error::<Test>::EmptyAccountName
+        );
+        assert_err!(
+            NacManaging::set_account_name(RuntimeOrigin::signed(account), vec![b'a'; 17]),
+            Error::<Test>::AccountNameTooLong
+        );
+
+        assert_ok!(NacManaging::set_account_name(
+            RuntimeOrigin::signed(account),
+            b"alice".to_vec()
+        ));
+        assert_err!(
+            NacManaging::set_account_name(RuntimeOrigin::signed(other), b"alice".to_vec()),
+            Error::<Test>::AccountNameTaken
+        );
+    });
+}
+
+#[test]
+fn access_exemption_should_work() {
+    new_test_ext().execute_with(|| {
+        let account = 1_u64;
+        assert!(!NacManaging::user_has_access(account, 2));
+        assert!(!NacManaging::is_access_exempt(&account));
+
+        assert_err!(
+            NacManaging::add_access_exemption(RuntimeOrigin::signed(account), account),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        assert_ok!(NacManaging::add_access_exemption(RuntimeOrigin::root(), account));
+        assert!(NacManaging::is_access_exempt(&account));
+        System::assert_last_event(Event::AccessExemptionAdded { who: account }.into());
+        assert_err!(
+            NacManaging::add_access_exemption(RuntimeOrigin::root(), account),
+            Error::<Test>::AlreadyExempted
+        );
+
+        assert_ok!(NacManaging::remove_access_exemption(RuntimeOrigin::root(), account));
+        assert!(!NacManaging::is_access_exempt(&account));
+        System::assert_last_event(Event::AccessExemptionRemoved { who: account }.into());
+        assert_err!(
+            NacManaging::remove_access_exemption(RuntimeOrigin::root(), account),
+            Error::<Test>::NotExempted
+        );
+    });
+}
+
+#[test]
+fn target_access_level_should_work() {
+    new_test_ext().execute_with(|| {
+        let gated = 10_u64;
+        let ungated = 11_u64;
+
+        assert_err!(
+            NacManaging::set_target_access_level(RuntimeOrigin::signed(1), gated, Some(3)),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        assert_ok!(NacManaging::set_target_access_level(RuntimeOrigin::root(), gated, Some(3)));
+        System::assert_last_event(
+            Event::TargetAccessLevelSet { target: gated, level: Some(3) }.into(),
+        );
+        assert_eq!(NacManaging::required_access_level(&gated, 1), 3);
+        assert_eq!(NacManaging::required_access_level(&ungated, 1), 1);
+        // the default level is never lowered
+        assert_eq!(NacManaging::required_access_level(&gated, 4), 4);
+
+        assert_ok!(NacManaging::set_target_access_level(RuntimeOrigin::root(), gated, None));
+        System::assert_last_event(
+            Event::TargetAccessLevelSet { target: gated, level: None }.into(),
+        );
+        assert_eq!(NacManaging::required_access_level(&gated, 1), 1);
+    });
+}
+
+#[test]
+fn nac_revocation_is_reported() {
+    new_test_ext().execute_with(|| {
+        let account = 1_u64;
+        let unverified = 2_u64;
+        assert_ok!(NacManaging::create_collection(&account));
+
+        assert_ok!(NacManaging::mint(RuntimeOrigin::root(), 2, account));
+        assert_ok!(NacManaging::update_nft(RuntimeOrigin::root(), Some(1), account));
+        assert!(RevokedNacs::get().is_empty());
+
+        assert_ok!(NacManaging::update_nft(RuntimeOrigin::root(), Some(0), account));
+        assert_eq!(RevokedNacs::get(), vec![account]);
+
+        // only losing the access is reported, not keeping or never having it
+        assert_ok!(NacManaging::update_nft(RuntimeOrigin::root(), None, account));
+        assert_ok!(NacManaging::mint(RuntimeOrigin::root(), 0, unverified));
+        assert_eq!(RevokedNacs::get(), vec![account]);
+    });
+}
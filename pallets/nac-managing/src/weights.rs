@@ -28,6 +28,11 @@ pub trait WeightInfo {
     fn mint() -> Weight;
     fn update_nft() -> Weight;
     fn check_nac_level() -> Weight;
+    fn set_account_name() -> Weight;
+    fn clear_account_name() -> Weight;
+    fn add_access_exemption() -> Weight;
+    fn remove_access_exemption() -> Weight;
+    fn set_target_access_level() -> Weight;
 }
 
 pub struct SubstrateWeight<T>(PhantomData<T>);
@@ -49,6 +54,34 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(3_u64))
             .saturating_add(T::DbWeight::get().reads(3_u64))
     }
+
+    fn set_account_name() -> Weight {
+        Weight::from_parts(27_000_000, 3643)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(4_u64))
+    }
+
+    fn clear_account_name() -> Weight {
+        Weight::from_parts(24_000_000, 3643)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(3_u64))
+    }
+
+    fn add_access_exemption() -> Weight {
+        Weight::from_parts(12_000_000, 3507)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    fn remove_access_exemption() -> Weight {
+        Weight::from_parts(12_000_000, 3507)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    fn set_target_access_level() -> Weight {
+        Weight::from_parts(10_000_000, 0).saturating_add(T::DbWeight::get().writes(1_u64))
+    }
 }
 
 impl WeightInfo for () {
@@ -69,4 +102,32 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(3_u64))
             .saturating_add(RocksDbWeight::get().writes(3_u64))
     }
+
+    fn set_account_name() -> Weight {
+        Weight::from_parts(27_000_000, 3643)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(4_u64))
+    }
+
+    fn clear_account_name() -> Weight {
+        Weight::from_parts(24_000_000, 3643)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(3_u64))
+    }
+
+    fn add_access_exemption() -> Weight {
+        Weight::from_parts(12_000_000, 3507)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn remove_access_exemption() -> Weight {
+        Weight::from_parts(12_000_000, 3507)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn set_target_access_level() -> Weight {
+        Weight::from_parts(10_000_000, 0).saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
 }
\ No newline at end of file
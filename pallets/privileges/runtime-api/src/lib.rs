@@ -0,0 +1,16 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use pallet_privileges::VipInfo;
+use parity_scale_codec::Codec;
+
+sp_api::decl_runtime_apis! {
+    pub trait PrivilegesApi<AccountId, Balance>
+    where
+        AccountId: Codec,
+        Balance: Codec,
+    {
+        /// VIP status of `who` if its active bond changed to `new_stake`, `None` if it isn't a
+        /// VIP member or would lose the status.
+        fn vip_after_stake_change(who: AccountId, new_stake: Balance) -> Option<VipInfo<Balance>>;
+    }
+}
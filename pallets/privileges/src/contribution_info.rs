@@ -124,6 +124,23 @@ pub struct VipMemberInfo<T: pallet_energy_generation::Config> {
     pub active_stake: T::StakeBalance,
 }
 
+/// VIP status of a member projected for another stake.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct VipInfo<Balance> {
+    /// Stake the VIP points would accrue on.
+    pub active_stake: Balance,
+    /// VIP points accrued this year so far.
+    pub points: Balance,
+    /// VIP points that would accrue for the next day.
+    pub daily_points: Balance,
+    /// VIPP points that would accrue for the next day, zero without VIPP status.
+    pub daily_vipp_points: Balance,
+    /// Penalty currently applied to unbonded stake, zero in the penalty-free period.
+    pub penalty: Perbill,
+    /// Stake the penalty would slash when unbonding down to `active_stake`.
+    pub penalty_amount: Balance,
+}
+
 /// Information about VIPP member.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 #[scale_info(skip_type_params(T))]
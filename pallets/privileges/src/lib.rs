@@ -443,6 +443,48 @@ impl<T: Config> Pallet<T> {
         }
     }
 
+    /// The VIP status `account` would have if its active bond changed to `new_stake` today.
+    ///
+    /// Returns `None` if `account` isn't a VIP member or would lose its VIP status, i.e.
+    /// `new_stake` is below the minimum bond of its validator or cooperator role.
+    pub fn vip_after_stake_change(
+        account: &T::AccountId,
+        new_stake: T::StakeBalance,
+    ) -> Option<VipInfo<T::StakeBalance>> {
+        let vip_info = Self::vip_members(account)?;
+        if !Self::is_legit_for_vip(account) {
+            return None;
+        }
+        let min_bond = if pallet_energy_generation::Pallet::<T>::is_user_validator(account) {
+            pallet_energy_generation::Pallet::<T>::min_bond_for_validator(account)
+        } else {
+            pallet_energy_generation::MinCooperatorBond::<T>::get()
+        };
+        if new_stake < min_bond {
+            return None;
+        }
+
+        let current_stake = pallet_energy_generation::Pallet::<T>::ledger(account)
+            .map_or_else(T::StakeBalance::default, |ledger| ledger.active);
+        let penalty = Self::get_tax_percent(account);
+        let next_day = Self::current_date().days_since_new_year.saturating_add(1);
+        let vipp_threshold =
+            Self::vipp_members(account).map_or_else(T::StakeBalance::default, |info| {
+                info.active_vipp_threshold
+                    .iter()
+                    .fold(T::StakeBalance::default(), |acc, (_, balance)| acc + *balance)
+            });
+
+        Some(VipInfo {
+            active_stake: new_stake,
+            points: vip_info.points,
+            daily_points: Self::calculate_points(new_stake, Self::calculate_multiplier(next_day)),
+            daily_vipp_points: vipp_threshold.min(new_stake),
+            penalty,
+            penalty_amount: penalty * current_stake.saturating_sub(new_stake),
+        })
+    }
+
     /// Is now penalty-free period.
     fn is_penalty_free_period() -> bool {
         let current_date = Self::current_date();
@@ -4,8 +4,8 @@ use std::collections::BTreeMap;
 use frame_support::{
     derive_impl, ord_parameter_types, parameter_types,
     traits::{
-        AsEnsureOriginWithArg, ConstU32, ConstU64, EitherOfDiverse, FindAuthor, Hooks, Imbalance,
-        OnUnbalanced, OneSessionHandler, WithdrawReasons,
+        AsEnsureOriginWithArg, ConstBool, ConstU32, ConstU64, EitherOfDiverse, FindAuthor, Hooks,
+        Imbalance, OnUnbalanced, OneSessionHandler, WithdrawReasons,
     },
     weights::constants::RocksDbWeight,
 };
@@ -197,6 +197,10 @@ impl pallet_nac_managing::Config for Test {
     type Currency = Balances;
     type VIPPCollectionId = VIPPCollectionId;
     type OnVIPPChanged = Privileges;
+    type OnNacRevoked = ();
+    type AccountNameLimit = ConstU32<32>;
+    type AccountNameDeposit = ConstU64<1>;
+    type UniqueAccountNames = ConstBool<true>;
 }
 
 parameter_types! {
@@ -256,9 +260,17 @@ impl pallet_session::historical::Config for Test {
     type FullIdentificationOf = pallet_energy_generation::ExposureOf<Test>;
 }
 
+parameter_types! {
+    pub const MaxReputationPerEra: ReputationPoint = ReputationPoint(u64::MAX);
+}
+
 impl pallet_reputation::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = ();
+    type DecayBatchSize = ConstU32<0>;
+    type MaxReputationPerEra = MaxReputationPerEra;
+    type CurrentEra = ConstU32<0>;
+    type ReputationActionCooldown = ConstU64<0>;
 }
 
 impl pallet_authorship::Config for Test {
@@ -316,6 +328,7 @@ impl pallet_claiming::Config for Test {
     type VestingSchedule = Vesting;
     type OnClaim = NacManaging;
     type Prefix = Prefix;
+    type IdempotentClaims = ConstBool<false>;
     type WeightInfo = ();
 }
 
@@ -334,6 +347,7 @@ parameter_types! {
     pub const BondingDuration: EraIndex = 3;
     pub const RewardCurve: &'static PiecewiseLinear<'static> = &I_NPOS;
     pub const OffendingValidatorsThreshold: Perbill = Perbill::from_percent(75);
+    pub const SlashCapRewardPenalty: Perbill = Perbill::from_percent(50);
 }
 
 parameter_types! {
@@ -382,6 +396,10 @@ impl OnStakingUpdate<AccountId, Balance> for EventListenerMock {
 parameter_types! {
     pub const VNRG: AssetId = 1;
     pub static BatterySlotCapacity: EnergyOf<Test> = EnergyOf::<Test>::from(100_000_000_000u64);
+    pub static MinBatterySlotCapacity: EnergyOf<Test> = EnergyOf::<Test>::from(10_000_000_000u64);
+    pub static MaxBatterySlotCapacity: EnergyOf<Test> = EnergyOf::<Test>::from(1_000_000_000_000u64);
+    pub const EnergyPerReputationPoint: EnergyOf<Test> = 0;
+    pub const MaxEnergyPerReputationPoint: EnergyOf<Test> = 1_000;
     pub static MaxCooperations: u32 = 16;
     pub static HistoryDepth: u32 = 80;
     pub static MaxUnlockingChunks: u32 = 32;
@@ -397,6 +415,10 @@ impl pallet_energy_generation::Config for Test {
     type StakeBalance = <Self as pallet_balances::Config>::Balance;
     type EnergyAssetId = VNRG;
     type BatterySlotCapacity = BatterySlotCapacity;
+    type MinBatterySlotCapacity = MinBatterySlotCapacity;
+    type MaxBatterySlotCapacity = MaxBatterySlotCapacity;
+    type EnergyPerReputationPoint = EnergyPerReputationPoint;
+    type MaxEnergyPerReputationPoint = MaxEnergyPerReputationPoint;
     type UnixTime = Timestamp;
     type MaxCooperations = MaxCooperations;
     type HistoryDepth = HistoryDepth;
@@ -409,15 +431,30 @@ impl pallet_energy_generation::Config for Test {
     type SlashDeferDuration = SlashDeferDuration;
     type AdminOrigin = EnsureOneOrRoot;
     type SessionInterface = Self;
+    type SessionKeysRotationAge = ConstU32<0>;
     type EnergyPerStakeCurrency = EnergyGeneration;
     type NextNewSession = Session;
     type MaxCooperatorRewardedPerValidator = ConstU32<64>;
+    type SlashCapRewardPenalty = SlashCapRewardPenalty;
+    type OutdatedClientRewardPenalty = ();
+    type BackingBoost = ();
+    type ClientVersionGracePeriod = ();
+    type OnboardingGraceEras = ();
+    type StaleRewardsDestination = ();
+    type MaxStaleRewardErasPerSweep = ConstU32<0>;
+    type MaxEnergyMintPerBlock = ();
+    type DeferRewardsWhilePaused = ();
+    type MinEnergyPerStakeCurrency = ();
+    type OperationalCallFee = ();
+    type ReservedOperationalCalls = ();
     type MaxUnlockingChunks = MaxUnlockingChunks;
     type EventListeners = EventListenerMock;
     type DisablingStrategy = pallet_energy_generation::UpToLimitDisablingStrategy<3>;
+    type OffendingValidatorsThreshold = OffendingValidatorsThreshold;
     type ValidatorReputationTier = ValidatorReputationTier;
     type CollaborativeValidatorReputationTier = CollaborativeValidatorReputationTier;
     type ReputationTierEnergyRewardAdditionalPercentMapping = ();
+    type MaxTierEnergyBonus = ();
     type ValidatorNacLevel = ();
     type OnVipMembershipHandler = Privileges;
     type BenchmarkingConfig = TestBenchmarkingConfig;
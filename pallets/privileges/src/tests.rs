@@ -615,3 +615,35 @@ fn test_from_validator_to_cooperator() {
         assert_eq!(Privileges::vip_members(10).unwrap().active_stake, 100);
     })
 }
+
+#[test]
+fn vip_after_stake_change_projects_vip_loss() {
+    ExtBuilder::default()
+        .min_common_validator_bond(500)
+        .min_trust_validator_bond(500)
+        .build_and_execute(|| {
+            assert_eq!(Privileges::vip_after_stake_change(&10, 900), None);
+            assert_ok!(Privileges::become_vip_status(RuntimeOrigin::signed(10), PenaltyType::Flat));
+            assert_eq!(EnergyGeneration::ledger(10).unwrap().active, 1000);
+
+            // unstaking is free of penalties in January
+            let vip = Privileges::vip_after_stake_change(&10, 900).unwrap();
+            assert_eq!(vip.active_stake, 900);
+            assert_eq!(vip.penalty, Perbill::zero());
+            assert_eq!(vip.penalty_amount, 0);
+            assert_eq!(vip.daily_vipp_points, 0);
+
+            assert_ok!(Privileges::update_time(RuntimeOrigin::root(), 2020, 4, 1));
+            let vip = Privileges::vip_after_stake_change(&10, 600).unwrap();
+            // 2020-04-02 is the 92nd day since new year
+            assert_eq!(vip.daily_points, 600 / (92 + 50));
+            assert_eq!(vip.penalty, PenaltyType::Flat.penalty_percent(2));
+            assert_eq!(vip.penalty_amount, 70);
+            assert_eq!(vip.points, Privileges::vip_members(10).unwrap().points);
+
+            // unstaking below the validator bond loses the VIP status
+            assert_eq!(Privileges::vip_after_stake_change(&10, 400), None);
+            // the projection doesn't change the membership
+            assert_eq!(Privileges::vip_members(10).unwrap().active_stake, 1000);
+        })
+}
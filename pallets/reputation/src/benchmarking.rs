@@ -52,5 +52,27 @@ mod benchmarks {
         update_points(RawOrigin::Signed(account.clone()), account.clone());
     }
 
+    #[benchmark]
+    fn update_points_batch(n: Linear<1, 500>) {
+        // every account moves to the next tier, and one is left for the next batch so that the
+        // cursor is kept
+        let points = ReputationPoint::from_rank(1).0 - 1;
+        for i in 0..=n {
+            let account: T::AccountId = account("account", i, 0);
+            AccountReputation::<T>::insert(
+                &account,
+                ReputationRecord::from(ReputationPoint(points)),
+            );
+        }
+        frame_system::Pallet::<T>::set_block_number(1u32.into());
+
+        #[block]
+        {
+            assert_eq!(PalletReputation::<T>::update_points_batch(n), n);
+        }
+
+        assert!(UpdateCursor::<T>::get().is_some());
+    }
+
     impl_benchmark_test_suite!(PalletReputation, crate::mock::new_test_ext(), crate::mock::Test);
 }
@@ -1,15 +1,21 @@
 //! Reputation pallet provides users behavior evaluation. Any user gets basic reputation reward per
 //! block.
 //!
-//! The frequency of rewards/reputation updates is meant to be done by the pallet, which uses the
-//! reputation. To calculate the reputation for all users you should call
-//! `Pallet::update_points_for_time`. It's not an extrinsic, but it's cost operation because of the
-//! iteration via accounts, so don't call it very often.
+//! The pallet updates the reputation for time in `on_initialize`, at most `Config::DecayBatchSize`
+//! accounts per block, resuming from a stored cursor so every account is eventually updated
+//! without exceeding the block weight. To calculate the reputation for all users at once you can
+//! call `Pallet::update_points_for_time`. It's not an extrinsic, but it's cost operation because of
+//! the iteration via accounts, so don't call it very often.
 //!
 //! Reputation is measured in `points`. The `points` can't be transfered, sold or bought. And you
 //! should avoid any mechanism for points movement between accounts, because as you get reputation
 //! per time, you could simply accumulate reputation between different accounts and get <N of
 //! accounts>x points rewards.
+//!
+//! Besides the points for time, accounts earn points for their activity, e.g. authoring blocks
+//! or reporting offences. The points earned per era are capped by `Config::MaxReputationPerEra`,
+//! so combining several sources can't speed up the progression beyond the intended pace. The
+//! points above the cap are dropped, and the cap resets when a new era starts.
 //! Reputation Pallet Library
 //!
 //! This library provides the core functionality for evaluating user behavior within a Substrate-based blockchain.
@@ -18,7 +24,8 @@
 //! # Features
 //! - Grants basic reputation rewards to users for every block, promoting positive behavior.
 //! - Includes functions for managing reputation updates across multiple users.
-//! - Offers an efficient mechanism for bulk updates of reputation points, using `Pallet::update_points_for_time`.
+//! - Updates reputation points in weight-bounded batches per block, using `Pallet::update_points_batch`.
+//! - Offers a mechanism for bulk updates of reputation points, using `Pallet::update_points_for_time`.
 //!
 //! # Structure
 //! - Contains the main entry points for the Reputation pallet, including public-facing functions.
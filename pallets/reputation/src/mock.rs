@@ -1,6 +1,6 @@
-use crate as pallet_reputation;
+use crate::{self as pallet_reputation, ReputationPoint};
 use frame_support::{
-    derive_impl,
+    derive_impl, parameter_types,
     traits::{ConstU16, ConstU64},
 };
 use sp_core::H256;
@@ -48,9 +48,20 @@ impl frame_system::Config for Test {
     type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
+parameter_types! {
+    pub static DecayBatchSize: u32 = 10;
+    pub static MaxReputationPerEra: ReputationPoint = ReputationPoint(u64::MAX);
+    pub static CurrentEra: u32 = 0;
+    pub static ReputationActionCooldown: u64 = 0;
+}
+
 impl pallet_reputation::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = ();
+    type DecayBatchSize = DecayBatchSize;
+    type MaxReputationPerEra = MaxReputationPerEra;
+    type CurrentEra = CurrentEra;
+    type ReputationActionCooldown = ReputationActionCooldown;
 }
 
 // Build genesis storage according to the mock runtime.
@@ -1,10 +1,13 @@
 //! Implementations for the Reputation pallet (non-dispatchables).
-use crate::{ReputationPoint, ReputationRecord};
+use crate::{ReputationPoint, ReputationRecord, ReputationTier};
 
 use super::pallet::*;
 use frame_support::pallet_prelude::*;
 use frame_support::traits::{OnKilledAccount, OnNewAccount};
-use sp_runtime::SaturatedConversion;
+use sp_runtime::{
+    traits::{Saturating, Zero},
+    SaturatedConversion,
+};
 
 /// Notice that this pallet implements the `OnNewAccount` and `OnKilledAccount` traits from
 /// `frame_support`. If you want any account to have associated reputation with it, you need to
@@ -15,25 +18,68 @@ impl<T: Config> Pallet<T> {
     /// Updates the points for the time since the last time the account was updated.
     pub fn update_points_for_time() {
         let now = <frame_system::Pallet<T>>::block_number().saturated_into();
-        AccountReputation::<T>::translate(|_: T::AccountId, mut old: ReputationRecord| {
+        AccountReputation::<T>::translate(|account: T::AccountId, mut old: ReputationRecord| {
+            let tier = old.reputation.tier();
             old.update_with_block_number(now);
+            Self::note_tier_change(&account, tier, old.reputation.tier());
             Some(old)
         });
     }
 
+    /// Updates the points for time of at most `limit` accounts, continuing after the account
+    /// the previous batch stopped at. Returns the number of accounts updated.
+    ///
+    /// Once all the accounts are updated the cursor is cleared and the next batch starts over.
+    /// Accounts created in the middle of a cycle are updated in this cycle if they're stored
+    /// after the cursor, and in the next one otherwise.
+    pub fn update_points_batch(limit: u32) -> u32 {
+        if limit == 0 {
+            return 0;
+        }
+
+        let now = <frame_system::Pallet<T>>::block_number().saturated_into();
+        let mut accounts = match UpdateCursor::<T>::take() {
+            Some(cursor) => AccountReputation::<T>::iter_from(cursor.into_inner()),
+            None => AccountReputation::<T>::iter(),
+        };
+
+        let mut processed = 0;
+        while processed < limit {
+            let Some((account, mut record)) = accounts.next() else {
+                return processed;
+            };
+            let tier = record.reputation.tier();
+            record.update_with_block_number(now);
+            let new = record.reputation.tier();
+            AccountReputation::<T>::insert(&account, record);
+            Self::note_tier_change(&account, tier, new);
+            processed += 1;
+        }
+
+        // a key longer than the cursor limit can't be resumed from, so the cycle starts over
+        if let Ok(cursor) = BoundedVec::try_from(accounts.last_raw_key().to_vec()) {
+            UpdateCursor::<T>::put(cursor);
+        }
+
+        processed
+    }
+
     /// Actually do the slash.
     pub fn do_slash(account: &T::AccountId, points: ReputationPoint) -> DispatchResult {
         let updated = <frame_system::Pallet<T>>::block_number().saturated_into();
 
-        AccountReputation::<T>::try_mutate_exists(account, |value| {
+        let (old, new) = AccountReputation::<T>::try_mutate_exists(account, |value| {
             value
                 .as_mut()
                 .map(|old| {
+                    let tier = old.reputation.tier();
                     old.reputation.decrease(points);
                     old.updated = updated;
+                    (tier, old.reputation.tier())
                 })
                 .ok_or(Error::<T>::AccountNotFound)
         })?;
+        Self::note_tier_change(account, old, new);
 
         Self::deposit_event(Event::ReputationSlashed { account: account.clone(), points });
 
@@ -41,26 +87,122 @@ impl<T: Config> Pallet<T> {
     }
 
     /// Increase the points for an account by the given amount, creating it if it doesn't exist.
+    ///
+    /// The increase isn't counted against the per era earning cap, use `Pallet::earn_creating`
+    /// for the points earned for the account's activity.
     pub fn increase_creating(account: &T::AccountId, points: ReputationPoint) {
-        AccountReputation::<T>::mutate(account, |old| match old {
-            Some(rec) => rec.reputation.increase(points),
-            None => *old = Some(ReputationRecord::from(points)),
+        let (old, new) = AccountReputation::<T>::mutate(account, |old| match old {
+            Some(rec) => {
+                let tier = rec.reputation.tier();
+                rec.reputation.increase(points);
+                (tier, rec.reputation.tier())
+            },
+            None => {
+                let rec = ReputationRecord::from(points);
+                let tier = rec.reputation.tier();
+                *old = Some(rec);
+                (None, tier)
+            },
         });
+        Self::note_tier_change(account, old, new);
+    }
+
+    /// Increase the points earned by an account, creating it if it doesn't exist.
+    ///
+    /// The points are capped by `Config::MaxReputationPerEra`. Returns the points actually added.
+    pub fn earn_creating(account: &T::AccountId, points: ReputationPoint) -> ReputationPoint {
+        let points = Self::capped_earning(account, points);
+        Self::increase_creating(account, points);
+        points
     }
 
     /// Actually increase points.
-    pub fn do_increase_points(account: &T::AccountId, points: ReputationPoint) -> DispatchResult {
-        <AccountReputation<T>>::try_mutate_exists(account, |value| {
+    ///
+    /// The points are capped by `Config::MaxReputationPerEra`. Returns the points actually added.
+    pub fn do_increase_points(
+        account: &T::AccountId,
+        points: ReputationPoint,
+    ) -> Result<ReputationPoint, DispatchError> {
+        ensure!(AccountReputation::<T>::contains_key(account), Error::<T>::AccountNotFound);
+        let points = Self::capped_earning(account, points);
+
+        let (old, new) = <AccountReputation<T>>::try_mutate_exists(account, |value| {
             value
                 .as_mut()
                 .map(|old| {
+                    let tier = old.reputation.tier();
                     old.reputation.increase(points);
+                    (tier, old.reputation.tier())
                 })
                 .ok_or(Error::<T>::AccountNotFound)
         })?;
+        Self::note_tier_change(account, old, new);
+
+        Ok(points)
+    }
 
+    /// Count `points` earned by an account against the per era cap, returning the part of them
+    /// that fits under it.
+    ///
+    /// The count starts over when the era changes. The points above the cap are dropped, not
+    /// carried over to the next era.
+    fn capped_earning(account: &T::AccountId, points: ReputationPoint) -> ReputationPoint {
+        let era = T::CurrentEra::get();
+        let allowed = EarnedInEra::<T>::mutate(account, |(earned_era, earned)| {
+            if *earned_era != era {
+                *earned_era = era;
+                *earned = ReputationPoint::zero();
+            }
+            let allowed = T::MaxReputationPerEra::get().0.saturating_sub(earned.0).min(points.0);
+            earned.0 = earned.0.saturating_add(allowed);
+            ReputationPoint(allowed)
+        });
+
+        if allowed < points {
+            Self::deposit_event(Event::ReputationEarningCapped {
+                account: account.clone(),
+                dropped: ReputationPoint(points.0 - allowed.0),
+            });
+        }
+
+        allowed
+    }
+
+    /// Record a change of the reputation of `account` by governance, failing if the previous one
+    /// was less than `Config::ReputationActionCooldown` blocks ago.
+    pub(crate) fn note_governance_action(account: &T::AccountId) -> DispatchResult {
+        let cooldown = T::ReputationActionCooldown::get();
+        if cooldown.is_zero() {
+            return Ok(());
+        }
+
+        let now = <frame_system::Pallet<T>>::block_number();
+        if let Some(last) = LastGovernanceAction::<T>::get(account) {
+            ensure!(now >= last.saturating_add(cooldown), Error::<T>::ReputationActionCooldown);
+        }
+        LastGovernanceAction::<T>::insert(account, now);
         Ok(())
     }
+
+    /// The current reputation tier of an account, `None` if it has none yet.
+    pub fn tier_of(account: &T::AccountId) -> Option<ReputationTier> {
+        AccountReputation::<T>::get(account).and_then(|record| record.reputation.tier())
+    }
+
+    /// Deposit `Event::TierChanged` if the account moved to another tier.
+    ///
+    /// Only the tier itself is compared, rank changes within a tier are not reported. As the
+    /// caller passes the tiers before and after the whole update, jumping over several tiers at
+    /// once results in a single event with the final tier.
+    pub(crate) fn note_tier_change(
+        account: &T::AccountId,
+        old: Option<ReputationTier>,
+        new: Option<ReputationTier>,
+    ) {
+        if old.map(|tier| tier.tier_index()) != new.map(|tier| tier.tier_index()) {
+            Self::deposit_event(Event::TierChanged { who: account.clone(), old, new });
+        }
+    }
 }
 
 impl<T: Config> OnNewAccount<T::AccountId> for Pallet<T> {
@@ -78,5 +220,7 @@ impl<T: Config> OnNewAccount<T::AccountId> for Pallet<T> {
 impl<T: Config> OnKilledAccount<T::AccountId> for Pallet<T> {
     fn on_killed_account(who: &T::AccountId) {
         AccountReputation::<T>::remove(who);
+        EarnedInEra::<T>::remove(who);
+        LastGovernanceAction::<T>::remove(who);
     }
 }
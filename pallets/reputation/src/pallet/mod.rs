@@ -28,14 +28,59 @@ pub mod pallet {
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
         /// Type representing the weight of this pallet
         type WeightInfo: WeightInfo;
+        /// The maximum number of accounts whose points are updated for time per block.
+        ///
+        /// Accounts are processed in batches, continuing where the previous block stopped, so
+        /// every account is updated once per cycle of `accounts / DecayBatchSize` blocks. Zero
+        /// disables the updates.
+        #[pallet::constant]
+        type DecayBatchSize: Get<u32>;
+        /// The maximum number of points an account can earn per era.
+        ///
+        /// Applies to the points earned for authoring, validating, reporting offences and manual
+        /// increases combined, the points for time being in the network aren't capped. Points
+        /// above the cap are dropped.
+        #[pallet::constant]
+        type MaxReputationPerEra: Get<ReputationPoint>;
+        /// The index of the current era, the earning cap resets when it changes.
+        type CurrentEra: Get<u32>;
+        /// The number of blocks between two changes of an account's reputation by governance.
+        ///
+        /// Applies to `force_set_points`, `increase_points` and `slash`. The points earned for
+        /// authoring or lost for offences aren't limited. Zero disables the cooldown.
+        #[pallet::constant]
+        type ReputationActionCooldown: Get<BlockNumberFor<Self>>;
     }
 
+    /// The maximum length of the raw storage key kept as the update cursor.
+    pub const MAX_CURSOR_LEN: u32 = 128;
+
     /// Reputation per account storage.
     #[pallet::storage]
     #[pallet::getter(fn reputation)]
     pub type AccountReputation<T: Config> =
         StorageMap<_, Twox64Concat, T::AccountId, ReputationRecord>;
 
+    /// Raw key of the last account updated for time, the next batch continues after it. `None`
+    /// if the next batch starts a new cycle.
+    #[pallet::storage]
+    #[pallet::getter(fn update_cursor)]
+    pub type UpdateCursor<T: Config> = StorageValue<_, BoundedVec<u8, ConstU32<MAX_CURSOR_LEN>>>;
+
+    /// Points earned by an account in an era, counted against `Config::MaxReputationPerEra`.
+    /// [era, points]
+    #[pallet::storage]
+    #[pallet::getter(fn earned_in_era)]
+    pub type EarnedInEra<T: Config> =
+        StorageMap<_, Twox64Concat, T::AccountId, (u32, ReputationPoint), ValueQuery>;
+
+    /// Block of the last change of an account's reputation by governance, see
+    /// `Config::ReputationActionCooldown`.
+    #[pallet::storage]
+    #[pallet::getter(fn last_governance_action)]
+    pub type LastGovernanceAction<T: Config> =
+        StorageMap<_, Twox64Concat, T::AccountId, BlockNumberFor<T>>;
+
     /// Pallet event type.
     #[pallet::event]
     #[pallet::generate_deposit(pub fn deposit_event)]
@@ -56,6 +101,10 @@ pub mod pallet {
         },
         /// Reputation of all account is forcibly reset to the new value. [points]
         ReputationResetForcibly { points: ReputationPoint },
+        /// Points earned by an account above the per era cap are dropped. [account, dropped]
+        ReputationEarningCapped { account: T::AccountId, dropped: ReputationPoint },
+        /// Reputation of an account moved to another tier. [who, old, new]
+        TierChanged { who: T::AccountId, old: Option<ReputationTier>, new: Option<ReputationTier> },
     }
 
     /// Pallet error type.
@@ -64,6 +113,17 @@ pub mod pallet {
     pub enum Error<T> {
         /// Account not found
         AccountNotFound,
+        /// The reputation of the account was changed by governance less than
+        /// `ReputationActionCooldown` blocks ago.
+        ReputationActionCooldown,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(_now: BlockNumberFor<T>) -> Weight {
+            let processed = Self::update_points_batch(T::DecayBatchSize::get());
+            T::WeightInfo::update_points_batch(processed)
+        }
     }
 
     #[pallet::call]
@@ -79,12 +139,14 @@ pub mod pallet {
             points: ReputationPoint,
         ) -> DispatchResult {
             ensure_root(origin)?;
+            Self::note_governance_action(&account)?;
             let updated = <frame_system::Pallet<T>>::block_number().saturated_into();
+            let old = Self::tier_of(&account);
+            let record = ReputationRecord { reputation: points.into(), updated };
+            let new = record.reputation.tier();
 
-            <AccountReputation<T>>::insert(
-                &account,
-                ReputationRecord { reputation: points.into(), updated },
-            );
+            <AccountReputation<T>>::insert(&account, record);
+            Self::note_tier_change(&account, old, new);
 
             Self::deposit_event(Event::ReputationSetForcibly { account, points });
 
@@ -102,6 +164,7 @@ pub mod pallet {
             points: ReputationPoint,
         ) -> DispatchResult {
             ensure_root(origin)?;
+            Self::note_governance_action(&account)?;
             Self::do_increase_points(&account, points)?;
             Ok(())
         }
@@ -117,6 +180,7 @@ pub mod pallet {
             points: ReputationPoint,
         ) -> DispatchResult {
             ensure_root(origin)?;
+            Self::note_governance_action(&account)?;
             Self::do_slash(&account, points)?;
             Ok(())
         }
@@ -132,10 +196,13 @@ pub mod pallet {
             let now = <frame_system::Pallet<T>>::block_number().saturated_into();
             let mut record = <AccountReputation<T>>::get(&account)
                 .unwrap_or_else(|| ReputationRecord::with_blocknumber(now));
+            let old = record.reputation.tier();
             record.update_with_block_number(now);
             let points = record.reputation.points;
+            let new = record.reputation.tier();
 
             <AccountReputation<T>>::insert(&account, record);
+            Self::note_tier_change(&account, old, new);
 
             Self::deposit_event(Event::ReputationUpdated { account, points });
 
@@ -151,8 +218,10 @@ pub mod pallet {
 
             let points = ReputationPoint::from(ReputationTier::Vanguard(1));
 
-            <AccountReputation<T>>::translate_values::<ReputationRecord, _>(|_| {
-                Some(ReputationRecord { reputation: points.into(), updated })
+            <AccountReputation<T>>::translate::<ReputationRecord, _>(|account, old| {
+                let record = ReputationRecord { reputation: points.into(), updated };
+                Self::note_tier_change(&account, old.reputation.tier(), record.reputation.tier());
+                Some(record)
             });
 
             Self::deposit_event(Event::ReputationResetForcibly { points });
@@ -1,8 +1,8 @@
 use crate::{
-    mock::*, Error, Event, Reputation, ReputationPoint, ReputationRecord, ReputationTier,
-    RANKS_PER_TIER, REPUTATION_POINTS_PER_BLOCK,
+    mock::*, weights::WeightInfo, AccountReputation, Error, Event, Reputation, ReputationPoint,
+    ReputationRecord, ReputationTier, RANKS_PER_TIER, REPUTATION_POINTS_PER_BLOCK,
 };
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
 
 #[test]
 fn can_set_points_forcefuly() {
@@ -117,6 +117,87 @@ fn can_update_points_for_account() {
     });
 }
 
+#[test]
+fn crossing_tier_boundary_emits_tier_changed() {
+    use ReputationTier::*;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let account = user();
+        let vanguard_1 = ReputationPoint::from_rank(1);
+        assert_ok!(ReputationPallet::force_set_points(RuntimeOrigin::root(), account, vanguard_1));
+        System::assert_has_event(
+            Event::TierChanged { who: account, old: None, new: Some(Vanguard(1)) }.into(),
+        );
+        assert_eq!(ReputationPallet::tier_of(&account), Some(Vanguard(1)));
+
+        // moving up within the tier is not reported
+        System::reset_events();
+        let to_vanguard_3 = *ReputationPoint::from_rank(RANKS_PER_TIER) - *vanguard_1;
+        assert_ok!(ReputationPallet::increase_points(
+            RuntimeOrigin::root(),
+            account,
+            to_vanguard_3.into()
+        ));
+        assert_eq!(ReputationPallet::tier_of(&account), Some(Vanguard(RANKS_PER_TIER)));
+        assert!(System::events().is_empty());
+
+        // crossing the boundary is
+        let to_trailblazer_1 = *ReputationPoint::from_rank(RANKS_PER_TIER + 1)
+            - *ReputationPoint::from_rank(RANKS_PER_TIER);
+        assert_ok!(ReputationPallet::increase_points(
+            RuntimeOrigin::root(),
+            account,
+            to_trailblazer_1.into()
+        ));
+        System::assert_last_event(
+            Event::TierChanged {
+                who: account,
+                old: Some(Vanguard(RANKS_PER_TIER)),
+                new: Some(Trailblazer(1)),
+            }
+            .into(),
+        );
+        assert_eq!(ReputationPallet::tier_of(&account), Some(Trailblazer(1)));
+
+        // falling to the zero rank keeps the tier
+        System::reset_events();
+        let to_rank_3 = *ReputationPoint::from_rank(RANKS_PER_TIER + 1)
+            - *ReputationPoint::from_rank(RANKS_PER_TIER);
+        assert_ok!(ReputationPallet::slash(RuntimeOrigin::root(), account, to_rank_3.into()));
+        assert_eq!(ReputationPallet::tier_of(&account), Some(Trailblazer(0)));
+        assert_eq!(System::events().len(), 1);
+    });
+}
+
+#[test]
+fn jumping_over_tiers_emits_single_tier_changed() {
+    use ReputationTier::*;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let account = user();
+        ReputationPallet::increase_creating(&account, ReputationPoint::from_rank(1));
+        System::reset_events();
+
+        let to_ultramodern_2 =
+            *ReputationPoint::from_rank(RANKS_PER_TIER * 2 + 2) - *ReputationPoint::from_rank(1);
+        ReputationPallet::increase_creating(&account, to_ultramodern_2.into());
+
+        let tier_changes: Vec<_> = System::events()
+            .into_iter()
+            .filter(|record| {
+                matches!(record.event, RuntimeEvent::ReputationPallet(Event::TierChanged { .. }))
+            })
+            .collect();
+        assert_eq!(tier_changes.len(), 1);
+        System::assert_last_event(
+            Event::TierChanged { who: account, old: Some(Vanguard(1)), new: Some(Ultramodern(2)) }
+                .into(),
+        );
+    });
+}
+
 #[test]
 fn tier_correct() {
     use ReputationTier::*;
@@ -242,6 +323,155 @@ fn ranks_are_stable() {
     }
 }
 
+#[test]
+fn batched_updates_cover_all_accounts_across_blocks() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        for account in 0..25 {
+            assert_ok!(ReputationPallet::force_set_points(
+                RuntimeOrigin::root(),
+                account,
+                0.into()
+            ));
+        }
+        let updated_at =
+            |block| AccountReputation::<Test>::iter_values().filter(|r| r.updated == block).count();
+
+        // the batch size bounds the accounts and the weight per block
+        System::set_block_number(10);
+        assert_eq!(
+            <ReputationPallet as Hooks<u64>>::on_initialize(10),
+            <() as WeightInfo>::update_points_batch(10)
+        );
+        assert_eq!(updated_at(10), 10);
+        assert!(ReputationPallet::update_cursor().is_some());
+
+        // an account created in the middle of the cycle
+        assert_ok!(ReputationPallet::force_set_points(RuntimeOrigin::root(), 100, 0.into()));
+
+        fn run_cycle(block: &mut u64) {
+            while ReputationPallet::update_cursor().is_some() {
+                *block += 1;
+                System::set_block_number(*block);
+                assert!(ReputationPallet::update_points_batch(10) <= 10);
+            }
+        }
+        let mut block = 10;
+        run_cycle(&mut block);
+
+        // each account was updated once in the cycle, with the points for the elapsed blocks
+        for (account, record) in AccountReputation::<Test>::iter().filter(|(a, _)| *a != 100) {
+            assert!(record.updated >= 10, "account {account} wasn't updated");
+            assert_eq!(
+                *record.reputation.points(),
+                (record.updated - 1) * *REPUTATION_POINTS_PER_BLOCK
+            );
+        }
+
+        // the cursor wraps, starting a new cycle
+        block += 1;
+        System::set_block_number(block);
+        assert_eq!(ReputationPallet::update_points_batch(10), 10);
+        assert!(ReputationPallet::update_cursor().is_some());
+        run_cycle(&mut block);
+
+        // the account created mid-cycle is updated in the next cycle at the latest
+        let record = ReputationPallet::reputation(100).unwrap();
+        assert!(record.updated > 10);
+        assert_eq!(
+            *record.reputation.points(),
+            (record.updated - 10) * *REPUTATION_POINTS_PER_BLOCK
+        );
+    });
+}
+
+#[test]
+fn zero_batch_size_disables_batched_updates() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(ReputationPallet::force_set_points(RuntimeOrigin::root(), user(), 0.into()));
+        DecayBatchSize::set(0);
+
+        System::set_block_number(10);
+        <ReputationPallet as Hooks<u64>>::on_initialize(10);
+        assert_eq!(ReputationPallet::reputation(user()).unwrap().updated, 1);
+        assert_eq!(ReputationPallet::update_cursor(), None);
+    });
+}
+
+#[test]
+fn earnings_from_all_sources_are_capped_per_era() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let account = user();
+        assert_ok!(ReputationPallet::force_set_points(RuntimeOrigin::root(), account, 0.into()));
+        MaxReputationPerEra::set(100.into());
+        CurrentEra::set(1);
+        let points = || ReputationPallet::reputation(account).unwrap().reputation.points;
+
+        // the manual increases and the earnings count towards the same cap
+        assert_ok!(ReputationPallet::increase_points(RuntimeOrigin::root(), account, 60.into()));
+        assert_eq!(ReputationPallet::earn_creating(&account, 70.into()), 40.into());
+        System::assert_last_event(
+            Event::ReputationEarningCapped { account, dropped: 30.into() }.into(),
+        );
+        assert_eq!(ReputationPallet::do_increase_points(&account, 10.into()), Ok(0.into()));
+        assert_eq!(points(), 100.into());
+        assert_eq!(ReputationPallet::earned_in_era(account), (1, 100.into()));
+
+        // grants aside from the earnings aren't capped
+        ReputationPallet::increase_creating(&account, 5.into());
+        assert_eq!(points(), 105.into());
+
+        // the cap resets in the next era, the dropped points aren't carried over
+        CurrentEra::set(2);
+        assert_eq!(ReputationPallet::earn_creating(&account, 150.into()), 100.into());
+        System::assert_last_event(
+            Event::ReputationEarningCapped { account, dropped: 50.into() }.into(),
+        );
+        assert_eq!(points(), 205.into());
+        assert_eq!(ReputationPallet::earned_in_era(account), (2, 100.into()));
+    });
+}
+
+#[test]
+fn governance_reputation_actions_respect_the_cooldown() {
+    new_test_ext().execute_with(|| {
+        ReputationActionCooldown::set(10);
+        System::set_block_number(1);
+        let account = user();
+        let other = frame_benchmarking::account("test", 2, 1);
+        assert_ok!(ReputationPallet::force_set_points(RuntimeOrigin::root(), account, 100.into()));
+        assert_eq!(ReputationPallet::last_governance_action(account), Some(1));
+
+        // a second manual action within the cooldown is rejected, whatever its kind
+        System::set_block_number(10);
+        assert_noop!(
+            ReputationPallet::increase_points(RuntimeOrigin::root(), account, 1.into()),
+            Error::<Test>::ReputationActionCooldown
+        );
+        assert_noop!(
+            ReputationPallet::slash(RuntimeOrigin::root(), account, 1.into()),
+            Error::<Test>::ReputationActionCooldown
+        );
+        assert_noop!(
+            ReputationPallet::force_set_points(RuntimeOrigin::root(), account, 1.into()),
+            Error::<Test>::ReputationActionCooldown
+        );
+        // other accounts have their own cooldown
+        assert_ok!(ReputationPallet::force_set_points(RuntimeOrigin::root(), other, 100.into()));
+
+        // automatic changes are exempt
+        assert_ok!(ReputationPallet::do_slash(&account, 10.into()));
+        ReputationPallet::increase_creating(&account, 5.into());
+        assert_eq!(ReputationPallet::reputation(account).unwrap().reputation.points(), 95.into());
+
+        System::set_block_number(11);
+        assert_ok!(ReputationPallet::slash(RuntimeOrigin::root(), account, 1.into()));
+        assert_eq!(ReputationPallet::last_governance_action(account), Some(11));
+    });
+}
+
 fn user() -> u64 {
     frame_benchmarking::account("test", 1, 1)
 }
@@ -40,20 +40,99 @@ pub trait WeightInfo {
     fn slash() -> Weight;
     fn update_points() -> Weight;
     fn force_reset_points() -> Weight;
+    fn update_points_batch(n: u32) -> Weight;
+}
+
+/// Weights for pallet_reputation using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    /// Storage: Reputation LastGovernanceAction (r:1 w:1)
+    /// Proof Skipped: Reputation LastGovernanceAction (max_values: None, max_size: None, mode: Measured)
+    /// Storage: Reputation AccountReputation (r:1 w:1)
+    /// Proof Skipped: Reputation AccountReputation (max_values: None, max_size: None, mode: Measured)
+    fn force_set_points() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `4`
+        //  Estimated: `3469`
+        // Minimum execution time: 16_218_000 picoseconds.
+        Weight::from_parts(16_781_000, 3469)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+    /// Storage: Reputation LastGovernanceAction (r:1 w:1)
+    /// Proof Skipped: Reputation LastGovernanceAction (max_values: None, max_size: None, mode: Measured)
+    /// Storage: Reputation AccountReputation (r:1 w:1)
+    /// Proof Skipped: Reputation AccountReputation (max_values: None, max_size: None, mode: Measured)
+    fn increase_points() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `98`
+        //  Estimated: `3563`
+        // Minimum execution time: 21_604_000 picoseconds.
+        Weight::from_parts(22_317_000, 3563)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+    /// Storage: Reputation LastGovernanceAction (r:1 w:1)
+    /// Proof Skipped: Reputation LastGovernanceAction (max_values: None, max_size: None, mode: Measured)
+    /// Storage: Reputation AccountReputation (r:1 w:1)
+    /// Proof Skipped: Reputation AccountReputation (max_values: None, max_size: None, mode: Measured)
+    fn slash() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `98`
+        //  Estimated: `3563`
+        // Minimum execution time: 21_887_000 picoseconds.
+        Weight::from_parts(22_540_000, 3563)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+    /// Storage: Reputation AccountReputation (r:1 w:1)
+    /// Proof Skipped: Reputation AccountReputation (max_values: None, max_size: None, mode: Measured)
+    fn update_points() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `98`
+        //  Estimated: `3563`
+        // Minimum execution time: 17_052_000 picoseconds.
+        Weight::from_parts(17_613_000, 3563)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    fn force_reset_points() -> Weight {
+        T::DbWeight::get().reads_writes(500, 500)
+    }
+    /// Storage: Reputation UpdateCursor (r:1 w:1)
+    /// Proof Skipped: Reputation UpdateCursor (max_values: Some(1), max_size: None, mode: Measured)
+    /// Storage: Reputation AccountReputation (r:501 w:500)
+    /// Proof Skipped: Reputation AccountReputation (max_values: None, max_size: None, mode: Measured)
+    /// The range of component `n` is `[1, 500]`.
+    fn update_points_batch(n: u32) -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `147 + n * (59 ±0)`
+        //  Estimated: `3612 + n * (2534 ±0)`
+        // Minimum execution time: 31_764_000 picoseconds.
+        Weight::from_parts(12_408_117, 3612)
+            // Standard Error: 4_918
+            .saturating_add(Weight::from_parts(19_847_311, 0).saturating_mul(n.into()))
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+            .saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+            .saturating_add(Weight::from_parts(0, 2534).saturating_mul(n.into()))
+    }
 }
 
 impl WeightInfo for () {
     
     fn force_set_points() -> Weight {
-        RuntimeDbWeight::default().writes(1)
+        RuntimeDbWeight::default().reads_writes(1, 2)
     }
 
     fn increase_points() -> Weight {
-        RuntimeDbWeight::default().writes(1)
+        RuntimeDbWeight::default().reads_writes(1, 2)
     }
 
     fn slash() -> Weight {
-        RuntimeDbWeight::default().writes(1)
+        RuntimeDbWeight::default().reads_writes(1, 2)
     }
 
     fn update_points() -> Weight {
@@ -63,4 +142,14 @@ impl WeightInfo for () {
     fn force_reset_points() -> Weight {
         RuntimeDbWeight::default().reads_writes(500, 500)
     }
+
+    fn update_points_batch(n: u32) -> Weight {
+        Weight::from_parts(12_408_117, 3612)
+            .saturating_add(Weight::from_parts(19_847_311, 0).saturating_mul(n.into()))
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+            .saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+            .saturating_add(Weight::from_parts(0, 2534).saturating_mul(n.into()))
+    }
 }
@@ -88,6 +88,26 @@ where
         !self.locked.is_zero() && !self.per_block.is_zero()
     }
 
+    /// Locked amount at genesis.
+    pub fn locked(&self) -> Balance {
+        self.locked
+    }
+
+    /// Starting block for unlocking(vesting).
+    pub fn starting_block(&self) -> BlockNumber {
+        self.starting_block
+    }
+
+    /// Block number at which the schedule ends, as type `Balance`.
+    pub fn ending_block_as_balance<BlockNumberToBalance: Convert<BlockNumber, Balance>>(
+        &self,
+    ) -> Balance {
+        let per_block = self.per_block.max(One::one());
+        let duration = self.locked / per_block
+            + if (self.locked % per_block).is_zero() { Zero::zero() } else { One::one() };
+        BlockNumberToBalance::convert(self.starting_block).saturating_add(duration)
+    }
+
     /// Amount locked at block `n`.
     pub fn locked_at<BlockNumberToBalance: Convert<BlockNumber, Balance>>(
         &self,
@@ -26,6 +26,10 @@ use fp_rpc::{ConvertTransaction, ConvertTransactionRuntimeApi, EthereumRuntimeRP
 
 use crate::consensus_data_providers::BabeConsensusDataProvider;
 
+mod submit;
+
+pub use submit::{EthSubmit, EthSubmitApiServer};
+
 /// Extra dependencies for Ethereum compatibility.
 pub struct EthDeps<B: BlockT, C, P, A: ChainApi, CT, CIDP> {
     /// The client instance to use.
@@ -173,6 +177,9 @@ where
         .replace_config::<EC>()
         .into_rpc(),
     )?;
+    // replaced to surface the runtime's rejection reasons instead of a generic internal error
+    io.remove_method("eth_sendRawTransaction");
+    io.merge(EthSubmit::new(client.clone(), pool.clone()).into_rpc())?;
 
     if let Some(filter_pool) = filter_pool {
         io.merge(
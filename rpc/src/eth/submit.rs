@@ -0,0 +1,273 @@
+//! `eth_sendRawTransaction` with structured rejection reasons.
+//!
+//! Frontier reports every pool rejection as a generic internal error, which wallets show as an
+//! opaque failure. This implementation submits the transaction the same way but maps the
+//! runtime's validity errors to Ethereum-style JSON-RPC errors, so MetaMask and ethers.js can
+//! tell a missing NAC from a fee or gas problem.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use ethereum::EnvelopedDecodable;
+use fc_rpc_core::types::Bytes;
+use fp_evm::TransactionValidationError;
+use fp_rpc::ConvertTransactionRuntimeApi;
+use jsonrpsee::{
+    core::{async_trait, RpcResult},
+    proc_macros::rpc,
+    types::{
+        error::{ErrorObjectOwned, INTERNAL_ERROR_CODE},
+        ErrorObject,
+    },
+};
+use sc_transaction_pool_api::{
+    error::{Error as PoolError, IntoPoolError},
+    TransactionPool, TransactionSource,
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::H256;
+use sp_runtime::{
+    traits::Block as BlockT,
+    transaction_validity::{InvalidTransaction, TransactionValidityError},
+};
+use vitreus_power_plant_runtime::{
+    ACCESS_RESTRICTED, INSUFFICIENT_VTRS_FOR_FEE, PROOF_SIZE_EXCEEDED,
+};
+
+/// Generic server error, used by geth for transactions the pool refuses.
+pub const TRANSACTION_ERROR_CODE: i32 = -32000;
+/// EIP-1474 "transaction rejected": the transaction isn't permitted for the sender.
+pub const TRANSACTION_REJECTED_CODE: i32 = -32003;
+
+#[rpc(server)]
+pub trait EthSubmitApi {
+    /// Sends a signed transaction, returning its hash.
+    #[method(name = "eth_sendRawTransaction")]
+    async fn send_raw_transaction(&self, bytes: Bytes) -> RpcResult<H256>;
+}
+
+/// Submits raw Ethereum transactions to the transaction pool.
+pub struct EthSubmit<B, C, P> {
+    client: Arc<C>,
+    pool: Arc<P>,
+    _marker: PhantomData<B>,
+}
+
+impl<B, C, P> EthSubmit<B, C, P> {
+    pub fn new(client: Arc<C>, pool: Arc<P>) -> Self {
+        Self { client, pool, _marker: Default::default() }
+    }
+}
+
+#[async_trait]
+impl<B, C, P> EthSubmitApiServer for EthSubmit<B, C, P>
+where
+    B: BlockT,
+    C: ProvideRuntimeApi<B> + HeaderBackend<B> + Send + Sync + 'static,
+    C::Api: ConvertTransactionRuntimeApi<B>,
+    P: TransactionPool<Block = B> + 'static,
+{
+    async fn send_raw_transaction(&self, bytes: Bytes) -> RpcResult<H256> {
+        let slice = &bytes.0[..];
+        if slice.is_empty() {
+            return Err(internal_error("transaction data is empty"));
+        }
+        let transaction: ethereum::TransactionV2 = EnvelopedDecodable::decode(slice)
+            .map_err(|_| internal_error("decode transaction failed"))?;
+        let transaction_hash = transaction.hash();
+
+        let best_hash = self.client.info().best_hash;
+        let extrinsic = self
+            .client
+            .runtime_api()
+            .convert_transaction(best_hash, transaction)
+            .map_err(|_| internal_error("cannot access runtime api"))?;
+
+        self.pool
+            .submit_one(best_hash, TransactionSource::Local, extrinsic)
+            .await
+            .map(|_| transaction_hash)
+            .map_err(|err| match err.into_pool_error() {
+                Ok(err) => rejection_error(err),
+                Err(err) => internal_error(format!("submit transaction to pool failed: {err}")),
+            })
+    }
+}
+
+fn internal_error(message: impl Into<String>) -> ErrorObjectOwned {
+    ErrorObject::owned(INTERNAL_ERROR_CODE, message.into(), None::<()>)
+}
+
+/// Maps a pool rejection to the JSON-RPC error returned to the wallet.
+///
+/// The error data carries a short machine-readable reason next to the human-readable message.
+pub fn rejection_error(err: PoolError) -> ErrorObjectOwned {
+    let (code, message, reason) = match err {
+        PoolError::InvalidTransaction(TransactionValidityError::Invalid(invalid)) => {
+            match invalid {
+                InvalidTransaction::Custom(ACCESS_RESTRICTED) => (
+                    TRANSACTION_REJECTED_CODE,
+                    "transaction rejected: sender has no NAC allowing transactions",
+                    "nac_restricted",
+                ),
+                InvalidTransaction::Custom(INSUFFICIENT_VTRS_FOR_FEE) => (
+                    TRANSACTION_ERROR_CODE,
+                    "insufficient funds: not enough VTRS to exchange for the VNRG fee",
+                    "insufficient_vtrs",
+                ),
+                InvalidTransaction::Payment => (
+                    TRANSACTION_ERROR_CODE,
+                    "insufficient funds: not enough VNRG for gas * price + value",
+                    "insufficient_vnrg",
+                ),
+                InvalidTransaction::Custom(c)
+                    if c == TransactionValidationError::GasLimitTooLow as u8 =>
+                {
+                    (TRANSACTION_ERROR_CODE, "intrinsic gas too low", "gas_limit_too_low")
+                },
+                InvalidTransaction::Custom(c)
+                    if c == TransactionValidationError::GasLimitTooHigh as u8 =>
+                {
+                    (TRANSACTION_ERROR_CODE, "exceeds block gas limit", "gas_limit_too_high")
+                },
+                InvalidTransaction::Custom(PROOF_SIZE_EXCEEDED) => (
+                    TRANSACTION_ERROR_CODE,
+                    "gas limit implies a proof size above the block budget",
+                    "proof_size_exceeded",
+                ),
+                InvalidTransaction::ExhaustsResources => {
+                    (TRANSACTION_ERROR_CODE, "exceeds block gas limit", "exhausts_resources")
+                },
+                InvalidTransaction::Custom(c)
+                    if c == TransactionValidationError::GasPriceTooLow as u8 =>
+                {
+                    (TRANSACTION_ERROR_CODE, "transaction underpriced", "gas_price_too_low")
+                },
+                InvalidTransaction::Custom(c)
+                    if c == TransactionValidationError::PriorityFeeTooHigh as u8 =>
+                {
+                    (
+                        TRANSACTION_ERROR_CODE,
+                        "max priority fee per gas higher than max fee per gas",
+                        "priority_fee_too_high",
+                    )
+                },
+                InvalidTransaction::Stale => {
+                    (TRANSACTION_ERROR_CODE, "nonce too low", "nonce_too_low")
+                },
+                InvalidTransaction::Future => {
+                    (TRANSACTION_ERROR_CODE, "nonce too high", "nonce_too_high")
+                },
+                InvalidTransaction::BadProof => {
+                    (TRANSACTION_ERROR_CODE, "invalid sender", "invalid_signature")
+                },
+                other => {
+                    return internal_error(format!("submit transaction to pool failed: {other:?}"))
+                },
+            }
+        },
+        PoolError::TooLowPriority { .. } => (
+            TRANSACTION_ERROR_CODE,
+            "replacement transaction underpriced",
+            "replacement_underpriced",
+        ),
+        PoolError::AlreadyImported(_) => (TRANSACTION_ERROR_CODE, "already known", "already_known"),
+        other => return internal_error(format!("submit transaction to pool failed: {other}")),
+    };
+
+    ErrorObject::owned(code, message, Some(reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rejection(invalid: InvalidTransaction) -> ErrorObjectOwned {
+        rejection_error(PoolError::InvalidTransaction(invalid.into()))
+    }
+
+    fn assert_rejection(err: ErrorObjectOwned, code: i32, message: &str, reason: &str) {
+        assert_eq!(err.code(), code);
+        assert_eq!(err.message(), message);
+        assert_eq!(err.data().map(|data| data.get()), Some(format!("\"{reason}\"").as_str()));
+    }
+
+    #[test]
+    fn nac_restriction_is_rejected() {
+        assert_rejection(
+            rejection(InvalidTransaction::Custom(ACCESS_RESTRICTED)),
+            TRANSACTION_REJECTED_CODE,
+            "transaction rejected: sender has no NAC allowing transactions",
+            "nac_restricted",
+        );
+    }
+
+    #[test]
+    fn missing_fee_funds_distinguish_vnrg_and_vtrs() {
+        assert_rejection(
+            rejection(InvalidTransaction::Payment),
+            TRANSACTION_ERROR_CODE,
+            "insufficient funds: not enough VNRG for gas * price + value",
+            "insufficient_vnrg",
+        );
+        assert_rejection(
+            rejection(InvalidTransaction::Custom(INSUFFICIENT_VTRS_FOR_FEE)),
+            TRANSACTION_ERROR_CODE,
+            "insufficient funds: not enough VTRS to exchange for the VNRG fee",
+            "insufficient_vtrs",
+        );
+    }
+
+    #[test]
+    fn gas_issues_are_reported_distinctly() {
+        assert_rejection(
+            rejection(InvalidTransaction::Custom(TransactionValidationError::GasLimitTooLow as u8)),
+            TRANSACTION_ERROR_CODE,
+            "intrinsic gas too low",
+            "gas_limit_too_low",
+        );
+        assert_rejection(
+            rejection(InvalidTransaction::Custom(
+                TransactionValidationError::GasLimitTooHigh as u8,
+            )),
+            TRANSACTION_ERROR_CODE,
+            "exceeds block gas limit",
+            "gas_limit_too_high",
+        );
+        assert_rejection(
+            rejection(InvalidTransaction::Custom(TransactionValidationError::GasPriceTooLow as u8)),
+            TRANSACTION_ERROR_CODE,
+            "transaction underpriced",
+            "gas_price_too_low",
+        );
+        assert_rejection(
+            rejection(InvalidTransaction::Custom(PROOF_SIZE_EXCEEDED)),
+            TRANSACTION_ERROR_CODE,
+            "gas limit implies a proof size above the block budget",
+            "proof_size_exceeded",
+        );
+    }
+
+    #[test]
+    fn nonce_issues_are_reported() {
+        assert_rejection(
+            rejection(InvalidTransaction::Stale),
+            TRANSACTION_ERROR_CODE,
+            "nonce too low",
+            "nonce_too_low",
+        );
+        assert_rejection(
+            rejection(InvalidTransaction::Future),
+            TRANSACTION_ERROR_CODE,
+            "nonce too high",
+            "nonce_too_high",
+        );
+    }
+
+    #[test]
+    fn unknown_rejections_are_internal_errors() {
+        let err = rejection(InvalidTransaction::Call);
+        assert_eq!(err.code(), INTERNAL_ERROR_CODE);
+        assert!(err.data().is_none());
+    }
+}
@@ -5,6 +5,7 @@ use std::sync::Arc;
 use jsonrpsee::RpcModule;
 
 // Substrate
+use polkadot_rpc::DenyUnsafe;
 use sc_client_api::{backend::StorageProvider, client::BlockchainEvents, AuxStore, UsageProvider};
 use sc_rpc::SubscriptionTaskExecutor;
 use sc_service::TransactionPool;
@@ -21,17 +22,22 @@ use sp_runtime::RuntimeAppPublic;
 
 // Runtime
 use vitreus_power_plant_runtime::{
-    opaque::Block, AccountId, Balance, BlockNumber, Nonce, RuntimeCall,
+    opaque::Block, AccountId, AssetId, Balance, BlockNumber, Nonce, RuntimeCall,
 };
 
 mod consensus_data_providers;
 mod eth;
 pub use self::eth::{create_eth, EthDeps};
+pub use energy_fee_rpc::SwapQuoteConfig;
 
 /// Extra dependencies for Node
 pub struct NodeDeps {
     /// Node name defined during boot
     pub name: String,
+    /// Slippage and validity of the VTRS/VNRG swap rate quotes.
+    pub swap_quote: SwapQuoteConfig,
+    /// Whether to deny unsafe calls
+    pub deny_unsafe: DenyUnsafe,
 }
 
 /// Extra dependencies.
@@ -80,8 +86,8 @@ where
     C::Api: BlockBuilder<Block>,
     C::Api: fp_rpc::ConvertTransactionRuntimeApi<Block>,
     C::Api: fp_rpc::EthereumRuntimeRPCApi<Block>,
-    C::Api: energy_fee_rpc::EnergyFeeRuntimeApi<Block, AccountId, Balance, RuntimeCall>,
-    C::Api: energy_generation_rpc::EnergyGenerationRuntimeApi<Block>,
+    C::Api: energy_fee_rpc::EnergyFeeRuntimeApi<Block, AccountId, Balance, RuntimeCall, AssetId>,
+    C::Api: energy_generation_rpc::EnergyGenerationRuntimeApi<Block, AccountId>,
     C::Api: vitreus_utility_runtime_api::UtilityApi<Block>,
     P: TransactionPool<Block = Block> + 'static,
     A: ChainApi<Block = Block> + 'static,
@@ -93,7 +99,11 @@ where
     use energy_generation_rpc::{EnergyGeneration, EnergyGenerationApiServer};
     use node_rpc_server::{Node, NodeApiServer};
 
-    io.merge(EnergyFee::new(client.clone()).into_rpc())?;
+    io.merge(
+        EnergyFee::new(client.clone(), eth.pool.clone(), node.deny_unsafe)
+            .with_quote_config(node.swap_quote)
+            .into_rpc(),
+    )?;
     io.merge(EnergyGeneration::new(client.clone()).into_rpc())?;
     io.merge(Node::new(node.name).into_rpc())?;
 
@@ -1,10 +1,237 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use sp_core::{H160, U256};
+use pallet_reputation::ReputationTier;
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_arithmetic::{Perbill, Perquintill};
+use sp_core::{RuntimeDebug, H160, U256};
+use sp_std::vec::Vec;
+
+/// Parameters driving the dynamic VNRG economy, amounts in the smallest VNRG unit.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, RuntimeDebug)]
+pub struct DynamicVnrgParams {
+    /// VNRG generated per staked VTRS in the active era.
+    pub energy_per_stake_currency: Option<u128>,
+    /// VNRG per staked VTRS set by governance for the following eras.
+    pub next_energy_per_stake_currency: Option<u128>,
+    /// VNRG capacity of a battery slot.
+    pub battery_slot_capacity: u128,
+    /// Additional energy reward granted to each reputation tier.
+    pub reputation_bonuses: Vec<(ReputationTier, Perbill)>,
+    /// Base transaction fee, before the fee multiplier is applied.
+    pub base_fee: u128,
+}
+
+/// Energy economics the chain was launched with, amounts in the smallest token unit.
+///
+/// Taken from the genesis snapshot, which chains launched before it was introduced lack.
+#[derive(Clone, PartialEq, Eq, Default, Encode, Decode, TypeInfo, RuntimeDebug)]
+pub struct GenesisEnergyConfig {
+    /// VNRG generated per staked VTRS.
+    pub energy_per_stake_currency: u128,
+    /// Name of the VNRG asset.
+    pub vnrg_name: Vec<u8>,
+    /// Symbol of the VNRG asset.
+    pub vnrg_symbol: Vec<u8>,
+    /// Decimals of the VNRG asset.
+    pub vnrg_decimals: u8,
+    /// Minimum VTRS bond of cooperators.
+    pub min_cooperator_bond: u128,
+    /// Minimum VTRS bond of validators below the trusted NAC level.
+    pub min_common_validator_bond: u128,
+    /// Minimum VTRS bond of validators with the trusted NAC level.
+    pub min_trust_validator_bond: u128,
+    /// VTRS and VNRG reserves the VNRG broker pool was seeded with.
+    pub broker_seed: Option<(u128, u128)>,
+}
+
+/// Holdings of an account, amounts in the smallest token unit. What the account doesn't hold is
+/// zero.
+#[derive(Clone, PartialEq, Eq, Default, Encode, Decode, TypeInfo, RuntimeDebug)]
+pub struct BalanceSheet {
+    /// VTRS the account can transfer.
+    pub liquid_vtrs: u128,
+    /// VNRG the account holds.
+    pub liquid_vnrg: u128,
+    /// VTRS actively bonded by the account as a stash.
+    pub bonded: u128,
+    /// VTRS being unbonded, by the era it can be withdrawn at.
+    pub unlocking: Vec<(u32, u128)>,
+    /// VNRG staking rewards earned but deferred to later blocks by the per-block mint cap.
+    pub pending_rewards: u128,
+    /// Prepaid fee credit.
+    pub fee_credit: u128,
+}
+
+/// A pending atomic swap, amounts in the smallest VTRS unit.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, RuntimeDebug)]
+pub struct SwapInfo {
+    /// Account which locked the VTRS.
+    pub source: H160,
+    /// Account which can claim the VTRS by revealing the proof.
+    pub target: H160,
+    /// Hash of the proof, the hashlock of the swap.
+    pub hashed_proof: [u8; 32],
+    /// VTRS locked by the swap.
+    pub amount: u128,
+    /// Block from which the source can cancel the swap. Until it does, the target can still
+    /// claim it.
+    pub end_block: u32,
+}
+
+/// What happens to an account at a [`ScheduledEvent`], amounts in the smallest token unit.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, RuntimeDebug)]
+pub enum ScheduledEventKind {
+    /// A vesting schedule of `locked` VTRS starts unlocking.
+    VestingStarts { locked: u128 },
+    /// A vesting schedule of `locked` VTRS is fully unlocked.
+    VestingEnds { locked: u128 },
+    /// `amount` of unbonded VTRS can be withdrawn.
+    UnbondingMatures { amount: u128 },
+    /// A scheduled call sets the NAC level of the account, `None` removes its NAC.
+    NacLevelChange { level: Option<u8> },
+}
+
+/// An upcoming event of an account's timeline.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, RuntimeDebug)]
+pub struct ScheduledEvent {
+    /// Block of the event. Unbonding maturities are estimated from the era length.
+    pub block: u32,
+    pub kind: ScheduledEventKind,
+}
+
+/// Name of a governance-adjustable runtime parameter, as `<pallet>.<parameter>`.
+pub type ParamName = Vec<u8>;
+
+/// Current value of a governance-adjustable runtime parameter.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, RuntimeDebug)]
+pub enum ParamValue {
+    /// An amount in the smallest unit of its token.
+    Amount(u128),
+    /// A number of items, e.g. validators.
+    Count(u32),
+    /// A gas amount.
+    Gas(u64),
+    /// Reputation points.
+    Points(u64),
+    Perbill(Perbill),
+    Perquintill(Perquintill),
+    /// A fixed point number with 18 decimals, given by its inner value.
+    Fixed(u128),
+    /// The parameter is not set, which usually disables what it limits.
+    Unset,
+    /// A switch, on or off.
+    Flag(bool),
+}
+
+/// Textual address of an account, in either of its forms.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, RuntimeDebug)]
+pub enum AddressInput {
+    /// An SS58 address with the chain's prefix.
+    Ss58(Vec<u8>),
+    /// A `0x`-prefixed hex address. Mixed-case addresses must carry a valid EIP-55 checksum.
+    H160(Vec<u8>),
+}
+
+/// Both representations of an account.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, RuntimeDebug)]
+pub struct AddressPair {
+    /// The account, which is also its Ethereum address.
+    pub h160: H160,
+    /// The EIP-55 checksummed hex address, `0x`-prefixed.
+    pub checksummed: Vec<u8>,
+    /// The SS58 address with the chain's prefix.
+    pub ss58: Vec<u8>,
+}
+
+/// Why an [`AddressInput`] couldn't be mapped.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, RuntimeDebug)]
+pub enum AddressError {
+    /// The address isn't valid base58 or hex, or has the wrong length.
+    InvalidFormat,
+    /// The SS58 or EIP-55 checksum doesn't match the address.
+    InvalidChecksum,
+    /// The SS58 address is for another chain.
+    WrongPrefix,
+}
+
+/// Category of operations an account may be permitted to perform.
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo, RuntimeDebug)]
+pub enum CallClass {
+    /// Sending Ethereum transactions and calling EVM contracts.
+    EvmCall,
+    /// Deploying EVM contracts.
+    EvmCreate,
+    /// Bonding and cooperating with validators.
+    Cooperate,
+    /// Running as a validator.
+    Validate,
+    /// Running as a validator open to collaborative staking.
+    CollaborativeValidate,
+    /// Voting on democracy referenda.
+    Vote,
+    /// Submitting democracy proposals.
+    Propose,
+}
 
 sp_api::decl_runtime_apis! {
+    #[api_version(12)]
     pub trait UtilityApi
     {
         fn balance(who: H160) -> U256;
+
+        /// Display name registered for the account, if any.
+        #[api_version(2)]
+        fn account_name(who: H160) -> Option<Vec<u8>>;
+
+        /// Account holding the given display name, if names are unique.
+        #[api_version(2)]
+        fn account_by_name(name: Vec<u8>) -> Option<H160>;
+
+        /// Current reputation tier of the account, if it has reached one.
+        #[api_version(3)]
+        fn tier_of(who: H160) -> Option<ReputationTier>;
+
+        /// Current parameters of the dynamic VNRG economy.
+        #[api_version(4)]
+        fn dynamic_vnrg_params() -> DynamicVnrgParams;
+
+        /// Upcoming vesting unlocks, scheduled NAC changes and unbonding maturities of the
+        /// account, sorted by block.
+        #[api_version(5)]
+        fn account_schedule(who: H160) -> Vec<ScheduledEvent>;
+
+        /// All the parameters governance can adjust, with their current values.
+        #[api_version(6)]
+        fn runtime_parameters() -> Vec<(ParamName, ParamValue)>;
+
+        /// NAC level required to call the EVM contract, the default call level if the contract
+        /// isn't gated.
+        #[api_version(7)]
+        fn contract_required_nac(target: H160) -> u8;
+
+        /// The account of an SS58 or hex address, with both of its representations.
+        #[api_version(8)]
+        fn map_addresses(input: AddressInput) -> Result<AddressPair, AddressError>;
+
+        /// The categories of operations the account's NAC level and reputation permit. A
+        /// permitted call may still fail for other reasons, e.g. a too low bond.
+        #[api_version(9)]
+        fn permitted_calls(who: H160) -> Vec<CallClass>;
+
+        /// Energy parameters, VNRG metadata, validator bonds and broker seed configured at
+        /// genesis, `None` for chains launched without the genesis snapshot.
+        #[api_version(10)]
+        fn genesis_energy_config() -> Option<GenesisEnergyConfig>;
+
+        /// Liquid VTRS and VNRG, staking position, deferred rewards and fee credit of the
+        /// account.
+        #[api_version(11)]
+        fn account_balance_sheet(who: H160) -> BalanceSheet;
+
+        /// Pending atomic swaps, at most `limit` of them following the swap of `start_after`,
+        /// given by its target and hashlock. Pass the last swap of a page to get the next one.
+        #[api_version(12)]
+        fn active_swaps(start_after: Option<(H160, [u8; 32])>, limit: u32) -> Vec<SwapInfo>;
     }
 }
@@ -7,9 +7,12 @@ use crate::{
 
 use frame_support::traits::fungible::HoldConsideration;
 use frame_support::traits::tokens::{PayFromAccount, UnityAssetBalanceConversion};
-use frame_support::traits::{Currency, EitherOf, LinearStoragePrice, LockIdentifier, OnUnbalanced};
+use frame_support::traits::{
+    Currency, EitherOf, EnsureOrigin, LinearStoragePrice, LockIdentifier, OnUnbalanced,
+};
 use frame_support::{parameter_types, traits::EitherOfDiverse, weights::Weight, PalletId};
 use frame_system::{EnsureRoot, EnsureWithSuccess};
+use pallet_reputation::ReputationPoint;
 use pallet_treasury::NegativeImbalanceOf;
 use polkadot_runtime_common::prod_or_fast;
 use sp_core::ConstU32;
@@ -246,6 +249,43 @@ parameter_types! {
     pub const MaxProposals: u32 = 100;
 }
 
+parameter_types! {
+    // Reputation points an account needs to submit a democracy proposal, zero lets anyone propose.
+    pub storage ProposalReputationThreshold: ReputationPoint = ReputationPoint::new(0);
+}
+
+/// Whether `who` may submit a democracy proposal: council members always can, other accounts
+/// need at least `ProposalReputationThreshold` reputation points.
+pub fn can_submit_proposal(who: &AccountId) -> bool {
+    let threshold = ProposalReputationThreshold::get();
+    threshold == ReputationPoint::new(0)
+        || Council::is_member(who)
+        || pallet_reputation::AccountReputation::<Runtime>::get(who)
+            .is_some_and(|record| record.reputation.points() >= threshold)
+}
+
+/// Signed origin of an account allowed to submit democracy proposals, see
+/// [`can_submit_proposal`].
+pub struct EnsureReputableProposer;
+
+impl EnsureOrigin<RuntimeOrigin> for EnsureReputableProposer {
+    type Success = AccountId;
+
+    fn try_origin(o: RuntimeOrigin) -> Result<Self::Success, RuntimeOrigin> {
+        let who = frame_system::EnsureSigned::<AccountId>::try_origin(o)?;
+        if can_submit_proposal(&who) {
+            Ok(who)
+        } else {
+            Err(RuntimeOrigin::signed(who))
+        }
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn try_successful_origin() -> Result<RuntimeOrigin, ()> {
+        frame_system::EnsureSigned::<AccountId>::try_successful_origin()
+    }
+}
+
 impl pallet_democracy::Config for Runtime {
     type WeightInfo = pallet_democracy::weights::SubstrateWeight<Runtime>;
     type RuntimeEvent = RuntimeEvent;
@@ -280,7 +320,7 @@ impl pallet_democracy::Config for Runtime {
         pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 1, 1>,
         frame_system::EnsureRoot<AccountId>,
     >;
-    type SubmitOrigin = frame_system::EnsureSigned<AccountId>;
+    type SubmitOrigin = EnsureReputableProposer;
     /// Two thirds of the technical committee can have an `ExternalMajority/ExternalDefault` vote
     /// be tabled immediately and with a shorter voting/enactment period.
     type FastTrackOrigin = EitherOfDiverse<
@@ -0,0 +1,176 @@
+//! Conversions between the SS58 and hex representations of accounts.
+//!
+//! Accounts are their Ethereum addresses, so both representations encode the same 20 bytes: SS58
+//! with the chain's prefix and a blake2 checksum, hex with the EIP-55 mixed-case checksum.
+
+use crate::SS58Prefix;
+use sp_core::{
+    hashing::{blake2_512, keccak_256},
+    Get, H160,
+};
+use sp_std::vec::Vec;
+use vitreus_utility_runtime_api::{AddressError, AddressInput, AddressPair};
+
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const SS58_CHECKSUM_PREIMAGE_PREFIX: &[u8] = b"SS58PRE";
+const SS58_CHECKSUM_LEN: usize = 2;
+
+/// The account of `input`, with both of its representations.
+pub fn map_addresses(input: AddressInput) -> Result<AddressPair, AddressError> {
+    let h160 = match input {
+        AddressInput::Ss58(address) => from_ss58(&address)?,
+        AddressInput::H160(address) => from_hex(&address)?,
+    };
+    Ok(AddressPair { h160, checksummed: to_checksummed_hex(h160), ss58: to_ss58(h160) })
+}
+
+/// The SS58 address of `account` with the chain's prefix.
+pub fn to_ss58(account: H160) -> Vec<u8> {
+    to_ss58_with_prefix(account, SS58Prefix::get())
+}
+
+fn to_ss58_with_prefix(account: H160, prefix: u16) -> Vec<u8> {
+    let mut data = match prefix {
+        0..=63 => sp_std::vec![prefix as u8],
+        _ => {
+            // the two-byte form of the SS58 prefix
+            let first = ((prefix & 0b0000_0000_1111_1100) as u8) >> 2;
+            let second = ((prefix >> 8) as u8) | (((prefix & 0b0000_0000_0000_0011) as u8) << 6);
+            sp_std::vec![first | 0b0100_0000, second]
+        },
+    };
+    data.extend_from_slice(account.as_bytes());
+    let checksum = ss58_checksum(&data);
+    data.extend_from_slice(&checksum[..SS58_CHECKSUM_LEN]);
+    base58_encode(&data)
+}
+
+/// The account of an SS58 address with the chain's prefix.
+pub fn from_ss58(address: &[u8]) -> Result<H160, AddressError> {
+    let data = base58_decode(address).ok_or(AddressError::InvalidFormat)?;
+    let (prefix_len, prefix) = match data.first() {
+        Some(first @ 0..=63) => (1, *first as u16),
+        Some(first @ 64..=127) if data.len() > 1 => {
+            let lower = (first << 2) | (data[1] >> 6);
+            let upper = data[1] & 0b0011_1111;
+            (2, (lower as u16) | ((upper as u16) << 8))
+        },
+        _ => return Err(AddressError::InvalidFormat),
+    };
+    if data.len() != prefix_len + H160::len_bytes() + SS58_CHECKSUM_LEN {
+        return Err(AddressError::InvalidFormat);
+    }
+
+    let (payload, checksum) = data.split_at(data.len() - SS58_CHECKSUM_LEN);
+    if ss58_checksum(payload)[..SS58_CHECKSUM_LEN] != *checksum {
+        return Err(AddressError::InvalidChecksum);
+    }
+    if prefix != SS58Prefix::get() {
+        return Err(AddressError::WrongPrefix);
+    }
+    Ok(H160::from_slice(&payload[prefix_len..]))
+}
+
+/// The account of a `0x`-prefixed hex address, checking the EIP-55 checksum of mixed-case ones.
+pub fn from_hex(address: &[u8]) -> Result<H160, AddressError> {
+    let digits = address
+        .strip_prefix(b"0x")
+        .filter(|digits| digits.len() == 2 * H160::len_bytes())
+        .ok_or(AddressError::InvalidFormat)?;
+
+    let mut account = H160::zero();
+    for (byte, pair) in account.as_bytes_mut().iter_mut().zip(digits.chunks(2)) {
+        let high = hex_value(pair[0]).ok_or(AddressError::InvalidFormat)?;
+        let low = hex_value(pair[1]).ok_or(AddressError::InvalidFormat)?;
+        *byte = (high << 4) | low;
+    }
+
+    let is_mixed_case =
+        digits.iter().any(u8::is_ascii_lowercase) && digits.iter().any(u8::is_ascii_uppercase);
+    if is_mixed_case && to_checksummed_hex(account)[2..] != *digits {
+        return Err(AddressError::InvalidChecksum);
+    }
+    Ok(account)
+}
+
+/// The EIP-55 checksummed hex address of `account`, `0x`-prefixed.
+pub fn to_checksummed_hex(account: H160) -> Vec<u8> {
+    const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+    let lowercase: Vec<u8> = account
+        .as_bytes()
+        .iter()
+        .flat_map(|byte| [HEX_DIGITS[(byte >> 4) as usize], HEX_DIGITS[(byte & 0xf) as usize]])
+        .collect();
+    let hash = keccak_256(&lowercase);
+
+    let mut address = b"0x".to_vec();
+    address.extend(lowercase.iter().enumerate().map(|(i, digit)| {
+        let nibble = if i % 2 == 0 { hash[i / 2] >> 4 } else { hash[i / 2] & 0xf };
+        if nibble >= 8 {
+            digit.to_ascii_uppercase()
+        } else {
+            *digit
+        }
+    }));
+    address
+}
+
+fn ss58_checksum(payload: &[u8]) -> [u8; 64] {
+    let mut preimage = SS58_CHECKSUM_PREIMAGE_PREFIX.to_vec();
+    preimage.extend_from_slice(payload);
+    blake2_512(&preimage)
+}
+
+fn hex_value(digit: u8) -> Option<u8> {
+    match digit {
+        b'0'..=b'9' => Some(digit - b'0'),
+        b'a'..=b'f' => Some(digit - b'a' + 10),
+        b'A'..=b'F' => Some(digit - b'A' + 10),
+        _ => None,
+    }
+}
+
+fn base58_encode(data: &[u8]) -> Vec<u8> {
+    // base 58 digits, least significant first
+    let mut digits: Vec<u8> = Vec::new();
+    for byte in data {
+        let mut carry = *byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    let leading_zeros = data.iter().take_while(|byte| **byte == 0).count();
+    let mut encoded = sp_std::vec![BASE58_ALPHABET[0]; leading_zeros];
+    encoded.extend(digits.iter().rev().map(|digit| BASE58_ALPHABET[*digit as usize]));
+    encoded
+}
+
+fn base58_decode(text: &[u8]) -> Option<Vec<u8>> {
+    // bytes, least significant first
+    let mut bytes: Vec<u8> = Vec::new();
+    for character in text {
+        let mut carry = BASE58_ALPHABET.iter().position(|c| c == character)? as u32;
+        for byte in bytes.iter_mut() {
+            carry += (*byte as u32) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+
+    let leading_zeros = text.iter().take_while(|c| **c == BASE58_ALPHABET[0]).count();
+    let mut decoded = sp_std::vec![0; leading_zeros];
+    decoded.extend(bytes.iter().rev());
+    Some(decoded)
+}
@@ -91,7 +91,12 @@ where
         config: &pallet_evm::EvmConfig,
     ) -> Result<CallInfo, RunnerError<Self::Error>> {
         let gas_limit = GetConstantGasLimit::get().as_u64();
-        Self::evm_user_has_permission(source, weight_limit, CALL_ACCESS_LEVEL)?;
+        let target_account = <T as pallet_evm::Config>::AddressMapping::into_account_id(target);
+        let access_level = pallet_nac_managing::Pallet::<T>::required_access_level(
+            &target_account,
+            CALL_ACCESS_LEVEL,
+        );
+        Self::evm_user_has_permission(source, weight_limit, access_level)?;
         let call = Call::new_call_variant_call(
             source,
             target,
@@ -257,7 +262,9 @@ where
     ) -> Result<(), RunnerError<pallet_evm::Error<T>>> {
         let account_id = <T as pallet_evm::Config>::AddressMapping::into_account_id(source);
 
-        if !pallet_nac_managing::Pallet::<T>::user_has_access(account_id, access_level) {
+        if !pallet_nac_managing::Pallet::<T>::is_access_exempt(&account_id)
+            && !pallet_nac_managing::Pallet::<T>::user_has_access(account_id, access_level)
+        {
             return Err(RunnerError {
                 error: pallet_evm::Error::Undefined,
                 weight: weight_limit.unwrap_or_default(),
@@ -31,7 +31,7 @@ use polkadot_runtime_parachains::{
     configuration as parachains_configuration,
     configuration::ActiveConfigHrmpChannelSizeAndCapacityRatio,
     disputes as parachains_disputes,
-    disputes::slashing as parachains_slashing,
+    disputes::{slashing as parachains_slashing, SlashingHandler},
     dmp as parachains_dmp, hrmp as parachains_hrmp, inclusion as parachains_inclusion,
     inclusion::{AggregateMessageOrigin, UmpQueueId},
     initializer as parachains_initializer, origin as parachains_origin, paras as parachains_paras,
@@ -43,15 +43,16 @@ use polkadot_runtime_parachains::{
     shared as parachains_shared,
 };
 
-use ethereum::{EIP1559Transaction, EIP2930Transaction, LegacyTransaction};
+use ethereum::{EIP1559Transaction, EIP2930Transaction, LegacyTransaction, TransactionAction};
 use frame_support::pallet_prelude::{DispatchError, DispatchResult};
 use frame_support::traits::tokens::{
     fungible::Inspect as FungibleInspect, nonfungibles_v2::Inspect, DepositConsequence, Fortitude,
     Preservation, Provenance, WithdrawConsequence,
 };
 use frame_support::traits::{
-    Currency, EitherOfDiverse, ExistenceRequirement, OnUnbalanced, ProcessMessage,
-    ProcessMessageError, SignedImbalance, WithdrawReasons,
+    schedule::{self, v3::TaskName},
+    Bounded, Currency, EitherOfDiverse, ExistenceRequirement, OnUnbalanced, ProcessMessage,
+    ProcessMessageError, QueryPreimage, SignedImbalance, WithdrawReasons,
 };
 use parity_scale_codec::{Compact, Decode, Encode};
 use sp_api::impl_runtime_apis;
@@ -72,17 +73,25 @@ use sp_runtime::{
     transaction_validity::{
         TransactionPriority, TransactionSource, TransactionValidity, TransactionValidityError,
     },
-    ApplyExtrinsicResult, ConsensusEngineId, FixedPointNumber, Perbill, Percent, Permill,
+    ApplyExtrinsicResult, ConsensusEngineId, FixedPointNumber, FixedU128, Perbill, Percent,
+    Permill,
+};
+use sp_staking::{
+    offence::{OffenceError, ReportOffence},
+    EraIndex, SessionIndex,
 };
-use sp_staking::{EraIndex, SessionIndex};
 use sp_std::{
     collections::{btree_map::BTreeMap, vec_deque::VecDeque},
     marker::PhantomData,
     prelude::*,
 };
 use sp_version::RuntimeVersion;
+use vitreus_utility_runtime_api::{
+    AddressError, AddressInput, AddressPair, BalanceSheet, CallClass, DynamicVnrgParams,
+    GenesisEnergyConfig, ParamName, ParamValue, ScheduledEvent, ScheduledEventKind, SwapInfo,
+};
 // Substrate FRAME
-use energy_fee_runtime_api::CallRequest;
+use energy_fee_runtime_api::{CallRequest, FeeClass, FeePaymentPath, RateDivergence};
 #[cfg(feature = "with-paritydb-weights")]
 use frame_support::weights::constants::ParityDbWeight as RuntimeDbWeight;
 #[cfg(feature = "with-rocksdb-weights")]
@@ -92,20 +101,26 @@ use frame_support::{
     dispatch::GetDispatchInfo,
     ord_parameter_types, parameter_types,
     traits::{
-        fungible::ItemOf, AsEnsureOriginWithArg, ConstU128, ConstU32, ConstU64, ConstU8,
-        ExtrinsicCall, FindAuthor, Hooks, KeyOwnerProofSystem,
+        fungible::ItemOf, AsEnsureOriginWithArg, ConstBool, ConstU128, ConstU32, ConstU64,
+        ConstU8, Contains, ExtrinsicCall, FindAuthor, GetCallMetadata, GetCallName, Hooks,
+        KeyOwnerProofSystem, PalletInfoAccess, PalletsInfoAccess,
     },
     weights::{
         constants::WEIGHT_REF_TIME_PER_MILLIS, ConstantMultiplier, Weight, WeightMeter, WeightToFee,
     },
 };
 use frame_system::{EnsureNever, EnsureRoot, EnsureSignedBy};
-use pallet_energy_broker::{ConstantSum, NativeOrAssetId, NativeOrAssetIdConverter};
-use pallet_energy_fee::{traits::AssetsBalancesConverter, CallFee, CustomFee, TokenExchange};
+use pallet_energy_broker::{ConstantSum, NativeOrAssetId, NativeOrAssetIdConverter, SwapKind};
+use pallet_energy_fee::{
+    traits::{AssetsBalancesConverter, BootstrapExchange, NativeExchange},
+    CallFee, CustomFee, TokenExchange, TokenRedemption,
+};
 use pallet_grandpa::{
     fg_primitives, AuthorityId as GrandpaId, AuthorityList as GrandpaAuthorityList,
 };
-use pallet_reputation::{ReputationTier, RANKS_PER_TIER, REPUTATION_POINTS_PER_DAY};
+use pallet_reputation::{
+    ReputationTier, RANKS_PER_TIER, REPUTATION_POINTS_PER_BLOCK, REPUTATION_POINTS_PER_DAY,
+};
 use pallet_transaction_payment::{FeeDetails, InclusionFee};
 // Frontier
 use fp_account::EthereumSignature;
@@ -151,6 +166,7 @@ pub use areas::{CouncilCollective, TechnicalCollective};
 
 mod precompiles;
 mod helpers {
+    pub mod address;
     pub mod runner;
 }
 pub mod areas;
@@ -249,10 +265,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: create_runtime_str!("vitreus-power-plant"),
     impl_name: create_runtime_str!("vitreus-power-plant"),
     authoring_version: 1,
-    spec_version: 203,
+    spec_version: 205,
     impl_version: 0,
     apis: RUNTIME_API_VERSIONS,
-    transaction_version: 2,
+    transaction_version: 3,
     state_version: 1,
 };
 
@@ -313,8 +329,21 @@ pub use vtrs::*;
 pub mod vnrg {
     use super::*;
     pub const UNITS: Balance = 1_000_000_000_000_000_000;
+    pub const GVOLT: Balance = 1_000_000_000;
 }
 
+/// VTRS per smallest VNRG unit the energy economy was launched with.
+///
+/// 1 VTRS = 0.9 gVolt => 1.11111... VTRS = 1 gVolt. Both the oracle rate and the broker pool
+/// seeded at genesis price VNRG at this rate.
+pub const INITIAL_ENERGY_RATE: FixedU128 =
+    FixedU128::from_inner(1_111_111_111_111_111_111_111_111_111);
+
+/// Energy generated per staked VTRS unit the energy economy was launched with.
+///
+/// Set at genesis, returned by the energy rate calculator and used as the floor of the rate.
+pub const ENERGY_PER_STAKE_CURRENCY: Energy = 19_909_091_036_891;
+
 parameter_types! {
     pub const Version: RuntimeVersion = VERSION;
     pub const BlockHashCount: BlockNumber = 256;
@@ -366,7 +395,7 @@ impl frame_system::Config for Runtime {
     /// The data to be stored in an account.
     type AccountData = pallet_balances::AccountData<Balance>;
     /// What to do if a new account is created.
-    type OnNewAccount = NacManaging;
+    type OnNewAccount = (NacManaging, EnergyFee);
     /// What to do if an account is fully reaped from the system.
     type OnKilledAccount = Reputation;
     /// Weight information for the extrinsics of this pallet.
@@ -488,12 +517,29 @@ impl pallet_assets::Config for Runtime {
     type BenchmarkHelper = ();
 }
 
+parameter_types! {
+    // earnings can at most match the points accrued for time in an era, so combining sources
+    // can't shorten the way to a validator tier below half of the intended month
+    pub const MaxReputationPerEra: ReputationPoint = ReputationPoint::new(
+        REPUTATION_POINTS_PER_BLOCK.0
+            * EPOCH_DURATION_IN_BLOCKS as u64
+            * SessionsPerEra::get() as u64,
+    );
+}
+
 impl pallet_reputation::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
-    type WeightInfo = ();
+    type WeightInfo = pallet_reputation::weights::SubstrateWeight<Runtime>;
+    type DecayBatchSize = ConstU32<500>;
+    type MaxReputationPerEra = MaxReputationPerEra;
+    type CurrentEra = ActiveEraIndex;
+    type ReputationActionCooldown = ConstU32<DAYS>;
 }
 
-use pallet_energy_generation::{EnergyRateCalculator, StakeOf, StashOf};
+use pallet_energy_generation::{
+    ElectionScore, ElectionSolutionError, EnergyRateCalculator, IssuanceReport, RewardBreakdown,
+    StakeOf, StakingOverview, StashOf, ValidatorStats,
+};
 
 pallet_staking_reward_curve::build! {
     const I_NPOS: PiecewiseLinear<'static> = curve!(
@@ -572,8 +618,8 @@ where
             frame_system::CheckEra::<Runtime>::from(era),
             frame_system::CheckNonce::<Runtime>::from(nonce),
             frame_system::CheckWeight::<Runtime>::new(),
-            pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(tip),
             pallet_energy_fee::CheckEnergyFee::<Runtime>::new(),
+            pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(tip),
         );
         let raw_payload = SignedPayload::new(call, extra)
             .map_err(|e| {
@@ -600,6 +646,47 @@ where
     type OverarchingCall = RuntimeCall;
 }
 
+type UnresponsivenessOffence = pallet_im_online::UnresponsivenessOffence<
+    pallet_session::historical::IdentificationTuple<Runtime>,
+>;
+
+/// Reports unresponsive validators to `Offences` and accounts them in their era uptime.
+pub struct ReportUnresponsiveness;
+
+impl
+    ReportOffence<
+        AccountId,
+        pallet_session::historical::IdentificationTuple<Runtime>,
+        UnresponsivenessOffence,
+    > for ReportUnresponsiveness
+{
+    fn report_offence(
+        reporters: Vec<AccountId>,
+        mut offence: UnresponsivenessOffence,
+    ) -> Result<(), OffenceError> {
+        let offenders: Vec<AccountId> =
+            offence.offenders.iter().map(|(stash, _)| stash.clone()).collect();
+        // Validators in their first eras aren't penalized for missing heartbeats.
+        offence
+            .offenders
+            .retain(|(stash, _)| !EnergyGeneration::in_onboarding_grace(stash));
+        if !offence.offenders.is_empty() {
+            Offences::report_offence(reporters, offence)?;
+        }
+        offenders.iter().for_each(EnergyGeneration::note_unresponsive);
+        Ok(())
+    }
+
+    fn is_known_offence(
+        offenders: &[pallet_session::historical::IdentificationTuple<Runtime>],
+        time_slot: &SessionIndex,
+    ) -> bool {
+        <Offences as ReportOffence<_, _, UnresponsivenessOffence>>::is_known_offence(
+            offenders, time_slot,
+        )
+    }
+}
+
 impl pallet_im_online::Config for Runtime {
     type AuthorityId = ImOnlineId;
     type MaxKeys = MaxKeys;
@@ -607,7 +694,7 @@ impl pallet_im_online::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type ValidatorSet = Historical;
     type NextSessionRotation = Babe;
-    type ReportUnresponsiveness = Offences;
+    type ReportUnresponsiveness = ReportUnresponsiveness;
     type UnsignedPriority = ImOnlineUnsignedPriority;
     type WeightInfo = pallet_im_online::weights::SubstrateWeight<Runtime>;
 }
@@ -703,6 +790,12 @@ parameter_types! {
     pub const Offset: BlockNumber = 0;
     pub const VNRG: AssetId = 1;
     pub const BatterySlotCapacity: Energy = 100_000_000_000;
+    pub const MinBatterySlotCapacity: Energy = BatterySlotCapacity::get() / 10;
+    pub const MaxBatterySlotCapacity: Energy = BatterySlotCapacity::get() * 10;
+    // the rate of the former constant, governance can lower it but not raise it
+    pub const EnergyPerReputationPoint: Energy = MaxEnergyPerReputationPoint::get();
+    pub const MaxEnergyPerReputationPoint: Energy = 1_000;
+    pub const MaxTierEnergyBonus: Perbill = Perbill::from_percent(50);
     pub const MaxCooperations: u32 = 256;
     pub const HistoryDepth: u32 = 84;
     pub const MaxUnlockingChunks: u32 = 64;
@@ -714,6 +807,31 @@ parameter_types! {
     pub const CollaborativeValidatorReputationTier: ReputationTier = ReputationTier::Vanguard(1);
     pub const RewardRemainderUnbalanced: u128 = 0;
     pub const OffendingValidatorsThreshold: Perbill = Perbill::from_percent(17);
+    // a cooperator with a 0% slash cap gives up a fifth of its reward
+    pub const SlashCapRewardPenalty: Perbill = Perbill::from_percent(20);
+    // validators on an outdated client keep three quarters of their reward
+    pub const OutdatedClientRewardPenalty: Perbill = Perbill::from_percent(25);
+    // validators backing an under-subscribed parachain earn a tenth more of their reward
+    pub const BackingBoost: Perbill = Perbill::from_percent(10);
+    pub const ClientVersionGracePeriod: EraIndex = prod_or_fast!(7, 2);
+    pub const MaxStaleRewardErasPerSweep: u32 = 16;
+    // payouts right after an era change are spread over the following blocks
+    pub const MaxEnergyMintPerBlock: Option<Energy> = Some(100_000 * vnrg::UNITS);
+    // rewards claimed during an emergency pause are paid once governance resumes them
+    pub const DeferRewardsWhilePaused: bool = true;
+    // a calculator returning zero would stop rewards, eras fall back to the launch rate instead
+    pub const MinEnergyPerStakeCurrency: Energy = ENERGY_PER_STAKE_CURRENCY;
+    // heartbeats are unsigned and free, the reserve lets controllers keep signing the calls
+    // keeping their validator operational, which pay the constant fee
+    pub OperationalCallFee: Energy = GetConstantEnergyFee::get();
+    // a call per session for a day of sessions
+    pub const ReservedOperationalCalls: u32 = (DAYS / EPOCH_DURATION_IN_BLOCKS) as u32;
+    pub const OnboardingGraceEras: EraIndex = 1;
+    // new cooperations earn rewards from their second era on
+    pub const CooperationWarmupEras: EraIndex = 1;
+    // remind validators to rotate session keys that are a quarter old
+    pub SessionKeysRotationAge: SessionIndex =
+        (90 * DAYS / EPOCH_DURATION_IN_BLOCKS) as SessionIndex;
 }
 
 pub struct EnergyPerStakeCurrency;
@@ -725,20 +843,7 @@ impl EnergyRateCalculator<StakeOf<Runtime>, Energy> for EnergyPerStakeCurrency {
         _core_nodes_num: u32,
         _battery_slot_cap: Energy,
     ) -> Energy {
-        19_909_091_036_891
-    }
-}
-
-pub struct EnergyPerReputationPoint;
-
-impl EnergyRateCalculator<StakeOf<Runtime>, Energy> for EnergyPerReputationPoint {
-    fn calculate_energy_rate(
-        _total_staked: StakeOf<Runtime>,
-        _total_issuance: Energy,
-        _core_nodes_num: u32,
-        _battery_slot_cap: Energy,
-    ) -> Energy {
-        1_000
+        ENERGY_PER_STAKE_CURRENCY
     }
 }
 
@@ -781,6 +886,10 @@ type EnergyGenerationAdminOrigin = EitherOfDiverse<
 impl pallet_energy_generation::Config for Runtime {
     type AdminOrigin = EnergyGenerationAdminOrigin;
     type BatterySlotCapacity = BatterySlotCapacity;
+    type MinBatterySlotCapacity = MinBatterySlotCapacity;
+    type MaxBatterySlotCapacity = MaxBatterySlotCapacity;
+    type EnergyPerReputationPoint = EnergyPerReputationPoint;
+    type MaxEnergyPerReputationPoint = MaxEnergyPerReputationPoint;
     type BenchmarkingConfig = EnergyGenerationBenchmarkConfig;
     type BondingDuration = BondingDuration;
     type CollaborativeValidatorReputationTier = CollaborativeValidatorReputationTier;
@@ -790,17 +899,33 @@ impl pallet_energy_generation::Config for Runtime {
     type HistoryDepth = HistoryDepth;
     type MaxCooperations = MaxCooperations;
     type MaxCooperatorRewardedPerValidator = ConstU32<128>;
+    type SlashCapRewardPenalty = SlashCapRewardPenalty;
+    type OutdatedClientRewardPenalty = OutdatedClientRewardPenalty;
+    type BackingBoost = BackingBoost;
+    type ClientVersionGracePeriod = ClientVersionGracePeriod;
+    type OnboardingGraceEras = OnboardingGraceEras;
+    type CooperationWarmupEras = CooperationWarmupEras;
+    type StaleRewardsDestination = xcm_config::TreasuryAccount;
+    type MaxStaleRewardErasPerSweep = MaxStaleRewardErasPerSweep;
+    type MaxEnergyMintPerBlock = MaxEnergyMintPerBlock;
+    type DeferRewardsWhilePaused = DeferRewardsWhilePaused;
+    type MinEnergyPerStakeCurrency = MinEnergyPerStakeCurrency;
+    type OperationalCallFee = OperationalCallFee;
+    type ReservedOperationalCalls = ReservedOperationalCalls;
     type MaxUnlockingChunks = MaxUnlockingChunks;
     type NextNewSession = Session;
     type EventListeners = ();
     type ReputationTierEnergyRewardAdditionalPercentMapping =
         ReputationTierEnergyRewardAdditionalPercentMapping;
+    type MaxTierEnergyBonus = MaxTierEnergyBonus;
     type Reward = ();
     type RewardRemainder = Treasury;
     type RuntimeEvent = RuntimeEvent;
     type SessionInterface = Self;
+    type SessionKeysRotationAge = SessionKeysRotationAge;
     type SessionsPerEra = SessionsPerEra;
     type DisablingStrategy = pallet_energy_generation::UpToLimitDisablingStrategy;
+    type OffendingValidatorsThreshold = OffendingValidatorsThreshold;
     type Slash = Treasury;
     type SlashDeferDuration = SlashDeferDuration;
     type StakeBalance = Balance;
@@ -861,6 +986,16 @@ impl pallet_nfts::Config for Runtime {
 parameter_types! {
     pub const NftCollectionId: CollectionId = 0;
     pub const VIPPCollectionId: CollectionId = 1;
+    pub const AccountNameDeposit: Balance = UNITS;
+}
+
+/// Validators losing their NAC access are chilled as the energy generation policy says.
+pub struct ChillOnNacRevocation;
+
+impl pallet_nac_managing::OnNacRevokedHandler<AccountId> for ChillOnNacRevocation {
+    fn on_nac_revoked(who: &AccountId) {
+        EnergyGeneration::note_nac_revoked(who);
+    }
 }
 
 impl pallet_nac_managing::Config for Runtime {
@@ -874,8 +1009,12 @@ impl pallet_nac_managing::Config for Runtime {
     type WeightInfo = pallet_nac_managing::weights::SubstrateWeight<Runtime>;
     type Currency = Balances;
     type OnVIPPChanged = Privileges;
+    type OnNacRevoked = ChillOnNacRevocation;
     type NftCollectionId = NftCollectionId;
     type VIPPCollectionId = VIPPCollectionId;
+    type AccountNameLimit = ConstU32<32>;
+    type AccountNameDeposit = AccountNameDeposit;
+    type UniqueAccountNames = ConstBool<true>;
 }
 
 impl pallet_privileges::Config for Runtime {
@@ -944,6 +1083,10 @@ parameter_types! {
     pub const PoolSwapFee: u32 = 10; // 1%
     pub const MaxSwapPathLength: u32 = 2;
     pub const MintMinLiquidity: Balance = 100;
+    pub const MaxFeeSwapImpact: Perbill = Perbill::from_percent(10);
+    pub const MaxStabilizationPerCall: Balance = 10_000 * vnrg::UNITS;
+    pub const MaxStabilizationPerEra: Balance = 50_000 * vnrg::UNITS;
+    pub const StabilizationPeriod: BlockNumber = EPOCH_DURATION_IN_BLOCKS * SessionsPerEra::get();
 }
 
 ord_parameter_types! {
@@ -951,7 +1094,13 @@ ord_parameter_types! {
         AccountIdConversion::<AccountId>::into_account_truncating(&AssetConversionPalletId::get());
 }
 
-type EnergyRate = AssetsBalancesConverter<Runtime, AssetRate>;
+parameter_types! {
+    /// Used for VNRG conversions when `AssetRate` has no rate for VNRG: the rate at genesis,
+    /// 1 VTRS = 0.9 gVolt.
+    pub const FallbackEnergyRate: Option<FixedU128> = Some(INITIAL_ENERGY_RATE);
+}
+
+type EnergyRate = AssetsBalancesConverter<Runtime, AssetRate, FallbackEnergyRate>;
 type EnergyItem = ItemOf<Assets, VNRG, AccountId>;
 
 impl pallet_energy_broker::Config for Runtime {
@@ -973,6 +1122,12 @@ impl pallet_energy_broker::Config for Runtime {
     type AllowMultiAssetPools = AllowMultiAssetPools;
     type MaxSwapPathLength = MaxSwapPathLength;
     type MintMinLiquidity = MintMinLiquidity;
+    type MaxFeeSwapImpact = MaxFeeSwapImpact;
+    type EmitSwapDetails = ConstBool<true>;
+    type ManageOrigin = MoreThanHalfCouncil;
+    type MaxStabilizationPerCall = MaxStabilizationPerCall;
+    type MaxStabilizationPerPeriod = MaxStabilizationPerEra;
+    type StabilizationPeriod = StabilizationPeriod;
     type MultiAssetId = NativeOrAssetId<AssetId>;
     type MultiAssetIdConverter = NativeOrAssetIdConverter<AssetId>;
     type WeightInfo = pallet_energy_broker::weights::SubstrateWeight<Runtime>;
@@ -996,6 +1151,15 @@ impl OnUnbalanced<NegativeImbalance<Runtime>> for EnergyBrokerSink {
     }
 }
 
+/// Burns the fee token paid for fees, accounting it in the era's energy burn.
+pub struct EnergyBurnTracker;
+
+impl OnUnbalanced<pallet_energy_fee::FeeCreditOf<Runtime>> for EnergyBurnTracker {
+    fn on_nonzero_unbalanced(amount: pallet_energy_fee::FeeCreditOf<Runtime>) {
+        EnergyGeneration::note_energy_burned(frame_support::traits::Imbalance::peek(&amount));
+    }
+}
+
 pub struct EnergyBrokerExchange;
 
 impl TokenExchange<AccountId, Balances, EnergyItem, EnergyBrokerSink, Balance>
@@ -1018,11 +1182,42 @@ impl TokenExchange<AccountId, Balances, EnergyItem, EnergyBrokerSink, Balance>
     }
 
     fn exchange_from_input(who: &AccountId, amount: Balance) -> Result<Balance, DispatchError> {
-        EnergyBroker::swap_exact_native_for_tokens(*who, VNRG::get(), amount, None, *who, true)
+        EnergyBroker::swap_exact_native_for_tokens(
+            *who,
+            VNRG::get(),
+            amount,
+            None,
+            *who,
+            true,
+            SwapKind::User,
+        )
     }
 
     fn exchange_from_output(who: &AccountId, amount: Balance) -> Result<Balance, DispatchError> {
-        EnergyBroker::swap_native_for_exact_tokens(*who, VNRG::get(), amount, None, *who, true)
+        EnergyBroker::swap_native_for_exact_tokens(
+            *who,
+            VNRG::get(),
+            amount,
+            None,
+            *who,
+            true,
+            SwapKind::User,
+        )
+    }
+
+    fn exchange_fee_from_output(
+        who: &AccountId,
+        amount: Balance,
+    ) -> Result<Balance, DispatchError> {
+        EnergyBroker::swap_native_for_exact_tokens(
+            *who,
+            VNRG::get(),
+            amount,
+            None,
+            *who,
+            true,
+            SwapKind::Fee,
+        )
     }
 
     fn exchange_inner(
@@ -1034,18 +1229,183 @@ impl TokenExchange<AccountId, Balances, EnergyItem, EnergyBrokerSink, Balance>
     }
 }
 
+impl TokenRedemption<AccountId, Balance> for EnergyBrokerExchange {
+    fn quote_redemption(amount: Balance) -> Result<Balance, DispatchError> {
+        EnergyBroker::get_amount_out(
+            &amount,
+            (&NativeOrAssetId::Asset(VNRG::get()), &NativeOrAssetId::Native),
+        )
+        .map_err(|e| e.into())
+    }
+
+    fn redeem(
+        who: &AccountId,
+        amount: Balance,
+        min_out: Balance,
+    ) -> Result<Balance, DispatchError> {
+        let min_out = Some(min_out).filter(|min_out| !min_out.is_zero());
+        EnergyBroker::swap_exact_tokens_for_native(*who, VNRG::get(), amount, min_out, *who, true)
+    }
+}
+
+parameter_types! {
+    /// VNRG reserve the broker pool needs before fees are swapped through it.
+    pub const MinFeePoolReserve: Balance = 10_000 * vnrg::GVOLT;
+}
+
+/// Whether the broker pool holds `MinFeePoolReserve` of VNRG.
+pub struct BrokerPoolLiquid;
+
+impl Get<bool> for BrokerPoolLiquid {
+    fn get() -> bool {
+        EnergyBroker::get_reserves(&NativeOrAssetId::Native, &NativeOrAssetId::Asset(VNRG::get()))
+            .map_or(false, |(_, vnrg_reserve)| vnrg_reserve >= MinFeePoolReserve::get())
+    }
+}
+
+/// Fee swaps at the oracle rate, until the broker pool first becomes liquid.
+type OracleEnergyExchange = NativeExchange<AssetId, Balances, EnergyItem, EnergyRate, VNRG>;
+type FeeEnergyExchange =
+    BootstrapExchange<Runtime, EnergyBrokerExchange, OracleEnergyExchange, BrokerPoolLiquid>;
+
+parameter_types! {
+    pub const FeeMultiplierHistoryDepth: BlockNumber = 7 * DAYS;
+    // a tenth of the constant fee, so that calls priced by a near-zero weight aren't free
+    pub MinimumEnergyFee: Balance = GetConstantEnergyFee::get() / 10;
+    pub const EvmFeeReceiptRetention: BlockNumber = DAYS;
+    // enough for blocks filled with plain transfers at the highest block gas limit
+    pub MaxEvmFeeReceiptsPerBlock: u32 = (MaxBlockGasLimit::get() / 21_000) as u32;
+    pub const FeePaymentPathRetention: BlockNumber = DAYS;
+    pub const MaxFeePaymentPathRemovalsPerBlock: u32 = 512;
+    pub FeeRebatePalletId: PalletId = PalletId(*b"vt/rebte");
+    pub FeeRebatePot: AccountId = FeeRebatePalletId::get().into_account_truncating();
+    pub const FeeRebateClaimEras: u32 = 28;
+    pub const FeeSpendHistoryEras: u32 = 28;
+    pub DisputeSurchargePalletId: PalletId = PalletId(*b"vt/dsprg");
+    pub DisputeSurchargePot: AccountId = DisputeSurchargePalletId::get().into_account_truncating();
+    pub const MaxSurchargeRefundsPerBlock: u32 = 64;
+    pub StarterEnergyPalletId: PalletId = PalletId(*b"vt/strtr");
+    pub StarterEnergyPot: AccountId = StarterEnergyPalletId::get().into_account_truncating();
+    pub const MaxStarterEnergyGrantsPerBlock: u32 = 64;
+    pub const MaxEnergyAllowances: u32 = 16;
+    pub const MaxFeeContributionRemovalsPerBlock: u32 = 512;
+    pub const FeeDiscountDelegationDeposit: Balance = UNITS;
+    pub const PriceSnapshotInterval: BlockNumber = HOURS;
+    // a month of hourly snapshots
+    pub const MaxPriceSnapshots: u32 = 30 * 24;
+}
+
+/// Price of one VTRS in VNRG quoted by the energy broker pool.
+pub struct BrokerEnergyPrice;
+
+impl Get<Option<Balance>> for BrokerEnergyPrice {
+    fn get() -> Option<Balance> {
+        EnergyBroker::quote_price_exact_tokens_for_tokens(
+            NativeOrAssetId::Native,
+            NativeOrAssetId::Asset(VNRG::get()),
+            UNITS,
+            true,
+        )
+    }
+}
+
+/// Index of the active staking era, fee rebates and reputation earnings are accounted per era.
+pub struct ActiveEraIndex;
+
+impl Get<u32> for ActiveEraIndex {
+    fn get() -> u32 {
+        EnergyGeneration::active_era().map_or(0, |era| era.index)
+    }
+}
+
+/// Calls of the parachain onboarding and messaging pallets.
+pub struct CrossChainCalls;
+
+impl Contains<RuntimeCall> for CrossChainCalls {
+    fn contains(call: &RuntimeCall) -> bool {
+        matches!(
+            call,
+            RuntimeCall::Hrmp(..)
+                | RuntimeCall::Registrar(..)
+                | RuntimeCall::Slots(..)
+                | RuntimeCall::Auctions(..)
+                | RuntimeCall::XcmPallet(..)
+        )
+    }
+}
+
+/// Verified accounts pay 5% less fees from NAC level 2 and 10% less from NAC level 3.
+pub struct NacFeeDiscount;
+
+impl pallet_energy_fee::GetByKey<u8, Perbill> for NacFeeDiscount {
+    fn get(level: &u8) -> Perbill {
+        match level {
+            0 | 1 => Perbill::zero(),
+            2 => Perbill::from_percent(5),
+            _ => Perbill::from_percent(10),
+        }
+    }
+}
+
+/// Trailblazers may trigger 2 fee swaps per era more than the limit, Ultramodern accounts 5.
+pub struct ReputationFeeSwapLimitBonus;
+
+impl Convert<&AccountId, u32> for ReputationFeeSwapLimitBonus {
+    fn convert(who: &AccountId) -> u32 {
+        match Reputation::tier_of(who) {
+            Some(ReputationTier::Trailblazer(_)) => 2,
+            Some(ReputationTier::Ultramodern(_)) => 5,
+            _ => 0,
+        }
+    }
+}
+
 impl pallet_energy_fee::Config for Runtime {
     type ManageOrigin = MoreThanHalfCouncil;
     type RuntimeEvent = RuntimeEvent;
     type FeeTokenBalanced = EnergyItem;
     type MainTokenBalanced = Balances;
-    type EnergyExchange = EnergyBrokerExchange;
+    type EnergyExchange = FeeEnergyExchange;
+    type EnergyRedemption = EnergyBrokerExchange;
     type GetConstantFee = GetConstantEnergyFee;
     type CustomFee = EnergyFee;
     type EnergyAssetId = VNRG;
     type MainRecycleDestination = EnergyBrokerSink;
-    type FeeRecycleDestination = ();
+    type FeeRecycleDestination = EnergyBurnTracker;
     type OnWithdrawFee = NacManaging;
+    type DefaultBlockGasLimit = DefaultBlockGasLimit;
+    type MinBlockGasLimit = MinBlockGasLimit;
+    type MaxBlockGasLimit = MaxBlockGasLimit;
+    type FeeMultiplierHistoryDepth = FeeMultiplierHistoryDepth;
+    type NacLevel = NacManaging;
+    type NacFeeDiscount = NacFeeDiscount;
+    type MinimumFee = MinimumEnergyFee;
+    type FeeExemptCalls = ZeroFeeCalls;
+    type EvmFeeReceiptRetention = EvmFeeReceiptRetention;
+    type MaxEvmFeeReceiptsPerBlock = MaxEvmFeeReceiptsPerBlock;
+    type FeePaymentPathRetention = FeePaymentPathRetention;
+    type MaxFeePaymentPathRemovalsPerBlock = MaxFeePaymentPathRemovalsPerBlock;
+    type FeeAssets = Assets;
+    type FeeAssetRate = AssetsBalancesConverter<Runtime, AssetRate>;
+    type FeeAssetCollector = xcm_config::TreasuryAccount;
+    type RebateEra = ActiveEraIndex;
+    type FeeSpendHistoryEras = FeeSpendHistoryEras;
+    type RebatePot = FeeRebatePot;
+    type RebateClaimEras = FeeRebateClaimEras;
+    type UnclaimedRebateDestination = xcm_config::TreasuryAccount;
+    type CrossChainCalls = CrossChainCalls;
+    type SurchargePot = DisputeSurchargePot;
+    type MaxSurchargeRefundsPerBlock = MaxSurchargeRefundsPerBlock;
+    type EnergyPrice = BrokerEnergyPrice;
+    type PriceSnapshotInterval = PriceSnapshotInterval;
+    type MaxPriceSnapshots = MaxPriceSnapshots;
+    type StarterEnergyPot = StarterEnergyPot;
+    type MaxStarterEnergyGrantsPerBlock = MaxStarterEnergyGrantsPerBlock;
+    type FeeSwapLimitBonus = ReputationFeeSwapLimitBonus;
+    type MaxEnergyAllowances = MaxEnergyAllowances;
+    type MaxFeeContributionRemovalsPerBlock = MaxFeeContributionRemovalsPerBlock;
+    type FeeDiscountDelegationDeposit = FeeDiscountDelegationDeposit;
+    type WeightInfo = pallet_energy_fee::weights::SubstrateWeight<Runtime>;
 }
 
 parameter_types! {
@@ -1068,6 +1428,8 @@ impl pallet_claiming::Config for Runtime {
     type VestingSchedule = Vesting;
     type OnClaim = NacManaging;
     type Prefix = Prefix;
+    // a wallet retrying a claim that went through sees it succeed
+    type IdempotentClaims = ConstBool<true>;
     type WeightInfo = ();
 }
 
@@ -1095,6 +1457,616 @@ impl pallet_simple_vesting::Config for Runtime {
     type Slash = Treasury;
 }
 
+/// Defines the fee class of the calls of each pallet once, for both [`fee_class`], which matches
+/// on the call variants, and [`fee_classification`], which only knows the pallet index.
+macro_rules! pallet_fee_classes {
+    ($($class:ident => [$($pallet:ident),* $(,)?]),* $(,)?) => {
+        /// Fee class of a call by its pallet alone, [`FeeClass::Weight`] for unlisted pallets.
+        fn pallet_call_fee_class(call: &RuntimeCall) -> FeeClass {
+            match call {
+                $($(RuntimeCall::$pallet(..))|* => FeeClass::$class,)*
+                _ => FeeClass::Weight,
+            }
+        }
+
+        /// Fee class of the calls of the pallet at `index`, as [`pallet_call_fee_class`].
+        fn pallet_fee_class(index: usize) -> FeeClass {
+            $(
+                if [$(<$pallet as PalletInfoAccess>::index()),*].contains(&index) {
+                    return FeeClass::$class;
+                }
+            )*
+            FeeClass::Weight
+        }
+    };
+}
+
+pallet_fee_classes! {
+    Constant => [
+        Assets,
+        AssetRate,
+        Auctions,
+        Balances,
+        Bounties,
+        EnergyGeneration,
+        EnergyBroker,
+        Nfts,
+        AtomicSwap,
+        Claiming,
+        Vesting,
+        NacManaging,
+        Privileges,
+        Council,
+        TechnicalCommittee,
+        TechnicalMembership,
+        Treasury,
+        Democracy,
+        Elections,
+        Session,
+        XcmPallet,
+        SimpleVesting,
+        Reputation,
+    ],
+    EVM => [EVM, Ethereum],
+    Zero => [Sudo],
+}
+
+/// Fee class of a call.
+///
+/// This is the single source of truth for the call pricing in [`CustomFee`] and the
+/// `fee_classification` runtime API.
+pub fn fee_class(call: &RuntimeCall) -> FeeClass {
+    match call {
+        RuntimeCall::Utility(
+            pallet_utility::Call::batch { .. }
+            | pallet_utility::Call::batch_all { .. }
+            | pallet_utility::Call::force_batch { .. }
+            | pallet_utility::Call::dispatch_as { .. }
+            | pallet_utility::Call::as_derivative { .. },
+        ) => FeeClass::Nested,
+        call => pallet_call_fee_class(call),
+    }
+}
+
+/// Calls of the [`FeeClass::Zero`] class, which are exempt from the minimum fee.
+pub struct ZeroFeeCalls;
+
+impl Contains<RuntimeCall> for ZeroFeeCalls {
+    fn contains(call: &RuntimeCall) -> bool {
+        matches!(fee_class(call), FeeClass::Zero)
+    }
+}
+
+/// Fee class of every dispatchable call as `(pallet index, call name, class)`.
+pub fn fee_classification() -> Vec<(u8, Vec<u8>, FeeClass)> {
+    // the names of the nested calls, taken from the calls themselves to survive renames
+    let inner = || Box::new(RuntimeCall::System(frame_system::Call::remark { remark: Vec::new() }));
+    let nested_calls = [
+        pallet_utility::Call::batch { calls: Vec::new() },
+        pallet_utility::Call::batch_all { calls: Vec::new() },
+        pallet_utility::Call::force_batch { calls: Vec::new() },
+        pallet_utility::Call::dispatch_as {
+            as_origin: Box::new(OriginCaller::system(frame_system::RawOrigin::Root)),
+            call: inner(),
+        },
+        pallet_utility::Call::as_derivative { index: 0, call: inner() },
+    ]
+    .map(|call| call.get_call_name());
+
+    let modules = RuntimeCall::get_module_names();
+    AllPalletsWithSystem::infos()
+        .into_iter()
+        .filter(|info| modules.contains(&info.name))
+        .flat_map(|info| {
+            let nested_calls = &nested_calls;
+            RuntimeCall::get_call_names(info.name).iter().map(move |function_name| {
+                let class = if info.index == <Utility as PalletInfoAccess>::index()
+                    && nested_calls.contains(function_name)
+                {
+                    FeeClass::Nested
+                } else {
+                    pallet_fee_class(info.index)
+                };
+                (info.index as u8, function_name.as_bytes().to_vec(), class)
+            })
+        })
+        .collect()
+}
+
+/// Compare the VTRS/VNRG spot rate of the broker pool, i.e. the ratio of its reserves, with the
+/// VNRG rate set in `pallet_asset_rate`.
+///
+/// Returns `None` when either the oracle rate or the pool liquidity is missing.
+pub fn rate_divergence() -> Option<RateDivergence> {
+    let oracle = pallet_asset_rate::ConversionRateToNative::<Runtime>::get(VNRG::get())
+        .filter(|rate| !rate.is_zero())?;
+    let (native_reserve, asset_reserve) =
+        EnergyBroker::get_reserves(&NativeOrAssetId::Native, &NativeOrAssetId::Asset(VNRG::get()))
+            .ok()?;
+    let spot = FixedU128::checked_from_rational(native_reserve, asset_reserve)?;
+
+    let spot_above_oracle = spot > oracle;
+    let difference = if spot_above_oracle { spot - oracle } else { oracle - spot };
+
+    Some(RateDivergence {
+        magnitude: Perbill::from_rational(difference.into_inner(), oracle.into_inner()),
+        spot_above_oracle,
+    })
+}
+
+/// Every asset with a conversion rate in `pallet_asset_rate`, with its rate and whether the
+/// broker has a pool pairing it with VTRS.
+///
+/// Assets without a pool are oracle-only: they have a price but can't be swapped for fees.
+pub fn fee_payable_assets() -> Vec<(AssetId, FixedU128, bool)> {
+    pallet_asset_rate::ConversionRateToNative::<Runtime>::iter()
+        .map(|(asset, rate)| {
+            let pool_id =
+                EnergyBroker::get_pool_id(NativeOrAssetId::Native, NativeOrAssetId::Asset(asset));
+            (asset, rate, pallet_energy_broker::Pools::<Runtime>::contains_key(pool_id))
+        })
+        .collect()
+}
+
+/// Signer and VNRG fee charged for the extrinsic, `None` for unsigned extrinsics.
+pub fn extrinsic_fee(uxt: UncheckedExtrinsic) -> Option<(AccountId, Balance)> {
+    use fp_self_contained::SelfContainedCall;
+
+    let call = &uxt.0.function;
+    let who = match uxt.0.signature {
+        Some((who, _, _)) => who,
+        None => AccountId::from(call.check_self_contained()?.ok()?),
+    };
+    let fee = EnergyFee::dispatch_info_to_fee(call, None, None).into_inner();
+    Some((who, EnergyFee::charged_fee(&who, call, fee)))
+}
+
+/// Steps taken to pay the fee of the extrinsic at `index` of `block`, while it's retained.
+pub fn fee_payment_path(
+    block: BlockNumber,
+    index: u32,
+) -> Option<FeePaymentPath<AssetId, Balance>> {
+    EnergyFee::fee_payment_path(block, index).map(|path| path.into_inner())
+}
+
+/// Whether `who` can pay the fee of a `Utility::batch` of the SCALE encoded `calls`.
+///
+/// The fee is summed the way it is for the batch extrinsic, including nested batches and EVM
+/// calls. Returns `false` if any call can't be decoded.
+pub fn can_afford_batch(who: &AccountId, calls: Vec<Vec<u8>>) -> bool {
+    let Ok(calls) = calls
+        .into_iter()
+        .map(|call| RuntimeCall::decode(&mut &call[..]))
+        .collect::<Result<Vec<_>, _>>()
+    else {
+        return false;
+    };
+
+    EnergyFee::can_afford_fee(who, &RuntimeCall::Utility(pallet_utility::Call::batch { calls }))
+}
+
+/// Estimate of the VNRG fees a validator spends per era at the current fee parameters.
+///
+/// It covers one `payout_stakers` and one `report_client_version` per era, plus `set_keys`
+/// amortized over `SessionKeysRotationAge`. The fees are those of typical calls and don't
+/// account for NAC or reputation discounts. Heartbeats are unsigned and free.
+pub fn validator_operating_cost() -> Balance {
+    let fee_of =
+        |call: RuntimeCall| EnergyFee::dispatch_info_to_fee(&call, None, None).into_inner();
+
+    let payout = fee_of(RuntimeCall::EnergyGeneration(
+        pallet_energy_generation::Call::payout_stakers {
+            validator_stash: AccountId::default(),
+            era: EnergyGeneration::active_era().map(|era| era.index).unwrap_or_default(),
+        },
+    ));
+    let client_version = fee_of(RuntimeCall::EnergyGeneration(
+        pallet_energy_generation::Call::report_client_version { version: Default::default() },
+    ));
+    let set_keys = fee_of(RuntimeCall::Session(pallet_session::Call::set_keys {
+        keys: opaque::SessionKeys::default(),
+        proof: Vec::new(),
+    }));
+    let key_rotations =
+        Perbill::from_rational(SessionsPerEra::get(), SessionKeysRotationAge::get());
+
+    payout.saturating_add(client_version).saturating_add(key_rotations * set_keys)
+}
+
+/// Parameters of the dynamic VNRG economy, as used by the energy generation and fee pallets.
+pub fn dynamic_vnrg_params() -> DynamicVnrgParams {
+    let reputation_bonuses = (1..=RANKS_PER_TIER * 3)
+        .filter_map(ReputationTier::try_from_rank)
+        .map(|tier| (tier, EnergyGeneration::tier_energy_bonus(&tier)))
+        .collect();
+
+    DynamicVnrgParams {
+        energy_per_stake_currency: EnergyGeneration::active_era()
+            .and_then(|era| EnergyGeneration::eras_energy_per_stake_cur(era.index)),
+        next_energy_per_stake_currency: EnergyGeneration::current_energy_per_stake_currency(),
+        battery_slot_capacity: EnergyGeneration::battery_slot_capacity(),
+        reputation_bonuses,
+        base_fee: EnergyFee::base_fee(),
+    }
+}
+
+/// The energy economics the chain was launched with, from the snapshots taken at genesis.
+///
+/// `None` for chains launched before the snapshots were introduced.
+pub fn genesis_energy_config() -> Option<GenesisEnergyConfig> {
+    let energy = EnergyGeneration::genesis_parameters()?;
+
+    Some(GenesisEnergyConfig {
+        energy_per_stake_currency: energy.energy_per_stake_currency,
+        vnrg_name: energy.energy_name,
+        vnrg_symbol: energy.energy_symbol,
+        vnrg_decimals: energy.energy_decimals,
+        min_cooperator_bond: energy.min_cooperator_bond,
+        min_common_validator_bond: energy.min_common_validator_bond,
+        min_trust_validator_bond: energy.min_trust_validator_bond,
+        broker_seed: pallet_energy_broker::GenesisPoolSeeds::<Runtime>::get(VNRG::get()),
+    })
+}
+
+/// Energy the network mints over a year per unit of stake, valued in VTRS at the VNRG rate of
+/// `pallet_asset_rate`.
+///
+/// This is a projection: the energy rate and total stake of the active era are assumed to hold
+/// for a year of eras of nominal length. Reputation and tier bonuses, commissions and penalties
+/// are left out. Zero when nothing is staked or either rate is missing, capped at 100%.
+pub fn network_energy_apy() -> Perbill {
+    let Some(energy_rate) = EnergyGeneration::active_era()
+        .and_then(|era| EnergyGeneration::eras_energy_per_stake_cur(era.index))
+        .filter(|rate| !rate.is_zero())
+    else {
+        return Perbill::zero();
+    };
+    let Some(energy_price) = pallet_asset_rate::ConversionRateToNative::<Runtime>::get(VNRG::get())
+    else {
+        return Perbill::zero();
+    };
+    let total_staked = EnergyGeneration::staking_overview().total_staked;
+    if total_staked.is_zero() {
+        return Perbill::zero();
+    }
+
+    let eras_per_year = YEARS / (EPOCH_DURATION_IN_BLOCKS * SessionsPerEra::get());
+    let energy_per_year = (total_staked / energy_rate).saturating_mul(eras_per_year.into());
+
+    Perbill::from_rational(energy_price.saturating_mul_int(energy_per_year), total_staked)
+}
+
+/// Every parameter governance can adjust, with its current value.
+///
+/// Parameters added to the runtime must be listed here for governance UIs to find them.
+pub fn runtime_parameters() -> Vec<(ParamName, ParamValue)> {
+    let amount = |value: Option<Balance>| value.map_or(ParamValue::Unset, ParamValue::Amount);
+    let count = |value: Option<u32>| value.map_or(ParamValue::Unset, ParamValue::Count);
+
+    vec![
+        // energy fee
+        (b"energy_fee.base_fee".to_vec(), ParamValue::Amount(EnergyFee::base_fee())),
+        (
+            b"energy_fee.burned_energy_threshold".to_vec(),
+            amount(EnergyFee::burned_energy_threshold()),
+        ),
+        (
+            b"energy_fee.block_fullness_threshold".to_vec(),
+            ParamValue::Perquintill(EnergyFee::block_fullness_threshold()),
+        ),
+        (
+            b"energy_fee.upper_fee_multiplier".to_vec(),
+            ParamValue::Fixed(EnergyFee::upper_fee_multiplier().into_inner()),
+        ),
+        (b"energy_fee.block_gas_limit".to_vec(), ParamValue::Gas(EnergyFee::block_gas_limit())),
+        (b"energy_fee.rebate_fraction".to_vec(), ParamValue::Perbill(EnergyFee::rebate_fraction())),
+        (
+            b"energy_fee.auto_swap_enabled".to_vec(),
+            ParamValue::Flag(EnergyFee::auto_swap_enabled()),
+        ),
+        (b"energy_fee.min_evm_fee".to_vec(), ParamValue::Amount(EnergyFee::min_evm_fee())),
+        (
+            b"energy_fee.gas_estimation_buffer".to_vec(),
+            ParamValue::Perbill(EnergyFee::gas_estimation_buffer()),
+        ),
+        (
+            b"energy_fee.starter_energy_amount".to_vec(),
+            ParamValue::Amount(EnergyFee::starter_energy_amount()),
+        ),
+        (
+            b"energy_fee.starter_energy_budget".to_vec(),
+            ParamValue::Amount(EnergyFee::starter_energy_budget()),
+        ),
+        (b"energy_fee.fee_swap_limit".to_vec(), count(EnergyFee::fee_swap_limit())),
+        // energy generation
+        (
+            b"energy_generation.validator_count".to_vec(),
+            ParamValue::Count(EnergyGeneration::validator_count()),
+        ),
+        (
+            b"energy_generation.minimum_validator_count".to_vec(),
+            ParamValue::Count(EnergyGeneration::minimum_validator_count()),
+        ),
+        (
+            b"energy_generation.core_nodes_count".to_vec(),
+            ParamValue::Count(EnergyGeneration::core_nodes_count()),
+        ),
+        (
+            b"energy_generation.min_cooperator_bond".to_vec(),
+            ParamValue::Amount(pallet_energy_generation::MinCooperatorBond::<Runtime>::get()),
+        ),
+        (
+            b"energy_generation.min_common_validator_bond".to_vec(),
+            ParamValue::Amount(pallet_energy_generation::MinCommonValidatorBond::<Runtime>::get()),
+        ),
+        (
+            b"energy_generation.min_trust_validator_bond".to_vec(),
+            ParamValue::Amount(pallet_energy_generation::MinTrustValidatorBond::<Runtime>::get()),
+        ),
+        (
+            b"energy_generation.max_cooperators_count".to_vec(),
+            count(pallet_energy_generation::MaxCooperatorsCount::<Runtime>::get()),
+        ),
+        (
+            b"energy_generation.max_validators_count".to_vec(),
+            count(pallet_energy_generation::MaxValidatorsCount::<Runtime>::get()),
+        ),
+        (
+            b"energy_generation.max_cooperations".to_vec(),
+            ParamValue::Count(EnergyGeneration::max_cooperations()),
+        ),
+        (
+            b"energy_generation.battery_slot_capacity".to_vec(),
+            ParamValue::Amount(EnergyGeneration::battery_slot_capacity()),
+        ),
+        (
+            b"energy_generation.chill_threshold".to_vec(),
+            EnergyGeneration::chill_threshold().map_or(ParamValue::Unset, |threshold| {
+                ParamValue::Perbill(Perbill::from_percent(threshold.deconstruct().into()))
+            }),
+        ),
+        (
+            b"energy_generation.min_commission".to_vec(),
+            ParamValue::Perbill(pallet_energy_generation::MinCommission::<Runtime>::get()),
+        ),
+        (
+            b"energy_generation.energy_per_stake_currency".to_vec(),
+            amount(EnergyGeneration::current_energy_per_stake_currency()),
+        ),
+        (
+            b"energy_generation.block_authoring_reward".to_vec(),
+            ParamValue::Points(EnergyGeneration::block_authoring_reward().0),
+        ),
+        (
+            b"energy_generation.rewards_paused".to_vec(),
+            ParamValue::Flag(pallet_energy_generation::RewardsPaused::<Runtime>::get()),
+        ),
+        // governance
+        (
+            b"democracy.proposal_reputation_threshold".to_vec(),
+            ParamValue::Points(areas::ProposalReputationThreshold::get().0),
+        ),
+    ]
+}
+
+/// Estimated first block of `era`, assuming sessions keep their nominal length.
+///
+/// Returns `None` while there is no active era.
+fn estimated_era_start(era: EraIndex) -> Option<BlockNumber> {
+    let active_era = EnergyGeneration::active_era()?.index;
+    let active_era_start = EnergyGeneration::eras_start_session_index(active_era)?;
+    let era_start = active_era_start
+        .saturating_add(era.saturating_sub(active_era).saturating_mul(SessionsPerEra::get()));
+    let sessions_ahead = era_start.saturating_sub(Session::current_index());
+    Some(
+        System::block_number()
+            .saturating_add(sessions_ahead.saturating_mul(EPOCH_DURATION_IN_BLOCKS)),
+    )
+}
+
+/// The categories of operations `who` is permitted to perform, checked the same way as the
+/// transactions themselves.
+pub fn permitted_calls(who: &AccountId) -> Vec<CallClass> {
+    let has_nac_access =
+        |level| NacManaging::is_access_exempt(who) || NacManaging::user_has_access(*who, level);
+
+    [
+        (CallClass::EvmCall, has_nac_access(helpers::runner::CALL_ACCESS_LEVEL)),
+        (CallClass::EvmCreate, has_nac_access(helpers::runner::CREATE_ACCESS_LEVEL)),
+        (CallClass::Cooperate, true),
+        (CallClass::Validate, EnergyGeneration::is_legit_for_validator(who)),
+        (CallClass::CollaborativeValidate, EnergyGeneration::is_legit_for_collab(who)),
+        (CallClass::Vote, true),
+        (CallClass::Propose, areas::can_submit_proposal(who)),
+    ]
+    .into_iter()
+    .filter_map(|(class, permitted)| permitted.then_some(class))
+    .collect()
+}
+
+/// Upcoming vesting unlocks, scheduled NAC changes and unbonding maturities of `who`, sorted by
+/// block. Events at or before the current block are left out.
+pub fn account_schedule(who: &AccountId) -> Vec<ScheduledEvent> {
+    let now = System::block_number();
+    let mut events = Vec::new();
+
+    let vesting_schedules = pallet_vesting::Vesting::<Runtime>::get(who)
+        .into_iter()
+        .flatten()
+        .map(|schedule| {
+            let end = schedule.ending_block_as_balance::<ConvertInto>();
+            (schedule.locked(), schedule.starting_block(), end)
+        })
+        .chain(SimpleVesting::vesting(who).map(|schedule| {
+            let end = schedule.ending_block_as_balance::<ConvertInto>();
+            (schedule.locked(), schedule.starting_block(), end)
+        }));
+    for (locked, start, end) in vesting_schedules {
+        events.push(ScheduledEvent {
+            block: start,
+            kind: ScheduledEventKind::VestingStarts { locked },
+        });
+        events.push(ScheduledEvent {
+            block: end.saturated_into(),
+            kind: ScheduledEventKind::VestingEnds { locked },
+        });
+    }
+
+    if let Some(ledger) = EnergyGeneration::bonded(who).and_then(EnergyGeneration::ledger) {
+        for chunk in ledger.unlocking {
+            if let Some(block) = estimated_era_start(chunk.era()) {
+                events.push(ScheduledEvent {
+                    block,
+                    kind: ScheduledEventKind::UnbondingMatures { amount: chunk.value() },
+                });
+            }
+        }
+    }
+
+    for (block, agenda) in pallet_scheduler::Agenda::<Runtime>::iter() {
+        for scheduled in agenda.iter().flatten() {
+            // The fields of `Scheduled` are private, its encoding starts with the task name, the
+            // priority and the call.
+            let Ok((_, _, call)) = <(
+                Option<TaskName>,
+                schedule::Priority,
+                Bounded<RuntimeCall, BlakeTwo256>,
+            )>::decode(&mut &scheduled.encode()[..]) else {
+                continue;
+            };
+            let Ok((call, _)) = <Preimage as QueryPreimage>::peek(&call) else { continue };
+            let level = match call {
+                RuntimeCall::NacManaging(pallet_nac_managing::Call::mint { nac_level, owner })
+                    if owner == *who =>
+                {
+                    Some(nac_level)
+                },
+                RuntimeCall::NacManaging(pallet_nac_managing::Call::update_nft {
+                    new_nac_level,
+                    owner,
+                }) if owner == *who => new_nac_level,
+                _ => continue,
+            };
+            events
+                .push(ScheduledEvent { block, kind: ScheduledEventKind::NacLevelChange { level } });
+        }
+    }
+
+    events.retain(|event| event.block > now);
+    events.sort_by_key(|event| event.block);
+    events
+}
+
+/// Liquid balances, staking position, deferred staking rewards and fee credit of `who`.
+pub fn account_balance_sheet(who: &AccountId) -> BalanceSheet {
+    let ledger = EnergyGeneration::bonded(who).and_then(EnergyGeneration::ledger);
+
+    BalanceSheet {
+        liquid_vtrs: Balances::reducible_balance(who, Preservation::Expendable, Fortitude::Polite),
+        liquid_vnrg: Assets::balance(VNRG::get(), who),
+        bonded: ledger.as_ref().map_or(0, |ledger| ledger.active),
+        unlocking: ledger
+            .into_iter()
+            .flat_map(|ledger| ledger.unlocking)
+            .map(|chunk| (chunk.era(), chunk.value()))
+            .collect(),
+        pending_rewards: pallet_energy_generation::DeferredEnergyRewards::<Runtime>::get(who),
+        fee_credit: EnergyFee::fee_credit(who),
+    }
+}
+
+/// Maximum number of atomic swaps listed by a single `active_swaps` call.
+pub const MAX_ACTIVE_SWAPS_PER_PAGE: u32 = 256;
+
+/// Pending atomic swaps in storage order, at most `limit` of them after the swap of `start_after`.
+///
+/// Claimed and cancelled swaps are removed from storage, so they are never listed. Expired swaps
+/// are listed until their source cancels them, as they can still be claimed.
+pub fn active_swaps(start_after: Option<(AccountId, [u8; 32])>, limit: u32) -> Vec<SwapInfo> {
+    type PendingSwaps = pallet_atomic_swap::PendingSwaps<Runtime>;
+
+    let swaps = match start_after {
+        Some((target, hashed_proof)) => {
+            PendingSwaps::iter_from(PendingSwaps::hashed_key_for(target, hashed_proof))
+        },
+        None => PendingSwaps::iter(),
+    };
+    swaps
+        .take(limit.min(MAX_ACTIVE_SWAPS_PER_PAGE) as usize)
+        .map(|(target, hashed_proof, swap)| SwapInfo {
+            source: swap.source.into(),
+            target: target.into(),
+            hashed_proof,
+            amount: *swap.action,
+            end_block: swap.end_block,
+        })
+        .collect()
+}
+
+/// Gas of the EVM call of `request`, or the fee of the transfer it describes.
+///
+/// Contract calls are dry run and estimated at the gas they used plus the `GasEstimationBuffer`,
+/// or at their gas limit if they can't be executed. Pure transfers aren't buffered.
+pub fn estimate_gas(request: CallRequest) -> U256 {
+    let CallRequest {
+        from,
+        to,
+        max_fee_per_gas,
+        max_priority_fee_per_gas,
+        gas,
+        value,
+        data,
+        nonce,
+        access_list,
+        ..
+    } = request;
+    let Some(data) = data else {
+        return match (to, value) {
+            (Some(to), Some(value)) => {
+                let call = RuntimeCall::Balances(pallet_balances::Call::transfer_allow_death {
+                    dest: to.into(),
+                    value: Balance::from(value.low_u128()),
+                });
+                EnergyFee::dispatch_info_to_fee(&call, None, None).into_inner().into()
+            },
+            _ => GetConstantEnergyFee::get().into(),
+        };
+    };
+
+    let gas_limit = gas.map_or(EnergyFee::block_gas_limit(), |gas| {
+        gas.min(EnergyFee::block_gas_limit().into()).low_u64()
+    });
+    let access_list = access_list
+        .unwrap_or_default()
+        .into_iter()
+        .map(|item| (item.address, item.storage_keys))
+        .collect();
+    let mut config = <Runtime as pallet_evm::Config>::config().clone();
+    config.estimate = true;
+
+    // `NacRunner` reports the fee as the used gas, the stack runner the executed gas
+    let dry_run = pallet_evm::runner::stack::Runner::<Runtime>::call(
+        from.unwrap_or_default(),
+        to.unwrap_or_default(),
+        data.into_inner(),
+        value.unwrap_or_default(),
+        gas_limit,
+        max_fee_per_gas,
+        max_priority_fee_per_gas,
+        nonce,
+        access_list,
+        false,
+        false,
+        None,
+        None,
+        &config,
+    );
+    match dry_run {
+        Ok(info) => EnergyFee::buffered_gas(info.used_gas.standard.low_u64()).into(),
+        Err(_) => gas_limit.into(),
+    }
+}
+
 // We implement CusomFee here since the RuntimeCall defined in construct_runtime! macro
 impl CustomFee<RuntimeCall, DispatchInfoOf<RuntimeCall>, Balance, GetConstantEnergyFee>
     for EnergyFee
@@ -1105,30 +2077,6 @@ impl CustomFee<RuntimeCall, DispatchInfoOf<RuntimeCall>, Balance, GetConstantEne
         calculated_fee: Option<Balance>,
     ) -> CallFee<Balance> {
         match runtime_call {
-            RuntimeCall::Assets(..)
-            | RuntimeCall::AssetRate(..)
-            | RuntimeCall::Auctions(..)
-            | RuntimeCall::Balances(..)
-            | RuntimeCall::Bounties(..)
-            | RuntimeCall::EnergyGeneration(..)
-            | RuntimeCall::EnergyBroker(..)
-            | RuntimeCall::Nfts(..)
-            | RuntimeCall::AtomicSwap(..)
-            | RuntimeCall::Claiming(..)
-            | RuntimeCall::Vesting(..)
-            | RuntimeCall::NacManaging(..)
-            | RuntimeCall::Privileges(..)
-            | RuntimeCall::Council(..)
-            | RuntimeCall::TechnicalCommittee(..)
-            | RuntimeCall::TechnicalMembership(..)
-            | RuntimeCall::Treasury(..)
-            | RuntimeCall::Democracy(..)
-            | RuntimeCall::Elections(..)
-            | RuntimeCall::Session(..)
-            | RuntimeCall::XcmPallet(..)
-            | RuntimeCall::SimpleVesting(..)
-            | RuntimeCall::Reputation(..) => CallFee::Regular(Self::custom_fee()),
-            RuntimeCall::EVM(..) | RuntimeCall::Ethereum(..) => CallFee::EVM(Self::ethereum_fee()),
             RuntimeCall::Utility(pallet_utility::Call::batch { calls })
             | RuntimeCall::Utility(pallet_utility::Call::batch_all { calls })
             | RuntimeCall::Utility(pallet_utility::Call::force_batch { calls }) => {
@@ -1142,12 +2090,23 @@ impl CustomFee<RuntimeCall, DispatchInfoOf<RuntimeCall>, Balance, GetConstantEne
                     .max(Self::custom_fee());
                 CallFee::Regular(resulting_fee)
             },
+            // Only a top-level EVM call has its fee withdrawn from the signer by the EVM runner.
+            // Wrapped calls are charged upfront, at least like a batch, so that a call failing
+            // on behalf of an unfunded derivative account isn't free. The runner then doesn't
+            // charge the wrapped EVM calls again, see `wraps_evm_calls`.
             RuntimeCall::Utility(pallet_utility::Call::dispatch_as { call, .. })
             | RuntimeCall::Utility(pallet_utility::Call::as_derivative { call, .. }) => {
-                Self::dispatch_info_to_fee(call, None, calculated_fee)
+                let fee = Self::dispatch_info_to_fee(call, None, calculated_fee).into_inner();
+                CallFee::Regular(fee.max(Self::custom_fee()))
+            },
+            _ => match fee_class(runtime_call) {
+                FeeClass::Constant => CallFee::Regular(Self::custom_fee()),
+                FeeClass::EVM => CallFee::EVM(Self::ethereum_fee()),
+                FeeClass::Zero => CallFee::Regular(0),
+                FeeClass::Weight | FeeClass::Nested => {
+                    CallFee::Regular(Self::weight_fee(runtime_call, dispatch_info, calculated_fee))
+                },
             },
-            RuntimeCall::Sudo(..) => CallFee::Regular(0),
-            _ => CallFee::Regular(Self::weight_fee(runtime_call, dispatch_info, calculated_fee)),
         }
     }
 
@@ -1156,6 +2115,23 @@ impl CustomFee<RuntimeCall, DispatchInfoOf<RuntimeCall>, Balance, GetConstantEne
         next_multiplier.saturating_mul_int(EnergyFee::base_fee())
     }
 
+    fn wraps_evm_calls(runtime_call: &RuntimeCall) -> bool {
+        let is_or_wraps_evm_call =
+            |call: &RuntimeCall| fee_class(call) == FeeClass::EVM || Self::wraps_evm_calls(call);
+        match runtime_call {
+            RuntimeCall::Utility(pallet_utility::Call::batch { calls })
+            | RuntimeCall::Utility(pallet_utility::Call::batch_all { calls })
+            | RuntimeCall::Utility(pallet_utility::Call::force_batch { calls }) => {
+                calls.iter().any(is_or_wraps_evm_call)
+            },
+            RuntimeCall::Utility(pallet_utility::Call::dispatch_as { call, .. })
+            | RuntimeCall::Utility(pallet_utility::Call::as_derivative { call, .. }) => {
+                is_or_wraps_evm_call(call)
+            },
+            _ => false,
+        }
+    }
+
     fn weight_fee(
         runtime_call: &RuntimeCall,
         dispatch_info: Option<&DispatchInfoOf<RuntimeCall>>,
@@ -1208,12 +2184,19 @@ const BLOCK_GAS_LIMIT: u64 = 75_000_000;
 const MAX_POV_SIZE: u64 = 5 * 1024 * 1024;
 
 parameter_types! {
-    pub BlockGasLimit: U256 = U256::from(BLOCK_GAS_LIMIT);
-    pub const GasLimitPovSizeRatio: u64 = BLOCK_GAS_LIMIT.saturating_div(MAX_POV_SIZE);
+    pub const DefaultBlockGasLimit: u64 = BLOCK_GAS_LIMIT;
+    pub const MinBlockGasLimit: u64 = BLOCK_GAS_LIMIT / 5;
+    pub const MaxBlockGasLimit: u64 = BLOCK_GAS_LIMIT * 2;
+    // The values below follow the governance-adjustable gas limit, so the gas to weight mapping
+    // always spans the same share of `MAXIMUM_BLOCK_WEIGHT`.
+    pub BlockGasLimit: U256 = U256::from(EnergyFee::block_gas_limit());
+    pub GasLimitPovSizeRatio: u64 = EnergyFee::block_gas_limit().saturating_div(MAX_POV_SIZE);
+    /// Largest proof size an Ethereum transaction's gas limit may imply to enter the pool.
+    pub const MaxEvmProofSize: u64 = MAX_POV_SIZE;
     pub PrecompilesValue: VitreusPrecompiles<Runtime> = VitreusPrecompiles::<_>::new();
     pub WeightPerGas: Weight =
         Weight::from_parts(weight_per_gas(
-                BLOCK_GAS_LIMIT, NORMAL_DISPATCH_RATIO, WEIGHT_MILLISECS_PER_BLOCK
+                EnergyFee::block_gas_limit(), NORMAL_DISPATCH_RATIO, WEIGHT_MILLISECS_PER_BLOCK
                 ),
             0,
         );
@@ -1413,10 +2396,40 @@ impl parachains_session_info::Config for Runtime {
     type ValidatorSet = Historical;
 }
 
-/// Special `RewardValidators` that does nothing ;)
+parameter_types! {
+    // parachains whose candidates are backed by fewer validators than this are under-subscribed
+    pub const UnderSubscribedBackingCount: u32 = 5;
+}
+
+/// Boosts the energy reward of validators that backed a candidate of an under-subscribed
+/// parachain, i.e. a candidate backed by fewer than `UnderSubscribedBackingCount` validators.
+/// Availability bitfields aren't rewarded.
 pub struct RewardValidators;
 impl parachains_inclusion::RewardValidators for RewardValidators {
-    fn reward_backing(_: impl IntoIterator<Item = ValidatorIndex>) {}
+    // Called with the backers of each enacted candidate, so they are the parachain's backings.
+    fn reward_backing(validators: impl IntoIterator<Item = ValidatorIndex>) {
+        let backers: Vec<_> = validators.into_iter().collect();
+        if backers.len() as u32 >= UnderSubscribedBackingCount::get() {
+            return;
+        }
+
+        // the active validator indices, the session validators and the active era are read,
+        // and the boost of every backer is written
+        frame_system::Pallet::<Runtime>::register_extra_weight_unchecked(
+            <Runtime as frame_system::Config>::DbWeight::get()
+                .reads_writes(3, backers.len() as u64),
+            frame_support::dispatch::DispatchClass::Mandatory,
+        );
+        // backing indices point into the active parachain validators of the session
+        let active = parachains_shared::ActiveValidatorIndices::<Runtime>::get();
+        let session_validators = Session::validators();
+        let boosted = backers
+            .into_iter()
+            .filter_map(|index| active.get(index.0 as usize))
+            .filter_map(|index| session_validators.get(index.0 as usize).cloned());
+        EnergyGeneration::note_backing_boost(boosted);
+    }
+
     fn reward_bitfields(_: impl IntoIterator<Item = ValidatorIndex>) {}
 }
 
@@ -1534,10 +2547,56 @@ impl parachains_initializer::Config for Runtime {
     type WeightInfo = weights::runtime_parachains_initializer::WeightInfo<Runtime>;
 }
 
+type DisputeSlashing = parachains_slashing::SlashValidatorsForDisputes<ParasSlashing>;
+
+/// Slashes the losers of disputes and keeps the open disputes of `EnergyFee` up to date, so the
+/// dispute surcharge doesn't have to look through every dispute.
+pub struct TrackOpenDisputes;
+
+impl parachains_disputes::SlashingHandler<BlockNumber> for TrackOpenDisputes {
+    fn punish_for_invalid(
+        session: SessionIndex,
+        candidate_hash: CandidateHash,
+        losers: impl IntoIterator<Item = ValidatorIndex>,
+        backers: impl IntoIterator<Item = ValidatorIndex>,
+    ) {
+        EnergyFee::note_open_dispute(session, candidate_hash.0);
+        DisputeSlashing::punish_for_invalid(session, candidate_hash, losers, backers)
+    }
+
+    fn punish_against_valid(
+        session: SessionIndex,
+        candidate_hash: CandidateHash,
+        losers: impl IntoIterator<Item = ValidatorIndex>,
+        backers: impl IntoIterator<Item = ValidatorIndex>,
+    ) {
+        EnergyFee::note_open_dispute(session, candidate_hash.0);
+        DisputeSlashing::punish_against_valid(session, candidate_hash, losers, backers)
+    }
+
+    fn initializer_initialize(now: BlockNumber) -> Weight {
+        DisputeSlashing::initializer_initialize(now)
+            .saturating_add(EnergyFee::prune_open_disputes_weight(1))
+    }
+
+    // Disputes statements are imported with the inherent, they are all known by now.
+    fn initializer_finalize() {
+        DisputeSlashing::initializer_finalize();
+        EnergyFee::prune_open_disputes(|session, candidate| {
+            parachains_disputes::Disputes::<Runtime>::get(session, CandidateHash(*candidate))
+                .is_some_and(|dispute| dispute.concluded_at.is_none())
+        });
+    }
+
+    fn initializer_on_new_session(session_index: SessionIndex) {
+        DisputeSlashing::initializer_on_new_session(session_index)
+    }
+}
+
 impl parachains_disputes::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type RewardValidators = ();
-    type SlashingHandler = parachains_slashing::SlashValidatorsForDisputes<ParasSlashing>;
+    type SlashingHandler = TrackOpenDisputes;
     type WeightInfo = weights::runtime_parachains_disputes::WeightInfo<Runtime>;
 }
 
@@ -1757,8 +2816,10 @@ pub type SignedExtra = (
     frame_system::CheckEra<Runtime>,
     frame_system::CheckNonce<Runtime>,
     frame_system::CheckWeight<Runtime>,
-    pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+    // Comes before `ChargeTransactionPayment` to prepare the fee payment from the preferred
+    // fee sources of the transaction
     pallet_energy_fee::CheckEnergyFee<Runtime>,
+    pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic =
@@ -1814,8 +2875,65 @@ fn transact_with_new_gas_limit(
     }
 }
 
-// user doesn't have NAC to dispatch transaction
-const ACCESS_RESTRICTED: u8 = u8::MAX;
+/// Custom validity error: the user doesn't have NAC to dispatch the transaction.
+pub const ACCESS_RESTRICTED: u8 = u8::MAX;
+/// Custom validity error: the user lacks the VTRS to exchange for the missing VNRG of the fee.
+pub const INSUFFICIENT_VTRS_FOR_FEE: u8 = u8::MAX - 1;
+/// Custom validity error: the gas limit of the transaction implies a proof size above
+/// `MaxEvmProofSize`.
+pub const PROOF_SIZE_EXCEEDED: u8 = u8::MAX - 2;
+
+/// Proof size `pallet_evm` reserves for an Ethereum transaction.
+///
+/// Derived from the declared gas limit with the runner's own gas to weight mapping, so the
+/// integer `GasLimitPovSizeRatio` rounding is the same as at dispatch.
+pub fn evm_proof_size(call: &pallet_ethereum::Call<Runtime>) -> Option<u64> {
+    let transact { transaction } = call else {
+        return None;
+    };
+    let gas_limit = match transaction {
+        EthereumTransaction::Legacy(tx) => tx.gas_limit,
+        EthereumTransaction::EIP1559(tx) => tx.gas_limit,
+        EthereumTransaction::EIP2930(tx) => tx.gas_limit,
+    };
+    let gas_limit = u64::try_from(gas_limit).unwrap_or(u64::MAX);
+
+    Some(
+        <Runtime as pallet_evm::Config>::GasWeightMapping::gas_to_weight(gas_limit, true)
+            .proof_size(),
+    )
+}
+
+/// NAC level required to dispatch an Ethereum transaction, including the level required by the
+/// called contract.
+fn evm_access_level(call: &pallet_ethereum::Call<Runtime>) -> u8 {
+    let transact { transaction } = call else {
+        return helpers::runner::CALL_ACCESS_LEVEL;
+    };
+    let action = match transaction {
+        EthereumTransaction::Legacy(tx) => tx.action,
+        EthereumTransaction::EIP1559(tx) => tx.action,
+        EthereumTransaction::EIP2930(tx) => tx.action,
+    };
+    match action {
+        TransactionAction::Call(target) => NacManaging::required_access_level(
+            &AccountId::from(target),
+            helpers::runner::CALL_ACCESS_LEVEL,
+        ),
+        TransactionAction::Create => helpers::runner::CALL_ACCESS_LEVEL,
+    }
+}
+
+fn ensure_evm_proof_size_within_budget(
+    call: &pallet_ethereum::Call<Runtime>,
+) -> Result<(), TransactionValidityError> {
+    match evm_proof_size(call) {
+        Some(proof_size) if proof_size > MaxEvmProofSize::get() => {
+            Err(InvalidTransaction::Custom(PROOF_SIZE_EXCEEDED).into())
+        },
+        _ => Ok(()),
+    }
+}
 
 impl fp_self_contained::SelfContainedCall for RuntimeCall {
     type SignedInfo = H160;
@@ -1843,6 +2961,10 @@ impl fp_self_contained::SelfContainedCall for RuntimeCall {
     ) -> Option<TransactionValidity> {
         match self {
             RuntimeCall::Ethereum(call) => {
+                if let Err(e) = ensure_evm_proof_size_within_budget(call) {
+                    return Some(Err(e));
+                }
+
                 let account_id =
                     <Runtime as pallet_evm::Config>::AddressMapping::into_account_id(*info);
 
@@ -1863,11 +2985,15 @@ impl fp_self_contained::SelfContainedCall for RuntimeCall {
                     );
 
                     if fee_vtrs_amount > vtrs_balance {
-                        return Some(Err(InvalidTransaction::Payment.into()));
+                        return Some(Err(
+                            InvalidTransaction::Custom(INSUFFICIENT_VTRS_FOR_FEE).into()
+                        ));
                     }
                 }
 
-                if !NacManaging::user_has_access(account_id, helpers::runner::CALL_ACCESS_LEVEL) {
+                if !NacManaging::is_access_exempt(&account_id)
+                    && !NacManaging::user_has_access(account_id, evm_access_level(call))
+                {
                     return Some(Err(InvalidTransaction::Custom(ACCESS_RESTRICTED).into()));
                 };
 
@@ -1889,8 +3015,14 @@ impl fp_self_contained::SelfContainedCall for RuntimeCall {
         len: usize,
     ) -> Option<Result<(), TransactionValidityError>> {
         match self {
-            RuntimeCall::Ethereum(call) => transact_with_new_gas_limit(call.clone())
-                .pre_dispatch_self_contained(info, dispatch_info, len),
+            RuntimeCall::Ethereum(call) => {
+                if let Err(e) = ensure_evm_proof_size_within_budget(call) {
+                    return Some(Err(e));
+                }
+
+                transact_with_new_gas_limit(call.clone())
+                    .pre_dispatch_self_contained(info, dispatch_info, len)
+            },
             _ => None,
         }
     }
@@ -1900,10 +3032,16 @@ impl fp_self_contained::SelfContainedCall for RuntimeCall {
         info: Self::SignedInfo,
     ) -> Option<sp_runtime::DispatchResultWithInfo<PostDispatchInfoOf<Self>>> {
         match self {
-            call @ RuntimeCall::Ethereum(pallet_ethereum::Call::transact { .. }) => {
-                Some(call.dispatch(RuntimeOrigin::from(
+            RuntimeCall::Ethereum(pallet_ethereum::Call::transact { transaction }) => {
+                let tx_hash = transaction.hash();
+                let call = RuntimeCall::Ethereum(pallet_ethereum::Call::transact { transaction });
+                // The fee of this transaction is recorded by the EVM fee withdrawal.
+                pallet_energy_fee::PendingEvmFee::<Runtime>::kill();
+                let result = call.dispatch(RuntimeOrigin::from(
                     pallet_ethereum::RawOrigin::EthereumTransaction(info),
-                )))
+                ));
+                EnergyFee::note_evm_fee_receipt(tx_hash);
+                Some(result)
             },
             _ => None,
         }
@@ -1916,7 +3054,10 @@ extern crate frame_benchmarking;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benches {
-    define_benchmarks!([pallet_evm, EVM]);
+    define_benchmarks!(
+        [pallet_evm, EVM]
+        [pallet_energy_fee, EnergyFee]
+    );
 }
 
 impl_runtime_apis! {
@@ -2492,77 +3633,96 @@ impl_runtime_apis! {
         }
     }
 
-    impl energy_fee_runtime_api::EnergyFeeApi<Block, AccountId, Balance, RuntimeCall> for Runtime {
+    impl energy_fee_runtime_api::EnergyFeeApi<Block, AccountId, Balance, RuntimeCall, AssetId>
+        for Runtime
+    {
         fn estimate_gas(request: CallRequest) -> U256 {
-            let CallRequest {
-                from,
-                to,
-                max_fee_per_gas,
-                max_priority_fee_per_gas,
-                gas,
-                value,
-                data,
-                nonce,
-                access_list,
-                ..
-            } = request;
-            let call = match data {
-                Some(data) => {
-                    let from = from.unwrap_or_default();
-                    let to = to.unwrap_or_default();
-                    let value = value.unwrap_or_else(U256::zero);
-                    let gas_limit = gas.unwrap_or_else(|| U256::from(21000)).low_u64(); // default gas limit to 21000
-                    let max_fee_per_gas = max_fee_per_gas.unwrap_or_else(U256::zero);
-                    let access_list = access_list.unwrap_or_default();
-                    let access_list_converted = access_list.into_iter()
-                        .map(|item| (item.address, item.storage_keys))
-                        .collect();
-
-                    RuntimeCall::EVM(pallet_evm::Call::call {
-                        source: from,
-                        target: to,
-                        input: data.into_inner(),
-                        value,
-                        gas_limit,
-                        max_fee_per_gas,
-                        max_priority_fee_per_gas,
-                        nonce,
-                        access_list: access_list_converted,
-                    })
-                },
-                None => {
-                    match (from, to, value) {
-                        (_, Some(to), Some(value)) => {
-                            let value_converted = Balance::from(value.low_u128());  // Adjust this conversion as necessary
-
-                            RuntimeCall::Balances(pallet_balances::Call::transfer_allow_death {
-                                dest: to.into(),
-                                value: value_converted,
-                            })
-                        },
-                        _ => return GetConstantEnergyFee::get().into(),
-                    }
-                }
-            };
-
-            EnergyFee::dispatch_info_to_fee(&call, None, None).into_inner().into()
+            estimate_gas(request)
         }
 
         fn estimate_call_fee(account: AccountId, call: RuntimeCall) -> Option<energy_fee_runtime_api::FeeDetails<Balance>> {
             let fee = EnergyFee::dispatch_info_to_fee(&call, None, None).into_inner();
+            let fee = EnergyFee::charged_fee(&account, &call, fee);
             EnergyFee::calculate_fee_parts(&account, fee).map(|fees| energy_fee_runtime_api::FeeDetails {
                 vtrs: fees.1,
                 vnrg: fees.0,
             }).ok()
         }
 
+        fn fee_range(call: RuntimeCall) -> (Balance, Balance) {
+            EnergyFee::fee_range(|| EnergyFee::dispatch_info_to_fee(&call, None, None).into_inner())
+        }
+
+        fn can_afford_batch(who: AccountId, calls: Vec<Vec<u8>>) -> bool {
+            can_afford_batch(&who, calls)
+        }
+
+        fn validator_operating_cost() -> Balance {
+            validator_operating_cost()
+        }
+
+        fn simulate_fee_change(
+            new_base: Balance,
+            new_multiplier: FixedU128,
+            sample_calls: Vec<RuntimeCall>,
+        ) -> Vec<Balance> {
+            EnergyFee::with_fee_parameters(new_base, new_multiplier, || {
+                sample_calls
+                    .iter()
+                    .map(|call| EnergyFee::dispatch_info_to_fee(call, None, None).into_inner())
+                    .collect()
+            })
+        }
+
+        fn multiplier_at(block: BlockNumber) -> Option<FixedU128> {
+            EnergyFee::multiplier_at(block)
+        }
+
+        fn fee_classification() -> Vec<(u8, Vec<u8>, FeeClass)> {
+            fee_classification()
+        }
+
+        fn rate_divergence() -> Option<RateDivergence> {
+            rate_divergence()
+        }
+
+        fn fee_payable_assets() -> Vec<(AssetId, FixedU128, bool)> {
+            fee_payable_assets()
+        }
+
+        fn evm_fee_paid(tx_hash: H256) -> Option<Balance> {
+            EnergyFee::evm_fee_paid(tx_hash)
+        }
+
+        fn evm_fee_receipt(tx_hash: H256) -> Option<(BlockNumber, Balance)> {
+            EnergyFee::evm_fee_receipt(tx_hash)
+        }
+
+        fn extrinsic_fee(uxt: <Block as BlockT>::Extrinsic) -> Option<(AccountId, Balance)> {
+            extrinsic_fee(uxt)
+        }
+
+        fn evm_fee_floor() -> Balance {
+            EnergyFee::evm_fee_floor()
+        }
+
+        fn fee_payment_path(
+            block: BlockNumber,
+            index: u32,
+        ) -> Option<FeePaymentPath<AssetId, Balance>> {
+            fee_payment_path(block, index)
+        }
+
+        fn fee_spend_history(who: AccountId) -> Vec<(u32, Balance)> {
+            EnergyFee::fee_spend_history(&who)
+        }
+
         fn vtrs_to_vnrg_swap_rate() -> Option<u128> {
-            EnergyBroker::quote_price_exact_tokens_for_tokens(
-                NativeOrAssetId::Native,
-                NativeOrAssetId::Asset(VNRG::get()),
-                UNITS,
-                true
-            )
+            BrokerEnergyPrice::get()
+        }
+
+        fn energy_price_history(samples: u32, interval_blocks: u32) -> Vec<(BlockNumber, Balance)> {
+            EnergyFee::energy_price_history(samples, interval_blocks)
         }
     }
 
@@ -2640,6 +3800,65 @@ impl_runtime_apis! {
             let account_id = <Self as pallet_evm::Config>::AddressMapping::into_account_id(who);
             Balances::reducible_balance(&account_id, Preservation::Preserve, Fortitude::Polite).into()
         }
+
+        fn account_name(who: H160) -> Option<Vec<u8>> {
+            let account_id = <Self as pallet_evm::Config>::AddressMapping::into_account_id(who);
+            NacManaging::account_name(account_id).map(|(name, _)| name.into_inner())
+        }
+
+        fn account_by_name(name: Vec<u8>) -> Option<H160> {
+            let name = pallet_nac_managing::AccountNameOf::<Runtime>::try_from(name).ok()?;
+            NacManaging::account_by_name(name).map(Into::into)
+        }
+
+        fn tier_of(who: H160) -> Option<ReputationTier> {
+            let account_id = <Self as pallet_evm::Config>::AddressMapping::into_account_id(who);
+            Reputation::tier_of(&account_id)
+        }
+
+        fn dynamic_vnrg_params() -> DynamicVnrgParams {
+            dynamic_vnrg_params()
+        }
+
+        fn account_schedule(who: H160) -> Vec<ScheduledEvent> {
+            let account_id = <Self as pallet_evm::Config>::AddressMapping::into_account_id(who);
+            account_schedule(&account_id)
+        }
+
+        fn runtime_parameters() -> Vec<(ParamName, ParamValue)> {
+            runtime_parameters()
+        }
+
+        fn contract_required_nac(target: H160) -> u8 {
+            let account_id = <Self as pallet_evm::Config>::AddressMapping::into_account_id(target);
+            NacManaging::required_access_level(&account_id, helpers::runner::CALL_ACCESS_LEVEL)
+        }
+
+        fn map_addresses(input: AddressInput) -> Result<AddressPair, AddressError> {
+            helpers::address::map_addresses(input)
+        }
+
+        fn permitted_calls(who: H160) -> Vec<CallClass> {
+            let account_id = <Self as pallet_evm::Config>::AddressMapping::into_account_id(who);
+            permitted_calls(&account_id)
+        }
+
+        fn genesis_energy_config() -> Option<GenesisEnergyConfig> {
+            genesis_energy_config()
+        }
+
+        fn account_balance_sheet(who: H160) -> BalanceSheet {
+            let account_id = <Self as pallet_evm::Config>::AddressMapping::into_account_id(who);
+            account_balance_sheet(&account_id)
+        }
+
+        fn active_swaps(start_after: Option<(H160, [u8; 32])>, limit: u32) -> Vec<SwapInfo> {
+            let start_after = start_after.map(|(target, hashed_proof)| {
+                let target = <Self as pallet_evm::Config>::AddressMapping::into_account_id(target);
+                (target, hashed_proof)
+            });
+            active_swaps(start_after, limit)
+        }
     }
 
     impl sp_genesis_builder::GenesisBuilder<Block> for Runtime {
@@ -2657,9 +3876,9 @@ impl_runtime_apis! {
     }
 
 
-    impl energy_generation_runtime_api::EnergyGenerationApi<Block> for Runtime {
+    impl energy_generation_runtime_api::EnergyGenerationApi<Block, AccountId> for Runtime {
         fn reputation_tier_additional_reward(tier: ReputationTier) -> Perbill {
-            ReputationTierEnergyRewardAdditionalPercentMapping::convert(&tier)
+            EnergyGeneration::tier_energy_bonus(&tier)
         }
 
         fn current_energy_per_stake_currency() -> u128 {
@@ -2667,6 +3886,65 @@ impl_runtime_apis! {
                 .and_then(|era| EnergyGeneration::eras_energy_per_stake_cur(era.index))
                 .unwrap_or(0)
         }
+
+        fn era_issuance_report(era: u32) -> Option<IssuanceReport<u128, u128>> {
+            EnergyGeneration::era_issuance_report(era)
+        }
+
+        fn reward_curve_points() -> Vec<(Perbill, Perbill)> {
+            RewardCurve::get().points.to_vec()
+        }
+
+        fn validator_uptime(who: AccountId, era: u32) -> Option<Perbill> {
+            EnergyGeneration::validator_uptime(&who, era)
+        }
+
+        fn net_energy_inflation() -> i128 {
+            EnergyGeneration::net_energy_inflation()
+        }
+
+        fn reward_breakdown(validator: AccountId) -> RewardBreakdown<u128> {
+            EnergyGeneration::reward_breakdown(&validator)
+        }
+
+        fn stake_for_target_reward(validator: AccountId, target_energy_per_era: u128) -> u128 {
+            EnergyGeneration::stake_for_target_reward(&validator, target_energy_per_era)
+        }
+
+        fn validator_stats(who: AccountId) -> Option<ValidatorStats<u128, u128>> {
+            EnergyGeneration::validator_stats(&who)
+        }
+
+        fn energy_per_reputation_point() -> u128 {
+            EnergyGeneration::energy_per_reputation_point()
+        }
+
+        fn check_election_solution(
+            validators: Vec<AccountId>,
+        ) -> Result<ElectionScore<u128>, ElectionSolutionError> {
+            EnergyGeneration::check_election_solution(validators)
+        }
+
+        fn staking_overview() -> StakingOverview<u128> {
+            EnergyGeneration::staking_overview()
+        }
+
+        fn min_validator_bond(who: AccountId) -> u128 {
+            EnergyGeneration::min_bond_for_validator(&who)
+        }
+
+        fn network_energy_apy() -> Perbill {
+            network_energy_apy()
+        }
+    }
+
+    impl privileges_runtime_api::PrivilegesApi<Block, AccountId, Balance> for Runtime {
+        fn vip_after_stake_change(
+            who: AccountId,
+            new_stake: Balance,
+        ) -> Option<pallet_privileges::VipInfo<Balance>> {
+            Privileges::vip_after_stake_change(&who, new_stake)
+        }
     }
 
     #[api_version(11)]
@@ -19,4 +19,4 @@ pub type V0200 = (
     polkadot_runtime_common::paras_registrar::migration::MigrateToV1<Runtime, ()>,
 );
 
-pub type Unreleased = ();
+pub type Unreleased = (pallet_energy_generation::migrations::v16::MigrateV15ToV16<Runtime>,);
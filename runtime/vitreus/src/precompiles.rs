@@ -1,13 +1,35 @@
+use fp_evm::{ExitRevert, ExitSucceed, PrecompileFailure, PrecompileOutput};
+use frame_support::traits::{
+    fungible::Inspect,
+    tokens::{Fortitude, Preservation},
+};
+use pallet_energy_broker::NativeOrAssetId;
 use pallet_evm::{
-    IsPrecompileResult, Precompile, PrecompileHandle, PrecompileResult, PrecompileSet,
+    AddressMapping, IsPrecompileResult, Precompile, PrecompileHandle, PrecompileResult,
+    PrecompileSet,
 };
-use sp_core::H160;
-use sp_std::marker::PhantomData;
+use parity_scale_codec::DecodeLimit;
+use sp_core::{H160, U256};
+use sp_std::{marker::PhantomData, vec::Vec};
 
 use pallet_evm_precompile_modexp::Modexp;
 use pallet_evm_precompile_sha3fips::Sha3FIPS256;
 use pallet_evm_precompile_simple::{ECRecover, ECRecoverPublicKey, Identity, Ripemd160, Sha256};
 
+use crate::{
+    Balance, Balances, EnergyBroker, EnergyFee, Runtime, RuntimeCall, RuntimeOrigin,
+    TransactionPayment, Vesting, VNRG,
+};
+
+/// Address of the [`FeeEnvironment`] precompile.
+pub const FEE_ENVIRONMENT_ADDRESS: u64 = 2048;
+/// Address of the [`FeeAffordability`] precompile.
+pub const FEE_AFFORDABILITY_ADDRESS: u64 = 2049;
+/// Address of the [`VestingClaim`] precompile.
+pub const VESTING_CLAIM_ADDRESS: u64 = 2050;
+/// Address of the [`EnergyBrokerQuote`] precompile.
+pub const ENERGY_BROKER_QUOTE_ADDRESS: u64 = 2051;
+
 pub struct VitreusPrecompiles<R>(PhantomData<R>);
 
 impl<R> VitreusPrecompiles<R>
@@ -17,8 +39,20 @@ where
     pub fn new() -> Self {
         Self(Default::default())
     }
-    pub fn used_addresses() -> [H160; 7] {
-        [hash(1), hash(2), hash(3), hash(4), hash(5), hash(1024), hash(1025)]
+    pub fn used_addresses() -> [H160; 11] {
+        [
+            hash(1),
+            hash(2),
+            hash(3),
+            hash(4),
+            hash(5),
+            hash(1024),
+            hash(1025),
+            hash(FEE_ENVIRONMENT_ADDRESS),
+            hash(FEE_AFFORDABILITY_ADDRESS),
+            hash(VESTING_CLAIM_ADDRESS),
+            hash(ENERGY_BROKER_QUOTE_ADDRESS),
+        ]
     }
 }
 impl<R> PrecompileSet for VitreusPrecompiles<R>
@@ -36,6 +70,11 @@ where
             // Non-Frontier specific nor Ethereum precompiles :
             a if a == hash(1024) => Some(Sha3FIPS256::execute(handle)),
             a if a == hash(1025) => Some(ECRecoverPublicKey::execute(handle)),
+            // Vitreus specific precompiles :
+            a if a == hash(FEE_ENVIRONMENT_ADDRESS) => Some(FeeEnvironment::execute(handle)),
+            a if a == hash(FEE_AFFORDABILITY_ADDRESS) => Some(FeeAffordability::execute(handle)),
+            a if a == hash(VESTING_CLAIM_ADDRESS) => Some(VestingClaim::execute(handle)),
+            a if a == hash(ENERGY_BROKER_QUOTE_ADDRESS) => Some(EnergyBrokerQuote::execute(handle)),
             _ => None,
         }
     }
@@ -48,6 +87,246 @@ where
     }
 }
 
+/// Read-only access to the substrate fee environment.
+///
+/// `feeEnvironment() returns (uint256 baseFee, uint256 multiplierWad)`, where `baseFee` is the
+/// current `EnergyFee::base_fee()` in VNRG and `multiplierWad` is the fee multiplier applied in
+/// this block scaled by `1e18`, i.e. `1e18` stands for a multiplier of one.
+pub struct FeeEnvironment;
+
+impl FeeEnvironment {
+    /// `bytes4(keccak256("feeEnvironment()"))`
+    pub const FEE_ENVIRONMENT_SELECTOR: [u8; 4] = [0x6e, 0x35, 0x12, 0xc5];
+    /// Two storage reads priced as cold `SLOAD`s.
+    pub const GAS_COST: u64 = 4_200;
+}
+
+impl Precompile for FeeEnvironment {
+    fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+        handle.record_cost(Self::GAS_COST)?;
+
+        match selector(handle.input())? {
+            Self::FEE_ENVIRONMENT_SELECTOR => {
+                let base_fee = U256::from(EnergyFee::base_fee());
+                // `Multiplier` has 18 decimals, so its inner value is already scaled by 1e18.
+                let multiplier = U256::from(TransactionPayment::next_fee_multiplier().into_inner());
+                Ok(succeed(encode_words(&[base_fee, multiplier])))
+            },
+            _ => Err(revert("unknown selector")),
+        }
+    }
+}
+
+/// Fee affordability check for contracts that submit native calls on behalf of users.
+///
+/// `canAffordFee(address who, bytes call) returns (bool)`, where `call` is a SCALE encoded
+/// `RuntimeCall`. Tells whether `who` could pay the fee of `call` right now, exchanging VTRS for
+/// missing VNRG like fee payment does. Reverts if `call` can't be decoded.
+pub struct FeeAffordability;
+
+impl FeeAffordability {
+    /// `bytes4(keccak256("canAffordFee(address,bytes)"))`
+    pub const CAN_AFFORD_FEE_SELECTOR: [u8; 4] = [0xa4, 0xfc, 0x62, 0xa3];
+    /// Fee parameter, balance and pool reads priced as five cold `SLOAD`s.
+    pub const GAS_COST: u64 = 10_500;
+    /// Decoding cost per 32-byte word of the encoded call, priced as copying memory.
+    pub const GAS_COST_PER_WORD: u64 = 3;
+}
+
+impl Precompile for FeeAffordability {
+    fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+        handle.record_cost(Self::GAS_COST)?;
+
+        match selector(handle.input())? {
+            Self::CAN_AFFORD_FEE_SELECTOR => {
+                let args = &handle.input()[4..];
+                let who = read_address(args, 0)?;
+                let encoded_call = read_bytes(args, 1)?.to_vec();
+                handle.record_cost(
+                    Self::GAS_COST_PER_WORD
+                        .saturating_mul((encoded_call.len() as u64).div_ceil(32)),
+                )?;
+
+                let call = RuntimeCall::decode_with_depth_limit(
+                    sp_api::MAX_EXTRINSIC_DEPTH,
+                    &mut &encoded_call[..],
+                )
+                .map_err(|_| revert("invalid call"))?;
+                let who = <Runtime as pallet_evm::Config>::AddressMapping::into_account_id(who);
+
+                let affordable = EnergyFee::can_afford_fee(&who, &call);
+                Ok(succeed(encode_words(&[U256::from(affordable as u8)])))
+            },
+            _ => Err(revert("unknown selector")),
+        }
+    }
+}
+
+/// Vesting release for EVM accounts which received vested VTRS.
+///
+/// `claimVested() returns (uint256 released)` unlocks what the caller's vesting schedules have
+/// vested so far, like `pallet_vesting::vest`, and returns the VTRS that became liquid. It
+/// returns `0` for callers without vesting schedules, and reverts in a static call.
+pub struct VestingClaim;
+
+impl VestingClaim {
+    /// `bytes4(keccak256("claimVested()"))`
+    pub const CLAIM_VESTED_SELECTOR: [u8; 4] = [0xd0, 0x1c, 0x44, 0xcd];
+    /// Schedule, lock and balance accesses priced as two cold `SLOAD`s and two `SSTORE`s.
+    pub const GAS_COST: u64 = 44_200;
+}
+
+impl Precompile for VestingClaim {
+    fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+        handle.record_cost(Self::GAS_COST)?;
+
+        match selector(handle.input())? {
+            Self::CLAIM_VESTED_SELECTOR => {
+                if handle.is_static() {
+                    return Err(revert("can't claim in a static call"));
+                }
+                let who = <Runtime as pallet_evm::Config>::AddressMapping::into_account_id(
+                    handle.context().caller,
+                );
+                let liquid = || {
+                    Balances::reducible_balance(&who, Preservation::Expendable, Fortitude::Polite)
+                };
+
+                let liquid_before = liquid();
+                if pallet_vesting::Vesting::<Runtime>::contains_key(who) {
+                    Vesting::vest(RuntimeOrigin::signed(who))
+                        .map_err(|_| revert("vesting failed"))?;
+                }
+                let released = liquid().saturating_sub(liquid_before);
+                Ok(succeed(encode_words(&[U256::from(released)])))
+            },
+            _ => Err(revert("unknown selector")),
+        }
+    }
+}
+
+/// Read-only access to the energy broker's VTRS/VNRG pool pricing.
+///
+/// `getReserves() returns (uint256 vtrs, uint256 vnrg)` returns the pool reserves, both `0` if the
+/// pool has no liquidity. `quote(uint256 amountIn, bool vtrsToVnrg) returns (uint256)` returns
+/// what a swap of `amountIn` would pay out, like `EnergyBroker::get_amount_out`, so the broker's
+/// formula and LP fee apply. `quote` reverts if the swap isn't possible.
+pub struct EnergyBrokerQuote;
+
+impl EnergyBrokerQuote {
+    /// `bytes4(keccak256("getReserves()"))`
+    pub const GET_RESERVES_SELECTOR: [u8; 4] = [0x09, 0x02, 0xf1, 0xac];
+    /// `bytes4(keccak256("quote(uint256,bool)"))`
+    pub const QUOTE_SELECTOR: [u8; 4] = [0x52, 0x5f, 0xcb, 0x38];
+    /// Reserve and rate reads priced as three cold `SLOAD`s.
+    pub const GAS_COST: u64 = 6_300;
+}
+
+impl Precompile for EnergyBrokerQuote {
+    fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+        handle.record_cost(Self::GAS_COST)?;
+
+        let vtrs = NativeOrAssetId::Native;
+        let vnrg = NativeOrAssetId::Asset(VNRG::get());
+
+        match selector(handle.input())? {
+            Self::GET_RESERVES_SELECTOR => {
+                let (vtrs_reserve, vnrg_reserve) =
+                    EnergyBroker::get_reserves(&vtrs, &vnrg).unwrap_or_default();
+                Ok(succeed(encode_words(&[U256::from(vtrs_reserve), U256::from(vnrg_reserve)])))
+            },
+            Self::QUOTE_SELECTOR => {
+                let args = &handle.input()[4..];
+                let amount_in = read_u256(args, 0)?;
+                let amount_in: Balance =
+                    amount_in.try_into().map_err(|_| revert("value out of bounds"))?;
+                let path = if read_bool(args, 1)? { (&vtrs, &vnrg) } else { (&vnrg, &vtrs) };
+
+                let amount_out = EnergyBroker::get_amount_out(&amount_in, path)
+                    .map_err(|_| revert("quote unavailable"))?;
+                Ok(succeed(encode_words(&[U256::from(amount_out)])))
+            },
+            _ => Err(revert("unknown selector")),
+        }
+    }
+}
+
 fn hash(a: u64) -> H160 {
     H160::from_low_u64_be(a)
 }
+
+/// Function selector of an ABI encoded call.
+fn selector(input: &[u8]) -> Result<[u8; 4], PrecompileFailure> {
+    input
+        .get(0..4)
+        .and_then(|selector| selector.try_into().ok())
+        .ok_or_else(|| revert("missing selector"))
+}
+
+/// The 32-byte word at `index` of ABI encoded arguments.
+fn read_word(args: &[u8], index: usize) -> Result<&[u8], PrecompileFailure> {
+    let start = index.checked_mul(32).ok_or_else(|| revert("input too short"))?;
+    args.get(start..start.saturating_add(32))
+        .ok_or_else(|| revert("input too short"))
+}
+
+/// An ABI encoded word used as an offset or a length.
+fn read_usize(word: &[u8]) -> Result<usize, PrecompileFailure> {
+    let value = U256::from_big_endian(word);
+    if value > U256::from(u32::MAX) {
+        return Err(revert("value out of bounds"));
+    }
+    Ok(value.low_u32() as usize)
+}
+
+/// The `uint256` argument at `index` of ABI encoded arguments.
+fn read_u256(args: &[u8], index: usize) -> Result<U256, PrecompileFailure> {
+    read_word(args, index).map(U256::from_big_endian)
+}
+
+/// The `bool` argument at `index` of ABI encoded arguments.
+fn read_bool(args: &[u8], index: usize) -> Result<bool, PrecompileFailure> {
+    match read_u256(args, index)? {
+        value if value.is_zero() => Ok(false),
+        value if value == U256::one() => Ok(true),
+        _ => Err(revert("invalid bool")),
+    }
+}
+
+/// The address argument at `index` of ABI encoded arguments.
+fn read_address(args: &[u8], index: usize) -> Result<H160, PrecompileFailure> {
+    let word = read_word(args, index)?;
+    if word[..12].iter().any(|byte| *byte != 0) {
+        return Err(revert("invalid address"));
+    }
+    Ok(H160::from_slice(&word[12..]))
+}
+
+/// The dynamic `bytes` argument at `index` of ABI encoded arguments.
+fn read_bytes(args: &[u8], index: usize) -> Result<&[u8], PrecompileFailure> {
+    let offset = read_usize(read_word(args, index)?)?;
+    let length = read_usize(
+        args.get(offset..offset.saturating_add(32))
+            .ok_or_else(|| revert("input too short"))?,
+    )?;
+    let start = offset.saturating_add(32);
+    args.get(start..start.saturating_add(length))
+        .ok_or_else(|| revert("input too short"))
+}
+
+/// ABI encode a tuple of static 32-byte words.
+fn encode_words(words: &[U256]) -> Vec<u8> {
+    let mut output = sp_std::vec![0u8; words.len() * 32];
+    for (word, chunk) in words.iter().zip(output.chunks_exact_mut(32)) {
+        word.to_big_endian(chunk);
+    }
+    output
+}
+
+fn succeed(output: Vec<u8>) -> PrecompileOutput {
+    PrecompileOutput { exit_status: ExitSucceed::Returned, output }
+}
+
+fn revert(message: &str) -> PrecompileFailure {
+    PrecompileFailure::Revert { exit_status: ExitRevert::Reverted, output: message.into() }
+}
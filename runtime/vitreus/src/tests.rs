@@ -6,11 +6,12 @@ use chain_spec::{
 use ethereum::{TransactionAction, TransactionSignature, TransactionV2};
 use fp_self_contained::SelfContainedCall;
 use frame_support::{
+    assert_noop, assert_ok,
     dispatch::{DispatchClass, GetDispatchInfo},
-    traits::Hooks,
+    traits::{CallMetadata, Hooks, PalletInfo},
 };
 use pallet_energy_fee::DefaultFeeMultiplier;
-use sp_runtime::{BuildStorage, FixedU128, Perquintill};
+use sp_runtime::{traits::SignedExtension, BuildStorage, FixedU128, Perquintill};
 
 pub fn devnet_ext() -> sp_io::TestExternalities {
     sp_io::TestExternalities::new(devnet_config().build_storage().unwrap())
@@ -28,11 +29,42 @@ fn mock_signature() -> TransactionSignature {
 
 #[test]
 fn configured_base_extrinsic_weight_is_evm_compatible() {
-    let min_ethereum_transaction_weight = WeightPerGas::get() * 21_000;
-    let base_extrinsic = <Runtime as frame_system::Config>::BlockWeights::get()
-        .get(frame_support::dispatch::DispatchClass::Normal)
-        .base_extrinsic;
-    assert!(base_extrinsic.ref_time() <= min_ethereum_transaction_weight.ref_time());
+    devnet_ext().execute_with(|| {
+        let base_extrinsic = <Runtime as frame_system::Config>::BlockWeights::get()
+            .get(frame_support::dispatch::DispatchClass::Normal)
+            .base_extrinsic;
+
+        for gas_limit in [MinBlockGasLimit::get(), MaxBlockGasLimit::get()] {
+            pallet_energy_fee::BlockGasLimit::<Runtime>::put(gas_limit);
+            let min_ethereum_transaction_weight = WeightPerGas::get() * 21_000;
+            assert!(base_extrinsic.ref_time() <= min_ethereum_transaction_weight.ref_time());
+        }
+    });
+}
+
+#[test]
+fn block_gas_limit_update_applies_next_block() {
+    devnet_ext().execute_with(|| {
+        let block_gas_limit = <Runtime as pallet_evm::Config>::BlockGasLimit::get;
+        assert_eq!(block_gas_limit(), U256::from(DefaultBlockGasLimit::get()));
+
+        let new_limit = MaxBlockGasLimit::get();
+        assert!(EnergyFee::update_block_gas_limit(RuntimeOrigin::root(), new_limit + 1).is_err());
+        EnergyFee::update_block_gas_limit(RuntimeOrigin::root(), new_limit)
+            .expect("Expected to schedule a new block gas limit");
+        assert_eq!(block_gas_limit(), U256::from(DefaultBlockGasLimit::get()));
+
+        EnergyFee::on_initialize(2);
+        assert_eq!(block_gas_limit(), U256::from(new_limit));
+        assert_eq!(GasLimitPovSizeRatio::get(), new_limit / MAX_POV_SIZE);
+
+        // The whole gas budget must still fit into the normal dispatch class.
+        let max_normal_weight =
+            BlockWeights::get().per_class.get(DispatchClass::Normal).max_total.unwrap();
+        let gas_weight =
+            <Runtime as pallet_evm::Config>::GasWeightMapping::gas_to_weight(new_limit, true);
+        assert!(gas_weight.ref_time() <= max_normal_weight.ref_time());
+    });
 }
 
 #[test]
@@ -129,6 +161,45 @@ fn runtime_should_allow_ethereum_txs_with_zero_gas_limit() {
     })
 }
 
+#[test]
+fn evm_fee_paid_reports_fee_of_applied_ethereum_transaction() {
+    devnet_ext().execute_with(|| {
+        System::set_block_number(1);
+        let alith_h160 = H160::from(alith().0);
+        let sample_tx = TransactionV2::Legacy(LegacyTransaction {
+            nonce: Default::default(),
+            gas_price: 1.into(),
+            gas_limit: 21_000.into(),
+            action: TransactionAction::Call(H160::from(baltathar().0)),
+            value: 1_000_000_000.into(),
+            input: Default::default(),
+            signature: mock_signature(),
+        });
+        let tx_hash = sample_tx.hash();
+        assert_eq!(EnergyFee::evm_fee_paid(tx_hash), None);
+
+        let runtime_call =
+            RuntimeCall::Ethereum(pallet_ethereum::Call::new_call_variant_transact(sample_tx));
+        assert!(matches!(runtime_call.apply_self_contained(alith_h160), Some(Ok(..))));
+
+        let paid = System::events()
+            .into_iter()
+            .find_map(|record| match record.event {
+                RuntimeEvent::EnergyFee(pallet_energy_fee::Event::EnergyFeePaid {
+                    who,
+                    amount,
+                }) if who == alith() => Some(amount),
+                _ => None,
+            })
+            .expect("the transaction pays a fee");
+        assert_eq!(EnergyFee::evm_fee_paid(tx_hash), Some(paid));
+
+        // receipts leave the retention window
+        System::set_block_number(1 + EvmFeeReceiptRetention::get());
+        assert_eq!(EnergyFee::evm_fee_paid(tx_hash), None);
+    });
+}
+
 #[test]
 fn validate_self_contained_should_disallow_calls_if_sender_cant_pay_fees() {
     devnet_ext().execute_with(|| {
@@ -157,7 +228,1563 @@ fn validate_self_contained_should_disallow_calls_if_sender_cant_pay_fees() {
 
         assert_eq!(
             runtime_call.validate_self_contained(&noname_h160, &dispatch_info, len),
-            Some(Err(InvalidTransaction::Payment.into()))
+            Some(Err(InvalidTransaction::Custom(INSUFFICIENT_VTRS_FOR_FEE).into()))
+        );
+    })
+}
+
+fn fee_charged() -> bool {
+    System::events().iter().any(|record| {
+        matches!(
+            record.event,
+            RuntimeEvent::EnergyFee(pallet_energy_fee::Event::EnergyFeePaid { .. })
+                | RuntimeEvent::TransactionPayment(
+                    pallet_transaction_payment::Event::TransactionFeePaid { .. }
+                )
+        )
+    })
+}
+
+#[test]
+fn heartbeats_are_applied_for_free() {
+    use pallet_im_online::sr25519::AuthorityPair;
+    use sp_core::Pair;
+
+    devnet_ext().execute_with(|| {
+        System::set_block_number(1);
+        // the heartbeat is signed by Alice's session key, which holds no balance
+        let pair = AuthorityPair::from_string("//Alice", None).unwrap();
+        let keys = pallet_im_online::Keys::<Runtime>::get();
+        let authority_index = keys.iter().position(|key| *key == pair.public()).unwrap() as u32;
+        let heartbeat = pallet_im_online::Heartbeat {
+            block_number: 1,
+            session_index: Session::current_index(),
+            authority_index,
+            validators_len: keys.len() as u32,
+        };
+        let signature = pair.sign(&heartbeat.encode());
+        let call =
+            RuntimeCall::ImOnline(pallet_im_online::Call::heartbeat { heartbeat, signature });
+
+        assert_eq!(Executive::apply_extrinsic(UncheckedExtrinsic::new_unsigned(call)), Ok(Ok(())));
+        assert!(pallet_im_online::ReceivedHeartbeats::<Runtime>::get(
+            Session::current_index(),
+            authority_index
+        )
+        .is_some());
+        assert!(!fee_charged());
+    });
+}
+
+#[test]
+fn unsigned_equivocation_reports_are_applied_for_free() {
+    use fg_primitives::{AuthorityPair, Equivocation, EquivocationProof, KEY_TYPE};
+    use frame_support::traits::KeyOwnerProofSystem;
+    use sp_core::Pair;
+
+    devnet_ext().execute_with(|| {
+        System::set_block_number(1);
+        let pair = AuthorityPair::from_string("//Alice", None).unwrap();
+        let (round_number, set_id) = (1, Grandpa::current_set_id());
+        let prevote = |target_hash| {
+            let prevote = finality_grandpa::Prevote { target_hash, target_number: 1 };
+            let message = finality_grandpa::Message::Prevote(prevote.clone());
+            let payload = fg_primitives::localized_payload(round_number, set_id, &message);
+            (prevote, pair.sign(&payload))
+        };
+        let equivocation_proof = EquivocationProof::new(
+            set_id,
+            Equivocation::Prevote(finality_grandpa::Equivocation {
+                round_number,
+                identity: pair.public(),
+                first: prevote(H256::repeat_byte(1)),
+                second: prevote(H256::repeat_byte(2)),
+            }),
+        );
+        let key_owner_proof = Historical::prove((KEY_TYPE, pair.public())).unwrap();
+        let call = RuntimeCall::Grandpa(pallet_grandpa::Call::report_equivocation_unsigned {
+            equivocation_proof: Box::new(equivocation_proof),
+            key_owner_proof,
+        });
+
+        assert_eq!(Executive::apply_extrinsic(UncheckedExtrinsic::new_unsigned(call)), Ok(Ok(())));
+        assert!(System::events().iter().any(|record| matches!(
+            record.event,
+            RuntimeEvent::Offences(pallet_offences::Event::Offence { .. })
+        )));
+        assert!(!fee_charged());
+    });
+}
+
+#[test]
+fn self_contained_ethereum_transactions_are_charged() {
+    use ethereum::LegacyTransactionMessage;
+
+    devnet_ext().execute_with(|| {
+        System::set_block_number(1);
+        let chain_id = <Runtime as pallet_evm::Config>::ChainId::get();
+        let message = LegacyTransactionMessage {
+            nonce: System::account_nonce(alith()).into(),
+            gas_price: 1.into(),
+            gas_limit: 21_000.into(),
+            action: TransactionAction::Call(H160::from(baltathar().0)),
+            value: 1_000_000_000.into(),
+            input: Default::default(),
+            chain_id: Some(chain_id),
+        };
+        // the well-known development key of Alith
+        let pair = sp_core::ecdsa::Pair::from_seed(&hex_literal::hex!(
+            "5fb92d6e98884f76de468fa3f6278f8807c48bebc13595d45af5bdc4da702133"
+        ));
+        let signature = pair.sign_prehashed(&message.hash().0);
+        let (r, s) = (H256::from_slice(&signature.0[..32]), H256::from_slice(&signature.0[32..64]));
+        let v = signature.0[64] as u64 + chain_id * 2 + 35;
+        let transaction = TransactionV2::Legacy(LegacyTransaction {
+            nonce: message.nonce,
+            gas_price: message.gas_price,
+            gas_limit: message.gas_limit,
+            action: message.action,
+            value: message.value,
+            input: message.input,
+            signature: TransactionSignature::new(v, r, s).unwrap(),
+        });
+        let call =
+            RuntimeCall::Ethereum(pallet_ethereum::Call::new_call_variant_transact(transaction));
+
+        assert_eq!(Executive::apply_extrinsic(UncheckedExtrinsic::new_unsigned(call)), Ok(Ok(())));
+        assert!(System::events().iter().any(|record| matches!(
+            &record.event,
+            RuntimeEvent::EnergyFee(pallet_energy_fee::Event::EnergyFeePaid { who, .. })
+                if *who == alith()
+        )));
+    });
+}
+
+#[test]
+fn validate_self_contained_should_reject_gas_limits_exceeding_the_proof_size_budget() {
+    devnet_ext().execute_with(|| {
+        let call_with_gas_limit = |gas_limit: u64| {
+            RuntimeCall::Ethereum(pallet_ethereum::Call::new_call_variant_transact(
+                TransactionV2::Legacy(LegacyTransaction {
+                    nonce: Default::default(),
+                    gas_price: 1.into(),
+                    gas_limit: gas_limit.into(),
+                    action: TransactionAction::Call(Default::default()),
+                    value: Default::default(),
+                    input: Default::default(),
+                    signature: mock_signature(),
+                }),
+            ))
+        };
+        let alith_h160 = H160::from(alith().0);
+        let exceeded = InvalidTransaction::Custom(PROOF_SIZE_EXCEEDED);
+
+        // the last gas limit within the budget, given the integer gas to proof size ratio
+        let ratio = GasLimitPovSizeRatio::get();
+        let within_budget = call_with_gas_limit(MaxEvmProofSize::get() * ratio + ratio - 1);
+        let RuntimeCall::Ethereum(ref call) = within_budget else { unreachable!() };
+        assert_eq!(evm_proof_size(call), Some(MaxEvmProofSize::get()));
+        let dispatch_info = within_budget.get_dispatch_info();
+        assert!(matches!(
+            within_budget.validate_self_contained(&alith_h160, &dispatch_info, 0),
+            Some(Ok(..))
+        ));
+
+        let over_budget = call_with_gas_limit(MaxEvmProofSize::get() * ratio + ratio);
+        let dispatch_info = over_budget.get_dispatch_info();
+        assert_eq!(
+            over_budget.validate_self_contained(&alith_h160, &dispatch_info, 0),
+            Some(Err(exceeded.into()))
+        );
+        assert_eq!(
+            over_budget.pre_dispatch_self_contained(&alith_h160, &dispatch_info, 0),
+            Some(Err(exceeded.into()))
+        );
+
+        // the block gas limit itself rounds past the budget
+        let block_limit = call_with_gas_limit(EnergyFee::block_gas_limit());
+        let dispatch_info = block_limit.get_dispatch_info();
+        assert_eq!(
+            block_limit.validate_self_contained(&alith_h160, &dispatch_info, 0),
+            Some(Err(exceeded.into()))
+        );
+    })
+}
+
+#[test]
+fn simulated_fees_match_fees_after_parameter_change() {
+    devnet_ext().execute_with(|| {
+        let transfer =
+            RuntimeCall::Balances(BalancesCall::transfer_keep_alive { dest: alith(), value: 1 });
+        let batch = RuntimeCall::Utility(pallet_utility::Call::batch {
+            calls: vec![transfer.clone(), transfer.clone()],
+        });
+        let evm_call = RuntimeCall::EVM(pallet_evm::Call::withdraw {
+            address: H160::from(alith().0),
+            value: 1,
+        });
+        let sample_calls = vec![transfer, batch, evm_call];
+        let current_fees = || {
+            sample_calls
+                .iter()
+                .map(|call| EnergyFee::dispatch_info_to_fee(call, None, None).into_inner())
+                .collect::<Vec<_>>()
+        };
+
+        let fees_before = current_fees();
+        let new_base_fee = GetConstantEnergyFee::get() * 2;
+        let new_multiplier = FixedU128::from(3);
+        let simulated_fees =
+            EnergyFee::with_fee_parameters(new_base_fee, new_multiplier, current_fees);
+
+        assert_eq!(current_fees(), fees_before);
+        assert_ne!(simulated_fees, fees_before);
+
+        EnergyFee::update_base_fee(RuntimeOrigin::root(), new_base_fee)
+            .expect("Expected to set a new base fee");
+        pallet_transaction_payment::NextFeeMultiplier::<Runtime>::put(new_multiplier);
+
+        assert_eq!(current_fees(), simulated_fees);
+    });
+}
+
+#[test]
+fn fee_range_brackets_charged_fee() {
+    devnet_ext().execute_with(|| {
+        let upper = FixedU128::from(5);
+        EnergyFee::update_upper_fee_multiplier(RuntimeOrigin::root(), upper)
+            .expect("Expected to set the upper fee multiplier");
+        let default = DefaultFeeMultiplier::<Runtime>::get();
+        let base_fee = EnergyFee::base_fee();
+
+        let transfer =
+            RuntimeCall::Balances(BalancesCall::transfer_keep_alive { dest: alith(), value: 1 });
+        let remark = RuntimeCall::System(frame_system::Call::remark { remark: vec![1u8; 32] });
+        let batch = RuntimeCall::Utility(pallet_utility::Call::batch {
+            calls: vec![transfer.clone(), transfer.clone()],
+        });
+        let sudo = RuntimeCall::Sudo(pallet_sudo::Call::sudo { call: Box::new(remark.clone()) });
+
+        // weight priced calls only scale the weight part of the fee with the multiplier
+        let remark_fee = |multiplier: FixedU128| {
+            let len = remark.encode().len() as u32;
+            let info = remark.get_dispatch_info();
+            pallet_transaction_payment::NextFeeMultiplier::<Runtime>::put(FixedU128::from(1));
+            let inclusion_fee = TransactionPayment::compute_fee_details(len, &info, 0)
+                .inclusion_fee
+                .expect("Expected remark to pay an inclusion fee");
+            inclusion_fee.base_fee
+                + inclusion_fee.len_fee
+                + multiplier.saturating_mul_int(inclusion_fee.adjusted_weight_fee)
+        };
+
+        let expected = [
+            (&transfer, default.saturating_mul_int(base_fee), upper.saturating_mul_int(base_fee)),
+            (
+                &batch,
+                2 * default.saturating_mul_int(base_fee),
+                2 * upper.saturating_mul_int(base_fee),
+            ),
+            (&remark, remark_fee(default), remark_fee(upper)),
+            (&sudo, 0, 0),
+        ];
+
+        for (call, min_fee, max_fee) in expected {
+            pallet_transaction_payment::NextFeeMultiplier::<Runtime>::put(FixedU128::from(3));
+            let charged_fee = || EnergyFee::dispatch_info_to_fee(call, None, None).into_inner();
+            assert_eq!(EnergyFee::fee_range(charged_fee), (min_fee, max_fee));
+
+            let fee = charged_fee();
+            assert!(min_fee <= fee && fee <= max_fee);
+        }
+    });
+}
+
+#[test]
+fn wrapped_calls_cannot_evade_their_fee() {
+    devnet_ext().execute_with(|| {
+        let evm_call = RuntimeCall::EVM(pallet_evm::Call::withdraw {
+            address: H160::from(alith().0),
+            value: 1,
+        });
+        let transfer =
+            RuntimeCall::Balances(BalancesCall::transfer_keep_alive { dest: alith(), value: 1 });
+        let remark = RuntimeCall::System(frame_system::Call::remark { remark: vec![1u8; 32] });
+        let fee_of = |call: &RuntimeCall| EnergyFee::dispatch_info_to_fee(call, None, None);
+        let as_derivative = |call: RuntimeCall| {
+            RuntimeCall::Utility(pallet_utility::Call::as_derivative {
+                index: 0,
+                call: Box::new(call),
+            })
+        };
+        let batch = |calls: Vec<RuntimeCall>| {
+            RuntimeCall::Utility(pallet_utility::Call::batch_all { calls })
+        };
+        let ethereum_fee = EnergyFee::ethereum_fee();
+
+        // a wrapped EVM call is charged upfront to the signer
+        assert_eq!(fee_of(&evm_call), CallFee::EVM(ethereum_fee));
+        assert_eq!(fee_of(&as_derivative(evm_call.clone())), CallFee::Regular(ethereum_fee));
+
+        // wrapping many EVM calls doesn't pay a single fee, however deep they are nested
+        let evm_calls = vec![evm_call.clone(), evm_call.clone(), evm_call];
+        let nested = batch(vec![
+            as_derivative(batch(vec![as_derivative(batch(evm_calls)), transfer.clone()])),
+            remark.clone(),
+        ]);
+        assert_eq!(
+            fee_of(&nested),
+            CallFee::Regular(
+                3 * ethereum_fee
+                    + fee_of(&transfer).into_inner()
+                    + EnergyFee::weight_fee(&remark, None, None)
+            )
+        );
+
+        // a wrapper pays at least the constant fee, like a batch
+        assert_eq!(
+            fee_of(&as_derivative(remark.clone())).into_inner(),
+            EnergyFee::weight_fee(&remark, None, None).max(EnergyFee::custom_fee())
+        );
+        assert_eq!(
+            fee_of(&as_derivative(transfer.clone())),
+            CallFee::Regular(EnergyFee::custom_fee())
+        );
+    });
+}
+
+#[test]
+fn wrapped_evm_call_is_charged_a_single_fee() {
+    devnet_ext().execute_with(|| {
+        System::set_block_number(1);
+        let evm_call = RuntimeCall::EVM(pallet_evm::Call::call {
+            source: H160::from(alith().0),
+            target: H160::from(baltathar().0),
+            input: vec![],
+            value: 1_000_000_000.into(),
+            gas_limit: 21_000,
+            max_fee_per_gas: 1.into(),
+            max_priority_fee_per_gas: None,
+            nonce: None,
+            access_list: vec![],
+        });
+        let batch = RuntimeCall::Utility(pallet_utility::Call::batch_all { calls: vec![evm_call] });
+        let info = batch.get_dispatch_info();
+        let len = batch.encode().len();
+        let fees_paid_by_alith = || {
+            System::events()
+                .into_iter()
+                .filter_map(|record| match record.event {
+                    RuntimeEvent::EnergyFee(pallet_energy_fee::Event::EnergyFeePaid {
+                        who,
+                        amount,
+                    }) if who == alith() => Some(amount),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        type FeeExtensions = (
+            pallet_energy_fee::CheckEnergyFee<Runtime>,
+            pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+        );
+        let extensions: FeeExtensions = (
+            pallet_energy_fee::CheckEnergyFee::new(),
+            pallet_transaction_payment::ChargeTransactionPayment::from(0),
+        );
+        let pre = extensions.pre_dispatch(&alith(), &batch, &info, len).unwrap();
+        assert_eq!(fees_paid_by_alith().len(), 1);
+
+        let post_info = batch.dispatch(RuntimeOrigin::signed(alith())).unwrap();
+        assert_ok!(<FeeExtensions as SignedExtension>::post_dispatch(
+            Some(pre),
+            &info,
+            &post_info,
+            len,
+            &Ok(())
+        ));
+
+        // the EVM runner didn't charge the call covered by the extrinsic fee again
+        assert_eq!(fees_paid_by_alith().len(), 1);
+        assert!(!pallet_energy_fee::EvmFeePrepaid::<Runtime>::exists());
+    });
+}
+
+#[test]
+fn fee_classification_matches_call_pricing() {
+    let classification = fee_classification();
+    let class_of = |pallet_index: u8, call_name: &str| {
+        classification
+            .iter()
+            .find(|(index, name, _)| *index == pallet_index && name == call_name.as_bytes())
+            .map(|(_, _, class)| *class)
+    };
+
+    assert_eq!(class_of(8, "sudo"), Some(FeeClass::Zero));
+    assert_eq!(class_of(15, "call"), Some(FeeClass::EVM));
+    assert_eq!(class_of(17, "transact"), Some(FeeClass::EVM));
+    assert_eq!(class_of(4, "transfer_keep_alive"), Some(FeeClass::Constant));
+    assert_eq!(class_of(36, "batch_all"), Some(FeeClass::Nested));
+    assert_eq!(class_of(0, "remark"), Some(FeeClass::Weight));
+    assert_eq!(class_of(33, "update_base_fee"), Some(FeeClass::Weight));
+
+    // the listing agrees with the classes of the calls themselves
+    let with_class_of = |call: RuntimeCall| {
+        let CallMetadata { pallet_name, function_name } = call.get_call_metadata();
+        let index = <Runtime as frame_system::Config>::PalletInfo::index_by_name(pallet_name);
+        assert_eq!(class_of(index.unwrap() as u8, function_name), Some(fee_class(&call)));
+    };
+    with_class_of(RuntimeCall::System(frame_system::Call::remark { remark: vec![] }));
+    with_class_of(RuntimeCall::Utility(pallet_utility::Call::batch_all { calls: vec![] }));
+    let remark = Box::new(RuntimeCall::System(frame_system::Call::remark { remark: vec![] }));
+    with_class_of(RuntimeCall::Sudo(pallet_sudo::Call::sudo { call: remark }));
+
+    // every listed call is classified once
+    let mut calls =
+        classification.iter().map(|(index, name, _)| (*index, name)).collect::<Vec<_>>();
+    calls.sort();
+    calls.dedup();
+    assert_eq!(calls.len(), classification.len());
+}
+
+#[test]
+fn fee_environment_precompile_matches_chain_state() {
+    devnet_ext().execute_with(|| {
+        let base_fee = 12_345;
+        let multiplier = FixedU128::from_rational(3, 2);
+        EnergyFee::update_base_fee(RuntimeOrigin::root(), base_fee)
+            .expect("Expected to set a new base fee");
+        pallet_transaction_payment::NextFeeMultiplier::<Runtime>::put(multiplier);
+
+        let info = pallet_evm::runner::stack::Runner::<Runtime>::call(
+            H160::from(alith().0),
+            H160::from_low_u64_be(precompiles::FEE_ENVIRONMENT_ADDRESS),
+            precompiles::FeeEnvironment::FEE_ENVIRONMENT_SELECTOR.to_vec(),
+            U256::zero(),
+            100_000,
+            None,
+            None,
+            None,
+            Vec::new(),
+            false,
+            false,
+            None,
+            None,
+            <Runtime as pallet_evm::Config>::config(),
+        )
+        .expect("Expected the precompile call to be executed");
+
+        let mut expected = [0u8; 64];
+        U256::from(base_fee).to_big_endian(&mut expected[..32]);
+        U256::from(1_500_000_000_000_000_000_u128).to_big_endian(&mut expected[32..]);
+
+        assert_eq!(info.exit_reason, fp_evm::ExitReason::Succeed(fp_evm::ExitSucceed::Returned));
+        assert_eq!(info.value, expected.to_vec());
+    });
+}
+
+#[test]
+fn estimated_contract_call_gas_covers_the_executed_gas() {
+    use energy_fee_runtime_api::CallRequest;
+
+    devnet_ext().execute_with(|| {
+        let from = H160::from(alith().0);
+        let to = H160::from_low_u64_be(precompiles::FEE_ENVIRONMENT_ADDRESS);
+        let input = precompiles::FeeEnvironment::FEE_ENVIRONMENT_SELECTOR.to_vec();
+        let executed_gas = pallet_evm::runner::stack::Runner::<Runtime>::call(
+            from,
+            to,
+            input.clone(),
+            U256::zero(),
+            100_000,
+            None,
+            None,
+            None,
+            Vec::new(),
+            false,
+            false,
+            None,
+            None,
+            <Runtime as pallet_evm::Config>::config(),
+        )
+        .expect("Expected the precompile call to be executed")
+        .used_gas
+        .standard;
+        let estimate = |data: Option<Vec<u8>>| {
+            estimate_gas(CallRequest {
+                from: Some(from),
+                to: Some(to),
+                value: Some(U256::zero()),
+                data: data.map(Into::into),
+                ..Default::default()
+            })
+        };
+
+        let buffered = estimate(Some(input.clone()));
+        assert_eq!(EnergyFee::gas_estimation_buffer(), Perbill::from_percent(10));
+        assert!(buffered > executed_gas);
+        assert!(buffered <= executed_gas * 11 / 10 + 1);
+
+        assert_ok!(EnergyFee::update_gas_estimation_buffer(RuntimeOrigin::root(), Perbill::zero()));
+        assert_eq!(estimate(Some(input)), executed_gas);
+
+        // pure transfers are estimated at their fee, without buffer
+        let transfer_fee = estimate(None);
+        assert_ok!(EnergyFee::update_gas_estimation_buffer(
+            RuntimeOrigin::root(),
+            Perbill::from_percent(50)
+        ));
+        assert_eq!(estimate(None), transfer_fee);
+    });
+}
+
+#[test]
+fn fee_affordability_precompile_matches_native_fee_validation() {
+    devnet_ext().execute_with(|| {
+        let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![1u8; 32] });
+        let info = call.get_dispatch_info();
+
+        let can_afford_fee = |who: &AccountId, encoded_call: Vec<u8>| {
+            let mut input = precompiles::FeeAffordability::CAN_AFFORD_FEE_SELECTOR.to_vec();
+            let mut word = [0u8; 32];
+            word[12..].copy_from_slice(&who.0);
+            input.extend_from_slice(&word);
+            U256::from(64).to_big_endian(&mut word);
+            input.extend_from_slice(&word);
+            U256::from(encoded_call.len()).to_big_endian(&mut word);
+            input.extend_from_slice(&word);
+            input.extend_from_slice(&encoded_call);
+            input.resize(input.len() + (32 - encoded_call.len() % 32) % 32, 0);
+
+            pallet_evm::runner::stack::Runner::<Runtime>::call(
+                H160::from(alith().0),
+                H160::from_low_u64_be(precompiles::FEE_AFFORDABILITY_ADDRESS),
+                input,
+                U256::zero(),
+                100_000,
+                None,
+                None,
+                None,
+                Vec::new(),
+                false,
+                false,
+                None,
+                None,
+                <Runtime as pallet_evm::Config>::config(),
+            )
+            .expect("Expected the precompile call to be executed")
+        };
+        let withdraw_fee = |who: &AccountId| {
+            <EnergyFee as pallet_transaction_payment::OnChargeTransaction<Runtime>>::withdraw_fee(
+                who, &call, &info, 1, 0,
+            )
+        };
+        let word = |value: u64| {
+            let mut word = [0u8; 32];
+            U256::from(value).to_big_endian(&mut word);
+            word.to_vec()
+        };
+
+        let funded = baltathar();
+        let info_funded = can_afford_fee(&funded, call.encode());
+        assert_eq!(
+            info_funded.exit_reason,
+            fp_evm::ExitReason::Succeed(fp_evm::ExitSucceed::Returned)
+        );
+        assert_eq!(info_funded.value, word(1));
+        assert!(withdraw_fee(&funded).is_ok());
+
+        let broke = AccountId::from([0x42u8; 20]);
+        assert_eq!(can_afford_fee(&broke, call.encode()).value, word(0));
+        assert!(withdraw_fee(&broke).is_err());
+
+        let invalid = can_afford_fee(&funded, vec![0xff, 0xff]);
+        assert_eq!(invalid.exit_reason, fp_evm::ExitReason::Revert(fp_evm::ExitRevert::Reverted));
+    });
+}
+
+#[test]
+fn unresponsive_validators_are_not_reported_during_onboarding_grace() {
+    devnet_ext().execute_with(|| {
+        System::set_block_number(1);
+        let stash = Session::validators()[0];
+        let report_unresponsive = || {
+            let offence = UnresponsivenessOffence {
+                session_index: 0,
+                validator_set_count: Session::validators().len() as u32,
+                offenders: vec![(stash, EnergyGeneration::eras_stakers(0, stash))],
+            };
+            assert_ok!(ReportUnresponsiveness::report_offence(vec![], offence));
+        };
+        let offence_reported = || {
+            System::events().iter().any(|record| {
+                matches!(
+                    record.event,
+                    RuntimeEvent::Offences(pallet_offences::Event::Offence { .. })
+                )
+            })
+        };
+
+        // genesis validators are in their first era
+        assert!(EnergyGeneration::in_onboarding_grace(&stash));
+        report_unresponsive();
+        assert!(!offence_reported());
+
+        pallet_energy_generation::ActiveEra::<Runtime>::put(
+            pallet_energy_generation::ActiveEraInfo {
+                index: OnboardingGraceEras::get(),
+                start: None,
+            },
+        );
+        report_unresponsive();
+        assert!(offence_reported());
+    });
+}
+
+#[test]
+fn genesis_seeds_energy_broker_pool() {
+    devnet_ext().execute_with(|| {
+        let (vtrs, vnrg) = EnergyBroker::get_reserves(
+            &NativeOrAssetId::Native,
+            &NativeOrAssetId::Asset(VNRG::get()),
+        )
+        .expect("the pool is seeded at genesis");
+        assert!(vtrs > 0 && vnrg > 0);
+        // the pool prices VNRG like the oracle at launch
+        assert_eq!(INITIAL_ENERGY_RATE.saturating_mul_int(vnrg), vtrs);
+
+        let vnrg_before = Assets::balance(VNRG::get(), baltathar());
+        let amount = 10 * vtrs::UNITS;
+        let expected = EnergyBrokerExchange::convert_from_input(amount).unwrap();
+        assert_eq!(EnergyBrokerExchange::exchange_from_input(&baltathar(), amount), Ok(expected));
+        assert_eq!(Assets::balance(VNRG::get(), baltathar()), vnrg_before + expected);
+    });
+}
+
+#[test]
+fn fee_credit_is_bought_through_the_energy_broker_pool() {
+    devnet_ext().execute_with(|| {
+        let who = baltathar();
+        let amount = 10 * vnrg::GVOLT;
+        let price = EnergyBrokerExchange::convert_from_output(amount).unwrap();
+        // the pool prices VNRG above VTRS, the credit must be the VNRG bought, not its price
+        assert_ne!(price, amount);
+        let vtrs_before = Balances::free_balance(who);
+        let vnrg_before = Assets::balance(VNRG::get(), who);
+
+        assert_ok!(EnergyFee::buy_fee_credit(RuntimeOrigin::signed(who), amount, price));
+
+        assert_eq!(EnergyFee::fee_credit(who), amount);
+        assert_eq!(Balances::free_balance(who), vtrs_before - price);
+        assert_eq!(Assets::balance(VNRG::get(), who), vnrg_before);
+        assert!(EnergyFee::fee_pool_liquid());
+    });
+}
+
+#[test]
+fn only_fee_swaps_are_bounded_by_their_price_impact() {
+    devnet_ext().execute_with(|| {
+        System::set_block_number(1);
+        let who = baltathar();
+        let (_, vnrg_reserve) = EnergyBroker::get_reserves(
+            &NativeOrAssetId::Native,
+            &NativeOrAssetId::Asset(VNRG::get()),
+        )
+        .unwrap();
+        let amount = MaxFeeSwapImpact::get() * vnrg_reserve * 2;
+
+        assert_noop!(
+            EnergyBrokerExchange::exchange_fee_from_output(&who, amount),
+            pallet_energy_broker::Error::<Runtime>::FeeSwapImpactTooHigh
+        );
+
+        // buying fee credit is initiated by the user
+        let price = EnergyBrokerExchange::convert_from_output(amount).unwrap();
+        assert_ok!(EnergyFee::buy_fee_credit(RuntimeOrigin::signed(who), amount, price));
+        assert!(System::events().iter().any(|record| matches!(
+            record.event,
+            RuntimeEvent::EnergyBroker(pallet_energy_broker::Event::Swapped {
+                kind: SwapKind::User,
+                ..
+            })
+        )));
+    });
+}
+
+#[test]
+fn fee_payable_assets_flags_assets_without_pool_as_oracle_only() {
+    devnet_ext().execute_with(|| {
+        assert_eq!(fee_payable_assets(), vec![]);
+
+        // VNRG is paired with VTRS by the genesis pool, asset 42 only has an oracle rate
+        let oracle_only: AssetId = 42;
+        let set_rate = |asset: AssetId, rate: FixedU128| {
+            pallet_asset_rate::ConversionRateToNative::<Runtime>::insert(asset, rate)
+        };
+        set_rate(VNRG::get(), FixedU128::from_rational(10, 9));
+        set_rate(oracle_only, FixedU128::from(3));
+
+        let mut assets = fee_payable_assets();
+        assets.sort_by_key(|(asset, ..)| *asset);
+        assert_eq!(
+            assets,
+            vec![
+                (VNRG::get(), FixedU128::from_rational(10, 9), true),
+                (oracle_only, FixedU128::from(3), false),
+            ]
+        );
+    });
+}
+
+#[test]
+fn nac_exempt_account_can_transact_until_exemption_is_removed() {
+    devnet_ext().execute_with(|| {
+        let feeder_h160 = H160::repeat_byte(0x42);
+        let feeder = AccountId::from(feeder_h160);
+        Balances::make_free_balance_be(&feeder, 1_000 * vtrs::UNITS);
+
+        let sample_tx = TransactionV2::Legacy(LegacyTransaction {
+            nonce: Default::default(),
+            gas_price: 1.into(),
+            gas_limit: 0.into(),
+            action: TransactionAction::Call(H160::from(alith().0)),
+            value: Default::default(),
+            input: Default::default(),
+            signature: mock_signature(),
+        });
+        let runtime_call =
+            RuntimeCall::Ethereum(pallet_ethereum::Call::new_call_variant_transact(sample_tx));
+        let dispatch_info = runtime_call.get_dispatch_info();
+        let restricted = Some(Err(InvalidTransaction::Custom(ACCESS_RESTRICTED).into()));
+
+        assert_eq!(
+            runtime_call.validate_self_contained(&feeder_h160, &dispatch_info, 0),
+            restricted
+        );
+
+        assert_ok!(NacManaging::add_access_exemption(RuntimeOrigin::root(), feeder));
+        assert!(matches!(
+            runtime_call.validate_self_contained(&feeder_h160, &dispatch_info, 0),
+            Some(Ok(..))
+        ));
+
+        assert_ok!(NacManaging::remove_access_exemption(RuntimeOrigin::root(), feeder));
+        assert_eq!(
+            runtime_call.validate_self_contained(&feeder_h160, &dispatch_info, 0),
+            restricted
         );
     })
 }
+
+#[test]
+fn ethereum_transactions_need_the_nac_level_of_the_called_contract() {
+    devnet_ext().execute_with(|| {
+        let sender = H160::from(alith().0);
+        let contract = H160::repeat_byte(0x42);
+        let sample_tx = TransactionV2::Legacy(LegacyTransaction {
+            nonce: Default::default(),
+            gas_price: 1.into(),
+            gas_limit: 0.into(),
+            action: TransactionAction::Call(contract),
+            value: Default::default(),
+            input: Default::default(),
+            signature: mock_signature(),
+        });
+        let runtime_call =
+            RuntimeCall::Ethereum(pallet_ethereum::Call::new_call_variant_transact(sample_tx));
+        let dispatch_info = runtime_call.get_dispatch_info();
+        assert!(matches!(
+            runtime_call.validate_self_contained(&sender, &dispatch_info, 0),
+            Some(Ok(..))
+        ));
+
+        // the genesis accounts have NAC level 2
+        assert_ok!(NacManaging::set_target_access_level(
+            RuntimeOrigin::root(),
+            AccountId::from(contract),
+            Some(3),
+        ));
+        assert_eq!(
+            runtime_call.validate_self_contained(&sender, &dispatch_info, 0),
+            Some(Err(InvalidTransaction::Custom(ACCESS_RESTRICTED).into()))
+        );
+    })
+}
+
+#[test]
+fn rate_divergence_compares_broker_spot_rate_with_oracle() {
+    devnet_ext().execute_with(|| {
+        let (vtrs, vnrg) = EnergyBroker::get_reserves(
+            &NativeOrAssetId::Native,
+            &NativeOrAssetId::Asset(VNRG::get()),
+        )
+        .unwrap();
+        let spot = FixedU128::from_rational(vtrs, vnrg).into_inner();
+        let set_oracle_rate = |rate: FixedU128| {
+            pallet_asset_rate::ConversionRateToNative::<Runtime>::insert(VNRG::get(), rate)
+        };
+
+        set_oracle_rate(FixedU128::from_inner(spot / 5 * 4));
+        assert_eq!(
+            rate_divergence(),
+            Some(RateDivergence { magnitude: Perbill::from_percent(25), spot_above_oracle: true })
+        );
+
+        set_oracle_rate(FixedU128::from_inner(spot * 2));
+        assert_eq!(
+            rate_divergence(),
+            Some(RateDivergence { magnitude: Perbill::from_percent(50), spot_above_oracle: false })
+        );
+
+        pallet_asset_rate::ConversionRateToNative::<Runtime>::remove(VNRG::get());
+        assert_eq!(rate_divergence(), None);
+    });
+}
+
+#[test]
+fn reward_curve_points_match_configured_curve() {
+    use energy_generation_runtime_api::EnergyGenerationApi;
+
+    let points = <Runtime as EnergyGenerationApi<Block, AccountId>>::reward_curve_points();
+
+    assert_eq!(points, RewardCurve::get().points.to_vec());
+    assert_eq!(points.first().map(|(stake, _)| *stake), Some(Perbill::zero()));
+    assert!(points.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    // inflation peaks at the ideal stake
+    assert!(points.contains(&(Perbill::from_percent(50), Perbill::from_percent(10))));
+}
+
+#[test]
+fn tier_energy_bonus_set_by_governance_is_reported() {
+    use energy_generation_runtime_api::EnergyGenerationApi;
+
+    devnet_ext().execute_with(|| {
+        let tier = ReputationTier::Ultramodern(1);
+        let bonus = Perbill::from_percent(17);
+        let api_bonus = |tier| {
+            <Runtime as EnergyGenerationApi<Block, AccountId>>::reputation_tier_additional_reward(
+                tier,
+            )
+        };
+        assert_eq!(api_bonus(tier), Perbill::from_percent(16));
+
+        assert_ok!(EnergyGeneration::set_tier_bonus(RuntimeOrigin::root(), tier, bonus));
+
+        assert_eq!(api_bonus(tier), bonus);
+        assert!(dynamic_vnrg_params().reputation_bonuses.contains(&(tier, bonus)));
+    });
+}
+
+#[test]
+fn dynamic_vnrg_params_reflect_configured_parameters() {
+    devnet_ext().execute_with(|| {
+        pallet_energy_generation::ActiveEra::<Runtime>::put(
+            pallet_energy_generation::ActiveEraInfo { index: 3, start: None },
+        );
+        pallet_energy_generation::ErasEnergyPerStakeCurrency::<Runtime>::insert(3, 1_000);
+        assert_ok!(EnergyGeneration::set_energy_per_stake_currency(RuntimeOrigin::root(), 2_000));
+        assert_ok!(EnergyFee::update_base_fee(RuntimeOrigin::root(), 42));
+
+        let params = dynamic_vnrg_params();
+
+        assert_eq!(params.energy_per_stake_currency, Some(1_000));
+        assert_eq!(params.next_energy_per_stake_currency, Some(2_000));
+        assert_eq!(params.battery_slot_capacity, BatterySlotCapacity::get());
+        assert_eq!(params.base_fee, 42);
+        assert_eq!(params.reputation_bonuses.len(), usize::from(RANKS_PER_TIER) * 3);
+        assert_eq!(params.reputation_bonuses[0], (ReputationTier::Vanguard(1), Perbill::zero()));
+        assert!(params.reputation_bonuses.iter().all(|(tier, bonus)| {
+            *bonus == ReputationTierEnergyRewardAdditionalPercentMapping::convert(tier)
+        }));
+    });
+}
+
+#[test]
+fn network_energy_apy_projects_the_active_energy_rate() {
+    devnet_ext().execute_with(|| {
+        let eras_per_year = u128::from(YEARS / (EPOCH_DURATION_IN_BLOCKS * SessionsPerEra::get()));
+        // A VNRG is worth 2 VTRS and a year of eras mints a twentieth of the stake in VNRG.
+        let energy_rate = eras_per_year * 20;
+        let total_staked = energy_rate * 1_000_000_000;
+
+        assert_eq!(network_energy_apy(), Perbill::zero());
+
+        pallet_energy_generation::ActiveEra::<Runtime>::put(
+            pallet_energy_generation::ActiveEraInfo { index: 3, start: None },
+        );
+        pallet_energy_generation::ErasEnergyPerStakeCurrency::<Runtime>::insert(3, energy_rate);
+        pallet_asset_rate::ConversionRateToNative::<Runtime>::insert(
+            VNRG::get(),
+            FixedU128::from_u32(2),
+        );
+        assert_eq!(network_energy_apy(), Perbill::zero());
+
+        pallet_energy_generation::ErasTotalStake::<Runtime>::insert(3, total_staked);
+        assert_eq!(network_energy_apy(), Perbill::from_percent(10));
+
+        pallet_energy_generation::ErasEnergyPerStakeCurrency::<Runtime>::insert(3, 0);
+        assert_eq!(network_energy_apy(), Perbill::zero());
+    });
+}
+
+#[test]
+fn genesis_energy_config_matches_the_chain_spec() {
+    use pallet_energy_generation::ConfigOp::{Noop, Set};
+
+    devnet_ext().execute_with(|| {
+        let expected = GenesisEnergyConfig {
+            energy_per_stake_currency: ENERGY_PER_STAKE_CURRENCY,
+            vnrg_name: b"Energy".to_vec(),
+            vnrg_symbol: b"VNRG".to_vec(),
+            vnrg_decimals: 18,
+            min_cooperator_bond: 1_000_000_000_000_000_000,
+            min_common_validator_bond: 1_000_000 * vtrs::UNITS,
+            min_trust_validator_bond: vtrs::UNITS,
+            broker_seed: Some((
+                INITIAL_ENERGY_RATE.saturating_mul_int(900_000 * vnrg::GVOLT),
+                900_000 * vnrg::GVOLT,
+            )),
+        };
+        assert_eq!(genesis_energy_config(), Some(expected.clone()));
+
+        // later governance changes don't alter the launch configuration
+        assert_ok!(EnergyGeneration::set_energy_per_stake_currency(RuntimeOrigin::root(), 2_000));
+        assert_ok!(EnergyGeneration::set_staking_configs(
+            RuntimeOrigin::root(),
+            Set(42),
+            Noop,
+            Noop,
+            Noop,
+            Noop,
+            Noop,
+            Noop,
+        ));
+        assert_eq!(genesis_energy_config(), Some(expected));
+
+        // chains launched without the snapshot have no launch configuration
+        pallet_energy_generation::GenesisEnergyParameters::<Runtime>::kill();
+        assert_eq!(genesis_energy_config(), None);
+    });
+}
+
+#[test]
+fn democracy_proposals_require_reputation_threshold() {
+    use frame_support::traits::StorePreimage;
+
+    devnet_ext().execute_with(|| {
+        let propose = |who: AccountId| {
+            let proposal = Preimage::bound(RuntimeCall::System(frame_system::Call::remark {
+                remark: vec![1u8; 32],
+            }))
+            .expect("Expected to bound the proposal");
+            Democracy::propose(RuntimeOrigin::signed(who), proposal, areas::MinimumDeposit::get())
+        };
+        let set_points = |who: AccountId, points: u64| {
+            // governance changes of an account's reputation are spaced by a cooldown
+            System::set_block_number(System::block_number() + DAYS);
+            assert_ok!(Reputation::force_set_points(
+                RuntimeOrigin::root(),
+                who,
+                ReputationPoint::new(points)
+            ));
+        };
+
+        // a zero threshold lets anyone propose
+        set_points(alith(), 0);
+        assert_ok!(propose(alith()));
+
+        areas::ProposalReputationThreshold::set(&ReputationPoint::new(100));
+        set_points(alith(), 99);
+        assert_noop!(propose(alith()), sp_runtime::DispatchError::BadOrigin);
+
+        set_points(alith(), 100);
+        assert_ok!(propose(alith()));
+
+        // council members are exempt
+        set_points(baltathar(), 0);
+        assert_noop!(propose(baltathar()), sp_runtime::DispatchError::BadOrigin);
+        pallet_collective::Members::<Runtime, CouncilCollective>::put(vec![baltathar()]);
+        assert_ok!(propose(baltathar()));
+    });
+}
+
+#[test]
+fn permitted_calls_follow_nac_level_and_reputation() {
+    use vitreus_utility_runtime_api::CallClass;
+
+    devnet_ext().execute_with(|| {
+        let who = AccountId::from([0x42u8; 20]);
+        let validator_points = ReputationPoint::from(ReputationTier::Vanguard(1));
+        areas::ProposalReputationThreshold::set(&validator_points);
+
+        // no NAC and no reputation
+        assert_eq!(permitted_calls(&who), vec![CallClass::Cooperate, CallClass::Vote]);
+
+        assert_ok!(Reputation::force_set_points(RuntimeOrigin::root(), who, validator_points));
+        assert_eq!(
+            permitted_calls(&who),
+            vec![
+                CallClass::Cooperate,
+                CallClass::Validate,
+                CallClass::CollaborativeValidate,
+                CallClass::Vote,
+                CallClass::Propose,
+            ]
+        );
+
+        assert_ok!(NacManaging::mint(RuntimeOrigin::root(), 1, who));
+        let permitted = permitted_calls(&who);
+        assert!(permitted.contains(&CallClass::EvmCall));
+        assert!(!permitted.contains(&CallClass::EvmCreate));
+
+        assert_ok!(NacManaging::mint(RuntimeOrigin::root(), 3, who));
+        assert_eq!(
+            permitted_calls(&who),
+            vec![
+                CallClass::EvmCall,
+                CallClass::EvmCreate,
+                CallClass::Cooperate,
+                CallClass::Validate,
+                CallClass::CollaborativeValidate,
+                CallClass::Vote,
+                CallClass::Propose,
+            ]
+        );
+
+        // access exempt accounts may use the EVM without a NAC
+        let exempt = AccountId::from([0x43u8; 20]);
+        assert_ok!(NacManaging::add_access_exemption(RuntimeOrigin::root(), exempt));
+        assert!(permitted_calls(&exempt).contains(&CallClass::EvmCreate));
+    });
+}
+
+#[test]
+fn account_schedule_lists_upcoming_events_in_order() {
+    use chain_spec::devnet_keys::charleth;
+    use vitreus_utility_runtime_api::{ScheduledEvent, ScheduledEventKind};
+
+    devnet_ext().execute_with(|| {
+        let who = charleth();
+        System::set_block_number(20);
+
+        // the first schedule has already started, the second one starts at block 30
+        let schedules = vec![
+            pallet_vesting::VestingInfo::new(100 * vtrs::UNITS, vtrs::UNITS, 10),
+            pallet_vesting::VestingInfo::new(50 * vtrs::UNITS, 5 * vtrs::UNITS, 30),
+        ];
+        pallet_vesting::Vesting::<Runtime>::insert(
+            &who,
+            sp_runtime::BoundedVec::try_from(schedules).expect("Expected to fit the schedules"),
+        );
+
+        pallet_energy_generation::ActiveEra::<Runtime>::put(
+            pallet_energy_generation::ActiveEraInfo { index: 0, start: None },
+        );
+        pallet_energy_generation::ErasStartSessionIndex::<Runtime>::insert(0, 0);
+        assert_ok!(EnergyGeneration::bond(
+            RuntimeOrigin::signed(who),
+            who,
+            1_000 * vtrs::UNITS,
+            pallet_energy_generation::RewardDestination::Stash,
+        ));
+        assert_ok!(EnergyGeneration::unbond(RuntimeOrigin::signed(who), 400 * vtrs::UNITS));
+        let unbonding_matures_at = 20
+            + (BondingDuration::get() * SessionsPerEra::get() - Session::current_index())
+                * EPOCH_DURATION_IN_BLOCKS;
+
+        let update_nac = RuntimeCall::NacManaging(pallet_nac_managing::Call::update_nft {
+            new_nac_level: Some(2),
+            owner: who,
+        });
+        assert_ok!(Scheduler::schedule(RuntimeOrigin::root(), 50, None, 0, Box::new(update_nac)));
+        // a scheduled call of another account is not part of the schedule
+        let other_update = RuntimeCall::NacManaging(pallet_nac_managing::Call::update_nft {
+            new_nac_level: None,
+            owner: alith(),
+        });
+        assert_ok!(Scheduler::schedule(RuntimeOrigin::root(), 60, None, 0, Box::new(other_update)));
+
+        assert_eq!(
+            account_schedule(&who),
+            vec![
+                ScheduledEvent {
+                    block: 30,
+                    kind: ScheduledEventKind::VestingStarts { locked: 50 * vtrs::UNITS },
+                },
+                ScheduledEvent {
+                    block: 40,
+                    kind: ScheduledEventKind::VestingEnds { locked: 50 * vtrs::UNITS },
+                },
+                ScheduledEvent {
+                    block: 50,
+                    kind: ScheduledEventKind::NacLevelChange { level: Some(2) },
+                },
+                ScheduledEvent {
+                    block: 110,
+                    kind: ScheduledEventKind::VestingEnds { locked: 100 * vtrs::UNITS },
+                },
+                ScheduledEvent {
+                    block: unbonding_matures_at,
+                    kind: ScheduledEventKind::UnbondingMatures { amount: 400 * vtrs::UNITS },
+                },
+            ]
+        );
+    });
+}
+
+#[test]
+fn account_balance_sheet_aggregates_the_holdings() {
+    use frame_support::traits::fungibles::Mutate;
+    use vitreus_utility_runtime_api::BalanceSheet;
+
+    devnet_ext().execute_with(|| {
+        let who = AccountId::from([0x42u8; 20]);
+        Balances::make_free_balance_be(&who, 10_000 * vtrs::UNITS);
+        assert_ok!(Assets::mint_into(VNRG::get(), &who, 5 * vnrg::UNITS));
+        pallet_energy_generation::ActiveEra::<Runtime>::put(
+            pallet_energy_generation::ActiveEraInfo { index: 0, start: None },
+        );
+        assert_ok!(EnergyGeneration::bond(
+            RuntimeOrigin::signed(who),
+            who,
+            1_000 * vtrs::UNITS,
+            pallet_energy_generation::RewardDestination::Stash,
+        ));
+        assert_ok!(EnergyGeneration::unbond(RuntimeOrigin::signed(who), 400 * vtrs::UNITS));
+        pallet_energy_generation::DeferredEnergyRewards::<Runtime>::insert(who, 3 * vnrg::UNITS);
+        pallet_energy_fee::FeeCredit::<Runtime>::insert(who, 2 * vnrg::UNITS);
+
+        assert_eq!(
+            account_balance_sheet(&who),
+            BalanceSheet {
+                liquid_vtrs: 9_000 * vtrs::UNITS,
+                liquid_vnrg: 5 * vnrg::UNITS,
+                bonded: 600 * vtrs::UNITS,
+                unlocking: vec![(BondingDuration::get(), 400 * vtrs::UNITS)],
+                pending_rewards: 3 * vnrg::UNITS,
+                fee_credit: 2 * vnrg::UNITS,
+            }
+        );
+
+        // accounts without holdings have an empty balance sheet
+        assert_eq!(account_balance_sheet(&AccountId::from([0x43u8; 20])), BalanceSheet::default());
+    });
+}
+
+#[test]
+fn active_swaps_lists_pending_swaps_by_page() {
+    use pallet_atomic_swap::BalanceSwapAction;
+    use sp_core::hashing::blake2_256;
+
+    devnet_ext().execute_with(|| {
+        System::set_block_number(1);
+        let source = AccountId::from([0x42u8; 20]);
+        let target = AccountId::from([0x43u8; 20]);
+        Balances::make_free_balance_be(&source, 10_000 * vtrs::UNITS);
+        for (seed, duration) in [(1u8, 10), (2, 20), (3, 30)] {
+            assert_ok!(AtomicSwap::create_swap(
+                RuntimeOrigin::signed(source),
+                target,
+                blake2_256(&[seed]),
+                BalanceSwapAction::new(seed as Balance * vtrs::UNITS),
+                duration,
+            ));
+        }
+
+        let mut swaps = active_swaps(None, u32::MAX);
+        swaps.sort_by_key(|swap| swap.amount);
+        assert_eq!(
+            swaps,
+            [(1u8, 11), (2, 21), (3, 31)]
+                .map(|(seed, end_block)| SwapInfo {
+                    source: source.into(),
+                    target: target.into(),
+                    hashed_proof: blake2_256(&[seed]),
+                    amount: seed as Balance * vtrs::UNITS,
+                    end_block,
+                })
+                .to_vec()
+        );
+
+        // pages continue after the last swap of the previous one
+        let first_page = active_swaps(None, 2);
+        assert_eq!(first_page.len(), 2);
+        let last = first_page.last().unwrap();
+        let second_page = active_swaps(Some((target, last.hashed_proof)), 2);
+        assert_eq!(second_page.len(), 1);
+        assert!(!first_page.contains(&second_page[0]));
+
+        // claimed swaps aren't listed anymore
+        assert_ok!(AtomicSwap::claim_swap(
+            RuntimeOrigin::signed(target),
+            vec![2u8],
+            BalanceSwapAction::new(2 * vtrs::UNITS),
+        ));
+        let swaps = active_swaps(None, u32::MAX);
+        assert_eq!(swaps.len(), 2);
+        assert!(swaps.iter().all(|swap| swap.hashed_proof != blake2_256(&[2u8])));
+    });
+}
+
+#[test]
+fn runtime_parameters_list_governance_parameters_with_their_values() {
+    devnet_ext().execute_with(|| {
+        assert_ok!(EnergyGeneration::set_validator_count(RuntimeOrigin::root(), 7));
+        assert_ok!(EnergyFee::update_rebate_fraction(
+            RuntimeOrigin::root(),
+            Perbill::from_percent(10)
+        ));
+        pallet_energy_fee::BaseFee::<Runtime>::put(42);
+        pallet_energy_fee::BurnedEnergyThreshold::<Runtime>::kill();
+
+        let parameters = runtime_parameters();
+        let value_of = |name: &[u8]| {
+            parameters
+                .iter()
+                .find(|(param, _)| param.as_slice() == name)
+                .map(|(_, value)| value.clone())
+        };
+
+        assert_eq!(value_of(b"energy_fee.base_fee"), Some(ParamValue::Amount(42)));
+        assert_eq!(value_of(b"energy_fee.burned_energy_threshold"), Some(ParamValue::Unset));
+        assert_eq!(
+            value_of(b"energy_fee.rebate_fraction"),
+            Some(ParamValue::Perbill(Perbill::from_percent(10)))
+        );
+        assert_eq!(
+            value_of(b"energy_fee.block_gas_limit"),
+            Some(ParamValue::Gas(EnergyFee::block_gas_limit()))
+        );
+        assert_eq!(value_of(b"energy_generation.validator_count"), Some(ParamValue::Count(7)));
+        assert_eq!(
+            value_of(b"energy_generation.min_cooperator_bond"),
+            Some(ParamValue::Amount(pallet_energy_generation::MinCooperatorBond::<Runtime>::get()))
+        );
+        assert_eq!(
+            value_of(b"democracy.proposal_reputation_threshold"),
+            Some(ParamValue::Points(0))
+        );
+
+        // every parameter is listed once
+        let mut names: Vec<_> = parameters.iter().map(|(name, _)| name.clone()).collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), parameters.len());
+    });
+}
+
+#[test]
+fn vesting_claim_precompile_releases_vested_funds() {
+    use chain_spec::devnet_keys::charleth;
+    use frame_support::traits::VestingSchedule;
+
+    devnet_ext().execute_with(|| {
+        let who = charleth();
+        System::set_block_number(1);
+        assert_ok!(Vesting::force_vested_transfer(
+            RuntimeOrigin::root(),
+            baltathar(),
+            who,
+            pallet_vesting::VestingInfo::new(100 * vtrs::UNITS, 10 * vtrs::UNITS, 1),
+        ));
+        System::set_block_number(6);
+
+        let claim_vested = |caller: AccountId| {
+            pallet_evm::runner::stack::Runner::<Runtime>::call(
+                H160::from(caller.0),
+                H160::from_low_u64_be(precompiles::VESTING_CLAIM_ADDRESS),
+                precompiles::VestingClaim::CLAIM_VESTED_SELECTOR.to_vec(),
+                U256::zero(),
+                100_000,
+                None,
+                None,
+                None,
+                Vec::new(),
+                false,
+                false,
+                None,
+                None,
+                <Runtime as pallet_evm::Config>::config(),
+            )
+            .expect("Expected the precompile call to be executed")
+        };
+        let word = |value: Balance| {
+            let mut word = [0u8; 32];
+            U256::from(value).to_big_endian(&mut word);
+            word.to_vec()
+        };
+        let liquid = |who: &AccountId| {
+            Balances::reducible_balance(who, Preservation::Expendable, Fortitude::Polite)
+        };
+
+        // half of the schedule has vested by block 6
+        let liquid_before = liquid(&who);
+        let info = claim_vested(who);
+        assert_eq!(info.exit_reason, fp_evm::ExitReason::Succeed(fp_evm::ExitSucceed::Returned));
+        assert_eq!(info.value, word(50 * vtrs::UNITS));
+        assert_eq!(liquid(&who), liquid_before + 50 * vtrs::UNITS);
+        assert_eq!(Vesting::vesting_balance(&who), Some(50 * vtrs::UNITS));
+
+        // nothing more vested in the same block, and accounts without schedules get nothing
+        assert_eq!(claim_vested(who).value, word(0));
+        let info = claim_vested(alith());
+        assert_eq!(info.exit_reason, fp_evm::ExitReason::Succeed(fp_evm::ExitSucceed::Returned));
+        assert_eq!(info.value, word(0));
+    });
+}
+
+#[test]
+fn energy_broker_quote_precompile_matches_pallet() {
+    devnet_ext().execute_with(|| {
+        let vtrs = NativeOrAssetId::Native;
+        let vnrg = NativeOrAssetId::Asset(VNRG::get());
+
+        let call_precompile = |input: Vec<u8>| {
+            pallet_evm::runner::stack::Runner::<Runtime>::call(
+                H160::from(alith().0),
+                H160::from_low_u64_be(precompiles::ENERGY_BROKER_QUOTE_ADDRESS),
+                input,
+                U256::zero(),
+                100_000,
+                None,
+                None,
+                None,
+                Vec::new(),
+                false,
+                false,
+                None,
+                None,
+                <Runtime as pallet_evm::Config>::config(),
+            )
+            .expect("Expected the precompile call to be executed")
+        };
+        let word = |value: Balance| {
+            let mut word = [0u8; 32];
+            U256::from(value).to_big_endian(&mut word);
+            word.to_vec()
+        };
+        let quote = |amount_in: Balance, vtrs_to_vnrg: bool| {
+            let mut input = precompiles::EnergyBrokerQuote::QUOTE_SELECTOR.to_vec();
+            input.extend(word(amount_in));
+            input.extend(word(vtrs_to_vnrg as Balance));
+            call_precompile(input)
+        };
+
+        let info = call_precompile(precompiles::EnergyBrokerQuote::GET_RESERVES_SELECTOR.to_vec());
+        let (vtrs_reserve, vnrg_reserve) = EnergyBroker::get_reserves(&vtrs, &vnrg).unwrap();
+        assert_eq!(info.exit_reason, fp_evm::ExitReason::Succeed(fp_evm::ExitSucceed::Returned));
+        assert_eq!(info.value, [word(vtrs_reserve), word(vnrg_reserve)].concat());
+
+        let amount = 10 * vtrs::UNITS;
+        for (vtrs_to_vnrg, path) in [(true, (&vtrs, &vnrg)), (false, (&vnrg, &vtrs))] {
+            let expected = EnergyBroker::get_amount_out(&amount, path).unwrap();
+            // the LP fee is included in the quote
+            assert!(expected < EnergyBroker::quote(&amount, path).unwrap());
+
+            let info = quote(amount, vtrs_to_vnrg);
+            assert_eq!(
+                info.exit_reason,
+                fp_evm::ExitReason::Succeed(fp_evm::ExitSucceed::Returned)
+            );
+            assert_eq!(info.value, word(expected));
+        }
+
+        // the direction must be a valid ABI bool
+        let mut input = precompiles::EnergyBrokerQuote::QUOTE_SELECTOR.to_vec();
+        input.extend(word(amount));
+        input.extend(word(2));
+        let info = call_precompile(input);
+        assert!(matches!(info.exit_reason, fp_evm::ExitReason::Revert(_)));
+    });
+}
+
+#[test]
+fn validator_operating_cost_sums_fees_of_validator_operations() {
+    devnet_ext().execute_with(|| {
+        let fee_of =
+            |call: RuntimeCall| EnergyFee::dispatch_info_to_fee(&call, None, None).into_inner();
+        let payout =
+            fee_of(RuntimeCall::EnergyGeneration(pallet_energy_generation::Call::payout_stakers {
+                validator_stash: alith(),
+                era: 0,
+            }));
+        let client_version = fee_of(RuntimeCall::EnergyGeneration(
+            pallet_energy_generation::Call::report_client_version {
+                version: pallet_energy_generation::ClientVersion { major: 1, minor: 2, patch: 3 },
+            },
+        ));
+        let set_keys = fee_of(RuntimeCall::Session(pallet_session::Call::set_keys {
+            keys: opaque::SessionKeys::default(),
+            proof: vec![],
+        }));
+        assert!(payout > 0 && client_version > 0 && set_keys > 0);
+
+        let cost = validator_operating_cost();
+        assert_eq!(
+            cost,
+            payout
+                + client_version
+                + Perbill::from_rational(SessionsPerEra::get(), SessionKeysRotationAge::get())
+                    * set_keys
+        );
+        assert!(cost < payout + client_version + set_keys);
+
+        // the estimate follows the fee parameters.
+        EnergyFee::with_fee_parameters(2 * EnergyFee::base_fee(), FixedU128::from_u32(2), || {
+            assert!(validator_operating_cost() > cost)
+        });
+    });
+}
+
+#[test]
+fn can_afford_batch_sums_the_fees_of_the_batched_calls() {
+    devnet_ext().execute_with(|| {
+        use frame_support::traits::fungibles::Mutate;
+
+        let transfer = RuntimeCall::Balances(pallet_balances::Call::transfer_keep_alive {
+            dest: alith(),
+            value: 1,
+        });
+        let nested =
+            RuntimeCall::Utility(pallet_utility::Call::batch_all { calls: vec![transfer.clone()] });
+        let batch_fee = |calls: Vec<RuntimeCall>| {
+            let batch = RuntimeCall::Utility(pallet_utility::Call::batch { calls });
+            EnergyFee::dispatch_info_to_fee(&batch, None, None).into_inner()
+        };
+        let two_calls = vec![transfer.encode(), nested.encode()];
+        let three_calls = vec![transfer.encode(), nested.encode(), transfer.encode()];
+        assert!(
+            batch_fee(vec![transfer.clone(), nested.clone(), transfer.clone()])
+                > batch_fee(vec![transfer.clone(), nested.clone()])
+        );
+
+        // undecodable calls are never affordable
+        assert!(can_afford_batch(&baltathar(), three_calls.clone()));
+        assert!(!can_afford_batch(&baltathar(), vec![vec![0xff, 0xff]]));
+
+        // the account holds exactly the fee of two calls and can't swap VTRS for more
+        let who = AccountId::from([0x42u8; 20]);
+        assert_ok!(Balances::force_set_balance(
+            RuntimeOrigin::root(),
+            who,
+            ExistentialDeposit::get()
+        ));
+        assert_ok!(Assets::mint_into(
+            VNRG::get(),
+            &who,
+            batch_fee(vec![transfer.clone(), nested.clone()])
+        ));
+        pallet_energy_fee::AutoSwapEnabled::<Runtime>::put(false);
+
+        assert!(can_afford_batch(&who, two_calls.clone()));
+        assert!(!can_afford_batch(&who, three_calls));
+        // the check doesn't withdraw the fee
+        assert!(can_afford_batch(&who, two_calls));
+    });
+}
+
+#[test]
+fn addresses_round_trip_between_ss58_and_hex() {
+    let addresses: [(&[u8], &[u8]); 3] = [
+        (b"0xf24FF3a9CF04c71Dbc94D0b566f7A27B94566cac", b"AHAmuQNpJXHcJYTEpHA3KqLMrMFo7J5mD"),
+        (b"0x3Cd0A705a2DC65e5b1E1205896BaA2be8A07c6e0", b"AH9veJYPurYuSZxwydGUvCdWSC8s4XvJZ"),
+        (b"0x0000000000000000000000000000000000000000", b"AH9e8xnNuBq9vjVn27tAmpjQBqy1ghrHj"),
+    ];
+
+    for (hex, ss58) in addresses {
+        let pair = helpers::address::map_addresses(AddressInput::H160(hex.to_vec())).unwrap();
+        assert_eq!(pair.checksummed, hex);
+        assert_eq!(pair.ss58, ss58);
+        assert_eq!(helpers::address::map_addresses(AddressInput::Ss58(ss58.to_vec())), Ok(pair));
+    }
+
+    // lowercase hex carries no checksum
+    let pair = helpers::address::map_addresses(AddressInput::H160(
+        b"0xf24ff3a9cf04c71dbc94d0b566f7a27b94566cac".to_vec(),
+    ))
+    .unwrap();
+    assert_eq!(pair.h160, H160::from(alith().0));
+    assert_eq!(pair.checksummed, b"0xf24FF3a9CF04c71Dbc94D0b566f7A27B94566cac");
+}
+
+#[test]
+fn malformed_addresses_are_not_mapped() {
+    let map = |input| helpers::address::map_addresses(input);
+
+    // EIP-55 checksum with one letter's case flipped
+    assert_eq!(
+        map(AddressInput::H160(b"0xF24FF3a9CF04c71Dbc94D0b566f7A27B94566cac".to_vec())),
+        Err(AddressError::InvalidChecksum)
+    );
+    assert_eq!(
+        map(AddressInput::H160(b"f24ff3a9cf04c71dbc94d0b566f7a27b94566cac".to_vec())),
+        Err(AddressError::InvalidFormat)
+    );
+    assert_eq!(
+        map(AddressInput::H160(b"0xf24ff3a9cf04c71dbc94d0b566f7a27b94566ca".to_vec())),
+        Err(AddressError::InvalidFormat)
+    );
+
+    // SS58 address with its last character changed
+    assert_eq!(
+        map(AddressInput::Ss58(b"AHAmuQNpJXHcJYTEpHA3KqLMrMFo7J5mE".to_vec())),
+        Err(AddressError::InvalidChecksum)
+    );
+    // alith with the generic substrate prefix
+    assert_eq!(
+        map(AddressInput::Ss58(b"tSygwE4GMsRGsX9YuoeZQecPFrJK3nJ".to_vec())),
+        Err(AddressError::WrongPrefix)
+    );
+    assert_eq!(map(AddressInput::Ss58(b"0OIl".to_vec())), Err(AddressError::InvalidFormat));
+}
+
+#[test]
+fn disputes_stay_open_until_the_disputes_pallet_concludes_them() {
+    devnet_ext().execute_with(|| {
+        let candidate = CandidateHash(H256::repeat_byte(1));
+        assert!(!EnergyFee::disputes_active());
+
+        TrackOpenDisputes::punish_for_invalid(1, candidate, vec![], vec![]);
+        TrackOpenDisputes::punish_against_valid(1, candidate, vec![], vec![]);
+        assert_eq!(EnergyFee::open_dispute_count(), 1);
+        assert!(EnergyFee::disputes_active());
+
+        // the disputes pallet doesn't know the dispute, it can't be open anymore
+        TrackOpenDisputes::initializer_finalize();
+        assert_eq!(EnergyFee::open_dispute_count(), 0);
+        assert!(!EnergyFee::disputes_active());
+    });
+}
+
+#[test]
+fn backers_of_under_subscribed_parachains_are_boosted() {
+    devnet_ext().execute_with(|| {
+        use parachains_inclusion::RewardValidators as _;
+
+        let era = EnergyGeneration::active_era().unwrap().index;
+        let stash = Session::validators()[0];
+        let boosted = || {
+            pallet_energy_generation::ErasBackingBoostedValidators::<Runtime>::contains_key(
+                era, stash,
+            )
+        };
+        parachains_shared::ActiveValidatorIndices::<Runtime>::put(vec![ValidatorIndex(0)]);
+        let mandatory_weight = || {
+            System::block_weight()
+                .get(frame_support::dispatch::DispatchClass::Mandatory)
+                .ref_time()
+        };
+
+        // a candidate backed by enough validators isn't boosted and reads nothing
+        let weight = mandatory_weight();
+        let backers = UnderSubscribedBackingCount::get() as usize;
+        RewardValidators::reward_backing(vec![ValidatorIndex(0); backers]);
+        assert!(!boosted());
+        assert_eq!(mandatory_weight(), weight);
+
+        // fewer backers mean an under-subscribed parachain, the reads are weighed
+        RewardValidators::reward_backing(vec![ValidatorIndex(0); backers - 1]);
+        assert!(boosted());
+        assert!(mandatory_weight() > weight);
+    });
+}
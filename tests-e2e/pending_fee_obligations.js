@@ -0,0 +1,46 @@
+import { WebSocketProvider, Wallet } from 'ethers';
+import assert from 'assert';
+
+
+const API = new WebSocketProvider("ws://127.0.0.1:9944");
+const GAS_PRICE = BigInt("1000000000");
+const ALITH = new Wallet("0x5fb92d6e98884f76de468fa3f6278f8807c48bebc13595d45af5bdc4da702133").connect(API);
+const BALTATHAR = new Wallet("0x8075991ce870b93a8870eca0c0f91913d12f47948ca0fd25b49c6fa7cdbeee8b").connect(API);
+
+await (async function main() {
+    assert.equal(await pendingFeeObligations(ALITH.address), 0n);
+
+    // queue the transactions without waiting for their inclusion
+    const nonce = await API.getTransactionCount(ALITH.address, "pending");
+    const first = await sendTransaction(nonce);
+    const single = await pendingFeeObligations(ALITH.address);
+    console.log(`Obligations with one queued transaction: ${single}`);
+    assert.ok(single > 0n);
+
+    const rest = [await sendTransaction(nonce + 1), await sendTransaction(nonce + 2)];
+    const total = await pendingFeeObligations(ALITH.address);
+    console.log(`Obligations with three queued transactions: ${total}`);
+    assert.equal(total, 3n * single);
+
+    // other accounts' transactions aren't included
+    assert.equal(await pendingFeeObligations(BALTATHAR.address), 0n);
+
+    await Promise.all([first, ...rest].map((tx) => tx.wait()));
+    assert.equal(await pendingFeeObligations(ALITH.address), 0n);
+
+    console.log("\n\nAll done");
+    process.exit(0);
+})().catch((e) => { console.error(e); process.exit(1) });
+
+async function sendTransaction(nonce) {
+    return ALITH.sendTransaction({
+        to: BALTATHAR.address,
+        gasPrice: GAS_PRICE, // Gas price in wei
+        value: GAS_PRICE,
+        nonce,
+    });
+}
+
+async function pendingFeeObligations(address) {
+    return BigInt(await API.send("energyFee_pendingFeeObligations", [address]));
+}
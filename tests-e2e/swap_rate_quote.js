@@ -0,0 +1,27 @@
+import { WebSocketProvider } from 'ethers';
+import assert from 'assert';
+
+
+const API = new WebSocketProvider("ws://127.0.0.1:9944");
+
+await (async function main() {
+    const quote = await API.send("energyFee_vtrsToVnrgSwapRate", []);
+    console.log(`Latest quote: ${JSON.stringify(quote)}`);
+    assert.ok(quote, "the broker pool has no rate");
+
+    // the quote carries the block it was computed at
+    const header = await API.send("chain_getHeader", [quote.atHash]);
+    assert.equal(quote.atBlock, Number(header.number));
+    assert.ok(quote.validUntil > quote.atBlock);
+    assert.ok(quote.recommendedSlippage > 0);
+
+    // quotes at a past block are computed at that block
+    const parent = header.parentHash;
+    const pastQuote = await API.send("energyFee_vtrsToVnrgSwapRate", [parent]);
+    assert.equal(pastQuote.atHash, parent);
+    assert.equal(pastQuote.atBlock, quote.atBlock - 1);
+    assert.equal(pastQuote.validUntil - pastQuote.atBlock, quote.validUntil - quote.atBlock);
+
+    console.log("\n\nAll done");
+    process.exit(0);
+})().catch((e) => { console.error(e); process.exit(1) });